@@ -0,0 +1,248 @@
+mod common;
+
+use common::*;
+
+use criterion::{
+    criterion_group, criterion_main,
+    BenchmarkId, Criterion, Throughput,
+};
+use bevy::prelude::{Interaction, Style, Val};
+use tomt_bevycss::prelude::*;
+
+const ENTITY_COUNTS: [usize; 2] = [1_000, 10_000];
+const RULE_COUNTS: [usize; 3] = [16, 64, 256];
+const HOVER_ENTITY_COUNT: usize = 2_000;
+
+/// Measures how fast a sheet is parsed into rules.
+fn parse(
+    c: &mut Criterion
+) {
+    let mut group = c.benchmark_group("parse");
+
+    for rule_count in RULE_COUNTS
+    {
+        let (content, _rules) = generate_sheet(rule_count, true);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(rule_count), &content, |b, content|
+        {
+            b.iter(|| StyleSheetAsset::parse("bench.css", content));
+        });
+    }
+
+    group.finish();
+}
+
+/// Runs a full update with the given sheet applied on a tree with the given number of entities.
+fn bench_update(
+    c: &mut Criterion,
+    name: &str,
+    declarations: bool
+) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+
+    for entity_count in ENTITY_COUNTS
+    {
+        for rule_count in RULE_COUNTS
+        {
+            let (content, rules) = generate_sheet(rule_count, declarations);
+
+            let id = BenchmarkId::new(format!("{entity_count}_entities"), rule_count);
+            group.throughput(Throughput::Elements(entity_count as u64));
+            group.bench_function(id, |b|
+            {
+                let (mut app, entities) = create_styled_app(entity_count, &content);
+                if declarations
+                {
+                    assert_styled(&app, &entities, &rules);
+                }
+
+                b.iter(||
+                {
+                    refresh_sheet(&mut app, entities[0]);
+                    app.update();
+                });
+
+                // Reapplying the same sheet must always yield the same result
+                if declarations
+                {
+                    assert_styled(&app, &entities, &rules);
+                }
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Measures selector matching only, since rules have no properties to be applied.
+fn matching(
+    c: &mut Criterion
+) {
+    bench_update(c, "match", false);
+}
+
+/// Measures selector matching and apply pass, the difference to [`matching`] is the apply pass cost.
+fn apply(
+    c: &mut Criterion
+) {
+    bench_update(c, "match_and_apply", true);
+}
+
+/// Measures matching a sheet which repeats the same few selectors on many rules, like `.c1` on every theme section.
+///
+/// Rules sharing a selector reuse the entities it matched, so only unique selectors are evaluated, whatever the rule count.
+fn shared_selectors(
+    c: &mut Criterion
+) {
+    let mut group = c.benchmark_group("shared_selectors");
+    group.sample_size(10);
+
+    for rule_count in RULE_COUNTS
+    {
+        let (content, _rules) = generate_sheet(rule_count, true);
+        let unique_selectors = StyleSheetAsset::parse("bench.css", &content).stats().unique_selectors;
+
+        let (mut app, entities) = create_styled_app(ENTITY_COUNTS[0], &content);
+
+        // Only the root is refreshed, so each unique selector is evaluated once on the whole tree
+        refresh_sheet(&mut app, entities[0]);
+        app.update();
+        let stats = *app.world.resource::<SelectorMatchStats>();
+        assert_eq!(stats.rules, rule_count);
+        assert_eq!(stats.evaluated, unique_selectors);
+
+        let id = BenchmarkId::new(format!("{}_entities", ENTITY_COUNTS[0]), rule_count);
+        group.bench_function(id, |b|
+        {
+            b.iter(||
+            {
+                refresh_sheet(&mut app, entities[0]);
+                app.update();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Measures matching a sheet where most rules reference classes no entity has, like a theme shared by many screens.
+///
+/// Those rules are skipped without being evaluated against the tree, so this must stay close to matching only the used rules.
+fn unused_classes(
+    c: &mut Criterion
+) {
+    let mut group = c.benchmark_group("unused_classes");
+    group.sample_size(10);
+
+    for rule_count in RULE_COUNTS
+    {
+        let (mut content, _rules) = generate_sheet(CLASS_COUNT, true);
+        for idx in 0..rule_count
+        {
+            content.push_str(&format!("#root .unused{idx} .c1 {{ width: {idx}px; }}\n"));
+        }
+
+        let (mut app, entities) = create_styled_app(ENTITY_COUNTS[0], &content);
+
+        refresh_sheet(&mut app, entities[0]);
+        app.update();
+        let stats = *app.world.resource::<SelectorMatchStats>();
+        assert_eq!(stats.rules, CLASS_COUNT);
+        assert_eq!(stats.skipped, rule_count);
+
+        let id = BenchmarkId::new(format!("{}_entities", ENTITY_COUNTS[0]), rule_count);
+        group.bench_function(id, |b|
+        {
+            b.iter(||
+            {
+                refresh_sheet(&mut app, entities[0]);
+                app.update();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Measures matching and applying rules with long selectors and many properties, like `#root .c1 > .c9 .c3`.
+///
+/// Selectors are split on nodes once when parsed, and property names are shared by all entities, so restyling the tree
+/// must not allocate them again for each rule or entity.
+fn nested_selectors(
+    c: &mut Criterion
+) {
+    let mut group = c.benchmark_group("nested_selectors");
+    group.sample_size(10);
+
+    for rule_count in RULE_COUNTS
+    {
+        let mut content = String::new();
+        for idx in 0..rule_count
+        {
+            let (parent, child, descendant) = (idx % CLASS_COUNT, (idx / CLASS_COUNT + 1) % CLASS_COUNT, (idx * 7 + 3) % CLASS_COUNT);
+            content.push_str(&format!(
+                "#root .c{parent} > .c{child} .c{descendant} {{ width: {idx}px; height: {idx}px; margin: {idx}px; padding: 2px; }}\n"
+            ));
+        }
+
+        let (mut app, entities) = create_styled_app(ENTITY_COUNTS[0], &content);
+
+        let id = BenchmarkId::new(format!("{}_entities", ENTITY_COUNTS[0]), rule_count);
+        group.throughput(Throughput::Elements(ENTITY_COUNTS[0] as u64));
+        group.bench_function(id, |b|
+        {
+            b.iter(||
+            {
+                refresh_sheet(&mut app, entities[0]);
+                app.update();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Measures hovering and unhovering a single entity, like when the mouse moves across a button.
+///
+/// Only rules sensitive to interaction changes should be evaluated, so this must stay way cheaper than [`apply`].
+fn hover(
+    c: &mut Criterion
+) {
+    let mut group = c.benchmark_group("hover");
+
+    for rule_count in RULE_COUNTS
+    {
+        let (mut content, _rules) = generate_sheet(rule_count, true);
+        content.push_str("#root .c1:hover { height: 999px; }\n#root .c1:hover .c2 { min-height: 10px; }\n");
+
+        let id = BenchmarkId::new(format!("{HOVER_ENTITY_COUNT}_entities"), rule_count);
+        group.bench_function(id, |b|
+        {
+            let (mut app, entities) = create_styled_app(HOVER_ENTITY_COUNT, &content);
+
+            // The first child of root always has the `c1` class
+            let target = entities[1];
+            app.world.entity_mut(target).insert(Interaction::Hovered);
+            app.update();
+            assert_eq!(app.world.get::<Style>(target).unwrap().height, Val::Px(999.0));
+
+            let mut hovered = true;
+            b.iter(||
+            {
+                hovered = !hovered;
+                *app.world.get_mut::<Interaction>(target).unwrap() = match hovered
+                {
+                    true => Interaction::Hovered,
+                    false => Interaction::None,
+                };
+                app.update();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, matching, apply, shared_selectors, unused_classes, nested_selectors, hover);
+criterion_main!(benches);
@@ -0,0 +1,185 @@
+//! Helpers shared by `tomt_bevycss` benchmarks.
+//!
+//! Everything here is deterministic, so the same tree and sheet are generated on every run,
+//! which allows results to be compared across refactors.
+
+use bevy::{
+    asset::AssetPlugin,
+    prelude::*,
+};
+use tomt_bevycss::prelude::*;
+
+/// Number of distinct class names used by generated trees and sheets.
+pub const CLASS_COUNT: usize = 16;
+
+/// Number of children of each node on generated trees.
+pub const BRANCHING: usize = 8;
+
+/// Class name assigned to the entity with the given index.
+pub fn class_name(
+    index: usize
+) -> String {
+    format!("c{}", index % CLASS_COUNT)
+}
+
+/// A generated rule, used to compute the expected result of a sheet without running it.
+pub struct GeneratedRule
+{
+    pub class: usize,
+    pub specificity: usize,
+    pub width: f32,
+}
+
+/// Generates a sheet with the given number of rules.
+///
+/// Even rules use a single class selector, like `.c1`, while odd rules use a descendant selector, like `#root .c1`.
+/// When `declarations` is `false`, rules have no properties, so only selector matching is performed.
+pub fn generate_sheet(
+    rule_count: usize,
+    declarations: bool
+) -> (String, Vec<GeneratedRule>) {
+    let mut content = String::new();
+    let mut rules = Vec::with_capacity(rule_count);
+
+    for idx in 0..rule_count
+    {
+        let class = idx % CLASS_COUNT;
+        let (selector, specificity) = match idx % 2
+        {
+            0 => (format!(".c{class}"), 1),
+            _ => (format!("#root .c{class}"), 3),
+        };
+
+        let width = idx as f32;
+        match declarations
+        {
+            true => content.push_str(&format!("{selector} {{ width: {width}px; background-color: #{:06x}; }}\n", idx * 97)),
+            false => content.push_str(&format!("{selector} {{ }}\n")),
+        }
+
+        rules.push(GeneratedRule { class, specificity, width });
+    }
+
+    (content, rules)
+}
+
+/// Computes the `width` expected to be applied on entities with the given class.
+///
+/// Mirrors the cascade order: higher specificity first, then later rules.
+pub fn expected_width(
+    rules: &[GeneratedRule],
+    class: usize
+) -> Option<f32> {
+    rules.iter()
+        .enumerate()
+        .filter(|(_idx, rule)| rule.class == class)
+        .max_by_key(|(idx, rule)| (rule.specificity, *idx))
+        .map(|(_idx, rule)| rule.width)
+}
+
+/// Creates an headless app with [`BevyCssPlugin`] and only the plugins required by it.
+pub fn create_app(
+    // no args
+) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        BevyCssPlugin::without_hot_reload(),
+    ));
+
+    app
+}
+
+/// Spawns a deterministic tree of UI nodes with the given number of entities.
+///
+/// Nodes are laid out as a complete tree with [`BRANCHING`] children per node, where the parent of node `i` is `(i - 1) / BRANCHING`.
+/// The root node is named `root` and every other node has a class given by [`class_name`].
+///
+/// Returns all spawned entities, in the same order as their indices.
+pub fn spawn_tree(
+    world: &mut World,
+    entity_count: usize
+) -> Vec<Entity> {
+    let mut entities = Vec::with_capacity(entity_count);
+
+    for idx in 0..entity_count
+    {
+        let mut node = world.spawn(NodeBundle::default());
+        match idx
+        {
+            0 => node.insert(Name::new("root")),
+            _ => node.insert(Class::new(class_name(idx))),
+        };
+
+        let entity = node.id();
+        if idx > 0
+        {
+            let parent = entities[(idx - 1) / BRANCHING];
+            world.entity_mut(parent).add_child(entity);
+        }
+
+        entities.push(entity);
+    }
+
+    entities
+}
+
+/// Adds the given sheet content as an asset and attaches it to the root entity.
+pub fn attach_sheet(
+    app: &mut App,
+    root: Entity,
+    content: &str
+) {
+    let handle = app.world
+        .resource_mut::<Assets<StyleSheetAsset>>()
+        .add(StyleSheetAsset::parse("bench.css", content));
+
+    app.world.entity_mut(root).insert(StyleSheet::new(handle));
+}
+
+/// Creates an app with a generated tree of the given size, already styled by the given sheet content.
+///
+/// The tree is updated once before attaching the sheet, so only the root entity is detected as changed,
+/// like when a sheet is reloaded on an existing UI.
+pub fn create_styled_app(
+    entity_count: usize,
+    content: &str
+) -> (App, Vec<Entity>) {
+    let mut app = create_app();
+    let entities = spawn_tree(&mut app.world, entity_count);
+    app.update();
+
+    attach_sheet(&mut app, entities[0], content);
+    app.update();
+
+    (app, entities)
+}
+
+/// Triggers the style sheet on the given entity to be reapplied on next update.
+pub fn refresh_sheet(
+    app: &mut App,
+    root: Entity
+) {
+    app.world
+        .get_mut::<StyleSheet>(root)
+        .expect("Root should have a style sheet")
+        .refresh();
+}
+
+/// Asserts every entity was styled as expected by the generated rules.
+pub fn assert_styled(
+    app: &App,
+    entities: &[Entity],
+    rules: &[GeneratedRule]
+) {
+    for (idx, entity) in entities.iter().enumerate().skip(1)
+    {
+        let style = app.world.get::<Style>(*entity).expect("Should have a style");
+        let expected = expected_width(rules, idx % CLASS_COUNT)
+            .map(Val::Px)
+            .unwrap_or_default();
+
+        assert_eq!(style.width, expected, "Unexpected width on entity {idx}");
+    }
+}
@@ -67,6 +67,9 @@ for AlphaProperty
     // It's not recommended to use only With<> and Without<>.
     type Filters = ();
 
+    // Resources needed when applying the cache, like Res<'static, AssetServer>. Nothing else is needed here.
+    type Param = ();
+
     fn name(
         // no args
     ) -> &'static str {
@@ -88,7 +91,7 @@ for AlphaProperty
     fn apply<'w>(
         cache: &Self::Cache,
         mut components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
+        _context: &PropertyContext<Self>,
         _commands: &mut Commands,
     ) {
         components.0.set_a(*cache);
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use tomt_bevycss::prelude::*;
+
+/// Style sheet embedded on the binary, so no `assets/` directory is needed to style the UI.
+const STYLE_SHEET: &str = include_str!("embedded.css");
+
+fn main(
+    // no args
+) {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(BevyCssPlugin::default());
+
+    let sheet = app.add_style_sheet("embedded.css", STYLE_SHEET);
+
+    app.insert_resource(EmbeddedSheet(sheet))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+#[derive(Resource)]
+struct EmbeddedSheet(Handle<StyleSheetAsset>);
+
+fn setup(
+    mut commands: Commands,
+    sheet: Res<EmbeddedSheet>
+) {
+    // Camera
+    commands.spawn(Camera2dBundle::default());
+
+    // root node
+    commands.spawn(NodeBundle::default())
+        .insert((
+            Name::new("ui-root"),
+            StyleSheet::new(sheet.0.clone()),
+        ))
+        .with_children(|parent|
+        {
+            parent.spawn(NodeBundle::default())
+                .insert(Class::new("card"))
+                .with_children(|parent|
+                {
+                    parent.spawn(TextBundle::from_section("Styled without any asset", TextStyle::default()));
+                });
+        });
+}
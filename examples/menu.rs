@@ -113,7 +113,15 @@ fn enter_main_menu(
                 {
                     let mut spawn_btn = |action: MainMenuSelection|
                     {
-                        parent.spawn(ButtonBundle::default())
+                        let mut button = parent.spawn(ButtonBundle::default());
+
+                        // No high scores yet, so this one can't be pressed
+                        if let MainMenuSelection::HighScores = action
+                        {
+                            button.insert(Disabled);
+                        }
+
+                        button
                             .with_children(|parent|
                             {
                                 parent.spawn(TextBundle::from_section(
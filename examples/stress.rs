@@ -0,0 +1,91 @@
+use bevy::{
+    diagnostic::{
+        FrameTimeDiagnosticsPlugin,
+        LogDiagnosticsPlugin,
+    },
+    prelude::*,
+};
+use tomt_bevycss::prelude::{
+    BevyCssPlugin,
+    Class,
+    StyleSheet,
+};
+
+/// Depth of the generated UI tree, not counting the root node.
+const DEPTH: usize = 6;
+
+/// Number of children of each node on the generated UI tree.
+const BRANCHING: usize = 4;
+
+/// Classes assigned to each level of the tree, cycling when the tree is deeper than this list.
+const CLASSES: [&str; 4] = ["red", "green", "blue", "purple"];
+
+/// Timer used to reapply the style sheet on the whole tree.
+#[derive(Resource, Deref, DerefMut)]
+struct ReloadTimer(Timer);
+
+fn main(
+    // no args
+) {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            BevyCssPlugin::default(),
+        ))
+        .insert_resource(ReloadTimer(Timer::from_seconds(1.0, TimerMode::Repeating)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, reload_sheet)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands.spawn(NodeBundle::default())
+        .insert((
+            Name::new("root"),
+            StyleSheet::new(asset_server.load("sheets/stress.css")),
+        ))
+        .with_children(|builder| spawn_level(builder, 0));
+
+    let node_count: usize = (0..=DEPTH).map(|level| BRANCHING.pow(level as u32)).sum();
+    info!("Spawned {node_count} UI nodes");
+}
+
+/// Recursively spawns [`BRANCHING`] children until [`DEPTH`] is reached.
+fn spawn_level(
+    builder: &mut ChildBuilder,
+    level: usize
+) {
+    if level >= DEPTH
+    {
+        return;
+    }
+
+    for _ in 0..BRANCHING
+    {
+        builder.spawn(NodeBundle::default())
+            .insert(Class::new(CLASSES[level % CLASSES.len()]))
+            .with_children(|builder| spawn_level(builder, level + 1));
+    }
+}
+
+/// Reapplies the style sheet every second, so its cost shows up on frame time diagnostics.
+fn reload_sheet(
+    time: Res<Time>,
+    mut timer: ResMut<ReloadTimer>,
+    mut q_sheets: Query<&mut StyleSheet>,
+) {
+    if timer.tick(time.delta()).just_finished()
+    {
+        for mut sheet in q_sheets.iter_mut()
+        {
+            sheet.refresh();
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Checks style sheets for parse errors and unknown classes or properties, like on a pre-commit hook.
+//!
+//! Sheets are parsed with [`StyleSheetAsset::parse_strict`], so a rule with any invalid declaration is reported.
+//!
+//! ```text
+//! cargo run --features cli --bin bevycss-check -- [--json] [--manifest known.ron] assets/ui/*.css
+//! ```
+//!
+//! Diagnostics are printed as `file:line:column: message`, or as a JSON array with `--json`.
+//! Exits with `1` when any diagnostic is found, and with `2` when arguments or files can't be read.
+
+use serde::{Deserialize, Serialize};
+use std::process::ExitCode;
+use tomt_bevycss::prelude::*;
+
+/// Classes and properties which sheets may use, read from a RON file like `(classes: ["button"], properties: ["width"])`.
+///
+/// Lists which are missing or empty aren't checked.
+#[derive(Default)]
+#[derive(Deserialize)]
+#[serde(default)]
+struct Manifest
+{
+    classes: Vec<String>,
+    properties: Vec<String>,
+}
+
+/// A diagnostic as printed by `--json`.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a>
+{
+    file: &'a str,
+    line: u32,
+    column: u32,
+    message: String,
+}
+
+/// Command line arguments.
+#[derive(Default)]
+struct Args
+{
+    json: bool,
+    manifest: Option<String>,
+    files: Vec<String>,
+}
+
+const USAGE: &str = "Usage: bevycss-check [--json] [--manifest <known.ron>] <files>...";
+
+fn parse_args(
+    // no args
+) -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut input = std::env::args().skip(1);
+
+    while let Some(arg) = input.next()
+    {
+        match arg.as_str()
+        {
+            "--json" => args.json = true,
+            "--manifest" => match input.next()
+            {
+                Some(path) => args.manifest = Some(path),
+                None => return Err("Missing path after --manifest".to_string()),
+            },
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {arg}")),
+            _ => args.files.push(arg),
+        }
+    }
+
+    match args.files.is_empty()
+    {
+        true => Err(USAGE.to_string()),
+        false => Ok(args),
+    }
+}
+
+fn read_known_names(
+    path: &str
+) -> Result<KnownNames, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| format!("{path}: {err}"))?;
+
+    let manifest: Manifest = ron::from_str(&content)
+        .map_err(|err| format!("{path}: {err}"))?;
+
+    Ok(KnownNames {
+        classes: manifest.classes.into_iter().collect(),
+        properties: manifest.properties.into_iter().collect(),
+    })
+}
+
+fn main(
+    // no args
+) -> ExitCode {
+    let args = match parse_args()
+    {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let known = match args.manifest.as_deref().map(read_known_names)
+    {
+        Some(Ok(known)) => known,
+        Some(Err(message)) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+        None => KnownNames::default(),
+    };
+
+    let mut sheets = Vec::with_capacity(args.files.len());
+    for path in args.files.iter()
+    {
+        match std::fs::read_to_string(path)
+        {
+            Ok(content) => sheets.push(StyleSheetAsset::parse_strict(path, &content)),
+            Err(err) => {
+                eprintln!("{path}: {err}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let mut json = vec![];
+    let mut found = false;
+    for sheet in sheets.iter()
+    {
+        let mut diagnostics = sheet.diagnostics().to_vec();
+        diagnostics.extend(sheet.validate(&known));
+        diagnostics.sort_by_key(|diagnostic| diagnostic.location);
+
+        for SheetDiagnostic { location, error } in diagnostics
+        {
+            found = true;
+            match args.json
+            {
+                true => json.push(JsonDiagnostic {
+                    file: sheet.path(),
+                    line: location.line,
+                    column: location.column,
+                    message: error.to_string(),
+                }),
+                false => println!("{}: {error}", sheet.describe_location(location)),
+            }
+        }
+    }
+
+    if args.json
+    {
+        match serde_json::to_string_pretty(&json)
+        {
+            Ok(output) => println!("{output}"),
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    match found
+    {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
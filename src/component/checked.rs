@@ -0,0 +1,28 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent
+};
+
+/// Marks an entity as checked, like a checkbox or a toggle which is on, so it can be selected by `:checked` pseudo-class.
+///
+/// It can be combined with `:hover` and `:click`, like `.toggle:checked:hover`, to style every state of a toggle.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn toggle(mut commands: Commands, q_toggles: Query<(Entity, &Interaction, Has<Checked>), Changed<Interaction>>) {
+///     for (entity, interaction, checked) in q_toggles.iter() {
+///         // Selected by ".checkbox:checked" while checked
+///         match (interaction, checked) {
+///             (Interaction::Pressed, true) => commands.entity(entity).remove::<Checked>(),
+///             (Interaction::Pressed, false) => commands.entity(entity).insert(Checked),
+///             _ => continue,
+///         };
+///     }
+/// }
+/// ```
+#[derive(Debug, Reflect, Component, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Checked;
@@ -44,6 +44,22 @@ impl Class
     ) -> bool {
         self.0.split_ascii_whitespace().any(|c| c == class)
     }
+
+    /// Checks if any of this class names starts with the given prefix, like `slot-0` for `.slot-*`
+    pub(crate) fn matches_prefix(
+        &self,
+        prefix: &str
+    ) -> bool {
+        self.0.split_ascii_whitespace().any(|c| c.starts_with(prefix))
+    }
+
+    /// Checks if any of this class names ends with the given suffix, like `tab-active` for `.*-active`
+    pub(crate) fn matches_suffix(
+        &self,
+        suffix: &str
+    ) -> bool {
+        self.0.split_ascii_whitespace().any(|c| c.ends_with(suffix))
+    }
 }
 
 impl MatchSelectorElement
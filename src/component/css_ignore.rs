@@ -0,0 +1,23 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent
+};
+
+/// Excludes an entity and all of its descendants from style sheets, like an embedded third-party widget.
+///
+/// Ignored entities are never matched by any rule, nor receive inherited properties, so their components are left untouched.
+/// Adding it reverts every property previously applied on the subtree, and removing it applies style sheets on the subtree again.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // Neither this node nor its children are styled by "node { ... }"
+///     commands.spawn((NodeBundle::default(), CssIgnore));
+/// }
+/// ```
+#[derive(Debug, Reflect, Component, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct CssIgnore;
@@ -0,0 +1,23 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent
+};
+
+/// Marks an entity as disabled, so it can be selected by `:disabled` pseudo-class.
+///
+/// Disabled entities aren't matched by `:hover` and `:click` pseudo-classes, even when their
+/// [`Interaction`](bevy::prelude::Interaction) changes, so hover and click styles are suppressed while disabled.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // Selected by "button:disabled", but not by "button:hover"
+///     commands.spawn((ButtonBundle::default(), Disabled));
+/// }
+/// ```
+#[derive(Debug, Reflect, Component, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Disabled;
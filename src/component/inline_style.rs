@@ -0,0 +1,93 @@
+use crate::prelude::{
+    BevyCssError,
+    StyleSheetAsset,
+};
+
+use bevy::prelude::Component;
+
+/// Applies a list of `css` declarations on the entity which has this component, like the `style` attribute on `html`.
+///
+/// Declarations are parsed once, when the component is created or [`set`](InlineStyle::set), and applied after all
+/// style sheet rules, so they override any of them, unless the rule declares the property as `!important`.
+/// Declarations marked as `!important` here override even `!important` rules.
+/// Invalid declarations are skipped and reported by [`StyleDiagnostic`](crate::prelude::StyleDiagnostic) events.
+///
+/// When removed, properties it applied are reverted and style sheets are applied again on the entity.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use tomt_bevycss::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle::default(),
+///         InlineStyle::new("width: 120px; background-color: #222;"),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct InlineStyle
+{
+    source: String,
+    sheet: StyleSheetAsset,
+    errors: Vec<BevyCssError>,
+}
+
+impl InlineStyle
+{
+    /// Creates a new [`InlineStyle`] from the given declarations, like `width: 120px; color: red;`.
+    pub fn new(
+        source: impl Into<String>
+    ) -> Self {
+        let source = source.into();
+        let (sheet, errors) = StyleSheetAsset::parse_inline(&source);
+
+        Self{
+            source,
+            sheet,
+            errors,
+        }
+    }
+
+    /// Replaces the declarations, which are applied again on the entity.
+    pub fn set(
+        &mut self,
+        source: impl Into<String>
+    ) {
+        *self = Self::new(source);
+    }
+
+    /// Declarations as given on [`new`](InlineStyle::new) or [`set`](InlineStyle::set).
+    pub fn source(
+        &self
+    ) -> &str {
+        &self.source
+    }
+
+    /// Errors found while parsing the declarations.
+    pub fn errors(
+        &self
+    ) -> &[BevyCssError] {
+        &self.errors
+    }
+
+    /// Internal sheet with a single rule made of the valid declarations.
+    pub(crate) fn sheet(
+        &self
+    ) -> &StyleSheetAsset {
+        &self.sheet
+    }
+}
+
+impl Default
+for InlineStyle
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self::new("")
+    }
+}
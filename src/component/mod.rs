@@ -1,6 +1,24 @@
+mod checked;
+pub use checked::*;
+
 mod class;
 pub use class::*;
 
+mod css_ignore;
+pub use css_ignore::*;
+
+mod disabled;
+pub use disabled::*;
+
+mod pseudo_classes;
+pub use pseudo_classes::*;
+
+mod inline_style;
+pub use inline_style::*;
+
+mod style_override;
+pub use style_override::*;
+
 mod style_sheet;
 pub use style_sheet::*;
 
@@ -0,0 +1,129 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent
+};
+
+/// Custom pseudo-class states of an entity, like `selected` or `invalid`, matched by selectors like `.item:selected`.
+///
+/// Changing a state only evaluates again the rules which reference that pseudo-class, and only for the entity and its
+/// descendants, so toggling a single item doesn't style the rest of the tree again.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn select(mut items: Query<&mut PseudoClasses>) {
+///     for mut pseudo_classes in items.iter_mut() {
+///         // Selected by ".item:selected" while set
+///         pseudo_classes.toggle("selected");
+///     }
+/// }
+/// ```
+#[derive(Debug, Reflect, Component, Default, Clone)]
+#[reflect(Component)]
+pub struct PseudoClasses
+{
+    active: Vec<String>,
+    /// Names which were set or unset since the rules referencing them were last evaluated.
+    #[reflect(ignore)]
+    flipped: Vec<String>,
+}
+
+impl PseudoClasses
+{
+    /// Creates a new [`PseudoClasses`] with the given states set.
+    pub fn new(
+        names: impl IntoIterator<Item = impl Into<String>>
+    ) -> Self {
+        let mut pseudo_classes = Self::default();
+        for name in names
+        {
+            pseudo_classes.insert(name);
+        }
+        pseudo_classes
+    }
+
+    /// Checks if the given state is set.
+    pub fn contains(
+        &self,
+        name: &str
+    ) -> bool {
+        self.active.iter().any(|active| active == name)
+    }
+
+    /// Sets or unsets the given state, returning whether it changed.
+    pub fn set(
+        &mut self,
+        name: impl Into<String>,
+        enabled: bool
+    ) -> bool {
+        let name = name.into();
+        if self.contains(&name) == enabled
+        {
+            return false;
+        }
+
+        match enabled
+        {
+            true => self.active.push(name.clone()),
+            false => self.active.retain(|active| *active != name),
+        }
+
+        if !self.flipped.contains(&name)
+        {
+            self.flipped.push(name);
+        }
+        true
+    }
+
+    /// Sets the given state, returning whether it wasn't set before.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>
+    ) -> bool {
+        self.set(name, true)
+    }
+
+    /// Unsets the given state, returning whether it was set before.
+    pub fn remove(
+        &mut self,
+        name: impl Into<String>
+    ) -> bool {
+        self.set(name, false)
+    }
+
+    /// Flips the given state, returning whether it's now set.
+    pub fn toggle(
+        &mut self,
+        name: impl Into<String>
+    ) -> bool {
+        let name = name.into();
+        let enabled = !self.contains(&name);
+        self.set(name, enabled);
+        enabled
+    }
+
+    /// Iterates over all states which are set.
+    pub fn iter(
+        &self
+    ) -> impl Iterator<Item = &str> {
+        self.active.iter().map(String::as_str)
+    }
+
+    /// Checks if any state was set or unset since the last call to [`take_flipped`](PseudoClasses::take_flipped).
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn has_flipped(
+        &self
+    ) -> bool {
+        !self.flipped.is_empty()
+    }
+
+    /// Takes the names of all states which were set or unset since the last call.
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn take_flipped(
+        &mut self
+    ) -> Vec<String> {
+        std::mem::take(&mut self.flipped)
+    }
+}
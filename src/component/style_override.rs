@@ -0,0 +1,51 @@
+use bevy::{
+    prelude::{
+        Component,
+        Deref, DerefMut,
+        Reflect, ReflectComponent,
+    },
+    utils::HashSet,
+};
+
+/// Claims ownership of some properties of an entity, so style sheets don't apply them, like when they're animated by a system.
+///
+/// Properties are listed by their `css` name, like `width` or `background-color`, and all other properties are still
+/// applied as usual. Changing or removing this component styles the entity again, so properties no longer listed are
+/// applied by style sheets once more.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // `width` is left to gameplay code, while style sheets still set `height`
+///     commands.spawn((
+///         NodeBundle::default(),
+///         Class::new("bar"),
+///         StyleOverride::new(["width"]),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Reflect, Component, Default, Clone)]
+#[derive(Deref, DerefMut)]
+#[reflect(Component)]
+pub struct StyleOverride(HashSet<String>);
+
+impl StyleOverride
+{
+    /// Creates a new [`StyleOverride`] with the given property names.
+    pub fn new(
+        properties: impl IntoIterator<Item = impl Into<String>>
+    ) -> Self {
+        Self(properties.into_iter().map(Into::into).collect())
+    }
+
+    /// Checks if the given property is overridden, so it isn't applied by style sheets.
+    pub fn overrides(
+        &self,
+        property: &str
+    ) -> bool {
+        self.0.contains(property)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::property::UnitCategory;
+
 use std::{
     error::Error,
     fmt::Display
@@ -5,7 +7,8 @@ use std::{
 
 /// Errors which can happens while parsing `css` into [`crate::selector::Selector`] or [`crate::Property`].
 // TODO: Change this to Cow<'static, str>
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
 pub enum BevyCssError
 {
     /// An unsupported selector was found on a style sheet rule.
@@ -17,11 +20,34 @@ pub enum BevyCssError
     /// An invalid property value was found on a style sheet rule.
     InvalidPropertyValue(String),
 
+    /// A valid `css` property value was found on a style sheet rule, but it can't be represented by Bevy.
+    UnsupportedPropertyValue(String),
+
+    /// A property value has a unit of another category than the property expects, like an angle on `width: 10deg;`,
+    /// see [`PropertyValues::expect_unit`](crate::prelude::PropertyValues::expect_unit).
+    MismatchedUnit
+    {
+        /// Name of the property.
+        property: String,
+        /// The value with the mismatched unit, like `10deg`.
+        value: String,
+        /// Category of unit expected by the property.
+        expected: UnitCategory,
+    },
+
     /// An invalid selector was found on a style sheet rule.
     InvalidSelector,
 
     /// An unexpected token was found on a style sheet rule.
     UnexpectedToken(String),
+
+    /// A style sheet rule doesn't match any entity, which is reported when enabled by
+    /// [`BevyCssPlugin::with_unmatched_rule_warnings`](crate::BevyCssPlugin::with_unmatched_rule_warnings).
+    UnmatchedRule(String),
+
+    /// A class used by a selector isn't on the list of known classes, which is reported by
+    /// [`StyleSheetAsset::validate`](crate::prelude::StyleSheetAsset::validate).
+    UnknownClass(String),
 }
 
 impl Error
@@ -42,8 +68,12 @@ for BevyCssError
             BevyCssError::UnsupportedSelector => write!(formatter, "Unsupported selector"),
             BevyCssError::UnsupportedProperty(prop) => write!(formatter, "Unsupported property: {}", prop),
             BevyCssError::InvalidPropertyValue(value) => write!(formatter, "Invalid property value: {}", value),
+            BevyCssError::UnsupportedPropertyValue(value) => write!(formatter, "Unsupported property value: {}", value),
+            BevyCssError::MismatchedUnit { property, value, expected } => write!(formatter, "Mismatched unit on property {property}: {value}, expected a {expected}"),
             BevyCssError::InvalidSelector => write!(formatter, "Invalid selector"),
             BevyCssError::UnexpectedToken(token) => write!(formatter, "Unexpected token: {}", token),
+            BevyCssError::UnmatchedRule(selector) => write!(formatter, "Rule doesn't match any entity: {}", selector),
+            BevyCssError::UnknownClass(class) => write!(formatter, "Unknown class: {}", class),
         }
     }
 }
@@ -0,0 +1,282 @@
+//! Interpolation of the values produced by style properties, like [`Val`], [`UiRect`], [`Color`] and [`Transform`].
+//!
+//! These helpers are meant for transitions and tween systems, so they all share the same semantics:
+//! - `t` is clamped to `0.0..=1.0`, so `0.0` returns `from` and `1.0` returns `to`.
+//! - Values which can't be interpolated, like [`Val::Auto`] and [`Val::Px`], snap from `from` to `to` at `t = 0.5`.
+//!
+//! To honor [`MotionConfig::reduced`], map `t` with [`MotionConfig::progress`] before calling them.
+//!
+//! [`OpacityTransition`] and [`TransformTransition`] use them to transition the opacity and transform of an entity over time,
+//! honoring [`MotionConfig`] already.
+
+use bevy::{
+    prelude::{Color, Transform},
+    ui::{UiRect, Val},
+};
+
+mod motion_config;
+pub use motion_config::MotionConfig;
+
+mod transition;
+pub use transition::{OpacityTransition, TransformTransition, TransitionTimer};
+pub(crate) use transition::{animate_opacity_transitions, animate_transform_transitions};
+
+#[cfg(test)]
+mod tests;
+
+/// Color space used by [`lerp_color`].
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub enum ColorSpace
+{
+    /// Interpolates each channel on linear RGB, which is physically correct for blending light.
+    #[default]
+    LinearRgb,
+
+    /// Interpolates lightness, chroma and hue, which is perceptually uniform,
+    /// so halfway between two colors also looks halfway. Hue takes the shortest path around the color wheel.
+    Lch,
+}
+
+fn lerp(
+    from: f32,
+    to: f32,
+    t: f32
+) -> f32 {
+    from + (to - from) * t
+}
+
+/// Interpolates two [`Val`], see [module documentation](self) for the shared semantics.
+///
+/// Values of the same unit are interpolated, while mismatched units snap at `t = 0.5`.
+/// Use [`lerp_val_with_basis`] to interpolate between [`Val::Px`] and [`Val::Percent`].
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::interpolate::lerp_val;
+/// assert_eq!(lerp_val(Val::Px(10.0), Val::Px(20.0), 0.5), Val::Px(15.0));
+/// assert_eq!(lerp_val(Val::Px(10.0), Val::Auto, 0.4), Val::Px(10.0));
+/// assert_eq!(lerp_val(Val::Px(10.0), Val::Auto, 0.5), Val::Auto);
+/// ```
+pub fn lerp_val(
+    from: Val,
+    to: Val,
+    t: f32
+) -> Val {
+    let t = t.clamp(0.0, 1.0);
+
+    match (from, to)
+    {
+        (Val::Px(from), Val::Px(to)) => Val::Px(lerp(from, to, t)),
+        (Val::Percent(from), Val::Percent(to)) => Val::Percent(lerp(from, to, t)),
+        (Val::Vw(from), Val::Vw(to)) => Val::Vw(lerp(from, to, t)),
+        (Val::Vh(from), Val::Vh(to)) => Val::Vh(lerp(from, to, t)),
+        (Val::VMin(from), Val::VMin(to)) => Val::VMin(lerp(from, to, t)),
+        (Val::VMax(from), Val::VMax(to)) => Val::VMax(lerp(from, to, t)),
+        _ => match t < 0.5
+        {
+            true => from,
+            false => to,
+        },
+    }
+}
+
+/// Same as [`lerp_val`], but [`Val::Px`] and [`Val::Percent`] are also interpolated, using `basis` as the size which
+/// percentages are relative to, like the width of the parent node.
+///
+/// When units are mismatched, the result is in [`Val::Px`], except on `t = 0.0` and `t = 1.0`, which return `from` and `to` as they are.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::interpolate::lerp_val_with_basis;
+/// // 50% of 200px is 100px, so halfway to 0px is 50px
+/// assert_eq!(lerp_val_with_basis(Val::Percent(50.0), Val::Px(0.0), 0.5, 200.0), Val::Px(50.0));
+/// assert_eq!(lerp_val_with_basis(Val::Percent(50.0), Val::Px(0.0), 0.0, 200.0), Val::Percent(50.0));
+/// ```
+pub fn lerp_val_with_basis(
+    from: Val,
+    to: Val,
+    t: f32,
+    basis: f32
+) -> Val {
+    let t = t.clamp(0.0, 1.0);
+    let to_px = |val: Val| match val
+    {
+        Val::Px(px) => Some(px),
+        Val::Percent(percent) => Some(percent / 100.0 * basis),
+        _ => None,
+    };
+
+    match (from, to)
+    {
+        (Val::Px(_), Val::Percent(_)) | (Val::Percent(_), Val::Px(_)) if t > 0.0 && t < 1.0 => {
+            match (to_px(from), to_px(to))
+            {
+                (Some(from), Some(to)) => Val::Px(lerp(from, to, t)),
+                _ => unreachable!("Both values are either Px or Percent"),
+            }
+        }
+        _ => lerp_val(from, to, t),
+    }
+}
+
+/// Interpolates each side of two [`UiRect`] with [`lerp_val`].
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::{UiRect, Val};
+/// # use tomt_bevycss::interpolate::lerp_rect;
+/// let rect = lerp_rect(UiRect::all(Val::Px(0.0)), UiRect::all(Val::Px(10.0)), 0.5);
+/// assert_eq!(rect, UiRect::all(Val::Px(5.0)));
+/// ```
+pub fn lerp_rect(
+    from: UiRect,
+    to: UiRect,
+    t: f32
+) -> UiRect {
+    UiRect {
+        left: lerp_val(from.left, to.left, t),
+        right: lerp_val(from.right, to.right, t),
+        top: lerp_val(from.top, to.top, t),
+        bottom: lerp_val(from.bottom, to.bottom, t),
+    }
+}
+
+/// Interpolates two [`Color`] on the given [`ColorSpace`], including the alpha channel.
+///
+/// The result is always returned as [`Color::Rgba`], like colors parsed from style sheets.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::Color;
+/// # use tomt_bevycss::interpolate::{lerp_color, ColorSpace};
+/// let color = lerp_color(Color::BLACK, Color::WHITE, 0.5, ColorSpace::LinearRgb);
+/// assert_eq!(color, Color::rgb_linear(0.5, 0.5, 0.5).as_rgba());
+/// ```
+pub fn lerp_color(
+    from: Color,
+    to: Color,
+    t: f32,
+    space: ColorSpace
+) -> Color {
+    let t = t.clamp(0.0, 1.0);
+
+    match space
+    {
+        ColorSpace::LinearRgb => {
+            let [from_r, from_g, from_b, from_a] = from.as_linear_rgba_f32();
+            let [to_r, to_g, to_b, to_a] = to.as_linear_rgba_f32();
+
+            Color::rgba_linear(
+                lerp(from_r, to_r, t),
+                lerp(from_g, to_g, t),
+                lerp(from_b, to_b, t),
+                lerp(from_a, to_a, t),
+            ).as_rgba()
+        }
+
+        ColorSpace::Lch => {
+            let [from_l, from_c, from_h, from_a] = from.as_lcha_f32();
+            let [to_l, to_c, to_h, to_a] = to.as_lcha_f32();
+
+            // Achromatic colors, like white and black, have no meaningful hue, so the other one is kept
+            let (from_h, to_h) = match (from_c < f32::EPSILON, to_c < f32::EPSILON)
+            {
+                (true, false) => (to_h, to_h),
+                (false, true) => (from_h, from_h),
+                _ => (from_h, to_h),
+            };
+
+            // Take the shortest path around the hue circle
+            let mut delta = (to_h - from_h) % 360.0;
+            if delta > 180.0
+            {
+                delta -= 360.0;
+            }
+            else if delta < -180.0
+            {
+                delta += 360.0;
+            }
+
+            Color::lcha(
+                lerp(from_l, to_l, t),
+                lerp(from_c, to_c, t),
+                (from_h + delta * t).rem_euclid(360.0),
+                lerp(from_a, to_a, t),
+            ).as_rgba()
+        }
+    }
+}
+
+/// Interpolates two opacities, which are clamped to `0.0..=1.0` first, see [module documentation](self) for the shared semantics.
+///
+/// Use [`with_opacity`] to apply the result to a color.
+///
+/// # Examples
+///
+/// ```
+/// # use tomt_bevycss::interpolate::lerp_opacity;
+/// assert_eq!(lerp_opacity(0.0, 1.0, 0.25), 0.25);
+/// assert_eq!(lerp_opacity(2.0, 0.0, 0.5), 0.5);
+/// ```
+pub fn lerp_opacity(
+    from: f32,
+    to: f32,
+    t: f32
+) -> f32 {
+    lerp(from.clamp(0.0, 1.0), to.clamp(0.0, 1.0), t.clamp(0.0, 1.0))
+}
+
+/// Scales the alpha channel of `color` by `opacity`, clamped to `0.0..=1.0`, so colors which are already translucent keep their ratio.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::Color;
+/// # use tomt_bevycss::interpolate::with_opacity;
+/// assert_eq!(with_opacity(Color::rgba(1.0, 0.0, 0.0, 0.5), 0.5), Color::rgba(1.0, 0.0, 0.0, 0.25));
+/// ```
+pub fn with_opacity(
+    color: Color,
+    opacity: f32
+) -> Color {
+    color.with_a(color.a() * opacity.clamp(0.0, 1.0))
+}
+
+/// Interpolates two [`Transform`], see [module documentation](self) for the shared semantics.
+///
+/// Translation and scale are interpolated on each axis, while rotation is spherically interpolated,
+/// taking the shortest path, so rotating from `350deg` to `10deg` goes through `0deg` instead of `180deg`.
+/// Matrices can be decomposed into a [`Transform`] with [`Transform::from_matrix`].
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::{Transform, Vec3};
+/// # use tomt_bevycss::interpolate::lerp_transform;
+/// let from = Transform::from_xyz(0.0, 0.0, 0.0);
+/// let to = Transform::from_xyz(10.0, 20.0, 0.0).with_scale(Vec3::splat(3.0));
+///
+/// let transform = lerp_transform(from, to, 0.5);
+/// assert_eq!(transform.translation, Vec3::new(5.0, 10.0, 0.0));
+/// assert_eq!(transform.scale, Vec3::splat(2.0));
+/// ```
+pub fn lerp_transform(
+    from: Transform,
+    to: Transform,
+    t: f32
+) -> Transform {
+    let t = t.clamp(0.0, 1.0);
+
+    Transform {
+        translation: from.translation.lerp(to.translation, t),
+        rotation: from.rotation.normalize().slerp(to.rotation.normalize(), t),
+        scale: from.scale.lerp(to.scale, t),
+    }
+}
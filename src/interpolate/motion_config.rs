@@ -0,0 +1,38 @@
+use bevy::prelude::Resource;
+
+/// Global motion preferences, like the `prefers-reduced-motion` media query on web browsers.
+///
+/// Transitions and animations should map their progress with [`progress`](MotionConfig::progress), so they
+/// snap to their final values when [`reduced`](MotionConfig::reduced) is set, instead of being interpolated.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::interpolate::{lerp_val, MotionConfig};
+/// let motion = MotionConfig { reduced: true };
+/// assert_eq!(lerp_val(Val::Px(0.0), Val::Px(10.0), motion.progress(0.2)), Val::Px(10.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct MotionConfig
+{
+    /// Disables all transitions and animations, so final values are applied immediately.
+    pub reduced: bool,
+}
+
+impl MotionConfig
+{
+    /// Maps the progress `t` of a transition or animation, returning `1.0` when motion is [`reduced`](MotionConfig::reduced).
+    pub fn progress(
+        &self,
+        t: f32
+    ) -> f32 {
+        match self.reduced
+        {
+            true => 1.0,
+            false => t,
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use super::*;
+use crate::{
+    prelude::BevyCssPlugin,
+    test_utils::{TestApp, TestNode},
+};
+
+use bevy::{
+    prelude::{BackgroundColor, Color, Entity, Quat, Transform, Vec3},
+    time::TimeUpdateStrategy,
+    ui::{UiRect, Val},
+    utils::Duration,
+};
+
+fn assert_color_eq(
+    left: Color,
+    right: Color
+) {
+    let left = left.as_rgba_f32();
+    let right = right.as_rgba_f32();
+
+    assert!(
+        left.iter().zip(right.iter()).all(|(l, r)| (l - r).abs() < 1e-3),
+        "{left:?} != {right:?}"
+    );
+}
+
+#[test]
+fn lerp_val_same_unit()
+{
+    assert_eq!(lerp_val(Val::Px(0.0), Val::Px(100.0), 0.25), Val::Px(25.0));
+    assert_eq!(lerp_val(Val::Percent(10.0), Val::Percent(20.0), 0.5), Val::Percent(15.0));
+    assert_eq!(lerp_val(Val::Vw(100.0), Val::Vw(0.0), 0.75), Val::Vw(25.0));
+}
+
+#[test]
+fn lerp_val_clamps_t()
+{
+    assert_eq!(lerp_val(Val::Px(0.0), Val::Px(100.0), -1.0), Val::Px(0.0));
+    assert_eq!(lerp_val(Val::Px(0.0), Val::Px(100.0), 2.0), Val::Px(100.0));
+}
+
+#[test]
+fn lerp_val_mismatched_units_snap_halfway()
+{
+    assert_eq!(lerp_val(Val::Auto, Val::Px(10.0), 0.0), Val::Auto);
+    assert_eq!(lerp_val(Val::Auto, Val::Px(10.0), 0.49), Val::Auto);
+    assert_eq!(lerp_val(Val::Auto, Val::Px(10.0), 0.5), Val::Px(10.0));
+    assert_eq!(lerp_val(Val::Px(10.0), Val::Percent(50.0), 0.4), Val::Px(10.0));
+    assert_eq!(lerp_val(Val::Px(10.0), Val::Percent(50.0), 0.6), Val::Percent(50.0));
+}
+
+#[test]
+fn lerp_val_with_basis_converts_percent()
+{
+    assert_eq!(lerp_val_with_basis(Val::Px(0.0), Val::Percent(100.0), 0.5, 300.0), Val::Px(150.0));
+    assert_eq!(lerp_val_with_basis(Val::Px(0.0), Val::Percent(100.0), 1.0, 300.0), Val::Percent(100.0));
+    // Same units and other mismatches behave like `lerp_val`
+    assert_eq!(lerp_val_with_basis(Val::Percent(0.0), Val::Percent(100.0), 0.5, 300.0), Val::Percent(50.0));
+    assert_eq!(lerp_val_with_basis(Val::Vh(10.0), Val::Percent(100.0), 0.4, 300.0), Val::Vh(10.0));
+}
+
+#[test]
+fn lerp_rect_each_side()
+{
+    let from = UiRect::new(Val::Px(0.0), Val::Auto, Val::Percent(0.0), Val::Px(10.0));
+    let to = UiRect::new(Val::Px(8.0), Val::Px(5.0), Val::Percent(40.0), Val::Px(10.0));
+
+    assert_eq!(
+        lerp_rect(from, to, 0.75),
+        UiRect::new(Val::Px(6.0), Val::Px(5.0), Val::Percent(30.0), Val::Px(10.0))
+    );
+}
+
+#[test]
+fn lerp_color_linear_rgb()
+{
+    let color = lerp_color(Color::rgba(1.0, 0.0, 0.0, 0.0), Color::rgba(0.0, 0.0, 1.0, 1.0), 0.5, ColorSpace::LinearRgb);
+    assert_color_eq(color, Color::rgba_linear(0.5, 0.0, 0.5, 0.5));
+
+    assert_color_eq(lerp_color(Color::RED, Color::BLUE, 0.0, ColorSpace::LinearRgb), Color::RED);
+    assert_color_eq(lerp_color(Color::RED, Color::BLUE, 1.0, ColorSpace::LinearRgb), Color::BLUE);
+}
+
+#[test]
+fn lerp_color_lch_shortest_hue()
+{
+    // 350° and 10° are 20° apart, so halfway is 0°, not 180°
+    let from = Color::lch(0.5, 0.5, 350.0);
+    let to = Color::lch(0.5, 0.5, 10.0);
+
+    assert_color_eq(lerp_color(from, to, 0.5, ColorSpace::Lch), Color::lch(0.5, 0.5, 0.0));
+    assert_color_eq(lerp_color(from, to, 0.0, ColorSpace::Lch), from);
+    assert_color_eq(lerp_color(from, to, 1.0, ColorSpace::Lch), to);
+}
+
+#[test]
+fn lerp_color_lch_keeps_hue_of_chromatic_color()
+{
+    let red = Color::lch(0.5, 0.5, 40.0);
+    let gray = Color::lch(0.5, 0.0, 200.0);
+
+    assert_color_eq(lerp_color(red, gray, 0.5, ColorSpace::Lch), Color::lch(0.5, 0.25, 40.0));
+}
+
+#[test]
+fn reduced_motion_applies_final_values_immediately()
+{
+    let motion = MotionConfig::default();
+    assert_eq!(lerp_val(Val::Px(0.0), Val::Px(100.0), motion.progress(0.25)), Val::Px(25.0));
+
+    let motion = MotionConfig { reduced: true };
+    assert_eq!(lerp_val(Val::Px(0.0), Val::Px(100.0), motion.progress(0.0)), Val::Px(100.0));
+    assert_color_eq(lerp_color(Color::BLACK, Color::WHITE, motion.progress(0.25), ColorSpace::Lch), Color::WHITE);
+}
+
+#[test]
+fn opacity_transition_midpoint()
+{
+    let opacity = lerp_opacity(1.0, 0.0, 0.5);
+    assert_eq!(opacity, 0.5);
+
+    assert_color_eq(with_opacity(Color::rgba(0.2, 0.4, 0.6, 0.8), opacity), Color::rgba(0.2, 0.4, 0.6, 0.4));
+    assert_color_eq(with_opacity(Color::WHITE, 0.0), Color::rgba(1.0, 1.0, 1.0, 0.0));
+}
+
+#[test]
+fn rotate_transition_midpoint_takes_shortest_path()
+{
+    // 350° and 10° are 20° apart, so halfway is 0°, not 180°
+    let from = Transform::from_rotation(Quat::from_rotation_z(350f32.to_radians()));
+    let to = Transform::from_rotation(Quat::from_rotation_z(10f32.to_radians()))
+        .with_translation(Vec3::new(10.0, 0.0, 0.0))
+        .with_scale(Vec3::splat(2.0));
+
+    let transform = lerp_transform(from, to, 0.5);
+    assert!(transform.rotation.angle_between(Quat::IDENTITY) < 1e-3, "{:?}", transform.rotation);
+    assert_eq!(transform.translation, Vec3::new(5.0, 0.0, 0.0));
+    assert_eq!(transform.scale, Vec3::splat(1.5));
+
+    assert!(lerp_transform(from, to, 0.0).rotation.angle_between(from.rotation) < 1e-3);
+    assert!(lerp_transform(from, to, 1.0).rotation.angle_between(to.rotation) < 1e-3);
+}
+
+/// Creates an app with a red node, which frames advance time by 100ms.
+fn create_transition_app(
+    plugin: BevyCssPlugin
+) -> (TestApp, Entity) {
+    let mut app = TestApp::with_plugin(plugin, ".panel { background-color: red; }");
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    let tree = app.spawn_tree(TestNode::node().class("panel"));
+    app.step_until_styled();
+
+    (app, tree[0])
+}
+
+#[test]
+fn opacity_transition_system_midpoint()
+{
+    let (mut app, panel) = create_transition_app(BevyCssPlugin::without_hot_reload());
+    app.world.entity_mut(panel).insert(OpacityTransition::new(1.0, 0.0, Duration::from_secs(1)));
+    app.step(5);
+
+    let background = app.world.get::<BackgroundColor>(panel).unwrap().0;
+    assert_color_eq(background, Color::rgba(1.0, 0.0, 0.0, 0.5));
+    assert!(app.world.get::<OpacityTransition>(panel).is_some());
+
+    app.step(5);
+    assert_color_eq(app.world.get::<BackgroundColor>(panel).unwrap().0, Color::rgba(1.0, 0.0, 0.0, 0.0));
+    assert!(app.world.get::<OpacityTransition>(panel).is_none(), "Should be removed once finished");
+}
+
+#[test]
+fn rotate_transition_system_midpoint_takes_shortest_path()
+{
+    let (mut app, panel) = create_transition_app(BevyCssPlugin::without_hot_reload());
+    let from = Transform::from_rotation(Quat::from_rotation_z(350f32.to_radians()));
+    let to = Transform::from_rotation(Quat::from_rotation_z(10f32.to_radians()));
+    app.world.entity_mut(panel).insert(TransformTransition::new(from, to, Duration::from_secs(1)));
+    app.step(5);
+
+    let rotation = app.world.get::<Transform>(panel).unwrap().rotation;
+    assert!(rotation.angle_between(Quat::IDENTITY) < 1e-3, "{rotation:?}");
+}
+
+#[test]
+fn reduced_motion_applies_transitions_instantly()
+{
+    let (mut app, panel) = create_transition_app(BevyCssPlugin::without_hot_reload().with_reduced_motion(true));
+    app.world.entity_mut(panel).insert(OpacityTransition::new(1.0, 0.0, Duration::from_secs(1)));
+    app.step(1);
+
+    assert_color_eq(app.world.get::<BackgroundColor>(panel).unwrap().0, Color::rgba(1.0, 0.0, 0.0, 0.0));
+    assert!(app.world.get::<OpacityTransition>(panel).is_none());
+}
@@ -0,0 +1,182 @@
+use super::{
+    lerp_opacity,
+    lerp_transform,
+    with_opacity,
+    MotionConfig,
+};
+
+use bevy::{
+    prelude::{
+        BackgroundColor,
+        Color,
+        Commands,
+        Component,
+        Entity,
+        Query,
+        Res,
+        Time,
+        Transform,
+    },
+    utils::Duration,
+};
+
+/// Time elapsed on a transition, shared by [`OpacityTransition`] and [`TransformTransition`].
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub struct TransitionTimer
+{
+    /// How long the transition takes to reach its final value.
+    pub duration: Duration,
+    elapsed: Duration,
+}
+
+impl TransitionTimer
+{
+    /// Creates a timer for a transition which takes the given duration.
+    pub fn new(
+        duration: Duration
+    ) -> Self {
+        Self { duration, elapsed: Duration::ZERO }
+    }
+
+    /// Time elapsed since the transition started, up to its [`duration`](TransitionTimer::duration).
+    pub fn elapsed(
+        &self
+    ) -> Duration {
+        self.elapsed
+    }
+
+    /// Advances the timer by `delta` and returns the progress of the transition, from `0.0` to `1.0`,
+    /// mapped by [`MotionConfig::progress`], so it's `1.0` right away when motion is reduced.
+    pub fn tick(
+        &mut self,
+        delta: Duration,
+        motion: &MotionConfig
+    ) -> f32 {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+
+        let t = match self.duration.is_zero()
+        {
+            true => 1.0,
+            false => self.elapsed.as_secs_f32() / self.duration.as_secs_f32(),
+        };
+
+        motion.progress(t)
+    }
+}
+
+/// Fades the [`BackgroundColor`] of an entity from one opacity to another, with [`lerp_opacity`].
+///
+/// The background color when the transition starts is taken as fully opaque, so its alpha is scaled by the opacity,
+/// see [`with_opacity`]. The component is removed once the transition is finished.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::{prelude::*, utils::Duration};
+/// # use tomt_bevycss::interpolate::OpacityTransition;
+/// fn fade_in(
+///     mut commands: Commands,
+///     q_spawned: Query<Entity, Added<Node>>,
+/// ) {
+///     for entity in q_spawned.iter()
+///     {
+///         commands.entity(entity).insert(OpacityTransition::new(0.0, 1.0, Duration::from_millis(300)));
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct OpacityTransition
+{
+    /// Opacity when the transition starts.
+    pub from: f32,
+    /// Opacity when the transition ends.
+    pub to: f32,
+    /// Time elapsed on the transition.
+    pub timer: TransitionTimer,
+    /// Background color when the transition started, which is faded.
+    color: Option<Color>,
+}
+
+impl OpacityTransition
+{
+    /// Creates a transition from the opacity `from` to `to`, which takes the given duration.
+    pub fn new(
+        from: f32,
+        to: f32,
+        duration: Duration
+    ) -> Self {
+        Self { from, to, timer: TransitionTimer::new(duration), color: None }
+    }
+}
+
+/// Rotates and scales an entity from one [`Transform`] to another, with [`lerp_transform`],
+/// so rotations take the shortest way around.
+///
+/// `bevy_ui` lays out the translation of nodes, so only rotation and scale are written.
+/// The component is removed once the transition is finished.
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+pub struct TransformTransition
+{
+    /// Transform when the transition starts.
+    pub from: Transform,
+    /// Transform when the transition ends.
+    pub to: Transform,
+    /// Time elapsed on the transition.
+    pub timer: TransitionTimer,
+}
+
+impl TransformTransition
+{
+    /// Creates a transition from the transform `from` to `to`, which takes the given duration.
+    pub fn new(
+        from: Transform,
+        to: Transform,
+        duration: Duration
+    ) -> Self {
+        Self { from, to, timer: TransitionTimer::new(duration) }
+    }
+}
+
+/// Advances each [`OpacityTransition`] and fades the [`BackgroundColor`] of its entity.
+pub(crate) fn animate_opacity_transitions(
+    time: Res<Time>,
+    motion: Res<MotionConfig>,
+    mut commands: Commands,
+    mut q_transitions: Query<(Entity, &mut OpacityTransition, &mut BackgroundColor)>,
+) {
+    for (entity, mut transition, mut background) in q_transitions.iter_mut()
+    {
+        let color = *transition.color.get_or_insert(background.0);
+        let t = transition.timer.tick(time.delta(), &motion);
+        background.0 = with_opacity(color, lerp_opacity(transition.from, transition.to, t));
+
+        if t >= 1.0
+        {
+            commands.entity(entity).remove::<OpacityTransition>();
+        }
+    }
+}
+
+/// Advances each [`TransformTransition`] and rotates and scales its entity.
+pub(crate) fn animate_transform_transitions(
+    time: Res<Time>,
+    motion: Res<MotionConfig>,
+    mut commands: Commands,
+    mut q_transitions: Query<(Entity, &mut TransformTransition, &mut Transform)>,
+) {
+    for (entity, mut transition, mut transform) in q_transitions.iter_mut()
+    {
+        let t = transition.timer.tick(time.delta(), &motion);
+        let interpolated = lerp_transform(transition.from, transition.to, t);
+        transform.rotation = interpolated.rotation;
+        transform.scale = interpolated.scale;
+
+        if t >= 1.0
+        {
+            commands.entity(entity).remove::<TransformTransition>();
+        }
+    }
+}
@@ -2,36 +2,73 @@
 
 mod component;
 pub mod error;
+pub mod interpolate;
 mod parser;
 pub mod plugins;
 pub mod property;
-mod selector;
+pub mod selector;
 mod stylesheet;
 pub mod system;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils;
 
 use crate::{
-    property::Property,
+    property::{text::TextBindingRegistry, Property},
+    stylesheet::StyleSheetAsset,
     system::{
         sets::BevyCssSet,
         ComponentFilterRegistry,
+        CssCapabilities, CssConditionFn, CssConditions,
+        FieldFilter,
+        is_registered_property,
+        PropertyResetRegistry, PropertyResetState,
+        StyleRootProvider, StyleRoots,
+        VariantFilter,
     },
 };
 
 use bevy::{
     ecs::system::SystemState,
     prelude::*,
+    reflect::{Enum, Reflect},
 };
 
 /// use `tomt_bevycss::prelude::*;` to import common components, and plugins and utility functions.
 pub mod prelude {
     pub use super::{
-        component::{Class, StyleSheet},
+        component::{Checked, Class, CssIgnore, Disabled, InlineStyle, PseudoClasses, StyleOverride, StyleSheet},
         error::BevyCssError,
+        parser::StyleSheetParser,
         plugins::BevyCssPlugin,
-        property::{Property, PropertyValues},
-        stylesheet::StyleSheetAsset,
+        property::{
+            BareNumberPolicy,
+            CascadeAuditEnabled, CascadeAuditReport, CascadeConflict,
+            Property, PropertyContext, PropertyValues,
+            RootFontSize,
+            RuleMatchTracking,
+        },
+        stylesheet::{KnownNames, SheetDiagnostic, SourceLocation, StyleRule, StyleSheetAsset, StyleSheetStats},
+        system::{
+            sets::BevyCssSet,
+            CssCapabilities,
+            CssConditions,
+            CssFlags,
+            HotReloadEnabled,
+            LastAppliedStyles,
+            NonUiDescendants,
+            SelectorMatchStats,
+            StyleApplied,
+            StyleChanged,
+            StyleDiagnostic,
+            StyleRootProvider,
+            StyleRoots,
+        },
+        AddStyleSheet,
         RegisterComponentSelector,
+        RegisterCssCondition,
         RegisterProperty,
+        RegisterStyleRootProvider,
+        RegisterTextBinding,
     };
 }
 
@@ -70,6 +107,76 @@ pub trait RegisterComponentSelector
     ) -> &mut Self
     where
         T: Component;
+
+    /// Same as [`register_component_selector`](RegisterComponentSelector::register_component_selector), but for enum
+    /// components which implements [`Reflect`], which can also be matched by their current variant, like `interaction(hovered)`.
+    ///
+    /// Variants are matched on `kebab-case`, ignoring case, so `MyState::FlexStart` is matched by `my-state(flex-start)`.
+    /// Entities are styled again whenever the component changes, so the matched rules follow the variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use tomt_bevycss::prelude::*;
+    /// #[derive(Component, Reflect)]
+    /// enum Health
+    /// {
+    ///     Alive,
+    ///     Dead,
+    /// }
+    ///
+    /// # fn some_main() {
+    /// # let mut app = App::new();
+    /// app.register_component_selector_reflect::<Health>("health");
+    /// // You may use it as selector now, like
+    /// // health(dead) .portrait {
+    /// //      background-color: gray;
+    /// // }
+    /// # }
+    /// ```
+    fn register_component_selector_reflect<T>(
+        &mut self,
+        name: &'static str
+    ) -> &mut Self
+    where
+        T: Component + Enum;
+
+    /// Registers a reflected component as a selector, which can also be matched by the value of its fields,
+    /// like `health[current=0]`. Rules matching a field are evaluated again whenever the component changes.
+    ///
+    /// Fields are accessed by their reflection path, like `stats.level`, and compared by value: numbers numerically,
+    /// booleans and strings by their text, and enums by their variant, like `fast-running`.
+    /// Components registered with [`register_component_selector_reflect`](RegisterComponentSelector::register_component_selector_reflect)
+    /// can be matched by their fields too, and by their variant, like `interaction[variant=pressed]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use tomt_bevycss::prelude::*;
+    /// #[derive(Component, Reflect)]
+    /// struct Health
+    /// {
+    ///     current: u32,
+    ///     poisoned: bool,
+    /// }
+    ///
+    /// # fn some_main() {
+    /// # let mut app = App::new();
+    /// app.register_component_selector_fields::<Health>("health");
+    /// // You may use it as selector now, like
+    /// // health[poisoned=true] .portrait {
+    /// //      background-color: green;
+    /// // }
+    /// # }
+    /// ```
+    fn register_component_selector_fields<T>(
+        &mut self,
+        name: &'static str
+    ) -> &mut Self
+    where
+        T: Component + Reflect;
 }
 
 impl RegisterComponentSelector
@@ -94,12 +201,52 @@ for bevy::prelude::App
 
         self
     }
+
+    fn register_component_selector_reflect<T>(
+        &mut self,
+        name: &'static str
+    ) -> &mut Self
+    where
+        T: Component + Enum,
+    {
+        let filter = VariantFilter::<T>::new(&mut self.world);
+
+        self.world
+            .get_resource_or_insert_with::<ComponentFilterRegistry>(|| {
+                ComponentFilterRegistry(Default::default())
+            })
+            .0
+            .insert(name, Box::new(filter));
+
+        self
+    }
+
+    fn register_component_selector_fields<T>(
+        &mut self,
+        name: &'static str
+    ) -> &mut Self
+    where
+        T: Component + Reflect,
+    {
+        let filter = FieldFilter::<T>::new(&mut self.world);
+
+        self.world
+            .get_resource_or_insert_with::<ComponentFilterRegistry>(|| {
+                ComponentFilterRegistry(Default::default())
+            })
+            .0
+            .insert(name, Box::new(filter));
+
+        self
+    }
 }
 
 /// Utility trait which adds the [`register_property`](RegisterProperty::register_property) function
 /// on [`App`](bevy::prelude::App) to add a [`Property`] parser.
 ///
 /// You need to register only custom properties which implements [`Property`] trait.
+/// A property registered with the [`name`](Property::name) of another one replaces it, like a custom `background-color`
+/// replacing the built-in one, since only the property registered last with a given name is applied and reset.
 pub trait RegisterProperty
 {
     fn register_property<T>(
@@ -118,6 +265,226 @@ for bevy::prelude::App
     where
         T: Property + 'static,
     {
-        self.add_systems(Update, T::apply_system.in_set(BevyCssSet::Apply))
+        self.world
+            .get_resource_or_insert_with(CssCapabilities::default)
+            .insert(T::name());
+
+        let reset_state = PropertyResetState::<T>::new(&mut self.world);
+        self.world
+            .get_resource_or_insert_with(PropertyResetRegistry::default)
+            .0
+            .insert(T::name(), Box::new(reset_state));
+
+        self.add_systems(Update, T::apply_system
+            .run_if(is_registered_property::<T>)
+            .in_set(BevyCssSet::Apply)
+        )
+    }
+}
+
+/// Utility trait which adds the [`add_style_sheet`](AddStyleSheet::add_style_sheet) function on [`App`](bevy::prelude::App)
+/// to add a style sheet which isn't read by the [`AssetServer`], like one embedded on the binary with `include_str!`.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_plugins((MinimalPlugins, AssetPlugin::default(), BevyCssPlugin::without_hot_reload()));
+/// // let handle = app.add_style_sheet("ui.css", include_str!("ui.css"));
+/// let handle = app.add_style_sheet("ui.css", ".panel { width: 10px; }");
+///
+/// app.world.spawn((NodeBundle::default(), StyleSheet::new(handle)));
+/// ```
+pub trait AddStyleSheet
+{
+    /// Parses the given content and adds it to [`Assets<StyleSheetAsset>`], returning its handle.
+    ///
+    /// `path` is used on diagnostics, and `@import`ed sheets are loaded by the [`AssetServer`] relative to it.
+    fn add_style_sheet(
+        &mut self,
+        path: &str,
+        content: &str
+    ) -> Handle<StyleSheetAsset>;
+}
+
+impl AddStyleSheet
+for bevy::prelude::App
+{
+    fn add_style_sheet(
+        &mut self,
+        path: &str,
+        content: &str
+    ) -> Handle<StyleSheetAsset> {
+        let mut sheet = StyleSheetAsset::parse(path, content);
+
+        // Imported paths are relative to the importing sheet, like on the asset loader
+        if let Some(asset_server) = self.world.get_resource::<AssetServer>()
+        {
+            let dir = std::path::Path::new(path)
+                .parent()
+                .map(|dir| dir.to_path_buf())
+                .unwrap_or_default();
+
+            sheet.imports = sheet.import_paths()
+                .iter()
+                .map(|path| asset_server.load(dir.join(path)))
+                .collect();
+        }
+
+        self.world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(sheet)
+    }
+}
+
+/// Utility trait which adds the [`register_css_condition`](RegisterCssCondition::register_css_condition) function
+/// on [`App`](bevy::prelude::App) to add a condition used by `@when` blocks of style sheets.
+///
+/// Rules inside a `@when` block are only applied while its condition holds, and they are applied or reverted
+/// as soon as the condition changes, see [`CssConditions`].
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_plugins((MinimalPlugins, AssetPlugin::default(), BevyCssPlugin::without_hot_reload()));
+/// #[derive(Resource)]
+/// struct InMenu(bool);
+///
+/// // Used as `@when state("menu") { .hud { display: none; } }`
+/// app.insert_resource(InMenu(false))
+///     .register_css_condition("menu", |world| world.resource::<InMenu>().0);
+/// ```
+pub trait RegisterCssCondition
+{
+    /// Adds a condition with the given name, which is evaluated against the [`World`] on every frame.
+    fn register_css_condition(
+        &mut self,
+        name: &str,
+        predicate: CssConditionFn
+    ) -> &mut Self;
+}
+
+impl RegisterCssCondition
+for bevy::prelude::App
+{
+    fn register_css_condition(
+        &mut self,
+        name: &str,
+        predicate: CssConditionFn
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(CssConditions::default)
+            .register(name, predicate);
+
+        self
+    }
+}
+
+/// Utility trait which adds the [`register_text_binding`](RegisterTextBinding::register_text_binding) function
+/// on [`App`](bevy::prelude::App) to reference fields of a component on `text-content`.
+///
+/// Placeholders are resolved against the component on the styled entity, or on its closest ancestor which has it,
+/// and the text is updated whenever the resolved value changes, see [`TextTemplate`](property::text::TextTemplate).
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_plugins((MinimalPlugins, AssetPlugin::default(), BevyCssPlugin::without_hot_reload()));
+/// #[derive(Component, Reflect)]
+/// struct Health
+/// {
+///     current: u32,
+/// }
+///
+/// // Used as `.hp-label { text-content: "HP: {health.current}"; }`
+/// app.register_text_binding::<Health>("health");
+/// ```
+pub trait RegisterTextBinding
+{
+    /// Adds a binding with the given name, which placeholders like `{name.field}` are resolved against.
+    fn register_text_binding<T>(
+        &mut self,
+        name: &str
+    ) -> &mut Self
+    where
+        T: Component + Reflect;
+}
+
+impl RegisterTextBinding
+for bevy::prelude::App
+{
+    fn register_text_binding<T>(
+        &mut self,
+        name: &str
+    ) -> &mut Self
+    where
+        T: Component + Reflect,
+    {
+        self.world
+            .get_resource_or_insert_with(TextBindingRegistry::default)
+            .register::<T>(name);
+
+        self
+    }
+}
+
+/// Utility trait which adds the [`register_style_root_provider`](RegisterStyleRootProvider::register_style_root_provider) function
+/// on [`App`](bevy::prelude::App) to change how the root entities which style sheets apply to are found.
+///
+/// The provider replaces the default one, which finds all UI nodes with a [`StyleSheet`](prelude::StyleSheet),
+/// so those are only roots if it returns them, like by using [`style_sheet_roots`](system::style_sheet_roots) too.
+/// See [`StyleRootProvider`] for more.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_plugins((MinimalPlugins, AssetPlugin::default(), BevyCssPlugin::without_hot_reload()));
+/// #[derive(Component)]
+/// struct UiRoot;
+///
+/// #[derive(Resource)]
+/// struct UiSheet(Handle<StyleSheetAsset>);
+///
+/// fn ui_roots(
+///     q_roots: Query<Entity, With<UiRoot>>,
+///     sheet: Res<UiSheet>,
+/// ) -> StyleRoots {
+///     q_roots.iter()
+///         .map(|root| (root, vec![sheet.0.clone()]))
+///         .collect()
+/// }
+///
+/// let handle = app.add_style_sheet("ui.css", ".panel { width: 10px; }");
+/// app.insert_resource(UiSheet(handle))
+///     .register_style_root_provider(ui_roots);
+/// ```
+pub trait RegisterStyleRootProvider
+{
+    /// Sets the system which finds the root entities, and the sheets applied to each of them, on every frame.
+    fn register_style_root_provider<M>(
+        &mut self,
+        provider: impl IntoSystem<(), StyleRoots, M>
+    ) -> &mut Self;
+}
+
+impl RegisterStyleRootProvider
+for bevy::prelude::App
+{
+    fn register_style_root_provider<M>(
+        &mut self,
+        provider: impl IntoSystem<(), StyleRoots, M>
+    ) -> &mut Self {
+        self.insert_resource(StyleRootProvider::new(provider))
     }
 }
@@ -1,17 +1,49 @@
 mod style_sheet_parser;
-pub(crate) use style_sheet_parser::*;
+pub use style_sheet_parser::StyleSheetParser;
+pub(crate) use style_sheet_parser::{ParsedStyleSheet, LINT_TARGET};
 
 mod property_parser;
-use property_parser::PropertyParser;
+pub(crate) use property_parser::PropertyParser;
 
-use crate::prelude::BevyCssError;
+use crate::{
+    prelude::BevyCssError,
+    property::{PropertyToken, PropertyValues},
+    stylesheet::SheetDiagnostic,
+};
 
 use cssparser::{
     Parser, ParseError,
     ToCss, Token,
 };
-use smallvec::{smallvec, SmallVec};
+use smallvec::SmallVec;
 
+/// Gets what went wrong on the given error, describing errors of `cssparser` itself as unexpected tokens.
+pub(crate) fn to_error(
+    error: &ParseError<BevyCssError>
+) -> BevyCssError {
+    match &error.kind
+    {
+        cssparser::ParseErrorKind::Basic(b) => match b
+        {
+            cssparser::BasicParseErrorKind::UnexpectedToken(token) => BevyCssError::UnexpectedToken(token.to_css_string()),
+            cssparser::BasicParseErrorKind::EndOfInput => BevyCssError::UnexpectedToken("end of input".to_owned()),
+            cssparser::BasicParseErrorKind::AtRuleInvalid(name) => BevyCssError::UnexpectedToken(format!("@{name}")),
+            cssparser::BasicParseErrorKind::AtRuleBodyInvalid => BevyCssError::UnexpectedToken("{".to_owned()),
+            cssparser::BasicParseErrorKind::QualifiedRuleInvalid => BevyCssError::InvalidSelector,
+        },
+        cssparser::ParseErrorKind::Custom(c) => c.clone(),
+    }
+}
+
+/// Gets what went wrong and where on the given error, see [`to_error`].
+fn to_diagnostic(
+    error: &ParseError<BevyCssError>
+) -> SheetDiagnostic {
+    SheetDiagnostic {
+        location: error.location.into(),
+        error: to_error(error),
+    }
+}
 
 fn format_error(
     error: ParseError<BevyCssError>
@@ -37,16 +69,94 @@ fn format_error(
     )
 }
 
+/// Parses all remaining values on the given parser into [`PropertyToken`]s.
+///
+/// Parsing stops on a trailing `!important`, in which case `true` is returned along with the values.
 fn parse_values<'i>(
     parser: &mut Parser<'i, '_>,
-) -> Result<SmallVec<[Token<'i>; 8]>, ParseError<'i, BevyCssError>> {
+) -> Result<(SmallVec<[PropertyToken; 8]>, bool), ParseError<'i, BevyCssError>> {
     let mut values = SmallVec::new();
+    loop
+    {
+        let important = parser.try_parse(|parser|
+        {
+            cssparser::parse_important(parser)?;
+            parser.expect_exhausted()
+        });
+
+        if important.is_ok()
+        {
+            return Ok((values, true));
+        }
+
+        let token = match parser.next_including_whitespace()
+        {
+            Ok(token) => token.clone(),
+            Err(_) => return Ok((values, false)),
+        };
+
+        if let Some(value) = parse_token(token, parser)?
+        {
+            values.push(value);
+        }
+    }
+}
+
+/// Parses the arguments of a function, like `color-mix(in srgb, red 30%, blue)`.
+///
+/// Each argument is separated by a comma and may have any number of values.
+fn parse_function_args<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> Result<Vec<PropertyValues>, ParseError<'i, BevyCssError>> {
+    let mut args = vec![];
+    let mut current = SmallVec::new();
+
     while let Ok(token) = parser.next_including_whitespace()
     {
-        values.push(token.clone())
+        match token.clone()
+        {
+            Token::Comma => args.push(PropertyValues(std::mem::take(&mut current))),
+            token => if let Some(value) = parse_token(token, parser)?
+            {
+                current.push(value);
+            }
+        }
     }
 
-    Ok(values)
+    if !current.is_empty() || !args.is_empty()
+    {
+        args.push(PropertyValues(current));
+    }
+
+    Ok(args)
+}
+
+/// Converts a single token into a [`PropertyToken`], parsing function arguments when needed.
+///
+/// Tokens which have no meaning as property values, like white spaces, are discarded, while dimensions on unknown units,
+/// like `10foo`, are rejected.
+fn parse_token<'i>(
+    token: Token<'i>,
+    parser: &mut Parser<'i, '_>,
+) -> Result<Option<PropertyToken>, ParseError<'i, BevyCssError>> {
+    match token
+    {
+        Token::Function(name) => {
+            let args = parser.parse_nested_block(parse_function_args)?;
+            Ok(Some(PropertyToken::Function(name.to_ascii_lowercase(), args)))
+        }
+        Token::CurlyBracketBlock => Err(parser.new_custom_error(
+            BevyCssError::UnexpectedToken(token.to_css_string())
+        )),
+        Token::Dimension { .. } => match PropertyToken::try_from(token.clone())
+        {
+            Ok(value) => Ok(Some(value)),
+            Err(()) => Err(parser.new_custom_error(
+                BevyCssError::UnsupportedPropertyValue(token.to_css_string())
+            )),
+        },
+        token => Ok(token.try_into().ok()),
+    }
 }
 
 #[cfg(test)]
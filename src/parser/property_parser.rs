@@ -1,7 +1,4 @@
-use super::{
-    parse_values,
-    smallvec,
-};
+use super::parse_values;
 use crate::{
     prelude::BevyCssError,
     property::PropertyValues,
@@ -18,7 +15,8 @@ pub struct PropertyParser;
 impl<'i> DeclarationParser<'i>
 for PropertyParser
 {
-    type Declaration = (String, PropertyValues);
+    /// Property name, values and if it was declared as `!important`
+    type Declaration = (String, PropertyValues, bool);
     type Error = BevyCssError;
 
     fn parse_value<'t>(
@@ -26,17 +24,21 @@ for PropertyParser
         name: cssparser::CowRcStr<'i>,
         parser: &mut Parser<'i, 't>,
     ) -> Result<Self::Declaration, ParseError<'i, BevyCssError>> {
-        let mut tokens = smallvec![];
-        for token in parse_values(parser)?
-        {
-            match token.try_into()
-            {
-                Ok(t) => tokens.push(t),
-                Err(_) => continue,
-            }
-        }
+        let (values, important) = Self::parse_property_values(parser)?;
+        Ok((name.to_string(), values, important))
+    }
+}
 
-        Ok((name.to_string(), PropertyValues(tokens)))
+impl PropertyParser
+{
+    /// Parses a single property value, like `10px 5%` or `red !important`, into [`PropertyValues`].
+    ///
+    /// Returns the parsed values and if the value was declared as `!important`.
+    pub(crate) fn parse_property_values<'i>(
+        parser: &mut Parser<'i, '_>,
+    ) -> Result<(PropertyValues, bool), ParseError<'i, BevyCssError>> {
+        let (values, important) = parse_values(parser)?;
+        Ok((PropertyValues(values), important))
     }
 }
 
@@ -44,6 +46,6 @@ impl<'i> AtRuleParser<'i>
 for PropertyParser
 {
     type Prelude = ();
-    type AtRule = (String, PropertyValues);
+    type AtRule = (String, PropertyValues, bool);
     type Error = BevyCssError;
 }
@@ -1,64 +1,273 @@
 use super::{
     format_error,
+    to_diagnostic,
     PropertyParser,
 };
 use crate::{
     prelude::BevyCssError,
+    property::{impls::BackgroundProperty, PropertyNameInterner, PropertyValues},
     selector::{Selector, SelectorElement},
-    stylesheet::StyleRule,
+    stylesheet::{SheetDiagnostic, SourceLocation, StyleRule},
 };
 
-use bevy::log::error;
+use bevy::{
+    log::{error, warn},
+    ui::Val,
+};
 use cssparser::{
     AtRuleParser,
     DeclarationListParser,
+    DeclarationParser,
+    Delimiter,
+    parse_nth,
     ParseError, Parser, ParserInput,
     QualifiedRuleParser,
     RuleListParser,
-    ToCss,
+    ToCss, Token,
 };
 use smallvec::{smallvec, SmallVec};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Log target used by lint diagnostics, like a property being declared twice on the same rule.
+///
+/// Those diagnostics can be disabled by filtering out this target on [`LogPlugin`](bevy::log::LogPlugin).
+pub(crate) const LINT_TARGET: &str = "tomt_bevycss::lint";
+
+/// Anonymous cascade layers declared so far by all sheets, used to give each one an unique name.
+static ANONYMOUS_LAYERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Pairs of `min-` and `max-` size properties, which are checked by [`StyleSheetParser::inverted_size_constraints`].
+const SIZE_CONSTRAINTS: [(&str, &str); 2] = [("min-width", "max-width"), ("min-height", "max-height")];
+
+/// Shorthand properties which are expanded into their longhands when parsed, see [`StyleSheetParser::longhands`].
+const SHORTHANDS: [(&str, [&str; 2]); 1] = [("background", ["background-color", "background-image"])];
 
 /// Parses a `css` string using [`RuleListParser`].
-pub(crate) struct StyleSheetParser;
+///
+/// Use [`parse_rule`](StyleSheetParser::parse_rule) to parse a single rule, like on a REPL.
+#[derive(Default)]
+pub struct StyleSheetParser
+{
+    /// Returns the first invalid declaration as an error, instead of logging and skipping it.
+    strict: bool,
+
+    /// Names of cascade layers, on the order they were declared with `@layer`.
+    layers: Vec<String>,
+
+    /// Paths of style sheets imported with `@import`, on the order they were declared.
+    imports: Vec<String>,
+
+    /// Rules and declarations which failed to parse and were skipped.
+    errors: Vec<SheetDiagnostic>,
+
+    /// Names of the properties declared so far, shared by all rules of the sheet.
+    property_names: PropertyNameInterner,
+}
+
+/// Result of parsing a whole style sheet with [`StyleSheetParser::parse_sheet`].
+pub(crate) struct ParsedStyleSheet
+{
+    pub rules: SmallVec<[StyleRule; 8]>,
+    /// Paths of style sheets imported with `@import`, on the order they were declared.
+    pub imports: Vec<String>,
+    /// Names of cascade layers, which [`StyleRule::layer`] indexes.
+    pub layers: Vec<String>,
+    /// Rules and declarations which failed to parse and were skipped, on the order they were declared.
+    pub errors: Vec<SheetDiagnostic>,
+}
+
+/// Prelude of at-rules supported by [`StyleSheetParser`].
+pub enum AtRulePrelude
+{
+    /// Names of the layers declared by `@layer`, empty for anonymous layers.
+    Layer(Vec<String>),
+    /// Path of the style sheet imported by `@import`.
+    Import(String),
+    /// Name of the condition of a `@when` block, like `menu` on `@when state("menu")`.
+    When(String),
+    /// Name of the flag of a `@when` block, like `experiment-b` on `@when experiment-b`.
+    Flag(String),
+    /// Name of the capability checked by a `@supports` block, like `grid` on `@supports (grid)`.
+    Supports(String),
+}
 
 impl StyleSheetParser
 {
+    /// Parses only the rules, ignoring imports and layer names.
+    #[cfg(test)]
     pub(crate) fn parse(
         content: &str
     ) -> SmallVec<[StyleRule; 8]> {
+        Self::parse_sheet(content).rules
+    }
+
+    /// Parses the rules, the paths of style sheets imported with `@import` and the names of layers declared with `@layer`.
+    pub(crate) fn parse_sheet(
+        content: &str
+    ) -> ParsedStyleSheet {
+        Self::parse_sheet_with(content, StyleSheetParser::default())
+    }
+
+    /// Parses a whole sheet like [`parse_sheet`](StyleSheetParser::parse_sheet), but rules with an invalid declaration
+    /// are skipped as a whole, like [`parse_rule`](StyleSheetParser::parse_rule) does, instead of only the declaration.
+    pub(crate) fn parse_sheet_strict(
+        content: &str
+    ) -> ParsedStyleSheet {
+        Self::parse_sheet_with(content, StyleSheetParser {
+            strict: true,
+            ..Default::default()
+        })
+    }
+
+    fn parse_sheet_with(
+        content: &str,
+        sheet_parser: StyleSheetParser
+    ) -> ParsedStyleSheet {
         let mut input = ParserInput::new(content);
         let mut parser = Parser::new(&mut input);
 
-        RuleListParser::new_for_stylesheet(&mut parser, StyleSheetParser)
-            .filter_map(|result| match result
+        let mut rules = smallvec![];
+        let mut errors = vec![];
+        let mut rule_list = RuleListParser::new_for_stylesheet(&mut parser, sheet_parser);
+        for result in rule_list.by_ref()
+        {
+            match result
             {
-                Ok(rule) => Some(rule),
+                Ok(mut parsed) => rules.extend(parsed.drain(..)),
                 Err((err, rule)) => {
+                    errors.push(to_diagnostic(&err));
                     error!(
                         "Failed to parse rule: {}. Error: {}",
                         rule,
                         format_error(err)
                     );
-                    None
                 }
+            }
+        }
+
+        // Errors inside rules are collected apart from the top-level ones, so both are sorted back into declaration order
+        errors.append(&mut rule_list.parser.errors);
+        errors.sort_by_key(|diagnostic| diagnostic.location);
+
+        ParsedStyleSheet {
+            rules,
+            imports: std::mem::take(&mut rule_list.parser.imports),
+            layers: std::mem::take(&mut rule_list.parser.layers),
+            errors,
+        }
+    }
+
+    /// Parses a single rule, like `.button { width: 10px; }`, returning the first error found instead of logging it.
+    ///
+    /// Since only one rule is returned, nested rules and at-rules like `@layer` aren't accepted.
+    /// Like when parsing a whole sheet, property values are only validated when applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let rule = StyleSheetParser::parse_rule(".button { width: 10px; }").unwrap();
+    /// assert_eq!(rule.selector.to_string(), ".button");
+    /// assert!(rule.properties.contains_key("width"));
+    ///
+    /// assert!(StyleSheetParser::parse_rule(".button { width 10px; }").is_err());
+    /// assert!(StyleSheetParser::parse_rule(".button { width: 10px; } .other { }").is_err());
+    /// ```
+    pub fn parse_rule(
+        content: &str
+    ) -> Result<StyleRule, BevyCssError> {
+        let mut input = ParserInput::new(content);
+        let mut input = Parser::new(&mut input);
+        let mut parser = StyleSheetParser {
+            strict: true,
+            ..Default::default()
+        };
+
+        let mut rules = input.parse_entirely(|input|
+            {
+                input.skip_whitespace();
+                let start = input.state();
+                let selector = input.parse_until_before(Delimiter::CurlyBracketBlock, |input|
+                    QualifiedRuleParser::parse_prelude(&mut parser, input)
+                )?;
+
+                input.expect_curly_bracket_block()?;
+                input.parse_nested_block(|input|
+                    QualifiedRuleParser::parse_block(&mut parser, selector, &start, input)
+                )
             })
-            .collect()
+            .map_err(|err| match err.kind
+            {
+                cssparser::ParseErrorKind::Custom(err) => err,
+                _ => BevyCssError::UnexpectedToken(format_error(err)),
+            })?;
+
+        match rules.len()
+        {
+            1 => Ok(rules.remove(0)),
+            _ => Err(BevyCssError::UnexpectedToken("nested rule".to_string())),
+        }
     }
-}
 
-impl<'i> QualifiedRuleParser<'i>
-for StyleSheetParser
-{
-    type Prelude = Selector;
-    type QualifiedRule = StyleRule;
-    type Error = BevyCssError;
+    /// Parses a list of declarations without a selector, like `width: 10px; color: red;`, into a single rule.
+    ///
+    /// Invalid declarations are skipped and returned along with the rule, so they can be reported to the user.
+    /// The returned rule has an empty selector, since it isn't meant to select entities.
+    pub(crate) fn parse_declarations(
+        content: &str
+    ) -> (StyleRule, Vec<SheetDiagnostic>) {
+        let mut input = ParserInput::new(content);
+        let mut input = Parser::new(&mut input);
 
-    fn parse_prelude<'t>(
-        &mut self,
-        input: &mut Parser<'i, 't>,
-    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        let mut rule = StyleRule::new(Selector::new(smallvec![]));
+        rule.location = input.current_source_location().into();
+        let mut errors = vec![];
+        let mut property_names = PropertyNameInterner::default();
+
+        let mut declarations = DeclarationListParser::new(&mut input, PropertyParser);
+        loop
+        {
+            // Declarations are separated by semicolons, which are consumed along with the previous declaration
+            declarations.input.skip_whitespace();
+            let location = declarations.input.current_source_location().into();
+
+            let result = match declarations.next()
+            {
+                Some(result) => result,
+                None => break,
+            };
+
+            match result
+            {
+                Ok((name, property, important)) => Self::insert_declaration(&mut rule, &mut property_names, name, property, important, location),
+                Err((err, declaration)) => errors.push(SheetDiagnostic {
+                    location,
+                    error: match err.kind
+                    {
+                        cssparser::ParseErrorKind::Custom(err) => err,
+                        _ => BevyCssError::UnexpectedToken(format!("{}: {}", declaration.trim(), format_error(err))),
+                    },
+                }),
+            }
+        }
+
+        Self::lint_size_constraints(&rule);
+        (rule, errors)
+    }
+
+    /// Parses a selector, like `button.enabled .border`.
+    ///
+    /// When parsing a nested rule, `parent` is the selector of the enclosing rule, which replaces any `&` on the selector.
+    /// If there is no `&`, the selector is considered a descendant of the enclosing rule, like `parent selector`.
+    fn parse_selector<'i>(
+        input: &mut Parser<'i, '_>,
+        parent: Option<&Selector>,
+    ) -> Result<Selector, ParseError<'i, BevyCssError>> {
         let mut elements = smallvec![];
+        let mut has_parent_ref = false;
 
         #[derive(Debug, Default, Clone)]
         enum DelimType
@@ -66,14 +275,14 @@ for StyleSheetParser
             #[default]
             None,
             Class,
-            #[cfg(feature = "pseudo_class")]
-            PseudoClass,
+            Colon,
             #[cfg(feature = "pseudo_prop")]
             PseudoProp,
         }
 
         let mut prev_delim = DelimType::None;
 
+        input.skip_whitespace();
         while let Ok(token) = input.next_including_whitespace() {
             use cssparser::Token::*;
 
@@ -87,13 +296,52 @@ for StyleSheetParser
 
                     DelimType::Class => {
                         prev_delim = DelimType::None;
-                        SelectorElement::Class(v.to_string())
+                        let class = v.to_string();
+
+                        // Only a `*` right after the class, like `.slot-*`, makes it a prefix, so `.slot` is still an exact match
+                        match input.try_parse(|input| match input.next_including_whitespace()
+                        {
+                            Ok(Delim('*')) => Ok(()),
+                            _ => Err(()),
+                        })
+                        {
+                            Ok(()) => {
+                                // Wildcards are only supported at the start or the end of a class, so `.slot-*-active` is rejected
+                                let has_trailing_text = input.try_parse(|input| match input.next_including_whitespace()
+                                {
+                                    Ok(Ident(_) | Number { .. } | Dimension { .. } | Percentage { .. }) => Ok(()),
+                                    _ => Err(()),
+                                });
+
+                                if has_trailing_text.is_ok()
+                                {
+                                    return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+                                }
+
+                                SelectorElement::ClassPrefix(class)
+                            }
+                            Err(()) => SelectorElement::Class(class),
+                        }
                     }
 
-                    #[cfg(feature = "pseudo_class")]
-                    DelimType::PseudoClass => {
+                    DelimType::Colon => {
                         prev_delim = DelimType::None;
-                        SelectorElement::PseudoClass(v.to_string())
+                        match v.as_ref()
+                        {
+                            // `:scope` doesn't depend on any state, so it's supported even without pseudo-classes
+                            "scope" => SelectorElement::Scope,
+
+                            #[cfg(feature = "pseudo_class")]
+                            _ => SelectorElement::PseudoClass(v.to_string()),
+
+                            #[cfg(not(feature = "pseudo_class"))]
+                            _ => {
+                                let err_str = format!(":{v}");
+                                return Err(
+                                    input.new_custom_error(BevyCssError::UnexpectedToken(err_str))
+                                );
+                            }
+                        }
                     }
 
                     #[cfg(feature = "pseudo_prop")]
@@ -105,13 +353,128 @@ for StyleSheetParser
                     }
                 }),
 
+                // Siblings are counted by the component of the compound, like `button:nth-of-type(2)`, so it's required
+                Function(name) if matches!(prev_delim, DelimType::Colon) && name.eq_ignore_ascii_case("nth-of-type") => {
+                    prev_delim = DelimType::None;
+
+                    let compound = elements.iter()
+                        .rposition(|element| matches!(element, SelectorElement::Child | SelectorElement::DirectChild))
+                        .map_or(0, |index| index + 1);
+                    let has_component = elements[compound..].iter()
+                        .any(|element| matches!(element,
+                            SelectorElement::Component(_) | SelectorElement::ComponentVariant(..) | SelectorElement::ComponentAttribute(..)
+                        ));
+
+                    if !has_component
+                    {
+                        return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+                    }
+
+                    let (a, b) = input.parse_nested_block(|input|
+                        {
+                            let nth = parse_nth(input)?;
+                            input.expect_exhausted()?;
+                            Ok(nth)
+                        })?;
+
+                    elements.push(SelectorElement::NthOfType(a, b));
+                }
+
+                Function(name) if matches!(prev_delim, DelimType::None) => {
+                    let name = name.to_string();
+                    let variant = input.parse_nested_block(|input|
+                        {
+                            let variant = input.expect_ident_cloned()?;
+                            input.expect_exhausted()?;
+                            Ok(variant)
+                        })?;
+
+                    elements.push(SelectorElement::ComponentVariant(name, variant.to_string()));
+                }
+
+                // Attributes of a component, like `health[current=0]`, which may be chained, like `health[current=0][max=10]`
+                SquareBracketBlock if matches!(prev_delim, DelimType::None) => {
+                    let component = match elements.last()
+                    {
+                        Some(SelectorElement::Component(component))
+                        | Some(SelectorElement::ComponentAttribute(component, _, _)) => component.clone(),
+                        _ => return Err(input.new_custom_error(BevyCssError::UnexpectedToken("[".to_string()))),
+                    };
+
+                    let (field, value) = input.parse_nested_block(|input|
+                        {
+                            // Fields of nested structs are accessed by their path, like `stats.level`
+                            let mut field = input.expect_ident()?.to_string();
+                            while input.try_parse(|input| input.expect_delim('.')).is_ok()
+                            {
+                                field.push('.');
+                                field.push_str(input.expect_ident()?);
+                            }
+
+                            input.expect_delim('=')?;
+                            let value = match input.next()?.clone()
+                            {
+                                Ident(value) | QuotedString(value) => value.to_string(),
+                                Number { value, .. } => value.to_string(),
+                                token => return Err(input.new_unexpected_token_error(token)),
+                            };
+                            input.expect_exhausted()?;
+                            Ok((field, value))
+                        })?;
+
+                    if matches!(elements.last(), Some(SelectorElement::Component(_)))
+                    {
+                        elements.pop();
+                    }
+                    elements.push(SelectorElement::ComponentAttribute(component, field, value));
+                }
+
                 IDHash(v) => match v.is_empty()
                 {
                     true => return Err(input.new_custom_error(BevyCssError::InvalidSelector)),
                     false => elements.push(SelectorElement::Name(v.to_string())),
                 }
                 
-                WhiteSpace(_) => elements.push(SelectorElement::Child),
+                // Spaces around a child combinator, like `.a > .b`, are part of it
+                WhiteSpace(_) => if elements.last() != Some(&SelectorElement::DirectChild)
+                {
+                    elements.push(SelectorElement::Child);
+                }
+
+                Delim('>') if matches!(prev_delim, DelimType::None) => {
+                    if elements.last() == Some(&SelectorElement::Child)
+                    {
+                        elements.pop();
+                    }
+
+                    if elements.last() == Some(&SelectorElement::DirectChild)
+                    {
+                        return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+                    }
+
+                    elements.push(SelectorElement::DirectChild);
+                }
+
+                Delim('&') if parent.is_some() && matches!(prev_delim, DelimType::None) => {
+                    has_parent_ref = true;
+                    elements.extend(parent.unwrap().elements().iter().cloned());
+                }
+
+                // A class starting with `*`, like `.*-active`, matches any class with that suffix
+                Delim('*') if matches!(prev_delim, DelimType::Class) => {
+                    prev_delim = DelimType::None;
+                    let suffix = match input.next_including_whitespace()
+                    {
+                        Ok(Ident(suffix)) => Some(suffix.to_string()),
+                        _ => None,
+                    };
+
+                    match suffix
+                    {
+                        Some(suffix) => elements.push(SelectorElement::ClassSuffix(suffix)),
+                        None => return Err(input.new_custom_error(BevyCssError::InvalidSelector)),
+                    }
+                }
 
                 Delim(c) => prev_delim = match (*c, prev_delim)
                 {
@@ -124,13 +487,12 @@ for StyleSheetParser
                     }
                 },
 
-                #[cfg(feature = "pseudo_class")]
                 Colon => prev_delim = match prev_delim
                 {
-                    DelimType::None => DelimType::PseudoClass,
+                    DelimType::None => DelimType::Colon,
 
                     #[cfg(feature = "pseudo_prop")]
-                    DelimType::PseudoClass => DelimType::PseudoProp,
+                    DelimType::Colon => DelimType::PseudoProp,
 
                     _ => {
                         let err_str = token.to_css_string();
@@ -158,36 +520,436 @@ for StyleSheetParser
             elements.remove(elements.len() - 1);
         }
 
+        // A child combinator must be followed by a child, and preceded by a parent, unless it's relative to the enclosing rule,
+        // like `> .title` nested on `.card`
+        let leading_combinator = elements.first() == Some(&SelectorElement::DirectChild);
+        if elements.last() == Some(&SelectorElement::DirectChild) || (leading_combinator && (parent.is_none() || has_parent_ref))
+        {
+            return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+        }
+
+        // Nested rules without `&` are descendants of the enclosing rule
+        if let (Some(parent), false) = (parent, has_parent_ref)
+        {
+            let mut nested: SmallVec<[SelectorElement; 8]> = parent.elements().iter().cloned().collect();
+            if !leading_combinator
+            {
+                nested.push(SelectorElement::Child);
+            }
+            nested.extend(elements);
+            elements = nested;
+        }
+
         Ok(Selector::new(elements))
     }
 
-    fn parse_block<'t>(
+    /// Parses the content of a rule block, which may have both declarations and nested rules.
+    ///
+    /// Nested rules are flattened and returned after the rule itself, so they are applied later than the enclosing rule.
+    fn parse_rule_body<'i>(
         &mut self,
-        prelude: Self::Prelude,
-        _start: &cssparser::ParserState,
-        input: &mut Parser<'i, 't>,
-    ) -> Result<Self::QualifiedRule, ParseError<'i, Self::Error>> {
-        let mut rule = StyleRule::new(prelude);
+        selector: Selector,
+        location: SourceLocation,
+        input: &mut Parser<'i, '_>,
+    ) -> Result<Vec<StyleRule>, ParseError<'i, BevyCssError>> {
+        let mut rule = StyleRule::new(selector);
+        rule.location = location;
+        let mut nested_rules = vec![];
 
-        for property in DeclarationListParser::new(input, PropertyParser)
+        loop
         {
-            match property
+            input.skip_whitespace();
+            if input.is_exhausted()
+            {
+                break;
+            }
+
+            let start = input.position();
+            let location = input.current_source_location().into();
+
+            // Try a declaration first, like `color: red;`, and fallback to a nested rule, like `& .title { ... }`
+            let declaration = input.try_parse(|input|
+                input.parse_until_after(Delimiter::Semicolon, |input|
+                {
+                    let name = input.expect_ident()?.clone();
+                    input.expect_colon()?;
+                    PropertyParser.parse_value(name, input)
+                })
+            );
+
+            let err = match declaration
             {
-                Ok((name, property)) => {
-                    rule.properties.insert(name, property);
+                Ok((name, property, important)) => {
+                    Self::insert_declaration(&mut rule, &mut self.property_names, name, property, important, location);
+                    continue;
+                }
+                Err(err) => err,
+            };
+
+            let nested = input.try_parse(|input|
+            {
+                let selector = input.parse_until_before(Delimiter::CurlyBracketBlock, |input|
+                    Self::parse_selector(input, Some(&rule.selector))
+                )?;
+
+                input.expect_curly_bracket_block()?;
+                input.parse_nested_block(|input| self.parse_rule_body(selector, location, input))
+            });
+
+            match nested
+            {
+                Ok(mut rules) => nested_rules.append(&mut rules),
+                Err(_) if self.strict => return Err(err),
+                Err(_) => {
+                    // Skip the invalid declaration or rule, including any block it may have
+                    while let Ok(token) = input.next()
+                    {
+                        if matches!(token, Token::Semicolon | Token::CurlyBracketBlock)
+                        {
+                            break;
+                        }
+                    }
+
+                    self.errors.push(to_diagnostic(&err));
+                    error!(
+                        "Failed to parse declaration: {}. Error: {}",
+                        input.slice_from(start).trim(),
+                        format_error(err)
+                    );
                 }
-                Err((err, a)) => println!("Failed: {:?} ({})", err, a),
             }
         }
 
-        Ok(rule)
+        Self::lint_size_constraints(&rule);
+
+        let mut rules = vec![rule];
+        rules.append(&mut nested_rules);
+        Ok(rules)
+    }
+
+    /// Gets the `min-` and `max-` size pairs declared on the given rule where the minimum is greater than the maximum,
+    /// like `min-width: 200px; max-width: 100px;`.
+    ///
+    /// Only sizes on the same unit can be compared. Such pairs are kept as declared, and the minimum wins once laid out, like on `css`.
+    pub(crate) fn inverted_size_constraints(
+        rule: &StyleRule
+    ) -> Vec<(&'static str, &'static str)> {
+        SIZE_CONSTRAINTS.into_iter()
+            .filter(|(min, max)|
+            {
+                let min = rule.properties.get(*min).and_then(|values| values.val());
+                let max = rule.properties.get(*max).and_then(|values| values.val());
+                match (min, max)
+                {
+                    (Some(Val::Px(min)), Some(Val::Px(max)))
+                    | (Some(Val::Percent(min)), Some(Val::Percent(max))) => min > max,
+                    _ => false,
+                }
+            })
+            .collect()
+    }
+
+    /// Warns about sizes which minimum is greater than its maximum on the given rule, see [`inverted_size_constraints`](Self::inverted_size_constraints).
+    fn lint_size_constraints(
+        rule: &StyleRule
+    ) {
+        for (min, max) in Self::inverted_size_constraints(rule)
+        {
+            warn!(
+                target: LINT_TARGET,
+                "Property '{min}' is greater than '{max}' on rule '{}', so '{min}' wins",
+                rule.selector
+            );
+        }
+    }
+
+    /// Gets the index of the given cascade layer, declaring it if it wasn't declared yet.
+    fn layer_index(
+        &mut self,
+        name: &str
+    ) -> usize {
+        match self.layers.iter().position(|layer| layer == name)
+        {
+            Some(index) => index,
+            None => {
+                self.layers.push(name.to_string());
+                self.layers.len() - 1
+            }
+        }
+    }
+
+    /// Parses the rules inside the block of an at-rule, like `@layer`, `@when` or `@supports`, logging and skipping invalid ones.
+    fn parse_nested_rules(
+        &mut self,
+        input: &mut Parser
+    ) -> Vec<StyleRule> {
+        // Rules inside the block share the layers declared so far
+        let parser = StyleSheetParser {
+            layers: std::mem::take(&mut self.layers),
+            property_names: std::mem::take(&mut self.property_names),
+            strict: self.strict,
+            ..Default::default()
+        };
+
+        let mut rules = vec![];
+        let mut nested = RuleListParser::new_for_nested_rule(input, parser);
+        for result in nested.by_ref()
+        {
+            match result
+            {
+                Ok(mut nested_rules) => rules.append(&mut nested_rules),
+                Err((err, rule)) => {
+                    self.errors.push(to_diagnostic(&err));
+                    error!(
+                        "Failed to parse rule: {}. Error: {}",
+                        rule,
+                        format_error(err)
+                    );
+                }
+            }
+        }
+
+        self.layers = std::mem::take(&mut nested.parser.layers);
+        self.property_names = std::mem::take(&mut nested.parser.property_names);
+        self.errors.append(&mut nested.parser.errors);
+
+        rules
+    }
+
+    /// Inserts a declaration on the given rule.
+    ///
+    /// The last declaration of a property wins, unless an earlier one was declared as `!important`.
+    /// Expands a declaration of a shorthand listed on [`SHORTHANDS`] into its longhands, unless its value is invalid.
+    fn longhands(
+        name: &str,
+        property: &PropertyValues
+    ) -> Option<[(&'static str, PropertyValues); 2]> {
+        match name
+        {
+            "background" => BackgroundProperty::longhands(property),
+            _ => None,
+        }
+    }
+
+    fn insert_declaration(
+        rule: &mut StyleRule,
+        property_names: &mut PropertyNameInterner,
+        name: String,
+        property: PropertyValues,
+        important: bool,
+        location: SourceLocation
+    ) {
+        // Shorthands are expanded into their longhands, so the cascade decides between them and the longhands declared on their own
+        if let Some(longhands) = Self::longhands(&name, &property)
+        {
+            for (longhand, property) in longhands
+            {
+                Self::insert_declaration(rule, property_names, longhand.to_string(), property, important, location);
+            }
+            return;
+        }
+
+        // Longhands of the same shorthand declaration share its location
+        let expanded = rule.property_locations.get(name.as_str())
+            .is_some_and(|previous| SHORTHANDS.iter()
+                .filter(|(_shorthand, longhands)| longhands.contains(&name.as_str()))
+                .any(|(_shorthand, longhands)| longhands.iter().all(|longhand| rule.property_locations.get(*longhand) == Some(previous)))
+            );
+
+        if rule.properties.contains_key(name.as_str()) && !expanded
+        {
+            warn!(
+                target: LINT_TARGET,
+                "Property '{name}' is declared more than once on rule '{}'",
+                rule.selector
+            );
+        }
+
+        if important
+        {
+            rule.important.insert(name.clone());
+        }
+        else if rule.is_important(&name)
+        {
+            return;
+        }
+
+        // Properties are kept on the order their winning declaration was declared
+        rule.property_locations.insert(name.clone(), location);
+        rule.properties.shift_remove(name.as_str());
+        rule.properties.insert(property_names.intern(&name), Arc::new(property));
+    }
+}
+
+impl<'i> QualifiedRuleParser<'i>
+for StyleSheetParser
+{
+    type Prelude = Selector;
+    type QualifiedRule = Vec<StyleRule>;
+    type Error = BevyCssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        Self::parse_selector(input, None)
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        start: &cssparser::ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::QualifiedRule, ParseError<'i, Self::Error>> {
+        self.parse_rule_body(prelude, start.source_location().into(), input)
     }
 }
 
 impl<'i> AtRuleParser<'i>
 for StyleSheetParser
 {
-    type Prelude = ();
-    type AtRule = StyleRule;
+    type Prelude = AtRulePrelude;
+    type AtRule = Vec<StyleRule>;
     type Error = BevyCssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: cssparser::CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        if name.eq_ignore_ascii_case("import")
+        {
+            // Accepts both `@import "path.css"` and `@import url(path.css)`
+            let path = input.expect_url_or_string()?.to_string();
+            return Ok(AtRulePrelude::Import(path));
+        }
+
+        if name.eq_ignore_ascii_case("when")
+        {
+            // A bare name, like `@when experiment-b`, is a flag instead of a condition
+            if let Ok(flag) = input.try_parse(|input| input.expect_ident().map(|flag| flag.to_string()))
+            {
+                input.expect_exhausted()?;
+                return Ok(AtRulePrelude::Flag(flag));
+            }
+
+            // Accepts both `@when state("menu")` and `@when state(menu)`
+            input.expect_function_matching("state")?;
+            let condition = input.parse_nested_block(|input| Ok(input.expect_ident_or_string()?.to_string()))?;
+            input.expect_exhausted()?;
+            return Ok(AtRulePrelude::When(condition));
+        }
+
+        if name.eq_ignore_ascii_case("supports")
+        {
+            input.expect_parenthesis_block()?;
+            let capability = input.parse_nested_block(|input|
+                {
+                    let capability = input.expect_ident()?.to_string();
+                    input.expect_exhausted()?;
+                    Ok(capability)
+                })?;
+            input.expect_exhausted()?;
+            return Ok(AtRulePrelude::Supports(capability));
+        }
+
+        if !name.eq_ignore_ascii_case("layer")
+        {
+            return Err(input.new_custom_error(BevyCssError::UnexpectedToken(format!("@{name}"))));
+        }
+
+        if input.is_exhausted()
+        {
+            return Ok(AtRulePrelude::Layer(vec![]));
+        }
+
+        input.parse_comma_separated(|input| Ok(input.expect_ident()?.to_string()))
+            .map(AtRulePrelude::Layer)
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &cssparser::ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude
+        {
+            AtRulePrelude::Import(path) => self.imports.push(path),
+            // Statement like `@layer base, theme;`, which only declares the layers order
+            AtRulePrelude::Layer(names) if !names.is_empty() => {
+                for name in names.iter()
+                {
+                    self.layer_index(name);
+                }
+            }
+            AtRulePrelude::Layer(_) | AtRulePrelude::When(_) | AtRulePrelude::Flag(_) | AtRulePrelude::Supports(_) => return Err(()),
+        }
+
+        Ok(vec![])
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &cssparser::ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
+        let prelude = match prelude
+        {
+            AtRulePrelude::Layer(names) => names,
+            AtRulePrelude::When(condition) => {
+                let mut rules = self.parse_nested_rules(input);
+                for rule in rules.iter_mut()
+                {
+                    // Rules of nested `@when` blocks must satisfy all conditions
+                    rule.conditions.insert(0, condition.clone());
+                }
+
+                return Ok(rules);
+            }
+            AtRulePrelude::Flag(flag) => {
+                let mut rules = self.parse_nested_rules(input);
+                for rule in rules.iter_mut()
+                {
+                    // Rules of nested `@when` blocks must have all flags enabled
+                    rule.flags.insert(0, flag.clone());
+                }
+
+                return Ok(rules);
+            }
+            AtRulePrelude::Supports(capability) => {
+                let mut rules = self.parse_nested_rules(input);
+                for rule in rules.iter_mut()
+                {
+                    // Rules of nested `@supports` blocks require all capabilities
+                    rule.capabilities.insert(0, capability.clone());
+                }
+
+                return Ok(rules);
+            }
+            AtRulePrelude::Import(_) => {
+                return Err(input.new_custom_error(BevyCssError::UnexpectedToken("{".to_string())));
+            }
+        };
+
+        let layer = match prelude.as_slice()
+        {
+            [] => {
+                // Anonymous layers can't be referenced again, so each one gets an unique name, since layers are shared by all sheets
+                let name = format!("<anonymous {}>", ANONYMOUS_LAYERS.fetch_add(1, Ordering::Relaxed));
+                self.layer_index(&name)
+            }
+            [name] => self.layer_index(name),
+            _ => return Err(input.new_custom_error(BevyCssError::UnexpectedToken(",".to_string()))),
+        };
+
+        let mut rules = self.parse_nested_rules(input);
+        for rule in rules.iter_mut()
+        {
+            // Nested layers are flattened, so their rules keep the innermost layer
+            rule.layer.get_or_insert(layer);
+        }
+
+        Ok(rules)
+    }
 }
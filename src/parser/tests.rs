@@ -4,6 +4,8 @@ use crate::{
     selector::SelectorElement,
 };
 
+use smallvec::smallvec;
+
 #[test]
 fn parse_empty(
     // no args
@@ -80,6 +82,48 @@ fn parse_single_class_selector_no_property(
     assert!(rule.properties.is_empty(), "Should have no token");
 }
 
+#[test]
+fn parse_class_prefix_and_suffix_selectors(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule(".slot-* .*-active.slot { width: 10px; }")
+        .expect("Should parse a valid rule");
+
+    use SelectorElement::*;
+    assert_eq!(rule.selector.elements(), [
+        ClassPrefix("slot-".to_string()),
+        Child,
+        ClassSuffix("-active".to_string()),
+        Class("slot".to_string()),
+    ], "Should keep classes without `*` as exact matches");
+    assert_eq!(rule.selector.to_string(), ".slot-* .*-active.slot");
+
+    assert!(StyleSheetParser::parse_rule(".* { width: 10px; }").is_err(), "Should require a prefix or a suffix");
+    assert!(StyleSheetParser::parse_rule(".* active { width: 10px; }").is_err());
+    assert!(StyleSheetParser::parse_rule(".slot-*-active { width: 10px; }").is_err(), "Should reject text after the `*`");
+    assert!(StyleSheetParser::parse_rule(".slot-*2 { width: 10px; }").is_err());
+}
+
+#[test]
+fn parse_nth_of_type_selector(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule("button:nth-of-type(2n+1) { width: 10px; }")
+        .expect("Should parse a valid rule");
+
+    use SelectorElement::*;
+    assert_eq!(rule.selector.elements(), [Component("button".to_string()), NthOfType(2, 1)]);
+    assert_eq!(rule.selector.to_string(), "button:nth-of-type(2n+1)");
+
+    let rule = StyleSheetParser::parse_rule(".menu button.big:nth-of-type(even) { width: 10px; }").expect("Should accept keywords");
+    assert_eq!(rule.selector.elements().last(), Some(&NthOfType(2, 0)));
+    assert_eq!(StyleSheetParser::parse_rule("text:nth-of-type(-n+3) { width: 10px; }").unwrap().selector.to_string(), "text:nth-of-type(-1n+3)");
+
+    assert!(StyleSheetParser::parse_rule(".item:nth-of-type(2) { width: 10px; }").is_err(), "Should require a component");
+    assert!(StyleSheetParser::parse_rule("button .item:nth-of-type(2) { width: 10px; }").is_err(), "Should require a component on the same compound");
+    assert!(StyleSheetParser::parse_rule("button:nth-of-type(x) { width: 10px; }").is_err());
+}
+
 #[test]
 fn parse_single_component_selector_no_property(
     // no args
@@ -223,11 +267,11 @@ fn parse_single_token(
 
     assert_eq!(properties.len(), 1, "Should have a single property");
     assert!(
-        properties.contains_key(&"b".to_string()),
+        properties.contains_key("b"),
         "Should have a property named \"b\""
     );
 
-    let values = properties.get(&"b".to_string()).unwrap();
+    let values = properties.get("b").unwrap();
     assert_eq!(values.len(), 1, "Should have a single property value");
 
     match &values[0]
@@ -292,6 +336,7 @@ fn parse_multiple_complex_properties(
     ];
 
     assert_eq!(properties.len(), expected.len(), "{:?}", properties);
+    assert!(properties.keys().map(|name| &**name).eq(expected.iter().map(|(name, _values)| *name)), "Should keep the declaration order");
     expected.into_iter()
         .for_each(|(name, values)|
         {
@@ -313,15 +358,22 @@ fn parse_multiple_rules(
 
     assert_eq!(rules.len(), 4, "Should have 4 rules");
 
+    let columns: Vec<u32> = rules.iter()
+        .map(|rule| rule.location.column)
+        .collect();
+    assert_eq!(columns, [1, 7, 13, 19], "Should locate each rule on the same line");
+
     for rule in rules
     {
+        assert_eq!(rule.property_location("a").map(|location| location.line), Some(1));
+
         match rule.selector.get_parent_tree()[0][0]
         {
             SelectorElement::Component(a) => assert_eq!(a, "a"),
             _ => panic!("Should have only a single component \"a\""),
         }
 
-        match rule.properties.get(&"a".to_string())
+        match rule.properties.get("a")
             .expect("Should have a single property named \"a\"")
             .iter()
             .next()
@@ -332,3 +384,480 @@ fn parse_multiple_rules(
         }
     }
 }
+
+#[test]
+fn parse_duplicated_property_last_wins(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("a { color: red; color: blue; }");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    assert_eq!(rules[0].property_location("color").map(|location| location.column), Some(17));
+
+    let properties = &rules[0].properties;
+    assert_eq!(properties.len(), 1, "Should have a single property");
+
+    match &properties.get("color").expect("Should have a color property")[..]
+    {
+        [PropertyToken::Identifier(ident)] => assert_eq!(ident, "blue"),
+        other => panic!("Should have only the last declared value, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_duplicated_property_keeps_winning_declaration_order(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule("a { color: red; width: 10px; color: blue; height: 5px; }").unwrap();
+    assert!(rule.properties.keys().map(|name| &**name).eq(["width", "color", "height"]), "Should be where the last declaration is");
+
+    let rule = StyleSheetParser::parse_rule("a { color: red !important; width: 10px; color: blue; }").unwrap();
+    assert!(rule.properties.keys().map(|name| &**name).eq(["color", "width"]), "Should be where the !important declaration is");
+}
+
+#[test]
+fn parse_shorthand_expands_into_longhands(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule("a { background: red; background-color: blue; }").unwrap();
+    assert!(rule.properties.keys().map(|name| &**name).eq(["background-image", "background-color"]), "Should only keep the longhands");
+    assert_eq!(**rule.properties.get("background-color").unwrap(), PropertyValues::parse("blue").unwrap(), "Later longhands should win");
+    assert_eq!(**rule.properties.get("background-image").unwrap(), PropertyValues::parse("none").unwrap());
+
+    let rule = StyleSheetParser::parse_rule("a { background-image: \"star.png\"; background: linear-gradient(red, blue) !important; }").unwrap();
+    assert_eq!(**rule.properties.get("background-color").unwrap(), PropertyValues::parse("white").unwrap(), "Images shouldn't be tinted");
+    assert_eq!(**rule.properties.get("background-image").unwrap(), PropertyValues::parse("linear-gradient(red, blue)").unwrap());
+    assert!(rule.is_important("background-image"), "Should keep the longhands important");
+
+    let rule = StyleSheetParser::parse_rule("a { background: 10px; }").unwrap();
+    assert!(rule.properties.contains_key("background"), "Invalid values should be kept to be reported");
+}
+
+#[test]
+fn parse_important_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("a { color: red !important; width: 10px; }");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let rule = &rules[0];
+    assert!(rule.is_important("color"), "Color should be important");
+    assert!(!rule.is_important("width"), "Width shouldn't be important");
+
+    match &rule.properties.get("color").expect("Should have a color property")[..]
+    {
+        [PropertyToken::Identifier(ident)] => assert_eq!(ident, "red"),
+        other => panic!("Should not keep the !important tokens, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_duplicated_property_important_wins(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("a { color: red ! important; color: blue; }");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let rule = &rules[0];
+    assert!(rule.is_important("color"), "Color should be important");
+
+    match &rule.properties.get("color").expect("Should have a color property")[..]
+    {
+        [PropertyToken::Identifier(ident)] => assert_eq!(ident, "red"),
+        other => panic!("Earlier !important declaration should win, got {other:?}"),
+    }
+    assert_eq!(
+        rule.property_location("color").map(|location| location.column),
+        Some(5),
+        "Should keep the location of the winning declaration"
+    );
+}
+
+#[test]
+fn parse_function_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("a { b: F(c, 1px 2%) d; }");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let values = rules[0].properties.get("b").expect("Should have a property named \"b\"");
+
+    use PropertyToken::*;
+    match &values[..]
+    {
+        [Function(name, args), Identifier(d)] => {
+            assert_eq!(name, "f", "Function name should be lower-case");
+            assert_eq!(d, "d");
+            assert_eq!(args.len(), 2, "Should have 2 arguments");
+            assert_eq!(&args[0][..], &[Identifier("c".to_string())]);
+            assert_eq!(&args[1][..], &[Dimension(1.0), Percentage(2.0)]);
+        }
+        other => panic!("Should have a function followed by an identifier, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_nested_rule_with_parent_reference(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(".card { color: #000; & .title { font-size: 20px; } }");
+    assert_eq!(rules.len(), 2, "Should flatten the nested rule");
+
+    use SelectorElement::*;
+    assert_eq!(rules[0].selector.to_string(), ".card");
+    assert!(rules[0].properties.contains_key("color"));
+
+    let tree = rules[1].selector.get_parent_tree();
+    assert_eq!(tree.len(), 2, "Should be a descendant selector");
+    assert_eq!(&tree[0][..], &[&Class("card".to_string())]);
+    assert_eq!(&tree[1][..], &[&Class("title".to_string())]);
+    assert!(rules[1].properties.contains_key("font-size"));
+    assert!(!rules[1].properties.contains_key("color"));
+}
+
+#[test]
+fn parse_nested_rule_compound_parent_reference(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("button { &.active { color: red; } }");
+    assert_eq!(rules.len(), 2, "Should flatten the nested rule");
+
+    use SelectorElement::*;
+    let tree = rules[1].selector.get_parent_tree();
+    assert_eq!(tree.len(), 1, "Should be a compound selector");
+    assert_eq!(&tree[0][..], &[&Component("button".to_string()), &Class("active".to_string())]);
+}
+
+#[test]
+fn parse_nested_rule_without_parent_reference(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#".card {
+            .header { .title { color: red; } }
+            width: 10px;
+        }"#,
+    );
+    assert_eq!(rules.len(), 3, "Should flatten all nested rules");
+    assert!(
+        rules[0].properties.contains_key("width"),
+        "Should keep declarations after nested rules"
+    );
+
+    use SelectorElement::*;
+    let tree = rules[2].selector.get_parent_tree();
+    assert_eq!(tree.len(), 3, "Should be a descendant selector");
+    assert_eq!(&tree[0][..], &[&Class("card".to_string())]);
+    assert_eq!(&tree[1][..], &[&Class("header".to_string())]);
+    assert_eq!(&tree[2][..], &[&Class("title".to_string())]);
+}
+
+#[test]
+fn parse_layer_rules(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"@layer base, theme;
+        @layer theme { .a { color: red; } }
+        @layer base { .b { color: blue; } }
+        .c { color: green; }"#,
+    );
+    assert_eq!(rules.len(), 3, "Should flatten rules inside layers");
+    assert_eq!(rules[0].layer, Some(1), "Should keep the declared order of theme");
+    assert_eq!(rules[1].layer, Some(0), "Should keep the declared order of base");
+    assert_eq!(rules[2].layer, None, "Should be an unlayered rule");
+}
+
+#[test]
+fn parse_when_rules(
+    // no args
+) {
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(
+        r#"@when state("menu") { .hud { display: none; } @when state(paused) { .timer { color: red; } } }
+        @layer base { @when state("menu") { .a { width: 1px; } } }
+        .b { width: 1px; }
+        @when hover("menu") { .c { width: 1px; } }
+        @when state("menu");"#,
+    );
+
+    assert_eq!(rules.len(), 4);
+    assert_eq!(rules[0].conditions, ["menu"]);
+    assert_eq!(rules[1].conditions, ["menu", "paused"], "Nested blocks should require all conditions");
+    assert_eq!((rules[2].conditions.as_slice(), rules[2].layer), (&["menu".to_string()][..], Some(0)));
+    assert!(rules[3].conditions.is_empty(), "Should be an unconditional rule");
+    assert_eq!(errors.len(), 2, "Should skip unknown predicates and blocks without rules");
+}
+
+#[test]
+fn parse_when_flag_rules(
+    // no args
+) {
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(
+        r#"@when experiment-b { .cta { width: 1px; } @when state("menu") { .hud { width: 1px; } } }
+        @when experiment-b dark { .d { width: 1px; } }"#,
+    );
+
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].flags, ["experiment-b"]);
+    assert!(rules[0].conditions.is_empty(), "Should be a flag instead of a condition");
+    assert_eq!((rules[1].flags.as_slice(), rules[1].conditions.as_slice()), (&["experiment-b".to_string()][..], &["menu".to_string()][..]));
+    assert_eq!(errors.len(), 1, "Should skip blocks with more than one flag");
+}
+
+#[test]
+fn parse_supports_rules(
+    // no args
+) {
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(
+        r#"@supports (grid) { .a { display: grid; } @supports (text-transform) { .b { text-transform: uppercase; } } }
+        @supports grid { .c { width: 1px; } }
+        .d { width: 1px; }"#,
+    );
+
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[0].capabilities, ["grid"]);
+    assert_eq!(rules[1].capabilities, ["grid", "text-transform"], "Nested blocks should require all capabilities");
+    assert!(rules[2].capabilities.is_empty());
+    assert_eq!(errors.len(), 1, "Should skip capabilities without parenthesis");
+}
+
+#[test]
+fn property_names_are_shared_by_rules_of_a_sheet(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(".a { width: 1px; } @layer base { .b { width: 2px; } } .c { & .d { width: 3px; } }");
+    let names: Vec<_> = rules.iter()
+        .filter_map(|rule| rule.properties.get_key_value("width"))
+        .map(|(name, _values)| name.clone())
+        .collect();
+
+    assert_eq!(names.len(), 3);
+    assert!(names.iter().all(|name| std::sync::Arc::ptr_eq(name, &names[0])), "Should intern names across nested and layered rules");
+}
+
+#[test]
+fn parse_sheet_strict_skips_rules_with_errors(
+    // no args
+) {
+    let content = ".a { width: 1px; height 2px; color: red; }\n.b { width: 1px; }\n.c { width; }\n.d { color: blue; }";
+
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(content);
+    assert_eq!(rules.len(), 4, "Should only skip the invalid declarations");
+    assert_eq!(errors.len(), 2);
+
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet_strict(content);
+    let selectors: Vec<_> = rules.iter().map(|rule| rule.selector.to_string()).collect();
+    assert_eq!(selectors, [".b", ".d"], "Should skip rules with an invalid declaration");
+    assert_eq!(
+        errors.iter().map(|diagnostic| diagnostic.location.line).collect::<Vec<_>>(),
+        [1, 3],
+        "Should report each invalid rule at its first invalid declaration"
+    );
+}
+
+#[test]
+fn parse_unknown_units_are_rejected(
+    // no args
+) {
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(".a { width: 10foo; height: 10px; margin: rect(1in); }");
+
+    let names: Vec<_> = rules[0].properties.keys().map(|name| name.to_string()).collect();
+    assert_eq!(names, ["height"], "Should skip declarations with unknown units instead of reading them as pixels");
+
+    let errors: Vec<_> = errors.into_iter().map(|diagnostic| diagnostic.error).collect();
+    assert_eq!(errors, [
+        BevyCssError::UnsupportedPropertyValue("10foo".to_string()),
+        BevyCssError::UnsupportedPropertyValue("1in".to_string()),
+    ]);
+}
+
+#[test]
+fn parse_rule_locations(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(".a { width: 1px; }\n\n  .b {\n    & .c { width: 2px; }\n  }\n@layer base { .d { width: 3px; } }");
+
+    let locations: Vec<(u32, u32)> = rules.iter()
+        .map(|rule| (rule.location.line, rule.location.column))
+        .collect();
+    assert_eq!(locations, [(1, 1), (3, 3), (4, 5), (6, 15)]);
+}
+
+#[test]
+fn parse_declaration_locations(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(".panel {\n    width: 10px;\n  height: 5px; color: red;\n}");
+
+    let location = |name: &str| rules[0].property_location(name).map(|location| (location.line, location.column));
+    assert_eq!(location("width"), Some((2, 5)));
+    assert_eq!(location("height"), Some((3, 3)));
+    assert_eq!(location("color"), Some((3, 16)));
+    assert_eq!(location("margin"), None);
+
+    let (rule, _errors) = StyleSheetParser::parse_declarations("width: 10px;  height 5px; color: red");
+    assert_eq!(rule.property_location("width").map(|location| location.column), Some(1));
+    assert_eq!(rule.property_location("color").map(|location| location.column), Some(27));
+}
+
+#[test]
+fn parse_counts_skipped_rules_and_declarations(
+    // no args
+) {
+    let ParsedStyleSheet { rules, errors, .. } = StyleSheetParser::parse_sheet(
+        r#".a { width: 1px; height 1px; & .b { color red; } }
+        @layer base { .c { width: 1px; } .d!e { width: 1px; } }
+        @unknown { .f { width: 1px; } }"#,
+    );
+
+    assert_eq!(rules.len(), 3);
+    assert_eq!(errors.len(), 4, "Should count errors of nested rules and layers too");
+
+    let lines: Vec<_> = errors.iter().map(|diagnostic| diagnostic.location.line).collect();
+    assert_eq!(lines, [1, 1, 2, 3], "Should report errors on declaration order");
+}
+
+#[test]
+fn parse_anonymous_and_undeclared_layers(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("@layer { .a { color: red; } } @layer extra { .b { color: red; } }");
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].layer, Some(0));
+    assert_eq!(rules[1].layer, Some(1), "Should declare the layer on first use");
+}
+
+#[test]
+fn parse_import_rules(
+    // no args
+) {
+    let ParsedStyleSheet { rules, imports, .. } = StyleSheetParser::parse_sheet(
+        r#"@import "base.css";
+        @import url(theme/dark.css);
+        .a { color: red; }"#,
+    );
+    assert_eq!(rules.len(), 1);
+    assert_eq!(imports, ["base.css", "theme/dark.css"]);
+}
+
+#[test]
+fn parse_single_rule(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule("  button.active { color: red; width: 10px !important; }  ")
+        .expect("Should parse a valid rule");
+
+    assert_eq!(rule.selector.to_string(), "button.active");
+    assert_eq!(rule.properties.len(), 2);
+    assert!(rule.is_important("width"));
+}
+
+#[test]
+fn parse_single_malformed_rule(
+    // no args
+) {
+    assert!(matches!(
+        StyleSheetParser::parse_rule(".a { color red; }"),
+        Err(BevyCssError::UnexpectedToken(_))
+    ));
+    assert!(matches!(
+        StyleSheetParser::parse_rule(".a..b { color: red; }"),
+        Err(BevyCssError::UnexpectedToken(_))
+    ));
+    assert!(StyleSheetParser::parse_rule("").is_err());
+    assert!(StyleSheetParser::parse_rule(".a { & .b { color: red; } }").is_err(), "Should reject nested rules");
+}
+
+#[test]
+fn parse_component_variant_selector(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule("interaction( hovered ).item icon { width: 10px; }")
+        .expect("Should parse a valid rule");
+
+    use SelectorElement::*;
+    assert_eq!(rule.selector.elements(), [
+        ComponentVariant("interaction".to_string(), "hovered".to_string()),
+        Class("item".to_string()),
+        Child,
+        Component("icon".to_string()),
+    ]);
+    assert_eq!(rule.selector.to_string(), "interaction(hovered).item icon");
+
+    assert!(StyleSheetParser::parse_rule("interaction() { width: 10px; }").is_err(), "Should require a variant");
+    assert!(StyleSheetParser::parse_rule("interaction(a b) { width: 10px; }").is_err(), "Should accept a single variant");
+    assert!(StyleSheetParser::parse_rule(".interaction(hovered) { width: 10px; }").is_err());
+}
+
+#[test]
+fn parse_component_attribute_selector(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule(r#"health[current=0][name="hero"].item icon[size=1.5] { width: 10px; }"#)
+        .expect("Should parse a valid rule");
+
+    use SelectorElement::*;
+    assert_eq!(rule.selector.elements(), [
+        ComponentAttribute("health".to_string(), "current".to_string(), "0".to_string()),
+        ComponentAttribute("health".to_string(), "name".to_string(), "hero".to_string()),
+        Class("item".to_string()),
+        Child,
+        ComponentAttribute("icon".to_string(), "size".to_string(), "1.5".to_string()),
+    ]);
+    assert_eq!(rule.selector.to_string(), r#"health[current=0][name="hero"].item icon[size=1.5]"#);
+    let reparsed = StyleSheetParser::parse_rule(&format!("{} {{ width: 10px; }}", rule.selector))
+        .expect("Should parse the written selector");
+    assert_eq!(reparsed.selector.elements(), rule.selector.elements(), "Should be written as it's parsed");
+
+    let rule = StyleSheetParser::parse_rule(r#"player[title="the \"best\" one"] { width: 10px; }"#).expect("Should accept quoted values");
+    assert_eq!(rule.selector.to_string(), r#"player[title="the \"best\" one"]"#);
+    assert!(rule.selector.has_component_variant(), "Should be evaluated again when the component changes");
+
+    let rule = StyleSheetParser::parse_rule("stats[level.max=10] { width: 10px; }").expect("Should accept field paths");
+    assert_eq!(rule.selector.to_string(), "stats[level.max=10]");
+
+    assert!(StyleSheetParser::parse_rule("health[current] { width: 10px; }").is_err(), "Should require a value");
+    assert!(StyleSheetParser::parse_rule(".item[current=0] { width: 10px; }").is_err(), "Should follow a component");
+    assert!(StyleSheetParser::parse_rule("[current=0] { width: 10px; }").is_err());
+}
+
+#[test]
+fn parse_inverted_size_constraints(
+    // no args
+) {
+    let rule = StyleSheetParser::parse_rule(".a { min-width: 200px; max-width: 100px; min-height: 10%; max-height: 20%; }").unwrap();
+    assert_eq!(StyleSheetParser::inverted_size_constraints(&rule), [("min-width", "max-width")]);
+
+    let rule = StyleSheetParser::parse_rule(".a { min-width: 50%; max-width: 100px; min-height: 30%; max-height: 20%; }").unwrap();
+    assert_eq!(StyleSheetParser::inverted_size_constraints(&rule), [("min-height", "max-height")], "Should only compare the same unit");
+
+    let rule = StyleSheetParser::parse_rule(".a { min-width: 100px; width: 300px; max-width: 100px; }").unwrap();
+    assert!(StyleSheetParser::inverted_size_constraints(&rule).is_empty());
+}
+
+#[test]
+fn parse_scope_and_child_combinator(
+    // no args
+) {
+    use SelectorElement::*;
+
+    let rule = StyleSheetParser::parse_rule(":scope > .column .cell { width: 10px; }").unwrap();
+    assert_eq!(rule.selector.elements(), [
+        Scope,
+        DirectChild,
+        Class("column".to_string()),
+        Child,
+        Class("cell".to_string()),
+    ]);
+    assert_eq!(rule.selector.to_string(), ":scope > .column .cell");
+
+    let rule = StyleSheetParser::parse_rule(".a>.b { width: 10px; }").unwrap();
+    assert_eq!(rule.selector.elements(), [Class("a".to_string()), DirectChild, Class("b".to_string())]);
+
+    let rules = StyleSheetParser::parse(".card { & > .title { width: 1px; } > .body { width: 2px; } }");
+    assert_eq!(rules[1].selector.to_string(), ".card > .title");
+    assert_eq!(rules[2].selector.to_string(), ".card > .body", "Should be relative to the enclosing rule");
+
+    assert!(StyleSheetParser::parse_rule("> .a { width: 10px; }").is_err(), "Should require a parent");
+    assert!(StyleSheetParser::parse_rule(".a > { width: 10px; }").is_err(), "Should require a child");
+    assert!(StyleSheetParser::parse_rule(".a > > .b { width: 10px; }").is_err());
+}
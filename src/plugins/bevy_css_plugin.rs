@@ -1,10 +1,36 @@
 use crate::{
+    interpolate::{self, MotionConfig},
     prelude::{
+        CascadeAuditEnabled,
+        CascadeAuditReport,
         Class,
+        CssIgnore,
+        LastAppliedStyles,
+        PseudoClasses,
+        StyleApplied,
+        StyleChanged,
+        StyleDiagnostic,
         StyleSheet,
     },
     property::{
         self,
+        impls::{
+            style::DisplayNoneHidesSubtree,
+            BackgroundColorProperty,
+            BackgroundImageProperty,
+            BackgroundProperty,
+            ClipProperty,
+            FocusPolicyProperty,
+            ImageScaleModeProperty,
+            ImageSliceProperty,
+            ImageTileProperty,
+            OverflowClipMarginProperty,
+        },
+        text::TextBindingRegistry,
+        BareNumberPolicy,
+        Property,
+        RootFontSize,
+        RuleMatchTracking,
         StyleSheetState,
     },
     stylesheet::{
@@ -13,7 +39,20 @@ use crate::{
     },
     system::{
         self,
-        ComponentFilterRegistry, PrepareParams,
+        ComponentFilterRegistry,
+        CssCapabilities,
+        CssConditions,
+        CssFlags,
+        HotReloadEnabled,
+        InlineStyleSheets,
+        NonUiDescendants,
+        PendingRuleDiffs,
+        PrepareParams,
+        PropertyResetRegistry,
+        SelectorMatchStats,
+        StyleRootProvider,
+        StyleSheetDependencies,
+        UnmatchedRuleWarnings,
     },
     RegisterComponentSelector,
     RegisterProperty,
@@ -23,18 +62,193 @@ use bevy::prelude::*;
 
 /// Plugin which add all types, assets, systems and internal resources needed by `tomt_bevycss`.
 /// You must add this plugin in order to use `tomt_bevycss`.
-#[derive(Default)]
+///
+/// Hot reloading is disabled by default, or only enabled on debug builds with the `debug_hot_reload` feature.
+/// Use [`with_hot_reload`](BevyCssPlugin::with_hot_reload) to enable it, or [`HotReloadEnabled`] to change it at runtime.
 pub struct BevyCssPlugin
 {
     hot_reload: bool,
+    bare_number_policy: BareNumberPolicy,
+    root_font_size: RootFontSize,
+    unmatched_rule_warnings: bool,
+    motion: MotionConfig,
+    rule_deduplication: bool,
+    normalization: bool,
+    cascade_audit: bool,
+    rule_match_tracking: bool,
+    retained_state: bool,
+    display_none_hides_subtree: bool,
+    non_ui_descendants: bool,
+}
+
+impl Default
+for BevyCssPlugin
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self{
+            hot_reload: HotReloadEnabled::default().is_enabled(),
+            bare_number_policy: BareNumberPolicy::default(),
+            root_font_size: RootFontSize::default(),
+            unmatched_rule_warnings: false,
+            motion: MotionConfig::default(),
+            rule_deduplication: false,
+            normalization: false,
+            cascade_audit: false,
+            rule_match_tracking: false,
+            retained_state: false,
+            display_none_hides_subtree: false,
+            non_ui_descendants: false,
+        }
+    }
 }
 
 impl BevyCssPlugin
 {
+    /// Creates the plugin with hot reloading enabled, regardless of build profile.
     pub fn with_hot_reload(
         // no args
     ) -> BevyCssPlugin {
-        BevyCssPlugin { hot_reload: true }
+        BevyCssPlugin { hot_reload: true, ..Default::default() }
+    }
+
+    /// Creates the plugin with hot reloading disabled, regardless of build profile.
+    pub fn without_hot_reload(
+        // no args
+    ) -> BevyCssPlugin {
+        BevyCssPlugin { hot_reload: false, ..Default::default() }
+    }
+
+    /// Sets how bare numbers are handled on properties which expects a length, like `width: 10;`.
+    ///
+    /// By default those are rejected, see [`BareNumberPolicy`] for more.
+    pub fn with_bare_number_policy(
+        mut self,
+        policy: BareNumberPolicy
+    ) -> BevyCssPlugin {
+        self.bare_number_policy = policy;
+        self
+    }
+
+    /// Sets the initial [`RootFontSize`], which `rem` values are relative to. Defaults to `16.0`.
+    ///
+    /// The [`RootFontSize`] resource can be changed at runtime, which reapplies all style sheets.
+    pub fn with_root_font_size(
+        mut self,
+        size: f32
+    ) -> BevyCssPlugin {
+        self.root_font_size = RootFontSize(size);
+        self
+    }
+
+    /// Sets [`MotionConfig::reduced`], so transitions and animations snap to their final values, for accessibility. Disabled by default.
+    ///
+    /// The [`MotionConfig`] resource can also be changed at runtime, like from a settings menu.
+    pub fn with_reduced_motion(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.motion.reduced = enabled;
+        self
+    }
+
+    /// Deduplicates every loaded style sheet, with [`StyleSheetAsset::deduplicate`], which reduces the memory used
+    /// by big generated sheets at the cost of a slower load. Disabled by default.
+    pub fn with_rule_deduplication(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.rule_deduplication = enabled;
+        self
+    }
+
+    /// Normalizes every loaded style sheet, with [`StyleSheetAsset::normalize`], which drops empty rules and merges rules
+    /// with the same selector, for faster matching and smaller memory at the cost of a slower load. Disabled by default.
+    ///
+    /// It implies [`with_rule_deduplication`](BevyCssPlugin::with_rule_deduplication).
+    pub fn with_normalization(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.normalization = enabled;
+        self
+    }
+
+    /// Enables warnings about style sheet rules which doesn't match any entity, which is useful to find typos
+    /// on class names or selectors which got stale after the UI changed. Disabled by default.
+    ///
+    /// Rules are checked whenever a [`StyleSheet`] changes or its asset is loaded, and each unmatched rule
+    /// is also sent as a [`StyleDiagnostic`] event, with [`BevyCssError::UnmatchedRule`](crate::prelude::BevyCssError::UnmatchedRule).
+    pub fn with_unmatched_rule_warnings(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.unmatched_rule_warnings = enabled;
+        self
+    }
+
+    /// Enables the cascade audit, which reports rules with the same specificity, layer and importance declaring different values
+    /// for the same property on the same entities, like `.panel { width: 200px; }` on two imported sheets. Disabled by default.
+    ///
+    /// Conflicts found while styling are logged as warnings and sent as a [`CascadeAuditReport`] event, with the sheet path
+    /// and location of each rule, see [`CascadeAuditEnabled`] to change it at runtime.
+    pub fn with_cascade_audit(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.cascade_audit = enabled;
+        self
+    }
+
+    /// Counts how many entities each rule matches over a whole session, so rules which never matched any entity can be listed
+    /// with [`StyleSheetState::unmatched_rules`], to prune dead `css`. Disabled by default.
+    ///
+    /// Rules are evaluated on the whole tree like by [`with_unmatched_rule_warnings`](BevyCssPlugin::with_unmatched_rule_warnings),
+    /// and the matches found while styling are added to them. See [`RuleMatchTracking`] to change it at runtime.
+    pub fn with_rule_match_tracking(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.rule_match_tracking = enabled;
+        self
+    }
+
+    /// Keeps the styles computed on the last frame which styled any entity on the [`LastAppliedStyles`] resource,
+    /// so they can be inspected after [`BevyCssSet::Cleanup`](system::sets::BevyCssSet::Cleanup), like by devtools. Disabled by default.
+    ///
+    /// Styles are moved there instead of cloned, but a second map is kept alive, see [`LastAppliedStyles`] for its memory cost.
+    pub fn with_retained_state(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.retained_state = enabled;
+        self
+    }
+
+    /// Makes `display: none` also hide the whole subtree of matched entities, setting [`Visibility::Hidden`] and [`FocusPolicy::Pass`](bevy::ui::FocusPolicy::Pass)
+    /// on them, so none of them is rendered or receives interactions. Disabled by default.
+    ///
+    /// Previous values are restored once `display` is no longer `none`, see [`DisplayNoneHidesSubtree`] to change it at runtime.
+    pub fn with_display_none_hides_subtree(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.display_none_hides_subtree = enabled;
+        self
+    }
+
+    /// Makes descendant selectors, like `#root .item`, go through entities without [`Node`], like a [`SpatialBundle`] grouping UI nodes,
+    /// so `.item` also matches below them. Disabled by default, so descendant traversal stops at the first entity without [`Node`].
+    ///
+    /// The direct child combinator, like `#root > .item`, still only matches the direct [`Parent`](bevy::prelude::Parent),
+    /// see [`NonUiDescendants`] to change it at runtime.
+    pub fn with_non_ui_descendants(
+        mut self,
+        enabled: bool
+    ) -> BevyCssPlugin {
+        self.non_ui_descendants = enabled;
+        self
     }
 
     fn register_component_selector(
@@ -46,7 +260,8 @@ impl BevyCssPlugin
         app.register_component_selector::<Node>("node");
         app.register_component_selector::<Style>("style");
         app.register_component_selector::<UiImage>("ui-image");
-        app.register_component_selector::<Interaction>("interaction");
+        app.register_component_selector_reflect::<Interaction>("interaction");
+        app.register_component_selector_reflect::<Visibility>("visibility");
     }
 
     fn register_properties(
@@ -65,11 +280,16 @@ impl BevyCssPlugin
         app.register_property::<JustifyContentProperty>();
         app.register_property::<OverflowXProperty>();
         app.register_property::<OverflowYProperty>();
+        app.register_property::<OverflowProperty>();
+        app.register_property::<ScrollLeftProperty>();
+        app.register_property::<ScrollTopProperty>();
+        app.register_property::<ScrollPositionProperty>();
 
         app.register_property::<LeftProperty>();
         app.register_property::<RightProperty>();
         app.register_property::<TopProperty>();
         app.register_property::<BottomProperty>();
+        app.register_property::<InsetProperty>();
         app.register_property::<WidthProperty>();
         app.register_property::<HeightProperty>();
         app.register_property::<MinWidthProperty>();
@@ -84,6 +304,21 @@ impl BevyCssPlugin
         app.register_property::<MarginProperty>();
         app.register_property::<PaddingProperty>();
         app.register_property::<BorderProperty>();
+        app.register_property::<BoxSizingProperty>();
+
+        app.register_property::<MarginLeftProperty>();
+        app.register_property::<MarginRightProperty>();
+        app.register_property::<MarginTopProperty>();
+        app.register_property::<MarginBottomProperty>();
+        app.register_property::<PaddingLeftProperty>();
+        app.register_property::<PaddingRightProperty>();
+        app.register_property::<PaddingTopProperty>();
+        app.register_property::<PaddingBottomProperty>();
+
+        app.register_property::<MarginInlineStartProperty>();
+        app.register_property::<MarginInlineEndProperty>();
+        app.register_property::<PaddingInlineStartProperty>();
+        app.register_property::<PaddingInlineEndProperty>();
 
         {
             use property::text::*;
@@ -93,10 +328,28 @@ impl BevyCssPlugin
             app.register_property::<FontSizeProperty>();
             app.register_property::<TextAlignProperty>();
             app.register_property::<TextContentProperty>();
+            app.register_property::<TextShadowProperty>();
+            app.register_property::<TextTransformProperty>();
+            app.register_property::<WhiteSpaceProperty>();
         }
 
-        use property::impls::BackgroundColorProperty;
         app.register_property::<BackgroundColorProperty>();
+        app.register_property::<BackgroundProperty>();
+        app.register_property::<BackgroundImageProperty>();
+        app.register_property::<ClipProperty>();
+        app.register_property::<FocusPolicyProperty>();
+        app.register_property::<OverflowClipMarginProperty>();
+
+        app.register_property::<ImageScaleModeProperty>();
+        app.register_property::<ImageSliceProperty>();
+        app.register_property::<ImageTileProperty>();
+
+        // Image slicing is only parsed, so `@supports` blocks checking it are skipped
+        let mut capabilities = app.world.resource_mut::<CssCapabilities>();
+        for name in [ImageScaleModeProperty::name(), ImageSliceProperty::name(), ImageTileProperty::name()]
+        {
+            capabilities.remove(name);
+        }
     }
 }
 
@@ -109,34 +362,125 @@ for BevyCssPlugin
     ) {
         // Type registration
         app.register_type::<Class>()
+            .register_type::<CssIgnore>()
+            .register_type::<PseudoClasses>()
             .register_type::<StyleSheet>();
 
         // Resources
         let prepared_state = PrepareParams::new(&mut app.world);
-        app.init_asset_loader::<StyleSheetLoader>()
+        app.register_asset_loader(StyleSheetLoader { deduplicate: self.rule_deduplication, normalize: self.normalization })
             .init_asset::<StyleSheetAsset>()
             .init_resource::<StyleSheetState>()
+            .insert_resource(self.root_font_size)
+            .insert_resource(self.motion)
+            .insert_resource(self.bare_number_policy)
+            .insert_resource(CascadeAuditEnabled(self.cascade_audit))
+            .insert_resource(RuleMatchTracking(self.rule_match_tracking))
+            .insert_resource(DisplayNoneHidesSubtree(self.display_none_hides_subtree))
+            .insert_resource(NonUiDescendants(self.non_ui_descendants))
             .init_resource::<ComponentFilterRegistry>()
+            .init_resource::<PropertyResetRegistry>()
+            .init_resource::<StyleSheetDependencies>()
+            .init_resource::<PendingRuleDiffs>()
+            .init_resource::<InlineStyleSheets>()
+            .init_resource::<SelectorMatchStats>()
+            .init_resource::<CssConditions>()
+            .init_resource::<CssFlags>()
+            .init_resource::<CssCapabilities>()
+            .init_resource::<TextBindingRegistry>()
+            .init_resource::<StyleRootProvider>()
+            .init_resource::<property::impls::style::ScrolledChildren>()
             .insert_resource(prepared_state);
 
         // Schedules
         use system::sets::*;
-        app.configure_sets(PreUpdate, (
-                BevyCssSet::Prepare,
-                BevyCssSet::Apply.after(BevyCssSet::Prepare)
-            ))
+        app.configure_sets(PreUpdate, BevyCssSet::Prepare)
+            .configure_sets(Update, (
+                BevyCssSet::PreApply,
+                BevyCssSet::Apply,
+                BevyCssSet::PostApply,
+            ).chain())
             .configure_sets(PostUpdate, BevyCssSet::Cleanup);
 
         // Systems
-        app.add_systems(PreUpdate, system::prepare.in_set(BevyCssSet::Prepare))
-            .add_systems(PostUpdate, system::clear_state.in_set(BevyCssSet::Cleanup));
+        app.add_event::<StyleApplied>()
+            .add_event::<StyleChanged>()
+            .add_event::<StyleDiagnostic>()
+            .add_event::<CascadeAuditReport>()
+            .add_systems(PreUpdate, system::refresh_on_root_font_size_change
+                .run_if(resource_changed::<RootFontSize>())
+                .before(BevyCssSet::Prepare)
+            )
+            .add_systems(PreUpdate, system::evaluate_css_conditions.before(BevyCssSet::Prepare))
+            .add_systems(PreUpdate, system::evaluate_css_flags.before(BevyCssSet::Prepare))
+            .add_systems(PreUpdate, system::prepare.in_set(BevyCssSet::Prepare))
+            .add_systems(Update, system::send_style_applied.in_set(BevyCssSet::Apply))
+            .add_systems(Update, system::send_style_changed
+                .after(BevyCssSet::Apply)
+                .before(BevyCssSet::PostApply)
+            )
+            // Components inserted by properties are only available once commands of `Update` are applied,
+            // and texts must be updated before they are measured.
+            // Bound texts must be resolved before being transformed, and shadows copy the displayed text.
+            .add_systems(PostUpdate, (
+                    property::text::sync_text_bindings,
+                    property::text::sync_text_transform,
+                    property::text::sync_text_shadows,
+                )
+                .chain()
+                .before(bevy::ui::widget::measure_text_system)
+                .before(bevy::ui::UiSystem::Layout)
+            )
+            .add_systems(PostUpdate, property::impls::style::sync_box_sizing.before(bevy::ui::UiSystem::Layout))
+            .add_systems(PostUpdate, property::impls::style::restore_scroll_positions.before(bevy::ui::UiSystem::Layout))
+            .add_systems(PostUpdate, property::impls::apply_background_gradients.before(bevy::ui::UiSystem::Layout))
+            // `bevy_ui` lays out the translation of children, so scroll offsets are removed before it and applied again after it
+            .add_systems(PostUpdate, property::impls::style::apply_scroll_positions
+                .after(bevy::ui::UiSystem::Layout)
+                .before(bevy::transform::TransformSystem::TransformPropagate)
+            )
+            // Shadows are moved over their owner once it's laid out and scrolled
+            .add_systems(PostUpdate, property::text::place_text_shadows
+                .after(bevy::ui::UiSystem::Layout)
+                .after(property::impls::style::apply_scroll_positions)
+                .before(bevy::transform::TransformSystem::TransformPropagate)
+            )
+            // `bevy_ui` replaces the clipping of all nodes every frame, so clip margins must be applied after it,
+            // and `clip` must be intersected after both
+            .add_systems(PostUpdate, property::impls::apply_overflow_clip_margins.after(bevy::ui::update::update_clipping_system))
+            .add_systems(PostUpdate, property::impls::apply_clip_rects
+                .after(bevy::ui::UiSystem::Layout)
+                .after(bevy::ui::update::update_clipping_system)
+                .after(property::impls::apply_overflow_clip_margins)
+            )
+            .add_systems(Update, property::impls::style::hide_added_children.in_set(BevyCssSet::PostApply))
+            .add_systems(Update, property::impls::style::forget_unmatched_scroll_offsets.in_set(BevyCssSet::PostApply))
+            .add_systems(Update, (
+                interpolate::animate_opacity_transitions,
+                interpolate::animate_transform_transitions,
+            ).in_set(BevyCssSet::PostApply))
+            .add_systems(PostUpdate, (
+                system::clear_state,
+                system::clear_removed_style_sheets,
+                system::clear_ignored_styles,
+            ).in_set(BevyCssSet::Cleanup));
 
-        if self.hot_reload
+        if self.retained_state
         {
-            app.configure_sets(PostUpdate, BevyCssHotReload)
-                .add_systems(PostUpdate, system::hot_reload_style_sheets.in_set(BevyCssHotReload));
+            app.init_resource::<LastAppliedStyles>();
         }
 
+        // Also used by rule match tracking, which can be enabled at runtime
+        app.insert_resource(UnmatchedRuleWarnings(self.unmatched_rule_warnings))
+            .add_systems(Update, system::report_unmatched_rules.in_set(BevyCssSet::PostApply));
+
+        app.insert_resource(HotReloadEnabled(self.hot_reload))
+            .configure_sets(PostUpdate, BevyCssHotReload)
+            .add_systems(PostUpdate, (
+                system::track_style_sheet_dependencies,
+                system::hot_reload_style_sheets.run_if(system::hot_reload_enabled),
+            ).chain().in_set(BevyCssHotReload));
+
         // CSS registrations
         Self::register_component_selector(app);
         Self::register_properties(app);
@@ -0,0 +1,19 @@
+use bevy::prelude::Resource;
+
+/// Determines how bare numbers, like `width: 10;`, are handled on properties which expects a length.
+///
+/// Lengths always require a unit, like `10px` or `10%`, except for `0` which is accepted without one, like on `css`.
+/// Properties which expects a plain number, like `flex-grow`, always accept bare numbers regardless of this policy.
+///
+/// This can be configured with [`BevyCssPlugin::with_bare_number_policy`](crate::prelude::BevyCssPlugin::with_bare_number_policy),
+/// and since parsed values are cached, it should not be changed after any style sheet is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource)]
+pub enum BareNumberPolicy
+{
+    /// Bare numbers other than `0` are rejected, so `width: 10;` is an invalid property value.
+    #[default]
+    Reject,
+
+    /// Bare numbers are treated as pixels, so `width: 10;` is the same as `width: 10px;`.
+    Px,
+}
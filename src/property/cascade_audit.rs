@@ -0,0 +1,107 @@
+use super::PropertyValues;
+use crate::stylesheet::SourceLocation;
+
+use bevy::prelude::{
+    Entity,
+    Event,
+    Resource,
+};
+use std::fmt;
+
+/// Enables the cascade audit, which reports rules overriding each other without any precedence between them.
+///
+/// The initial value is set by [`BevyCssPlugin::with_cascade_audit`](crate::prelude::BevyCssPlugin::with_cascade_audit),
+/// and it can be changed at any time, like from a dev console. Disabled by default, since it slows down styling.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct CascadeAuditEnabled(pub bool);
+
+/// A declaration of a property by a style sheet rule, as listed by [`CascadeConflict`].
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub struct CascadeDeclaration
+{
+    /// Asset path of the sheet which declared the rule.
+    pub sheet: String,
+    /// Selector of the rule, like `.panel`.
+    pub selector: String,
+    /// Where the property was declared on its sheet, like on [`StyleRule::property_location`](crate::prelude::StyleRule::property_location),
+    /// so declarations of rules merged by [`deduplicate`](crate::prelude::StyleSheetAsset::deduplicate) keep their own location.
+    pub location: SourceLocation,
+}
+
+impl fmt::Display
+for CascadeDeclaration
+{
+    fn fmt(
+        &self,
+        formatter: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        write!(formatter, "'{}' ({}:{})", self.selector, self.sheet, self.location)
+    }
+}
+
+/// Rules with the same specificity, layer and importance which declared different values for the same property
+/// on the same entities, so only the rule applied last wins.
+///
+/// This usually means contradictory rules, like `.panel { width: 200px; }` and `.panel { width: 240px; }` on different sheets,
+/// where the winner depends only on the order sheets are applied.
+#[derive(Clone, Debug)]
+#[derive(PartialEq)]
+pub struct CascadeConflict
+{
+    /// Name of the property, like `width`.
+    pub property: String,
+    /// Entities on which the declarations conflicted.
+    pub entities: Vec<Entity>,
+    /// Conflicting declarations, on the order they were applied, so the last one is the winner.
+    pub declarations: Vec<CascadeDeclaration>,
+    /// Value declared by the winner.
+    pub value: PropertyValues,
+}
+
+impl CascadeConflict
+{
+    /// Declaration which won the cascade.
+    pub fn winner(
+        &self
+    ) -> Option<&CascadeDeclaration> {
+        self.declarations.last()
+    }
+}
+
+impl fmt::Display
+for CascadeConflict
+{
+    fn fmt(
+        &self,
+        formatter: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        let (winner, overridden) = match self.declarations.split_last()
+        {
+            Some(split) => split,
+            None => return write!(formatter, "Property '{}' has no declarations", self.property),
+        };
+
+        write!(formatter, "Property '{}' on {} entities is declared by", self.property, self.entities.len())?;
+        for declaration in overridden
+        {
+            write!(formatter, " {declaration},")?;
+        }
+
+        write!(formatter, " but {winner} wins with {:?}", self.value.as_slice())
+    }
+}
+
+/// Event sent by the cascade audit, enabled by [`CascadeAuditEnabled`], with all [`CascadeConflict`]s found
+/// while styling entities on a frame.
+///
+/// Conflicts are reported every time the entities are styled again, and each one is also logged as a warning.
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq)]
+#[derive(Event)]
+pub struct CascadeAuditReport
+{
+    pub conflicts: Vec<CascadeConflict>,
+}
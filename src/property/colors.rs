@@ -1,8 +1,188 @@
+use super::{
+    PropertyToken,
+    PropertyValues,
+};
+
 use bevy::{
     log::error,
     prelude::Color,
 };
 
+/// Parses a single [`PropertyToken`] into a [`Color`].
+///
+/// This is the entry point for every color value, so color functions arguments are also parsed by it.
+pub(super) fn parse_color(
+    token: &PropertyToken
+) -> Option<Color> {
+    match token
+    {
+        PropertyToken::Identifier(name) => parse_named_color(name.as_str()),
+        PropertyToken::Hash(hash) => parse_hex_color(hash.as_str()),
+        PropertyToken::Function(name, args) if name == "color-mix" => parse_color_mix(args),
+        PropertyToken::Function(name, args) if name == "lighten" => parse_lightness("lighten", args, 1.0),
+        PropertyToken::Function(name, args) if name == "darken" => parse_lightness("darken", args, -1.0),
+        PropertyToken::Function(name, args) if name == "alpha" => parse_alpha(args),
+        _ => None,
+    }
+}
+
+/// Parses the arguments of a [`color-mix()`](https://developer.mozilla.org/en-US/docs/Web/CSS/color_value/color-mix) function.
+///
+/// Only the `srgb` color space is supported. Percentages are normalized like on CSS, so `red 30%, blue 30%` is the same
+/// as `red 50%, blue 50%` but with the resulting alpha multiplied by `0.6`.
+fn parse_color_mix(
+    args: &[PropertyValues]
+) -> Option<Color> {
+    let [space, lhs, rhs] = args
+    else {
+        error!("Expected 3 arguments on `color-mix()`, found {}", args.len());
+        return None;
+    };
+
+    match &space[..]
+    {
+        [PropertyToken::Identifier(kw), PropertyToken::Identifier(space)]
+            if kw == "in" && space.eq_ignore_ascii_case("srgb") => (),
+        _ => {
+            error!("Unsupported color space on `color-mix()`, only `in srgb` is supported");
+            return None;
+        }
+    }
+
+    let (lhs, lhs_pct) = parse_color_mix_operand(lhs)?;
+    let (rhs, rhs_pct) = parse_color_mix_operand(rhs)?;
+
+    let (lhs_pct, rhs_pct) = match (lhs_pct, rhs_pct)
+    {
+        (None, None) => (50.0, 50.0),
+        (Some(lhs), None) => (lhs, 100.0 - lhs),
+        (None, Some(rhs)) => (100.0 - rhs, rhs),
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+    };
+
+    let total = lhs_pct + rhs_pct;
+    if !(0.0..=100.0).contains(&lhs_pct) || !(0.0..=100.0).contains(&rhs_pct) || total <= 0.0
+    {
+        error!("Invalid percentages on `color-mix()`: {lhs_pct}% and {rhs_pct}%");
+        return None;
+    }
+
+    let lhs_weight = lhs_pct / total;
+    let rhs_weight = rhs_pct / total;
+
+    // Mix using premultiplied alpha, as CSS does
+    let [lr, lg, lb, la] = lhs.as_rgba_f32();
+    let [rr, rg, rb, ra] = rhs.as_rgba_f32();
+
+    let alpha = la * lhs_weight + ra * rhs_weight;
+    let mix = |l: f32, r: f32| match alpha > 0.0
+    {
+        true => (l * la * lhs_weight + r * ra * rhs_weight) / alpha,
+        false => 0.0,
+    };
+
+    Some(Color::rgba(
+        mix(lr, rr),
+        mix(lg, rg),
+        mix(lb, rb),
+        alpha * total.min(100.0) / 100.0
+    ))
+}
+
+/// Parses a single `color-mix()` operand, which is a color optionally followed or preceded by a percentage.
+fn parse_color_mix_operand(
+    values: &PropertyValues
+) -> Option<(Color, Option<f32>)> {
+    match &values[..]
+    {
+        [color] => parse_color(color).map(|color| (color, None)),
+
+        [color, PropertyToken::Percentage(pct)]
+        | [PropertyToken::Percentage(pct), color] => parse_color(color).map(|color| (color, Some(*pct))),
+
+        _ => {
+            error!("Invalid `color-mix()` operand: {values:?}");
+            None
+        }
+    }
+}
+
+/// Parses the arguments of `lighten()` and `darken()`, like `lighten(#3050ff, 10%)`, which add or remove
+/// the given percentage from the lightness of a color, in HSL, clamping it between `0%` and `100%`.
+///
+/// `sign` is `1.0` for `lighten()` and `-1.0` for `darken()`.
+fn parse_lightness(
+    function: &str,
+    args: &[PropertyValues],
+    sign: f32
+) -> Option<Color> {
+    let (color, amount) = parse_color_and_amount(function, args)?;
+    let amount = match amount
+    {
+        PropertyToken::Percentage(pct) => pct / 100.0,
+        _ => {
+            error!("Expected a percentage as second argument of `{function}()`, found {amount:?}");
+            return None;
+        }
+    };
+
+    let [hue, saturation, lightness, alpha] = color.as_hsla_f32();
+    let lightness = (lightness + amount * sign).clamp(0.0, 1.0);
+
+    Some(Color::hsla(hue, saturation, lightness, alpha).as_rgba())
+}
+
+/// Parses the arguments of `alpha()`, like `alpha(#3050ff, 0.5)` or `alpha(#3050ff, 50%)`,
+/// which replaces the alpha of a color.
+fn parse_alpha(
+    args: &[PropertyValues]
+) -> Option<Color> {
+    let (color, amount) = parse_color_and_amount("alpha", args)?;
+    let alpha = match amount
+    {
+        PropertyToken::Number(alpha) => *alpha,
+        PropertyToken::Percentage(pct) => pct / 100.0,
+        _ => {
+            error!("Expected a number or a percentage as second argument of `alpha()`, found {amount:?}");
+            return None;
+        }
+    };
+
+    Some(color.as_rgba().with_a(alpha.clamp(0.0, 1.0)))
+}
+
+/// Parses the arguments of color functions which take a color and an amount, like `lighten(#3050ff, 10%)`.
+fn parse_color_and_amount<'a>(
+    function: &str,
+    args: &'a [PropertyValues]
+) -> Option<(Color, &'a PropertyToken)> {
+    let [color_arg, amount] = args
+    else {
+        error!("Expected 2 arguments on `{function}()`, found {}", args.len());
+        return None;
+    };
+
+    let color = match &color_arg[..]
+    {
+        [color] => parse_color(color),
+        _ => None,
+    };
+    let Some(color) = color
+    else {
+        error!("Expected a color as first argument of `{function}()`, found {color_arg:?}");
+        return None;
+    };
+
+    match &amount[..]
+    {
+        [amount] => Some((color, amount)),
+        _ => {
+            error!("Expected a single value as second argument of `{function}()`, found {amount:?}");
+            None
+        }
+    }
+}
+
 pub(super) fn parse_hex_color(
     hex_str: &str
 ) -> Option<Color> {
@@ -0,0 +1,184 @@
+use super::LinearGradient;
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyToken, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        AssetServer,
+        Commands,
+        Component,
+        Entity,
+        Handle,
+        Image,
+        Node,
+        Res,
+        UiImage,
+        With,
+    },
+};
+
+/// Value of the `background-image` property, either an image path or a gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundImage
+{
+    /// Path of an image, loaded with the [`AssetServer`].
+    Path(String),
+    /// Gradient, which image is generated, see [`LinearGradient`].
+    LinearGradient(LinearGradient),
+}
+
+/// Texture assigned to [`UiImage`] by the `background-image` property, so only images loaded by style sheets are removed.
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Component)]
+pub struct StyledBackgroundImage(pub Handle<Image>);
+
+/// Applies the `background-image` property on the texture of [`UiImage`] component of matched entities.
+///
+/// It accepts an image path, like `background-image: "icons/star.png";`, which is loaded with the [`AssetServer`],
+/// a gradient, like `linear-gradient(to bottom, #202030, #101018)`, kept on [`LinearGradient`], or `none` to remove it.
+/// Since `bevy_ui` tints images with [`BackgroundColor`](bevy::prelude::BackgroundColor), it should be white to show the image as it is.
+///
+/// [`UiImage`] is inserted on nodes without one. Only images set by this property are removed, so an image set by your own
+/// systems is kept by `none`, and when the property is reverted.
+///
+/// # Examples
+///
+/// ```
+/// # use tomt_bevycss::{prelude::*, property::impls::{BackgroundImage, BackgroundImageProperty}};
+/// let path = BackgroundImageProperty::parse(&PropertyValues::parse(r#""icons/star.png""#).unwrap()).unwrap();
+/// assert_eq!(path, Some(BackgroundImage::Path("icons/star.png".to_string())));
+///
+/// assert_eq!(BackgroundImageProperty::parse(&PropertyValues::parse("none").unwrap()), Ok(None));
+/// assert!(BackgroundImageProperty::parse(&PropertyValues::parse("icons").unwrap()).is_err());
+/// ```
+#[derive(Default)]
+pub struct BackgroundImageProperty;
+
+impl Property
+for BackgroundImageProperty
+{
+    type Cache = Option<BackgroundImage>;
+    type Components = (
+        Entity,
+        Option<&'static mut UiImage>,
+        Option<&'static StyledBackgroundImage>,
+        Option<&'static mut LinearGradient>,
+    );
+    type Filters = With<Node>;
+    type Param = Res<'static, AssetServer>;
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "background-image"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        match values.as_slice()
+        {
+            [PropertyToken::Function(name, args)] if name.eq_ignore_ascii_case("linear-gradient") => {
+                if let Some(gradient) = LinearGradient::parse_args(args)
+                {
+                    return Ok(Some(BackgroundImage::LinearGradient(gradient)));
+                }
+            }
+            [_] => {
+                if let Some(path) = values.string()
+                {
+                    return Ok(Some(BackgroundImage::Path(path)));
+                }
+
+                if values.keyword(&["none"]).is_some()
+                {
+                    return Ok(None);
+                }
+            }
+            _ => (),
+        }
+
+        Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, image, styled, gradient): QueryItem<Self::Components>,
+        asset_server: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match cache
+        {
+            Some(BackgroundImage::Path(path)) => {
+                let texture = asset_server.load(path);
+
+                match image
+                {
+                    Some(mut image) => if image.texture != texture
+                    {
+                        image.texture = texture.clone();
+                    },
+                    None => {
+                        commands.entity(entity).try_insert(UiImage::new(texture.clone()));
+                    }
+                }
+
+                if styled.map_or(true, |styled| styled.0 != texture)
+                {
+                    commands.entity(entity).try_insert(StyledBackgroundImage(texture));
+                }
+
+                // The image of the gradient isn't restored, since the texture was already replaced
+                if gradient.is_some()
+                {
+                    commands.entity(entity).remove::<LinearGradient>();
+                }
+            }
+            Some(BackgroundImage::LinearGradient(cache)) => {
+                // The image is generated and assigned by `apply_background_gradients`
+                match gradient
+                {
+                    Some(mut gradient) => if *gradient != *cache
+                    {
+                        *gradient = cache.clone();
+                    },
+                    None => {
+                        commands.entity(entity).try_insert(cache.clone());
+                    }
+                }
+
+                if styled.is_some()
+                {
+                    commands.entity(entity).remove::<StyledBackgroundImage>();
+                }
+            }
+            None => Self::reset((entity, image, styled, gradient), asset_server, commands),
+        }
+    }
+
+    fn reset<'w>(
+        (entity, image, styled, gradient): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        // Images of gradients are restored by `apply_background_gradients` once the gradient is removed
+        if gradient.is_some()
+        {
+            commands.entity(entity).remove::<LinearGradient>();
+        }
+
+        let Some(styled) = styled
+        else {
+            return;
+        };
+
+        if let Some(mut image) = image.filter(|image| image.texture == styled.0)
+        {
+            image.texture = Handle::default();
+        }
+
+        commands.entity(entity).remove::<StyledBackgroundImage>();
+    }
+}
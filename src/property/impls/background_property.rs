@@ -0,0 +1,473 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{colors, Property, PropertyContext, PropertyToken, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Assets,
+        BackgroundColor,
+        Changed,
+        Color,
+        Commands,
+        Component,
+        Entity,
+        Handle,
+        Image,
+        Local,
+        Node,
+        Query,
+        RemovedComponents,
+        ResMut,
+        UiImage,
+        With,
+    },
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    utils::{HashMap, HashSet},
+};
+use smallvec::smallvec;
+
+/// Length, in pixels, of images generated for [`LinearGradient`]s, which are stretched over the whole node.
+const GRADIENT_IMAGE_SIZE: u32 = 64;
+
+/// A color of a [`LinearGradient`], at a position along the gradient line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop
+{
+    pub color: Color,
+    /// Position along the gradient line, from `0.0` at its start to `1.0` at its end.
+    pub position: f32,
+}
+
+/// Gradient set by the `background` property, like `linear-gradient(to bottom, #202030, #101018)`.
+///
+/// An [`Image`] is generated for each distinct gradient and assigned to the [`UiImage`] of the entity,
+/// which is stretched over the whole node.
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Component)]
+pub struct LinearGradient
+{
+    /// Direction of the gradient line, in degrees, where `0.0` points to the top and `90.0` to the right, like on `css`.
+    pub angle: f32,
+    /// Colors along the gradient line, sorted by position.
+    pub stops: Vec<ColorStop>,
+}
+
+impl LinearGradient
+{
+    /// Color of the gradient at the given position along its line, from `0.0` to `1.0`.
+    ///
+    /// Colors are interpolated between the closest stops, and the first and last colors are used before and after all stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::Color;
+    /// # use tomt_bevycss::property::impls::{ColorStop, LinearGradient};
+    /// let gradient = LinearGradient {
+    ///     angle: 180.0,
+    ///     stops: vec![
+    ///         ColorStop { color: Color::BLACK, position: 0.0 },
+    ///         ColorStop { color: Color::WHITE, position: 1.0 },
+    ///     ],
+    /// };
+    ///
+    /// assert_eq!(gradient.sample(0.5), Color::rgb(0.5, 0.5, 0.5));
+    /// ```
+    pub fn sample(
+        &self,
+        position: f32
+    ) -> Color {
+        let (first, last) = match (self.stops.first(), self.stops.last())
+        {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::NONE,
+        };
+
+        if position <= first.position
+        {
+            return first.color;
+        }
+
+        self.stops.windows(2)
+            .find(|stops| position < stops[1].position)
+            .map(|stops|
+            {
+                let (start, end) = (stops[0], stops[1]);
+                let t = (position - start.position) / (end.position - start.position);
+                let [r0, g0, b0, a0] = start.color.as_rgba_f32();
+                let [r1, g1, b1, a1] = end.color.as_rgba_f32();
+
+                Color::rgba(r0 + (r1 - r0) * t, g0 + (g1 - g0) * t, b0 + (b1 - b0) * t, a0 + (a1 - a0) * t)
+            })
+            .unwrap_or(last.color)
+    }
+
+    /// Generates an image of the gradient, to be stretched over a node.
+    ///
+    /// Vertical and horizontal gradients only need a single column or row of pixels, while other angles use a square image.
+    /// Since the image is stretched, diagonal gradients like `to bottom right` always go from corner to corner, like on `css`.
+    pub fn to_image(
+        &self
+    ) -> Image {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (sin, cos) = (round_unit(sin), round_unit(cos));
+
+        let width = if sin == 0.0 { 1 } else { GRADIENT_IMAGE_SIZE };
+        let height = if cos == 0.0 { 1 } else { GRADIENT_IMAGE_SIZE };
+        // Half the length of the gradient line, on a unit square
+        let half_length = (sin.abs() + cos.abs()) / 2.0;
+
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height
+        {
+            for x in 0..width
+            {
+                // Offset of the pixel center from the center of the image, where `y` grows downwards
+                let dx = (x as f32 + 0.5) / width as f32 - 0.5;
+                let dy = (y as f32 + 0.5) / height as f32 - 0.5;
+                let position = (dx * sin - dy * cos) / half_length / 2.0 + 0.5;
+
+                data.extend_from_slice(&self.sample(position).as_rgba_u8());
+            }
+        }
+
+        Image::new(
+            Extent3d { width, height, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    /// Parses the arguments of a `linear-gradient()` function.
+    ///
+    /// The first argument is an optional direction, either an angle in degrees, like `90deg`, or `to` followed by one or two sides,
+    /// like `to bottom right`, and defaults to `to bottom`. Each other argument is a color with an optional percentage position.
+    /// Stops without a position are spread evenly between their neighbors, like on `css`.
+    pub(crate) fn parse_args(
+        args: &[PropertyValues]
+    ) -> Option<Self> {
+        let (angle, stops) = match args.split_first()
+        {
+            Some((first, stops)) => match Self::parse_direction(first)
+            {
+                Some(angle) => (angle, stops),
+                None => (180.0, args),
+            },
+            None => return None,
+        };
+
+        let stops = stops.iter()
+            .map(|stop| match stop.as_slice()
+            {
+                [color] => colors::parse_color(color).map(|color| (color, None)),
+                [color, PropertyToken::Percentage(position)] => colors::parse_color(color).map(|color| (color, Some(position / 100.0))),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if stops.len() < 2
+        {
+            return None;
+        }
+
+        Some(Self{
+            angle,
+            stops: resolve_stop_positions(&stops),
+        })
+    }
+
+    fn parse_direction(
+        values: &PropertyValues
+    ) -> Option<f32> {
+        let sides: Vec<&str> = match values.as_slice()
+        {
+            [angle @ PropertyToken::Angle(..)] => return angle.degrees(),
+            [PropertyToken::Number(degrees)] if *degrees == 0.0 => return Some(0.0),
+            [PropertyToken::Identifier(to), sides @ ..] if to.eq_ignore_ascii_case("to") => sides.iter()
+                .map(|side| match side
+                {
+                    PropertyToken::Identifier(side) => Some(side.as_str()),
+                    _ => None,
+                })
+                .collect::<Option<_>>()?,
+            _ => return None,
+        };
+
+        let mut sides: Vec<String> = sides.iter().map(|side| side.to_ascii_lowercase()).collect();
+        sides.sort();
+
+        let sides: Vec<&str> = sides.iter().map(String::as_str).collect();
+        match sides.as_slice()
+        {
+            ["top"] => Some(0.0),
+            ["right", "top"] => Some(45.0),
+            ["right"] => Some(90.0),
+            ["bottom", "right"] => Some(135.0),
+            ["bottom"] => Some(180.0),
+            ["bottom", "left"] => Some(225.0),
+            ["left"] => Some(270.0),
+            ["left", "top"] => Some(315.0),
+            _ => None,
+        }
+    }
+
+    /// Key identifying gradients which generate the same image.
+    fn image_key(
+        &self
+    ) -> Vec<u32> {
+        std::iter::once(self.angle.rem_euclid(360.0).to_bits())
+            .chain(self.stops.iter().flat_map(|stop| [stop.color.as_rgba_u32(), stop.position.to_bits()]))
+            .collect()
+    }
+}
+
+/// Rounds values close to `-1.0`, `0.0` or `1.0`, so right angles produce exact axis aligned gradients.
+fn round_unit(
+    value: f32
+) -> f32 {
+    match value.round()
+    {
+        rounded if (value - rounded).abs() < 1e-5 => rounded,
+        _ => value,
+    }
+}
+
+/// Gives a position to every stop, spreading stops without one evenly between their neighbors,
+/// and ensuring no stop comes before the previous one.
+fn resolve_stop_positions(
+    stops: &[(Color, Option<f32>)]
+) -> Vec<ColorStop> {
+    let last = stops.len() - 1;
+    let mut positions: Vec<Option<f32>> = stops.iter()
+        .enumerate()
+        .map(|(index, (_color, position))| match index
+        {
+            0 => Some(position.unwrap_or(0.0)),
+            index if index == last => Some(position.unwrap_or(1.0)),
+            _ => *position,
+        })
+        .collect();
+
+    let mut max = f32::MIN;
+    for position in positions.iter_mut().flatten()
+    {
+        max = position.max(max);
+        *position = max;
+    }
+
+    let mut start = 0;
+    for end in 1..positions.len()
+    {
+        let Some(end_position) = positions[end]
+        else {
+            continue;
+        };
+
+        let start_position = positions[start].unwrap_or_default();
+        let step = (end_position - start_position) / (end - start) as f32;
+        for (offset, position) in positions[start + 1..end].iter_mut().enumerate()
+        {
+            *position = Some(start_position + step * (offset + 1) as f32);
+        }
+
+        start = end;
+    }
+
+    stops.iter()
+        .zip(positions)
+        .map(|((color, _position), position)| ColorStop { color: *color, position: position.unwrap_or_default() })
+        .collect()
+}
+
+/// Value of the `background` property, either a flat color or a gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Background
+{
+    Color(Color),
+    LinearGradient(LinearGradient),
+}
+
+impl Default
+for Background
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self::Color(BackgroundColor::DEFAULT.0)
+    }
+}
+
+/// Applies the `background` shorthand property, which accepts either a color, like `background-color`,
+/// or a gradient, like `linear-gradient(to bottom, #202030, #101018)`, like `background-image`.
+///
+/// Declarations are expanded into `background-color` and `background-image` when sheets are parsed, see [`longhands`](BackgroundProperty::longhands),
+/// so the cascade decides between them and the longhands declared on their own, like `background: red; background-color: blue;`.
+/// A flat color removes the image, while a gradient sets the color to white, since `bevy_ui` tints images with [`BackgroundColor`].
+/// Only invalid values are kept as `background`, which are reported once applied.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::Color;
+/// # use tomt_bevycss::{prelude::*, property::impls::{Background, BackgroundProperty}};
+/// let Ok(Background::LinearGradient(gradient)) = BackgroundProperty::parse(&PropertyValues::parse("linear-gradient(90deg, red, blue 80%)").unwrap())
+/// else {
+///     panic!("Should be a gradient");
+/// };
+///
+/// assert_eq!(gradient.angle, 90.0);
+/// assert_eq!(gradient.stops[1].position, 0.8);
+///
+/// assert_eq!(BackgroundProperty::parse(&PropertyValues::parse("red").unwrap()).unwrap(), Background::Color(Color::RED));
+/// ```
+#[derive(Default)]
+pub struct BackgroundProperty;
+
+impl BackgroundProperty
+{
+    /// Expands a `background` declaration into its `background-color` and `background-image` longhands,
+    /// or returns `None` if the value is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::{prelude::*, property::impls::BackgroundProperty};
+    /// let [(color, color_value), (image, image_value)] = BackgroundProperty::longhands(&PropertyValues::parse("red").unwrap()).unwrap();
+    ///
+    /// assert_eq!((color, color_value), ("background-color", PropertyValues::parse("red").unwrap()));
+    /// assert_eq!((image, image_value), ("background-image", PropertyValues::parse("none").unwrap()));
+    /// ```
+    pub fn longhands(
+        values: &PropertyValues
+    ) -> Option<[(&'static str, PropertyValues); 2]> {
+        let keyword = |name: &str| PropertyValues(smallvec![PropertyToken::Identifier(name.to_string())]);
+
+        match Self::parse(values).ok()?
+        {
+            Background::Color(_) => Some([
+                ("background-color", values.clone()),
+                ("background-image", keyword("none")),
+            ]),
+            Background::LinearGradient(_) => Some([
+                ("background-color", keyword("white")),
+                ("background-image", values.clone()),
+            ]),
+        }
+    }
+}
+
+impl Property
+for BackgroundProperty
+{
+    type Cache = Background;
+    type Components = Entity;
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "background"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        if let Some(color) = values.color()
+        {
+            return Ok(Background::Color(color));
+        }
+
+        match values.as_slice()
+        {
+            [PropertyToken::Function(name, args)] if name.eq_ignore_ascii_case("linear-gradient") => LinearGradient::parse_args(args)
+                .map(Background::LinearGradient)
+                .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        _cache: &Self::Cache,
+        _entity: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        // Valid values are expanded into longhands when parsed, so there is nothing left to apply
+    }
+}
+
+/// Gradients which changed since last time images were assigned.
+type QueryChangedGradients<'w, 's> = Query<
+    'w, 's,
+    (Entity, &'static LinearGradient),
+    Changed<LinearGradient>,
+>;
+
+/// Assigns an image generated from [`LinearGradient`] to the [`UiImage`] of each entity which gradient changed.
+///
+/// Images are cached by the gradient parameters, so entities with identical gradients share the same image, and images
+/// are only kept while some entity uses them. Entities which no longer have a gradient get back the default image.
+pub(crate) fn apply_background_gradients(
+    mut commands: Commands,
+    q_changed: QueryChangedGradients,
+    q_gradients: Query<&LinearGradient>,
+    mut q_images: Query<&mut UiImage>,
+    mut removed: RemovedComponents<LinearGradient>,
+    images: Option<ResMut<Assets<Image>>>,
+    mut cache: Local<HashMap<Vec<u32>, Handle<Image>>>,
+) {
+    // Images can't be generated without a renderer, like on headless apps
+    let Some(mut images) = images
+    else {
+        return;
+    };
+
+    let mut changed = false;
+
+    for (entity, gradient) in q_changed.iter()
+    {
+        let handle = cache.entry(gradient.image_key())
+            .or_insert_with(|| images.add(gradient.to_image()))
+            .clone();
+
+        match q_images.get_mut(entity)
+        {
+            Ok(mut image) => {
+                if image.texture != handle
+                {
+                    image.texture = handle;
+                }
+            }
+            Err(_) => {
+                commands.entity(entity).try_insert(UiImage::new(handle));
+            }
+        }
+
+        changed = true;
+    }
+
+    for entity in removed.read()
+    {
+        if let Ok(mut image) = q_images.get_mut(entity)
+        {
+            if cache.values().any(|handle| *handle == image.texture)
+            {
+                image.texture = Default::default();
+            }
+        }
+
+        changed = true;
+    }
+
+    // Images of gradients no longer used by any entity are dropped
+    if changed
+    {
+        let used: HashSet<Vec<u32>> = q_gradients.iter()
+            .map(LinearGradient::image_key)
+            .collect();
+        cache.retain(|key, _handle| used.contains(key));
+    }
+}
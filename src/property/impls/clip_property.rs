@@ -0,0 +1,297 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyToken, PropertyValues, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    math::{Rect, Vec2},
+    prelude::{
+        Children,
+        Commands,
+        Component,
+        Entity,
+        GlobalTransform,
+        Node,
+        Parent,
+        Query,
+        Style,
+        With,
+    },
+    ui::{CalculatedClip, OverflowAxis, Val},
+    utils::HashMap,
+};
+
+/// Rectangle set by the `clip` property, out of which an entity and its descendants aren't rendered.
+///
+/// Like on `css`, all sides are offsets from the top-left corner of the node, so `right` and `bottom` are measured
+/// from the left and top edges, instead of from the right and bottom ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Component)]
+pub struct ClipRect
+{
+    pub top: Val,
+    pub right: Val,
+    pub bottom: Val,
+    pub left: Val,
+}
+
+impl ClipRect
+{
+    /// Resolves the clipping rectangle, in the same logical coordinates as the given node rectangle.
+    ///
+    /// Percentages are relative to the node width on `left` and `right`, and to the node height on `top` and `bottom`.
+    /// `auto` sides fall back to the node edges, so `auto` on all sides doesn't clip anything beyond the node bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::{math::{Rect, Vec2}, ui::Val};
+    /// # use tomt_bevycss::property::impls::ClipRect;
+    /// let clip = ClipRect { top: Val::Px(0.0), right: Val::Px(100.0), bottom: Val::Percent(50.0), left: Val::Auto };
+    /// let node = Rect::new(10.0, 10.0, 210.0, 110.0);
+    ///
+    /// assert_eq!(clip.resolve(node), Rect::new(10.0, 10.0, 110.0, 60.0));
+    /// ```
+    pub fn resolve(
+        &self,
+        node: Rect
+    ) -> Rect {
+        let size = node.size();
+        let offset = |val: Val, length: f32, auto: f32| match val
+        {
+            Val::Px(px) => px,
+            Val::Percent(percent) => length * percent / 100.0,
+            _ => auto,
+        };
+
+        let min = Vec2::new(offset(self.left, size.x, 0.0), offset(self.top, size.y, 0.0));
+        let max = Vec2::new(offset(self.right, size.x, size.x), offset(self.bottom, size.y, size.y));
+
+        Rect::from_corners(node.min + min, node.min + max)
+    }
+}
+
+/// Applies the `clip` property, which constrains the rendering of matched entities, and all their descendants,
+/// to a rectangle, like `clip: rect(0px, 100px, 50px, 0px);`.
+///
+/// Values are given in `top`, `right`, `bottom` and `left` order, either comma or space separated,
+/// and each one can be a dimension, a percentage or `auto`, see [`ClipRect`].
+/// `clip: auto` removes the clipping.
+///
+/// The rectangle is kept on [`ClipRect`], and it's intersected with the [`CalculatedClip`] computed by `bevy_ui`
+/// from `overflow`, so both can be used together.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::{prelude::*, property::impls::{ClipProperty, ClipRect}};
+/// let clip = ClipProperty::parse(&PropertyValues::parse("rect(0px, 100px, 50px, 0px)").unwrap()).unwrap();
+/// assert_eq!(clip, Some(ClipRect { top: Val::Px(0.0), right: Val::Px(100.0), bottom: Val::Px(50.0), left: Val::Px(0.0) }));
+///
+/// assert_eq!(ClipProperty::parse(&PropertyValues::parse("auto").unwrap()).unwrap(), None);
+/// ```
+#[derive(Default)]
+pub struct ClipProperty;
+
+impl Property
+for ClipProperty
+{
+    type Cache = Option<ClipRect>;
+    type Components = (Entity, Option<&'static mut ClipRect>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "clip"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        let args = match values.as_slice()
+        {
+            [PropertyToken::Function(name, args)] if name.eq_ignore_ascii_case("rect") => args,
+            _ => return match values.keyword(&["auto"])
+            {
+                Some(_) if values.len() == 1 => Ok(None),
+                _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            },
+        };
+
+        for arg in args
+        {
+            arg.expect_unit(Self::name(), UnitCategory::Length)?;
+        }
+
+        // Sides are either comma separated, as on `rect(0px, 100px, 50px, 0px)`, or space separated, as on `rect(0px 100px 50px 0px)`
+        let sides = match args.as_slice()
+        {
+            [single] => single.iter()
+                .map(PropertyToken::to_val)
+                .collect::<Option<Vec<Val>>>(),
+            args => args.iter()
+                .map(|arg| match arg.as_slice()
+                {
+                    [token] => token.to_val(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<Val>>>(),
+        };
+
+        match sides.as_deref()
+        {
+            Some(&[top, right, bottom, left]) => Ok(Some(ClipRect { top, right, bottom, left })),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, clip): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match (cache, clip)
+        {
+            (Some(cache), Some(mut clip)) => {
+                if *clip != *cache
+                {
+                    *clip = *cache;
+                }
+            }
+            (Some(cache), None) => {
+                commands.entity(entity).try_insert(*cache);
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<ClipRect>();
+            }
+            (None, None) => (),
+        }
+    }
+
+    fn reset<'w>(
+        (entity, clip): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if clip.is_some()
+        {
+            commands.entity(entity).remove::<ClipRect>();
+        }
+    }
+}
+
+/// Nodes clipped by the `clip` property, along with their layout.
+type QueryClippedNodes<'w, 's> = Query<
+    'w, 's,
+    (Entity, &'static Node, &'static GlobalTransform, &'static ClipRect),
+>;
+
+/// Clipping computed by `bevy_ui` of all nodes.
+type QueryCalculatedClips<'w, 's> = Query<
+    'w, 's,
+    Option<&'static mut CalculatedClip>,
+    With<Node>,
+>;
+
+/// Clipping `bevy_ui` computes for an entity from the `overflow` of its ancestors, if any clips it.
+fn overflow_clip(
+    entity: Entity,
+    q_parents: &Query<&Parent>,
+    q_nodes: &Query<(&Node, &GlobalTransform, &Style)>,
+) -> Option<Rect> {
+    let mut clip = None::<Rect>;
+    let mut current = entity;
+    while let Ok(parent) = q_parents.get(current)
+    {
+        current = parent.get();
+        let Ok((node, transform, style)) = q_nodes.get(current)
+        else {
+            continue;
+        };
+
+        if style.overflow.is_visible()
+        {
+            continue;
+        }
+
+        let mut rect = node.logical_rect(transform);
+        if style.overflow.x == OverflowAxis::Visible
+        {
+            rect.min.x = -f32::INFINITY;
+            rect.max.x = f32::INFINITY;
+        }
+        if style.overflow.y == OverflowAxis::Visible
+        {
+            rect.min.y = -f32::INFINITY;
+            rect.max.y = f32::INFINITY;
+        }
+
+        clip = Some(clip.map_or(rect, |clip| clip.intersect(rect)));
+    }
+
+    clip
+}
+
+/// Intersects the [`CalculatedClip`] of nodes with [`ClipRect`], and of all their descendants, with the clipping rectangle.
+///
+/// Must run after `bevy_ui` computes the clipping from `overflow`, since it replaces [`CalculatedClip`] every frame.
+/// Clips are only written when they differ, but `bevy_ui` removes the clip of nodes it doesn't clip itself,
+/// so those are inserted again.
+pub(crate) fn apply_clip_rects(
+    mut commands: Commands,
+    q_clipped: QueryClippedNodes,
+    mut q_clips: QueryCalculatedClips,
+    q_children: Query<&Children>,
+    q_parents: Query<&Parent>,
+    q_nodes: Query<(&Node, &GlobalTransform, &Style)>,
+) {
+    let mut clips = HashMap::<Entity, Rect>::new();
+    for (entity, node, transform, clip) in q_clipped.iter()
+    {
+        let rect = clip.resolve(node.logical_rect(transform));
+
+        let mut pending = vec![entity];
+        while let Some(entity) = pending.pop()
+        {
+            clips.entry(entity)
+                .and_modify(|clip| *clip = clip.intersect(rect))
+                .or_insert(rect);
+
+            if let Ok(children) = q_children.get(entity)
+            {
+                pending.extend(children.iter());
+            }
+        }
+    }
+
+    for (entity, rect) in clips
+    {
+        let Ok(calculated) = q_clips.get_mut(entity)
+        else {
+            continue;
+        };
+
+        let overflow_clip = overflow_clip(entity, &q_parents, &q_nodes);
+        match calculated
+        {
+            // The clip computed by `bevy_ui` this frame, which may be extended by `overflow-clip-margin`.
+            // `CalculatedClip` isn't comparable, so `set_if_neq` can't be used
+            Some(mut calculated) if overflow_clip.is_some() => {
+                let clip = calculated.clip.intersect(rect);
+                if calculated.clip != clip
+                {
+                    calculated.clip = clip;
+                }
+            }
+            // Either missing, or a clip of the previous frame that `bevy_ui` is removing
+            _ => {
+                let clip = overflow_clip.map_or(rect, |overflow_clip| overflow_clip.intersect(rect));
+                commands.entity(entity).try_insert(CalculatedClip { clip });
+            }
+        }
+    }
+}
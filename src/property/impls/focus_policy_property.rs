@@ -0,0 +1,127 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Commands,
+        Component,
+        Entity,
+        Node,
+        With,
+    },
+    ui::FocusPolicy,
+};
+
+/// Keeps the [`FocusPolicy`] an entity had before `focus-policy` was applied, so it can be restored later.
+///
+/// It's `None` when the entity had no [`FocusPolicy`], so it's removed again.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct PreviousFocusPolicy(pub Option<FocusPolicy>);
+
+/// Applies the `focus-policy` property on [`FocusPolicy`] component of matched entities, which is either `block` or `pass`.
+///
+/// Unlike `pointer-events` on `css`, it only controls if interactions are blocked from reaching the entities below,
+/// like a modal overlay blocking the UI behind it, while the entity itself can still be interacted with.
+/// The previous value is kept on [`PreviousFocusPolicy`] and restored when the property is reset, since bundles
+/// like `ButtonBundle` and `NodeBundle` have different defaults.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::FocusPolicy;
+/// # use tomt_bevycss::{prelude::*, property::impls::FocusPolicyProperty};
+/// assert_eq!(FocusPolicyProperty::parse(&PropertyValues::parse("block").unwrap()), Ok(FocusPolicy::Block));
+/// assert!(FocusPolicyProperty::parse(&PropertyValues::parse("none").unwrap()).is_err());
+/// ```
+#[derive(Default)]
+pub struct FocusPolicyProperty;
+
+impl Property
+for FocusPolicyProperty
+{
+    type Cache = FocusPolicy;
+    type Components = (Entity, Option<&'static mut FocusPolicy>, Option<&'static PreviousFocusPolicy>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "focus-policy"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        if values.len() != 1
+        {
+            return Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()));
+        }
+
+        match values.keyword(&["block", "pass"])
+        {
+            Some("block") => Ok(FocusPolicy::Block),
+            Some(_) => Ok(FocusPolicy::Pass),
+            None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, focus_policy, previous): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if previous.is_none()
+        {
+            let current = focus_policy.as_deref().copied();
+            commands.entity(entity).try_insert(PreviousFocusPolicy(current));
+        }
+
+        match focus_policy
+        {
+            Some(mut focus_policy) => {
+                if *focus_policy != *cache
+                {
+                    *focus_policy = *cache;
+                }
+            }
+            None => {
+                commands.entity(entity).try_insert(*cache);
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (entity, focus_policy, previous): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        let Some(previous) = previous
+        else {
+            return;
+        };
+
+        let mut entity = commands.entity(entity);
+        entity.remove::<PreviousFocusPolicy>();
+
+        match (previous.0, focus_policy)
+        {
+            (Some(previous), Some(mut focus_policy)) => {
+                if *focus_policy != previous
+                {
+                    *focus_policy = previous;
+                }
+            }
+            (Some(previous), None) => {
+                entity.try_insert(previous);
+            }
+            (None, Some(_)) => {
+                entity.remove::<FocusPolicy>();
+            }
+            (None, None) => (),
+        }
+    }
+}
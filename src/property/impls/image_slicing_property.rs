@@ -0,0 +1,166 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{parse_keyword, Property, PropertyContext, PropertyToken, PropertyValues, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    math::BVec2,
+    prelude::{
+        Commands,
+        UiImage,
+        With,
+    },
+    ui::{UiRect, Val},
+};
+
+/// Scaling of the image of a node, declared by `image-scale-mode`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageScaling
+{
+    /// The image is stretched over the node, which is what `bevy_ui` does.
+    #[default]
+    Stretch,
+    /// The image is split on nine parts by the insets of `image-slice`, and only its center and edges are scaled.
+    Sliced,
+    /// The image is repeated over the node, on the axes enabled by `image-tile`.
+    Tiled,
+}
+
+/// Builds the error reported by image slicing properties, which are valid but need texture slicing,
+/// that isn't available on the targeted Bevy version.
+fn unsupported(
+    name: &str
+) -> BevyCssError {
+    BevyCssError::UnsupportedPropertyValue(format!("{name}: texture slicing isn't available on this Bevy version"))
+}
+
+/// Parses the insets of `image-slice`, which are 1 to 4 `00.00px` values applied like `margin`.
+fn parse_insets(
+    values: &PropertyValues
+) -> Option<UiRect> {
+    let insets = values.as_slice()
+        .iter()
+        .map(|token| match token.to_val()
+        {
+            Some(Val::Px(px)) if px >= 0.0 => Some(Val::Px(px)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match insets.as_slice()
+    {
+        [all] => Some(UiRect::all(*all)),
+        [vertical, horizontal] => Some(UiRect::new(*horizontal, *horizontal, *vertical, *vertical)),
+        [top, horizontal, bottom] => Some(UiRect::new(*horizontal, *horizontal, *top, *bottom)),
+        [top, right, bottom, left] => Some(UiRect::new(*left, *right, *top, *bottom)),
+        _ => None,
+    }
+}
+
+/// Parses whether an axis of `image-tile` repeats the image, which is either `repeat` or `stretch`.
+fn parse_tile_axis(
+    token: &PropertyToken
+) -> Option<bool> {
+    parse_keyword(token, &["repeat", "stretch"])
+        .map(|keyword| keyword == "repeat")
+}
+
+macro_rules! impl_image_slicing_property {
+    ($name:expr, $struct:ident, $cache:ty, $parse:expr $(, $unit:ident)?) => {
+        impl Property
+        for $struct
+        {
+            type Cache = $cache;
+            type Components = ();
+            type Filters = With<UiImage>;
+            type Param = ();
+
+            fn name(
+                // no args
+            ) -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError> {
+                $(values.expect_unit(Self::name(), UnitCategory::$unit)?;)?
+
+                let parse: fn(&PropertyValues) -> Option<$cache> = $parse;
+                match parse(values)
+                {
+                    Some(_value) => Err(unsupported(Self::name())),
+                    None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+                }
+            }
+
+            fn apply<'w>(
+                _cache: &Self::Cache,
+                _components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                // Never parsed successfully without texture slicing
+            }
+
+            fn reset<'w>(
+                _components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                // Nothing was applied
+            }
+        }
+    };
+}
+
+/// Parses the `image-scale-mode` property, which is `stretch`, `sliced` or `tiled`, like `image-scale-mode: sliced;`.
+///
+/// Texture slicing isn't available on the targeted Bevy version, so valid values are reported as
+/// [`BevyCssError::UnsupportedPropertyValue`] instead of an unknown property, and sheets using it keep working once it is.
+///
+/// # Examples
+///
+/// ```
+/// # use tomt_bevycss::{prelude::*, property::impls::ImageScaleModeProperty};
+/// let parse = |value| ImageScaleModeProperty::parse(&PropertyValues::parse(value).unwrap());
+///
+/// assert!(matches!(parse("sliced"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+/// assert!(matches!(parse("sideways"), Err(BevyCssError::InvalidPropertyValue(_))));
+/// ```
+#[derive(Default)]
+pub struct ImageScaleModeProperty;
+
+/// Parses the `image-slice` property, which are the insets splitting the image of `image-scale-mode: sliced`,
+/// given as 1 to 4 `00.00px` values, in the same order as `margin`, like `image-slice: 16px 8px;`.
+///
+/// Like `image-scale-mode`, valid values are reported as [`BevyCssError::UnsupportedPropertyValue`].
+#[derive(Default)]
+pub struct ImageSliceProperty;
+
+/// Parses the `image-tile` property, which tells if the image of `image-scale-mode: tiled` is repeated on each axis,
+/// given as one `repeat` or `stretch` keyword used by both axes, or one for each axis, like `image-tile: repeat stretch;`.
+///
+/// Like `image-scale-mode`, valid values are reported as [`BevyCssError::UnsupportedPropertyValue`].
+#[derive(Default)]
+pub struct ImageTileProperty;
+
+impl_image_slicing_property!("image-scale-mode", ImageScaleModeProperty, ImageScaling, |values| match values.as_slice()
+{
+    [token] => parse_keyword(token, &["stretch", "sliced", "tiled"]).map(|keyword| match keyword
+    {
+        "sliced" => ImageScaling::Sliced,
+        "tiled" => ImageScaling::Tiled,
+        _ => ImageScaling::Stretch,
+    }),
+    _ => None,
+});
+
+impl_image_slicing_property!("image-slice", ImageSliceProperty, UiRect, parse_insets, Length);
+
+impl_image_slicing_property!("image-tile", ImageTileProperty, BVec2, |values| match values.as_slice()
+{
+    [both] => parse_tile_axis(both).map(BVec2::splat),
+    [x, y] => parse_tile_axis(x).zip(parse_tile_axis(y)).map(|(x, y)| BVec2::new(x, y)),
+    _ => None,
+});
@@ -1,19 +1,38 @@
-use super::{Property, PropertyValues};
+use super::{Property, PropertyContext, PropertyValues, set_if_changed};
 use crate::prelude::BevyCssError;
 
 /// Impls for `bevy_ui` [`Style`] component
 pub mod style;
 
+mod background_image_property;
+pub use background_image_property::*;
+
+mod background_property;
+pub use background_property::*;
+
+mod clip_property;
+pub use clip_property::*;
+
+mod focus_policy_property;
+pub use focus_policy_property::*;
+
+mod image_slicing_property;
+pub use image_slicing_property::*;
+
+mod overflow_clip_margin_property;
+pub use overflow_clip_margin_property::*;
+
 use bevy::{ecs::query::QueryItem, prelude::*};
 
 /// Applies the `background-color` property on [`BackgroundColor`] component of matched entities.
 #[derive(Default)]
-pub(crate) struct BackgroundColorProperty;
+pub struct BackgroundColorProperty;
 
 impl Property for BackgroundColorProperty {
     type Cache = Color;
-    type Components = Entity;
+    type Components = &'static mut BackgroundColor;
     type Filters = With<BackgroundColor>;
+    type Param = ();
 
     fn name() -> &'static str {
         "background-color"
@@ -29,10 +48,23 @@ impl Property for BackgroundColorProperty {
 
     fn apply<'w>(
         cache: &Self::Cache,
-        components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
-        commands: &mut Commands,
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
     ) {
-        commands.entity(components).insert(BackgroundColor(*cache));
+        set_if_changed(&mut components, |background| &mut background.0, *cache);
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut components, |background| &mut background.0, BackgroundColor::DEFAULT.0);
+    }
+
+    fn insert_missing(entity: &mut EntityWorldMut) -> bool {
+        entity.insert(BackgroundColor::DEFAULT);
+        true
     }
 }
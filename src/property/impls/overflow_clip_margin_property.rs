@@ -0,0 +1,255 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    math::{Rect, Vec2},
+    prelude::{
+        Children,
+        Commands,
+        Component,
+        Entity,
+        GlobalTransform,
+        HierarchyQueryExt,
+        Node,
+        Parent,
+        Query,
+        Style,
+        With,
+    },
+    ui::{CalculatedClip, OverflowAxis, Val},
+};
+
+/// Margin set by the `overflow-clip-margin` property, by which the clipping of the children of a node with `overflow: clip`
+/// extends beyond the node bounds, like on `css`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Component)]
+pub struct OverflowClipMargin(pub Val);
+
+impl Default
+for OverflowClipMargin
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self(Val::Px(0.0))
+    }
+}
+
+impl OverflowClipMargin
+{
+    /// Resolves the margin for a node of the given size.
+    ///
+    /// Percentages are relative to the node width on the horizontal axis, and to the node height on the vertical one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::{math::Vec2, ui::Val};
+    /// # use tomt_bevycss::property::impls::OverflowClipMargin;
+    /// assert_eq!(OverflowClipMargin(Val::Px(8.0)).resolve(Vec2::new(200.0, 100.0)), Vec2::new(8.0, 8.0));
+    /// assert_eq!(OverflowClipMargin(Val::Percent(10.0)).resolve(Vec2::new(200.0, 100.0)), Vec2::new(20.0, 10.0));
+    /// ```
+    pub fn resolve(
+        &self,
+        size: Vec2
+    ) -> Vec2 {
+        match self.0
+        {
+            Val::Px(px) => Vec2::splat(px),
+            Val::Percent(percent) => size * percent / 100.0,
+            _ => Vec2::ZERO,
+        }
+    }
+}
+
+/// Applies the `overflow-clip-margin` property, which extends the clipping of children of matched nodes beyond their bounds,
+/// on axes where `overflow` clips, like `overflow-clip-margin: 8px;`.
+///
+/// The value is a `00.00px` or `00.00%` length and it's kept on [`OverflowClipMargin`].
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::{prelude::*, property::impls::{OverflowClipMargin, OverflowClipMarginProperty}};
+/// let margin = OverflowClipMarginProperty::parse(&PropertyValues::parse("10%").unwrap()).unwrap();
+/// assert_eq!(margin, OverflowClipMargin(Val::Percent(10.0)));
+///
+/// assert!(OverflowClipMarginProperty::parse(&PropertyValues::parse("auto").unwrap()).is_err());
+/// ```
+#[derive(Default)]
+pub struct OverflowClipMarginProperty;
+
+impl Property
+for OverflowClipMarginProperty
+{
+    type Cache = OverflowClipMargin;
+    type Components = (Entity, Option<&'static mut OverflowClipMargin>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "overflow-clip-margin"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+        match values.as_slice()
+        {
+            [token] => token.to_val()
+                .filter(|val| matches!(val, Val::Px(_) | Val::Percent(_)))
+                .map(OverflowClipMargin)
+                .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, margin): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match margin
+        {
+            Some(mut margin) => {
+                if *margin != *cache
+                {
+                    *margin = *cache;
+                }
+            }
+            None => {
+                commands.entity(entity).try_insert(*cache);
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (entity, margin): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if margin.is_some()
+        {
+            commands.entity(entity).remove::<OverflowClipMargin>();
+        }
+    }
+}
+
+/// Layout and overflow of all nodes, used to compute the clipping of their children again.
+type QueryClippingNodes<'w, 's> = Query<
+    'w, 's,
+    (&'static Node, &'static GlobalTransform, &'static Style, Option<&'static OverflowClipMargin>, Option<&'static Children>),
+>;
+
+/// Clipping computed by `bevy_ui` of all nodes.
+type QueryCalculatedClips<'w, 's> = Query<
+    'w, 's,
+    Option<&'static mut CalculatedClip>,
+    With<Node>,
+>;
+
+/// Computes again the [`CalculatedClip`] of descendants of nodes with [`OverflowClipMargin`], like `bevy_ui` does, but with
+/// the clipping rectangle of those nodes extended by their margin.
+///
+/// Must run after `bevy_ui` computes the clipping from `overflow`, since it replaces [`CalculatedClip`] every frame,
+/// and before the clipping is constrained by `clip`.
+pub(crate) fn apply_overflow_clip_margins(
+    mut commands: Commands,
+    q_margins: Query<Entity, With<OverflowClipMargin>>,
+    q_nodes: QueryClippingNodes,
+    q_parents: Query<&Parent>,
+    mut q_clips: QueryCalculatedClips,
+) {
+    let mut clips = Vec::new();
+    for entity in q_margins.iter()
+    {
+        // Nested nodes with a margin are computed while visiting the top-most one
+        if q_parents.iter_ancestors(entity).any(|ancestor| q_margins.contains(ancestor))
+        {
+            continue;
+        }
+
+        let inherited = q_clips.get(entity)
+            .ok()
+            .flatten()
+            .map(|calculated| calculated.clip);
+        collect_children_clips(entity, inherited, &q_nodes, &mut clips);
+    }
+
+    for (entity, rect) in clips
+    {
+        match q_clips.get_mut(entity)
+        {
+            Ok(Some(mut calculated)) => {
+                if calculated.clip != rect
+                {
+                    calculated.clip = rect;
+                }
+            }
+            Ok(None) => {
+                commands.entity(entity).try_insert(CalculatedClip { clip: rect });
+            }
+            Err(_) => (),
+        }
+    }
+}
+
+/// Collects the clipping of all descendants of the given node, which inherits the given clipping, like `bevy_ui` does,
+/// but extending the clipping of nodes with [`OverflowClipMargin`].
+fn collect_children_clips(
+    entity: Entity,
+    inherited: Option<Rect>,
+    q_nodes: &QueryClippingNodes,
+    clips: &mut Vec<(Entity, Rect)>
+) {
+    let Ok((node, transform, style, margin, children)) = q_nodes.get(entity)
+    else {
+        return;
+    };
+
+    let children_clip = match style.overflow.is_visible()
+    {
+        true => inherited,
+        false => {
+            let mut rect = node.logical_rect(transform);
+            if let Some(margin) = margin
+            {
+                let margin = margin.resolve(rect.size());
+                rect = Rect::from_corners(rect.min - margin, rect.max + margin);
+            }
+
+            if style.overflow.x == OverflowAxis::Visible
+            {
+                rect.min.x = f32::NEG_INFINITY;
+                rect.max.x = f32::INFINITY;
+            }
+            if style.overflow.y == OverflowAxis::Visible
+            {
+                rect.min.y = f32::NEG_INFINITY;
+                rect.max.y = f32::INFINITY;
+            }
+
+            Some(inherited.map_or(rect, |inherited| inherited.intersect(rect)))
+        }
+    };
+
+    // Without any clipping, descendants are left as `bevy_ui` computed them
+    let Some(children_clip) = children_clip
+    else {
+        return;
+    };
+
+    for &child in children.into_iter().flatten()
+    {
+        clips.push((child, children_clip));
+        collect_children_clips(child, Some(children_clip), q_nodes, clips);
+    }
+}
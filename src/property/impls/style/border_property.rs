@@ -0,0 +1,172 @@
+use super::insert_missing_style;
+use crate::{
+    prelude::BevyCssError,
+    property::{colors, parse_keyword, Property, PropertyContext, PropertyValues, set_if_changed, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        BorderColor,
+        Color,
+        Commands,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Style,
+        With,
+    },
+    ui::{UiRect, Val},
+};
+
+/// Line styles accepted by the `border` property. Only `none` and `hidden` have an effect, since `bevy_ui` only draws solid borders.
+const LINE_STYLES: &[&str] = &[
+    "none", "hidden",
+    "solid", "dashed", "dotted", "double",
+    "groove", "ridge", "inset", "outset",
+];
+
+/// Values parsed from the `border` property, see [`BorderProperty`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Border
+{
+    /// Width of each side, when declared.
+    pub width: Option<UiRect>,
+    /// Border color, when declared.
+    pub color: Option<Color>,
+}
+
+/// Applies the `border` shorthand property on [`Style::border`] field and [`BorderColor`] component of matched entities.
+///
+/// Like on `css`, it accepts a width, a line style and a color, all optional and in any order:
+/// - `border: 2px solid #fff` applies `2px` to all sides and a white color.
+/// - `border: 1px 2px 3px 4px` applies to `top`, `right`, `bottom` and `left`, respectively, like [`margin`](super::MarginProperty).
+/// - `border: none` applies `0px` to all sides. Other line styles, like `solid`, are accepted but ignored.
+///
+/// Only the declared parts are applied, so `border: red` keeps the current width.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::{prelude::*, property::impls::style::BorderProperty};
+/// let border = BorderProperty::parse(&PropertyValues::parse("solid 2px #fff").unwrap()).unwrap();
+/// assert_eq!(border.width, Some(UiRect::all(Val::Px(2.0))));
+/// assert_eq!(border.color, Some(Color::WHITE));
+///
+/// let border = BorderProperty::parse(&PropertyValues::parse("none").unwrap()).unwrap();
+/// assert_eq!(border.width, Some(UiRect::all(Val::Px(0.0))));
+/// ```
+#[derive(Default)]
+pub struct BorderProperty;
+
+impl Property
+for BorderProperty
+{
+    type Cache = Border;
+    type Components = (Entity, &'static mut Style, Option<&'static mut BorderColor>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "border"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        values.expect_unit(Self::name(), UnitCategory::Length)?;
+        let invalid = || BevyCssError::InvalidPropertyValue(Self::name().to_string());
+
+        let mut widths = PropertyValues::default();
+        let mut line_style = None;
+        let mut color = None;
+
+        for token in values.iter()
+        {
+            if token.to_val().is_some()
+            {
+                widths.0.push(token.clone());
+            }
+            else if let Some(style) = parse_keyword(token, LINE_STYLES)
+            {
+                if line_style.replace(style).is_some()
+                {
+                    return Err(invalid());
+                }
+            }
+            else if let Some(parsed) = colors::parse_color(token)
+            {
+                if color.replace(parsed).is_some()
+                {
+                    return Err(invalid());
+                }
+            }
+            else
+            {
+                return Err(invalid());
+            }
+        }
+
+        // A border without line has no width, so widths can't be declared along
+        let width = match line_style
+        {
+            Some("none" | "hidden") if widths.is_empty() => Some(UiRect::all(Val::Px(0.0))),
+            Some("none" | "hidden") => return Err(invalid()),
+            _ if widths.is_empty() => None,
+            _ if widths.len() > 4 => return Err(invalid()),
+            _ => Some(widths.rect().ok_or_else(invalid)?),
+        };
+
+        if width.is_none() && line_style.is_none() && color.is_none()
+        {
+            return Err(invalid());
+        }
+
+        Ok(Border { width, color })
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, mut style, border_color): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if let Some(width) = cache.width
+        {
+            set_if_changed(&mut style, |style| &mut style.border, width);
+        }
+
+        if let Some(color) = cache.color
+        {
+            match border_color
+            {
+                Some(mut border_color) => set_if_changed(&mut border_color, |border_color| &mut border_color.0, color),
+                None => {
+                    commands.entity(entity).try_insert(BorderColor(color));
+                }
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (_entity, mut style, border_color): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut style, |style| &mut style.border, Style::DEFAULT.border);
+
+        if let Some(mut border_color) = border_color
+        {
+            set_if_changed(&mut border_color, |border_color| &mut border_color.0, BorderColor::DEFAULT.0);
+        }
+    }
+
+    fn insert_missing(
+        entity: &mut EntityWorldMut
+    ) -> bool {
+        insert_missing_style(entity)
+    }
+}
+
@@ -0,0 +1,269 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Added,
+        Changed,
+        Commands,
+        Component,
+        DetectChangesMut,
+        Entity,
+        Mut,
+        Node,
+        Or,
+        Query,
+        Style,
+        With,
+    },
+    ui::{UiRect, Val},
+};
+
+/// Box sizing model set by `box-sizing` property, which defines what the declared sizes of an entity include.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BoxSizing
+{
+    /// Sizes include only the content, so padding and border are added to them.
+    ContentBox,
+    /// Sizes include padding and border, which is how `bevy_ui` always lays out nodes.
+    #[default]
+    BorderBox,
+}
+
+/// Number of size fields of [`Style`] adjusted by [`BoxSizing::ContentBox`].
+const SIZE_COUNT: usize = 6;
+
+/// Getter, mutable getter and whether it's horizontal, of a size field of [`Style`].
+type SizeField = (fn(&Style) -> Val, fn(&mut Style) -> &mut Val, bool);
+
+/// Size fields of [`Style`] adjusted by [`BoxSizing::ContentBox`].
+const SIZES: [SizeField; SIZE_COUNT] = [
+    (|style| style.width, |style| &mut style.width, true),
+    (|style| style.min_width, |style| &mut style.min_width, true),
+    (|style| style.max_width, |style| &mut style.max_width, true),
+    (|style| style.height, |style| &mut style.height, false),
+    (|style| style.min_height, |style| &mut style.min_height, false),
+    (|style| style.max_height, |style| &mut style.max_height, false),
+];
+
+impl BoxSizing
+{
+    /// Computes the size `bevy_ui` must use for the given declared size, padding and border.
+    ///
+    /// Only sizes in `px` can be adjusted on [`BoxSizing::ContentBox`], and only when padding and border are in `px` too,
+    /// since there is no `calc()` on `bevy_ui` to add values of different units. Other sizes are kept as declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::ui::{UiRect, Val};
+    /// # use tomt_bevycss::property::impls::style::BoxSizing;
+    /// let padding = UiRect::all(Val::Px(10.0));
+    /// let border = UiRect::all(Val::Px(2.0));
+    ///
+    /// assert_eq!(BoxSizing::ContentBox.size(Val::Px(100.0), true, padding, border), Val::Px(124.0));
+    /// assert_eq!(BoxSizing::BorderBox.size(Val::Px(100.0), true, padding, border), Val::Px(100.0));
+    /// assert_eq!(BoxSizing::ContentBox.size(Val::Percent(50.0), true, padding, border), Val::Percent(50.0));
+    /// ```
+    pub fn size(
+        &self,
+        declared: Val,
+        horizontal: bool,
+        padding: UiRect,
+        border: UiRect
+    ) -> Val {
+        let sides = match horizontal
+        {
+            true => [padding.left, padding.right, border.left, border.right],
+            false => [padding.top, padding.bottom, border.top, border.bottom],
+        };
+
+        let extra = sides.into_iter()
+            .try_fold(0.0, |sum, side| match side
+            {
+                Val::Px(px) => Some(sum + px),
+                Val::Auto => Some(sum),
+                _ => None,
+            });
+
+        match (self, declared, extra)
+        {
+            (BoxSizing::ContentBox, Val::Px(px), Some(extra)) => Val::Px(px + extra),
+            _ => declared,
+        }
+    }
+}
+
+/// Keeps the sizes declared on an entity styled with `box-sizing`, so padding and border can be added to them
+/// again whenever any of them changes, without adding it twice.
+#[derive(Debug, Default, Clone, Component)]
+pub struct BoxSizingSource
+{
+    sizing: BoxSizing,
+    declared: [Val; SIZE_COUNT],
+    written: [Val; SIZE_COUNT],
+}
+
+impl BoxSizingSource
+{
+    fn new(
+        style: &Style
+    ) -> Self {
+        let sizes = SIZES.map(|(get, _get_mut, _horizontal)| get(style));
+
+        Self{
+            sizing: BoxSizing::BorderBox,
+            declared: sizes,
+            written: sizes,
+        }
+    }
+
+    /// Gets the box sizing model applied.
+    pub fn sizing(
+        &self
+    ) -> BoxSizing {
+        self.sizing
+    }
+
+    /// Gets the declared `width`, without padding and border.
+    pub fn width(
+        &self
+    ) -> Val {
+        self.declared[0]
+    }
+
+    /// Gets the declared `height`, without padding and border.
+    pub fn height(
+        &self
+    ) -> Val {
+        self.declared[3]
+    }
+
+    /// Updates the declared sizes with any size changed since it was last written,
+    /// like when `width` is applied by a style sheet or by user code.
+    fn sync(
+        &mut self,
+        style: &Style
+    ) {
+        for (index, (get, _get_mut, _horizontal)) in SIZES.iter().enumerate()
+        {
+            let size = get(style);
+            if size != self.written[index]
+            {
+                self.declared[index] = size;
+            }
+        }
+    }
+
+    /// Writes the sizes computed from the declared ones on the given style, only touching the fields which differ.
+    fn write(
+        &mut self,
+        style: &mut Mut<Style>
+    ) {
+        for (index, (get, get_mut, horizontal)) in SIZES.iter().enumerate()
+        {
+            let size = self.sizing.size(self.declared[index], *horizontal, style.padding, style.border);
+            if get(style) != size
+            {
+                *get_mut(style) = size;
+            }
+
+            self.written[index] = size;
+        }
+    }
+}
+
+/// Applies the `box-sizing` property, which defines whether `width` and `height`, along with their `min-` and `max-` variants,
+/// include padding and border.
+///
+/// `bevy_ui` has no box sizing setting and always lays out nodes as `border-box`, so `content-box` is emulated by adding
+/// padding and border to the sizes of [`Style`] whenever any of them changes, see [`BoxSizing::size`].
+/// The declared sizes are kept on [`BoxSizingSource`], so they're restored once the property is reverted.
+///
+/// Supports `content-box` and `border-box`, which is the default.
+#[derive(Default)]
+pub struct BoxSizingProperty;
+
+impl Property
+for BoxSizingProperty
+{
+    type Cache = BoxSizing;
+    type Components = (Entity, &'static mut Style, Option<&'static mut BoxSizingSource>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "box-sizing"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        match values.keyword(&["content-box", "border-box"])
+        {
+            Some("content-box") => Ok(BoxSizing::ContentBox),
+            Some("border-box") => Ok(BoxSizing::BorderBox),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, mut style, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match source
+        {
+            Some(mut source) => {
+                // The source only keeps track of declared sizes, so only changes on the style itself are detected
+                let source = source.bypass_change_detection();
+                source.sync(&style);
+                source.sizing = *cache;
+                source.write(&mut style);
+            }
+            None => {
+                let mut source = BoxSizingSource::new(&style);
+                source.sizing = *cache;
+                source.write(&mut style);
+                commands.entity(entity).try_insert(source);
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (entity, mut style, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if let Some(mut source) = source
+        {
+            source.sync(&style);
+            source.sizing = BoxSizing::BorderBox;
+            source.write(&mut style);
+            commands.entity(entity).remove::<BoxSizingSource>();
+        }
+    }
+}
+
+/// Styles which were sized for the first time or changed since the last time they were sized.
+type QuerySizedStyles<'w, 's> = Query<
+    'w, 's,
+    (&'static mut Style, &'static mut BoxSizingSource),
+    Or<(Added<BoxSizingSource>, Changed<Style>)>,
+>;
+
+/// Applies again the `box-sizing` of entities which sizes, padding or border were changed, like by [`WidthProperty`](super::WidthProperty).
+pub(crate) fn sync_box_sizing(
+    mut q_styles: QuerySizedStyles
+) {
+    for (mut style, mut source) in q_styles.iter_mut()
+    {
+        source.sync(&style);
+        source.write(&mut style);
+    }
+}
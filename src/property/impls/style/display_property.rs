@@ -0,0 +1,252 @@
+use super::insert_missing_style;
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues, set_if_changed},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Changed,
+        Children,
+        Commands,
+        Component,
+        Display,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Query,
+        Resource,
+        Style,
+        Visibility,
+        With,
+        World,
+    },
+    ui::FocusPolicy,
+    utils::HashSet,
+};
+use smallvec::SmallVec;
+
+/// Keywords of `css` `display` property which have no equivalent on Bevy [`Display`].
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "block",
+    "inline",
+    "inline-block",
+    "inline-flex",
+    "inline-grid",
+    "flow-root",
+    "contents",
+    "list-item",
+    "table",
+];
+
+/// Keeps the [`Display`] an entity had before `display: none` was applied, so it can be restored later.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct PreviousDisplay(pub Display);
+
+/// Controls if `display: none` also hides the whole subtree of the entity, setting [`Visibility::Hidden`] and [`FocusPolicy::Pass`]
+/// on the entity and all its descendants, so none of them is rendered or blocks interactions.
+///
+/// The initial value is set by [`BevyCssPlugin::with_display_none_hides_subtree`](crate::prelude::BevyCssPlugin::with_display_none_hides_subtree).
+/// Disabled by default, so only the layout is changed.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct DisplayNoneHidesSubtree(pub bool);
+
+/// Keeps the [`Visibility`] and [`FocusPolicy`] an entity had before it was hidden by `display: none`, on itself or on an ancestor,
+/// see [`DisplayNoneHidesSubtree`].
+///
+/// Both are restored once no entity hiding it has `display: none` anymore, and they are `None` when the entity didn't have the component.
+#[derive(Debug, Clone)]
+#[derive(Component)]
+pub struct HiddenByDisplay
+{
+    pub visibility: Option<Visibility>,
+    pub focus_policy: Option<FocusPolicy>,
+    /// Entities with `display: none` which hid this one.
+    hidden_by: SmallVec<[Entity; 2]>,
+}
+
+/// Hides the given entity and all its descendants, if [`DisplayNoneHidesSubtree`] is enabled.
+fn hide_subtree(
+    world: &mut World,
+    root: Entity
+) {
+    if !world.get_resource::<DisplayNoneHidesSubtree>().is_some_and(|enabled| enabled.0)
+    {
+        return;
+    }
+
+    let mut pending = vec![root];
+    while let Some(entity) = pending.pop()
+    {
+        let Some(mut entity_mut) = world.get_entity_mut(entity)
+        else {
+            continue;
+        };
+
+        match entity_mut.get_mut::<HiddenByDisplay>()
+        {
+            Some(hidden) if hidden.hidden_by.contains(&root) => (),
+            Some(mut hidden) => hidden.hidden_by.push(root),
+            None => {
+                let hidden = HiddenByDisplay {
+                    visibility: entity_mut.get::<Visibility>().copied(),
+                    focus_policy: entity_mut.get::<FocusPolicy>().copied(),
+                    hidden_by: smallvec::smallvec![root],
+                };
+                entity_mut.insert((hidden, Visibility::Hidden, FocusPolicy::Pass));
+            }
+        }
+
+        if let Some(children) = entity_mut.get::<Children>()
+        {
+            pending.extend(children.iter());
+        }
+    }
+}
+
+/// Hides children added to entities hidden by `display: none`, see [`DisplayNoneHidesSubtree`].
+pub(crate) fn hide_added_children(
+    mut commands: Commands,
+    q_hidden: Query<&HiddenByDisplay, Changed<Children>>,
+) {
+    let roots: HashSet<Entity> = q_hidden.iter()
+        .flat_map(|hidden| hidden.hidden_by.iter().copied())
+        .collect();
+
+    for root in roots
+    {
+        commands.add(move |world: &mut World| hide_subtree(world, root));
+    }
+}
+
+/// Restores entities hidden by the given entity, unless they are still hidden by another one.
+fn show_subtree(
+    world: &mut World,
+    root: Entity
+) {
+    let hidden: Vec<Entity> = world.query::<(Entity, &HiddenByDisplay)>()
+        .iter(world)
+        .filter(|(_entity, hidden)| hidden.hidden_by.contains(&root))
+        .map(|(entity, _hidden)| entity)
+        .collect();
+
+    for entity in hidden
+    {
+        let mut entity_mut = world.entity_mut(entity);
+        let Some(mut hidden) = entity_mut.get_mut::<HiddenByDisplay>()
+        else {
+            continue;
+        };
+
+        hidden.hidden_by.retain(|hidden_by| *hidden_by != root);
+        if !hidden.hidden_by.is_empty()
+        {
+            continue;
+        }
+
+        let (visibility, focus_policy) = (hidden.visibility, hidden.focus_policy);
+        entity_mut.remove::<HiddenByDisplay>();
+        match visibility
+        {
+            Some(visibility) => entity_mut.insert(visibility),
+            None => entity_mut.remove::<Visibility>(),
+        };
+        match focus_policy
+        {
+            Some(focus_policy) => entity_mut.insert(focus_policy),
+            None => entity_mut.remove::<FocusPolicy>(),
+        };
+    }
+}
+
+/// Applies the `display` property on [`Style::display`] field of matched [`Style`] components.
+///
+/// Supports `flex`, `grid` and `none`. Other `css` keywords, like `block` or `inline`, are reported as unsupported,
+/// since Bevy has no equivalent layout.
+///
+/// When `none` is applied, the previous value is kept on [`PreviousDisplay`] and restored when the property is reset,
+/// which also happens once a pseudo-class rule, like `.item:hover { display: none; }`, no longer matches.
+/// With [`DisplayNoneHidesSubtree`] enabled, the entity and all its descendants are hidden too, see [`HiddenByDisplay`],
+/// including children added while it's hidden.
+#[derive(Default)]
+pub struct DisplayProperty;
+
+impl Property
+for DisplayProperty
+{
+    type Cache = Display;
+    type Components = (Entity, &'static mut Style, Option<&'static PreviousDisplay>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "display"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        match values.keyword(&["flex", "grid", "none"])
+        {
+            Some("flex") => Ok(Display::Flex),
+            Some("grid") => Ok(Display::Grid),
+            Some(_) => Ok(Display::None),
+            None => match values.keyword(UNSUPPORTED_KEYWORDS)
+            {
+                Some(keyword) => Err(BevyCssError::UnsupportedPropertyValue(format!("{}: {keyword}", Self::name()))),
+                None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            },
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, mut style, previous): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match (cache, style.display, previous)
+        {
+            // Keep the value before hiding, even when it's `none` already, so the subtree is shown again once reset
+            (Display::None, current, None) => {
+                commands.entity(entity).try_insert(PreviousDisplay(current));
+                commands.add(move |world: &mut World| hide_subtree(world, entity));
+            }
+            (Display::None, _, _) => (),
+            (_, _, Some(_)) => {
+                commands.entity(entity).remove::<PreviousDisplay>();
+                commands.add(move |world: &mut World| show_subtree(world, entity));
+            }
+            (_, _, None) => (),
+        }
+
+        set_if_changed(&mut style, |style| &mut style.display, *cache);
+    }
+
+    fn reset<'w>(
+        (entity, mut style, previous): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        let display = match previous
+        {
+            Some(previous) => {
+                commands.entity(entity).remove::<PreviousDisplay>();
+                commands.add(move |world: &mut World| show_subtree(world, entity));
+                previous.0
+            }
+            None => Style::DEFAULT.display,
+        };
+        set_if_changed(&mut style, |style| &mut style.display, display);
+    }
+
+    fn insert_missing(
+        entity: &mut EntityWorldMut
+    ) -> bool {
+        insert_missing_style(entity)
+    }
+}
@@ -0,0 +1,90 @@
+use super::insert_missing_style;
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyToken, PropertyValues, set_if_changed, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Commands,
+        EntityWorldMut,
+        Node,
+        Style,
+        With,
+    },
+    ui::{UiRect, Val},
+};
+
+/// Applies the `inset` shorthand property on [`Style::top`], [`Style::right`], [`Style::bottom`] and [`Style::left`]
+/// fields of matched [`Style`] components.
+///
+/// Like on `css`, it accepts from one to four values, each one being a dimension, a percentage or `auto`:
+/// - `inset: 10px` applies to all sides.
+/// - `inset: 10px 5%` applies `10px` to `top` and `bottom`, and `5%` to `left` and `right`.
+/// - `inset: 10px 5% auto` applies `10px` to `top`, `5%` to `left` and `right`, and `auto` to `bottom`.
+/// - `inset: 10px 5% auto 0px` applies to `top`, `right`, `bottom` and `left`, respectively.
+#[derive(Default)]
+pub struct InsetProperty;
+
+impl Property
+for InsetProperty
+{
+    type Cache = UiRect;
+    type Components = &'static mut Style;
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "inset"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+        let sides = values.iter()
+            .map(PropertyToken::to_val)
+            .collect::<Option<Vec<Val>>>();
+
+        match sides.as_deref()
+        {
+            Some(&[all]) => Ok(UiRect::all(all)),
+            Some(&[vertical, horizontal]) => Ok(UiRect::new(horizontal, horizontal, vertical, vertical)),
+            Some(&[top, horizontal, bottom]) => Ok(UiRect::new(horizontal, horizontal, top, bottom)),
+            Some(&[top, right, bottom, left]) => Ok(UiRect::new(left, right, top, bottom)),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut components, |style| &mut style.top, cache.top);
+        set_if_changed(&mut components, |style| &mut style.right, cache.right);
+        set_if_changed(&mut components, |style| &mut style.bottom, cache.bottom);
+        set_if_changed(&mut components, |style| &mut style.left, cache.left);
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut components, |style| &mut style.top, Style::DEFAULT.top);
+        set_if_changed(&mut components, |style| &mut style.right, Style::DEFAULT.right);
+        set_if_changed(&mut components, |style| &mut style.bottom, Style::DEFAULT.bottom);
+        set_if_changed(&mut components, |style| &mut style.left, Style::DEFAULT.left);
+    }
+
+    fn insert_missing(
+        entity: &mut EntityWorldMut
+    ) -> bool {
+        insert_missing_style(entity)
+    }
+}
@@ -0,0 +1,157 @@
+use super::insert_missing_style;
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues, set_if_changed, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Commands,
+        Direction,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Parent,
+        Style,
+        With,
+        World,
+    },
+    ui::{UiRect, Val},
+};
+
+/// Side of the inline axis targeted by a logical property, like `margin-inline-start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InlineSide
+{
+    Start,
+    End,
+}
+
+/// Resolves the computed [`Direction`] of the given entity, walking up ancestors while it's [`Direction::Inherit`].
+///
+/// Root entities which inherit the direction are considered [`Direction::LeftToRight`].
+fn computed_direction(
+    world: &World,
+    entity: Entity
+) -> Direction {
+    let mut current = Some(entity);
+    while let Some(entity) = current
+    {
+        match world.get::<Style>(entity).map(|style| style.direction)
+        {
+            Some(Direction::Inherit) | None => current = world.get::<Parent>(entity).map(|parent| parent.get()),
+            Some(direction) => return direction,
+        }
+    }
+
+    Direction::LeftToRight
+}
+
+/// Gets the physical side of the given rect which matches the inline side on the given direction.
+fn physical_side(
+    rect: &mut UiRect,
+    side: InlineSide,
+    direction: Direction
+) -> &mut Val {
+    match (side, direction)
+    {
+        (InlineSide::Start, Direction::RightToLeft) => &mut rect.right,
+        (InlineSide::Start, _) => &mut rect.left,
+        (InlineSide::End, Direction::RightToLeft) => &mut rect.left,
+        (InlineSide::End, _) => &mut rect.right,
+    }
+}
+
+/// Sets the given value on the physical side of the rect returned by `rect`, once the direction of the entity is known.
+///
+/// This is deferred to when commands are applied, so the `direction` property applied on the same frame is already known.
+fn set_logical_side(
+    entity: Entity,
+    side: InlineSide,
+    value: Val,
+    rect: fn(&mut Style) -> &mut UiRect,
+    commands: &mut Commands
+) {
+    commands.add(move |world: &mut World|
+    {
+        let direction = computed_direction(world, entity);
+        if let Some(mut style) = world.get_mut::<Style>(entity)
+        {
+            set_if_changed(&mut style, |style| physical_side(rect(style), side, direction), value);
+        }
+    });
+}
+
+/// Implements a new logical property, which is applied on the left or right side of a [`Style`] rect field,
+/// depending on the computed [`Direction`] of the entity.
+macro_rules! impl_style_logical
+{
+    ($name:expr, $struct:ident, $style_prop:ident, $side:expr) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` logical property on the left or right side of [Style::"]
+        #[doc = stringify!($style_prop)]
+        #[doc = "](`Style`) field, depending on the computed [`Direction`] of matched entities."]
+        ///
+        /// Entities with [`Direction::Inherit`] use the direction of the closest ancestor which sets it, or left to right.
+        #[derive(Default)]
+        pub struct $struct;
+
+        impl Property
+        for $struct
+        {
+            type Cache = Val;
+            type Components = Entity;
+            type Filters = (With<Node>, With<Style>);
+            type Param = ();
+
+            fn name(
+                // no args
+            ) -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError> {
+                values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+                match values.val()
+                {
+                    Some(val) => Ok(val),
+                    None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+                }
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                commands: &mut Commands,
+            ) {
+                set_logical_side(components, $side, *cache, |style| &mut style.$style_prop, commands);
+            }
+
+            fn reset<'w>(
+                components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                commands: &mut Commands,
+            ) {
+                // Both sides have the same default value
+                let value = Style::DEFAULT.$style_prop.left;
+                set_logical_side(components, $side, value, |style| &mut style.$style_prop, commands);
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_missing_style(entity)
+            }
+        }
+    };
+}
+
+impl_style_logical!("margin-inline-start", MarginInlineStartProperty, margin, InlineSide::Start);
+impl_style_logical!("margin-inline-end", MarginInlineEndProperty, margin, InlineSide::End);
+impl_style_logical!("padding-inline-start", PaddingInlineStartProperty, padding, InlineSide::Start);
+impl_style_logical!("padding-inline-end", PaddingInlineEndProperty, padding, InlineSide::End);
@@ -1,4 +1,6 @@
 /// Implements a new property for [`Style`] component which expects a rect value.
+///
+/// Rects are always lengths, so values on another unit, like `10deg`, are reported as [`BevyCssError::MismatchedUnit`].
 macro_rules! impl_style_rect
 {
     ($name:expr, $struct:ident, $style_prop:ident$(.$style_field:ident)*) => {
@@ -9,13 +11,14 @@ macro_rules! impl_style_rect
         $(#[doc = concat!("::",stringify!($style_field))])*
         #[doc = "](`Style`) field of all sections on matched [`Style`] components."]
         #[derive(Default)]
-        pub(crate) struct $struct;
+        pub struct $struct;
 
         impl Property for $struct
         {
             type Cache = UiRect;
             type Components = &'static mut Style;
             type Filters = With<Node>;
+            type Param = ();
 
             fn name()
             -> &'static str {
@@ -25,6 +28,8 @@ macro_rules! impl_style_rect
             fn parse<'a>(
                 values: &PropertyValues
             ) -> Result<Self::Cache, BevyCssError> {
+                values.expect_unit(Self::name(), crate::property::UnitCategory::Length)?;
+
                 if let Some(val) = values.rect()
                 {
                     Ok(val)
@@ -38,33 +43,62 @@ macro_rules! impl_style_rect
             fn apply<'w>(
                 cache: &Self::Cache,
                 mut components: QueryItem<Self::Components>,
-                _asset_server: &AssetServer,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop$(.$style_field)?, *cache);
+            }
+
+            fn reset<'w>(
+                mut components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
                 _commands: &mut Commands,
             ) {
-                components.$style_prop$(.$style_field)? = *cache;
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop$(.$style_field)?, Style::DEFAULT.$style_prop$(.$style_field)?);
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_missing_style(entity)
             }
         }
     };
 }
 
 /// Implements a new property for [`Style`] component which expects a single value.
+///
+/// The [`UnitCategory`](crate::property::UnitCategory) given after `unit:`, like `unit: Length`, is the only one accepted,
+/// so values on another unit are reported as [`BevyCssError::MismatchedUnit`].
+/// Keywords, or functions, listed after `unsupported:` are valid `css`, but can't be represented by Bevy,
+/// so those are reported as [`BevyCssError::UnsupportedPropertyValue`] instead of an invalid value.
 macro_rules! impl_style_single_value
 {
-    ($name:expr, $struct:ident, $cache:ty, $parse_func:ident, $style_prop:ident$(.$style_field:ident)*) => {
+    (
+        $name:expr, $struct:ident, $cache:ty, $parse_func:ident, $style_prop:ident$(.$style_field:ident)*
+        $(; unit: $unit:ident)?
+        $(; min: $min:expr)?
+        $(; unsupported: $($unsupported:expr),+$(,)?)?
+    ) => {
         #[doc = "Applies the `"]
         #[doc = $name]
         #[doc = "` property on [Style::"]
         #[doc = stringify!($style_prop)]
         $(#[doc = concat!("::",stringify!($style_field))])*
         #[doc = "](`Style`) field of all sections on matched [`Style`] components."]
+        $(
+            #[doc = ""]
+            #[doc = concat!("Values lower than `", stringify!($min), "` are clamped to it, and a warning is logged.")]
+        )?
         #[derive(Default)]
-        pub(crate) struct $struct;
+        pub struct $struct;
 
         impl Property for $struct
         {
             type Cache = $cache;
             type Components = &'static mut Style;
             type Filters = With<Node>;
+            type Param = ();
 
             fn name()
             -> &'static str
@@ -76,32 +110,65 @@ macro_rules! impl_style_single_value
                 values: &PropertyValues
             ) -> Result<Self::Cache, BevyCssError>
             {
+                $(values.expect_unit(Self::name(), crate::property::UnitCategory::$unit)?;)?
+
                 if let Some(val) = values.$parse_func()
                 {
-                    Ok(val)
+                    $(
+                        if val < $min
+                        {
+                            bevy::log::warn!("Property {} can't be lower than {}, so {val} is clamped to it", Self::name(), $min);
+                            return Ok($min);
+                        }
+                    )?
+                    return Ok(val);
                 }
-                else
+
+                let unsupported: &[&str] = &[$($($unsupported),+)?];
+                if let Some(keyword) = values.keyword(unsupported).or_else(|| values.function(unsupported))
                 {
-                    Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+                    return Err(BevyCssError::UnsupportedPropertyValue(format!("{}: {keyword}", Self::name())));
                 }
+
+                Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
             }
 
             fn apply<'w>(
                 cache: &Self::Cache,
                 mut components: QueryItem<Self::Components>,
-                _asset_server: &AssetServer,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop$(.$style_field)?, *cache);
+            }
+
+            fn reset<'w>(
+                mut components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
                 _commands: &mut Commands,
             ) {
-                components.$style_prop$(.$style_field)? = *cache;
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop$(.$style_field)?, Style::DEFAULT.$style_prop$(.$style_field)?);
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_missing_style(entity)
             }
         }
     };
 }
 
 /// Implements a new property for [`Style`] component which expects an enum.
+///
+/// Keywords listed after `unsupported:` are valid `css`, but can't be represented by Bevy,
+/// so those are reported as [`BevyCssError::UnsupportedPropertyValue`] instead of an invalid value.
 macro_rules! impl_style_enum
 {
-    ($cache:ty, $name:expr, $struct:ident, $style_prop:ident, $($prop:expr => $variant:expr),+$(,)?) => {
+    (
+        $cache:ty, $name:expr, $struct:ident, $style_prop:ident, $($prop:expr => $variant:expr),+$(,)?
+        $(; unsupported: $($unsupported:expr),+$(,)?)?
+    ) => {
         #[doc = "Applies the `"]
         #[doc = $name]
         #[doc = "` property on [Style::"]
@@ -110,13 +177,14 @@ macro_rules! impl_style_enum
         #[doc = concat!("`", stringify!($cache), "`")]
         #[doc = ") field of all sections on matched [`Style`] components."]
         #[derive(Default)]
-        pub(crate) struct $struct;
+        pub struct $struct;
 
         impl Property for $struct
         {
             type Cache = $cache;
             type Components = &'static mut Style;
             type Filters = With<Node>;
+            type Param = ();
 
             fn name()
             -> &'static str
@@ -128,28 +196,47 @@ macro_rules! impl_style_enum
                 values: &PropertyValues
             ) -> Result<Self::Cache, BevyCssError>
             {
-                if let Some(identifier) = values.identifier()
+                if let Some(keyword) = values.keyword(&[$($prop),+])
                 {
                     use $cache::*;
                     // Chain if-let when `cargofmt` supports it
                     // https://github.com/rust-lang/rustfmt/pull/5203
-                    match identifier
+                    match keyword
                     {
                         $($prop => return Ok($variant)),+,
                         _ => (),
                     }
                 }
 
+                if let Some(keyword) = values.keyword(&[$($($unsupported),+)?])
+                {
+                    return Err(BevyCssError::UnsupportedPropertyValue(format!("{}: {keyword}", Self::name())));
+                }
+
                 Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
             }
 
             fn apply<'w>(
                 cache: &Self::Cache,
                 mut components: QueryItem<Self::Components>,
-                _asset_server: &AssetServer,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop, *cache);
+            }
+
+            fn reset<'w>(
+                mut components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
                 _commands: &mut Commands,
             ) {
-                components.$style_prop = *cache;
+                crate::property::set_if_changed(&mut components, |style| &mut style.$style_prop, Style::DEFAULT.$style_prop);
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_missing_style(entity)
             }
         }
     };
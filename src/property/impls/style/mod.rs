@@ -1,37 +1,76 @@
 #[macro_use]
 mod macros;
 
+mod border_property;
+pub use border_property::*;
+
+mod box_sizing_property;
+pub use box_sizing_property::*;
+
+mod display_property;
+pub use display_property::*;
+
+mod inset_property;
+pub use inset_property::*;
+
+mod logical_property;
+pub use logical_property::*;
+
+mod overflow_property;
+pub use overflow_property::*;
+
+mod scroll_offset_property;
+pub use scroll_offset_property::*;
+
+mod side_property;
+pub use side_property::*;
+
 use super::*;
 use crate::{
     prelude::BevyCssError,
     property::{Property, PropertyValues},
 };
 
+/// Inserts a default [`Style`] on a UI node missing it, so style properties can be applied on it.
+///
+/// Returns `false` when the entity isn't a UI node, or already has a [`Style`], since inserting one wouldn't help.
+fn insert_missing_style(
+    entity: &mut EntityWorldMut
+) -> bool {
+    if !entity.contains::<Node>() || entity.contains::<Style>()
+    {
+        return false;
+    }
+
+    entity.insert(Style::default());
+    true
+}
+
 // Rect type property fields
 impl_style_rect!("margin", MarginProperty, margin);
 impl_style_rect!("padding", PaddingProperty, padding);
-impl_style_rect!("border", BorderProperty, border);
 
 // Val (number) type property fields
-impl_style_single_value!("left", LeftProperty, Val, val, left);
-impl_style_single_value!("right", RightProperty, Val, val, right);
-impl_style_single_value!("top", TopProperty, Val, val, top);
-impl_style_single_value!("bottom", BottomProperty, Val, val, bottom);
+impl_style_single_value!("left", LeftProperty, Val, val, left; unit: Length);
+impl_style_single_value!("right", RightProperty, Val, val, right; unit: Length);
+impl_style_single_value!("top", TopProperty, Val, val, top; unit: Length);
+impl_style_single_value!("bottom", BottomProperty, Val, val, bottom; unit: Length);
 
-impl_style_single_value!("width", WidthProperty, Val, val, width);
-impl_style_single_value!("height", HeightProperty, Val, val, height);
+// Content sizing keywords are only supported by grid tracks on Bevy, so they are reported as unsupported on sizes
+impl_style_single_value!("width", WidthProperty, Val, val, width; unit: Length; unsupported: "max-content", "min-content", "fit-content");
+impl_style_single_value!("height", HeightProperty, Val, val, height; unit: Length; unsupported: "max-content", "min-content", "fit-content");
 
-impl_style_single_value!("min-width", MinWidthProperty, Val, val, min_width);
-impl_style_single_value!("min-height", MinHeightProperty, Val, val, min_height);
+impl_style_single_value!("min-width", MinWidthProperty, Val, val, min_width; unit: Length; unsupported: "max-content", "min-content", "fit-content");
+impl_style_single_value!("min-height", MinHeightProperty, Val, val, min_height; unit: Length; unsupported: "max-content", "min-content", "fit-content");
 
-impl_style_single_value!("max-width", MaxWidthProperty, Val, val, max_width);
-impl_style_single_value!("max-height", MaxHeightProperty, Val, val, max_height);
+impl_style_single_value!("max-width", MaxWidthProperty, Val, val, max_width; unit: Length; unsupported: "max-content", "min-content", "fit-content");
+impl_style_single_value!("max-height", MaxHeightProperty, Val, val, max_height; unit: Length; unsupported: "max-content", "min-content", "fit-content");
 
-impl_style_single_value!("flex-basis", FlexBasisProperty, Val, val, max_height);
+impl_style_single_value!("flex-basis", FlexBasisProperty, Val, val, flex_basis; unit: Length; unsupported: "max-content", "min-content", "fit-content");
 
-// f32 (number) type property fields
-impl_style_single_value!("flex-grow", FlexGrowProperty, f32, f32, flex_grow);
-impl_style_single_value!("flex-shrink", FlexShrinkProperty, f32, f32, flex_shrink);
+// f32 (number) type property fields, where negative factors are invalid on `css`
+impl_style_single_value!("flex-grow", FlexGrowProperty, f32, f32, flex_grow; min: 0.0);
+impl_style_single_value!("flex-shrink", FlexShrinkProperty, f32, f32, flex_shrink; min: 0.0);
 
 impl_style_single_value!("aspect-ratio", AspectRatioProperty, Option<f32>, option_f32, aspect_ratio);
 
@@ -40,18 +79,12 @@ impl_style_single_value!("overflow-x", OverflowXProperty, OverflowAxis, overflow
 impl_style_single_value!("overflow-y", OverflowYProperty, OverflowAxis, overflow, overflow.y);
 
 impl_style_enum!(
-    Display,            // Bevy enum
-    "display",          // CSS property name
-    DisplayProperty,    // Library structure to map
-    display,            // Property to access on bevy::ui::Style
-
-    "flex" => Flex,     // Text-to-Bevy enum mappings
-    "none" => None
-);
+    PositionType,           // Bevy enum
+    "position-type",        // CSS property name
+    PositionTypeProperty,   // Library structure to map
+    position_type,          // Property to access on bevy::ui::Style
 
-impl_style_enum!(
-    PositionType, "position-type", PositionTypeProperty, position_type,
-    "absolute" => Absolute,
+    "absolute" => Absolute, // Text-to-Bevy enum mappings
     "relative" => Relative,
 );
 
@@ -60,6 +93,8 @@ impl_style_enum!(
     "inherit" => Inherit,
     "left-to-right" => LeftToRight,
     "right-to-left" => RightToLeft,
+    "ltr" => LeftToRight,
+    "rtl" => RightToLeft,
 );
 
 impl_style_enum!(
@@ -79,39 +114,56 @@ impl_style_enum!(
 
 impl_style_enum!(
     AlignItems, "align-items", AlignItemsProperty, align_items,
+    "normal" => Default,
+    "start" => Start,
+    "end" => End,
     "flex-start" => FlexStart,
     "flex-end" => FlexEnd,
     "center" => Center,
     "baseline" => Baseline,
-    "stretch" => Stretch,
+    "stretch" => Stretch;
+    unsupported: "self-start", "self-end", "first", "last", "safe", "unsafe",
 );
 
 impl_style_enum!(
     AlignSelf, "align-self", AlignSelfProperty, align_self,
     "auto" => Auto,
+    "start" => Start,
+    "end" => End,
     "flex-start" => FlexStart,
     "flex-end" => FlexEnd,
     "center" => Center,
     "baseline" => Baseline,
-    "stretch" => Stretch,
+    "stretch" => Stretch;
+    unsupported: "normal", "self-start", "self-end", "first", "last", "safe", "unsafe",
 );
 
 impl_style_enum!(
     AlignContent, "align-content", AlignContentProperty, align_content,
+    "normal" => Default,
+    "start" => Start,
+    "end" => End,
     "flex-start" => FlexStart,
     "flex-end" => FlexEnd,
     "center" => Center,
     "stretch" => Stretch,
     "space-between" => SpaceBetween,
     "space-around" => SpaceAround,
+    "space-evenly" => SpaceEvenly;
+    unsupported: "baseline", "first", "last", "safe", "unsafe",
 );
 
 impl_style_enum!(
     JustifyContent, "justify-content", JustifyContentProperty, justify_content,
+    "normal" => Default,
+    "start" => Start,
+    "end" => End,
     "flex-start" => FlexStart,
     "flex-end" => FlexEnd,
     "center" => Center,
+    "stretch" => Stretch,
     "space-between" => SpaceBetween,
     "space-around" => SpaceAround,
-    "space-evenly" => SpaceEvenly,
+    "space-evenly" => SpaceEvenly;
+    unsupported: "baseline", "left", "right", "safe", "unsafe",
 );
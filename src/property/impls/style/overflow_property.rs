@@ -0,0 +1,362 @@
+use super::{insert_missing_style, StyledScrollOffset};
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyToken, PropertyValues, set_if_changed},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    math::{BVec2, Vec2},
+    prelude::{
+        Children,
+        Commands,
+        Component,
+        DetectChanges,
+        DetectChangesMut,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Query,
+        Res,
+        ResMut,
+        Resource,
+        Style,
+        Transform,
+        With,
+    },
+    ui::{Overflow, OverflowAxis},
+    utils::HashMap,
+};
+
+/// Value of the `overflow` property, with the [`Overflow`] applied on [`Style`] and which axes can be scrolled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowValue
+{
+    /// Overflow applied on [`Style::overflow`], where scrolled axes are clipped.
+    pub overflow: Overflow,
+    /// Axes which are scrolled by [`ScrollPosition`].
+    pub scroll: BVec2,
+}
+
+/// Scroll offset of an entity styled with `overflow: scroll`, which children are translated by it.
+///
+/// The offset is only changed by `tomt_bevycss` when `scroll-left`, `scroll-top` or `scroll-position` declare a new one,
+/// see [`StyledScrollOffset`], so it's up to your own systems to adjust it, like from mouse wheel events,
+/// using [`scroll_by`](ScrollPosition::scroll_by) to keep it within the content.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::{input::mouse::MouseWheel, prelude::*};
+/// # use tomt_bevycss::property::impls::style::ScrollPosition;
+/// fn scroll_panels(
+///     mut wheel: EventReader<MouseWheel>,
+///     mut q_scroll: Query<&mut ScrollPosition>
+/// ) {
+///     let delta: f32 = wheel.read().map(|event| event.y * 20.0).sum();
+///     for mut scroll in q_scroll.iter_mut()
+///     {
+///         scroll.scroll_by(Vec2::new(0.0, -delta));
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Component)]
+pub struct ScrollPosition
+{
+    /// How much the content is scrolled, from its top-left corner, in logical pixels.
+    ///
+    /// Only axes which scroll are applied, see [`axes`](ScrollPosition::axes).
+    pub offset: Vec2,
+    axes: BVec2,
+    max_offset: Vec2,
+}
+
+impl ScrollPosition
+{
+    /// Axes which scroll, as declared by `overflow`.
+    pub fn axes(
+        &self
+    ) -> BVec2 {
+        self.axes
+    }
+
+    /// Maximum offset, so the bottom-right corner of the content reaches the bottom-right corner of the entity.
+    ///
+    /// It's computed from the layout of the content every frame, including descendants overflowing children unless they are clipped,
+    /// and it's zero on axes which don't scroll.
+    pub fn max_offset(
+        &self
+    ) -> Vec2 {
+        self.max_offset
+    }
+
+    /// Adds the given delta to the offset, keeping it between zero and [`max_offset`](ScrollPosition::max_offset).
+    pub fn scroll_by(
+        &mut self,
+        delta: Vec2
+    ) {
+        self.offset = (self.offset + delta).clamp(Vec2::ZERO, self.max_offset);
+    }
+
+    /// Offset applied on children, which is zero on axes which don't scroll.
+    fn applied_offset(
+        &self
+    ) -> Vec2 {
+        Vec2::select(self.axes, self.offset, Vec2::ZERO)
+    }
+}
+
+/// Applies the `overflow` shorthand property on [`Style::overflow`] field of matched [`Style`] components.
+///
+/// Like on `css`, it accepts one value, used by both axes, or two values, for `x` and `y` respectively,
+/// each one being `visible`, `hidden`, `clip` or `scroll`.
+///
+/// `scroll` clips the axis, like `hidden`, and adds a [`ScrollPosition`] which offset translates all children.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::{math::BVec2, ui::Overflow};
+/// # use tomt_bevycss::{prelude::*, property::impls::style::OverflowProperty};
+/// let value = OverflowProperty::parse(&PropertyValues::parse("hidden scroll").unwrap()).unwrap();
+///
+/// assert_eq!(value.overflow, Overflow::clip());
+/// assert_eq!(value.scroll, BVec2::new(false, true));
+/// ```
+#[derive(Default)]
+pub struct OverflowProperty;
+
+impl Property
+for OverflowProperty
+{
+    type Cache = OverflowValue;
+    type Components = (Entity, &'static mut Style, Option<&'static mut ScrollPosition>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "overflow"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        let axes = values.iter()
+            .map(|token| match token
+            {
+                PropertyToken::Identifier(keyword) => match keyword.to_ascii_lowercase().as_str()
+                {
+                    "visible" => Some((OverflowAxis::Visible, false)),
+                    "hidden" | "clip" => Some((OverflowAxis::Clip, false)),
+                    "scroll" => Some((OverflowAxis::Clip, true)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+
+        let ((x, scroll_x), (y, scroll_y)) = match axes.as_deref()
+        {
+            Some(&[both]) => (both, both),
+            Some(&[x, y]) => (x, y),
+            _ => return Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        };
+
+        Ok(OverflowValue{
+            overflow: Overflow { x, y },
+            scroll: BVec2::new(scroll_x, scroll_y),
+        })
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, mut style, scroll): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        set_if_changed(&mut style, |style| &mut style.overflow, cache.overflow);
+
+        match (cache.scroll.any(), scroll)
+        {
+            (true, Some(mut scroll)) => {
+                if scroll.axes != cache.scroll
+                {
+                    scroll.axes = cache.scroll;
+                }
+            }
+            (true, None) => {
+                commands.entity(entity).try_insert(ScrollPosition { axes: cache.scroll, ..Default::default() });
+            }
+            (false, Some(_)) => {
+                commands.entity(entity).remove::<ScrollPosition>();
+            }
+            (false, None) => (),
+        }
+    }
+
+    fn reset<'w>(
+        (entity, mut style, scroll): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        set_if_changed(&mut style, |style| &mut style.overflow, Style::DEFAULT.overflow);
+
+        if scroll.is_some()
+        {
+            commands.entity(entity).remove::<ScrollPosition>();
+        }
+    }
+
+    fn insert_missing(
+        entity: &mut EntityWorldMut
+    ) -> bool {
+        insert_missing_style(entity)
+    }
+}
+
+/// Children translated by [`apply_scroll_positions`], with the translation `bevy_ui` laid them out at and the applied offset.
+#[derive(Default)]
+#[derive(Resource)]
+pub(crate) struct ScrolledChildren(HashMap<Entity, (Vec2, Vec2)>);
+
+/// Scrolled entities, along with their layout, children and the offset declared by style sheets.
+type QueryScrolledNodes<'w, 's> = Query<
+    'w, 's,
+    (&'static Node, &'static mut ScrollPosition, &'static Children, Option<&'static mut StyledScrollOffset>),
+>;
+
+/// Layout of the content of scrolled entities, which children are translated by the scroll offset.
+type QueryScrolledContent<'w, 's> = Query<
+    'w, 's,
+    (&'static Node, &'static mut Transform, Option<&'static Children>, Option<&'static Style>),
+>;
+
+/// Moves scrolled children back to where `bevy_ui` laid them out, without triggering change detection,
+/// so the layout only writes their translation when it actually moves them.
+///
+/// Must run before `bevy_ui` lays out nodes.
+pub(crate) fn restore_scroll_positions(
+    scrolled: Res<ScrolledChildren>,
+    mut q_content: QueryScrolledContent,
+) {
+    for (&child, &(base, _offset)) in scrolled.0.iter()
+    {
+        if let Ok((_node, mut transform, _children, _style)) = q_content.get_mut(child)
+        {
+            let transform = transform.bypass_change_detection();
+            transform.translation = base.extend(transform.translation.z);
+        }
+    }
+}
+
+/// Bottom-right corner of the content of a scrolled entity, from its top-left corner.
+///
+/// Descendants overflowing their parent are part of the content, unless their parent clips them.
+fn content_extent(
+    size: Vec2,
+    children: &Children,
+    q_content: &QueryScrolledContent,
+) -> Vec2 {
+    let mut extent = size;
+    let mut pending = children.iter().map(|&child| (child, Vec2::ZERO)).collect::<Vec<_>>();
+    while let Some((entity, parent_center)) = pending.pop()
+    {
+        let Ok((node, transform, children, style)) = q_content.get(entity)
+        else {
+            continue;
+        };
+
+        // Nodes are translated from the center of their parent, while the extent is from the top-left corner of the entity
+        let center = parent_center + transform.translation.truncate();
+        extent = extent.max(center + (size + node.size()) / 2.0);
+
+        if let (Some(children), true) = (children, style.map_or(true, |style| style.overflow.is_visible()))
+        {
+            pending.extend(children.iter().map(|&child| (child, center)));
+        }
+    }
+
+    extent
+}
+
+/// Translates children of entities with [`ScrollPosition`] by its offset, and updates how far they can be scrolled.
+///
+/// Offsets declared by style sheets, which changed since last frame, are applied first, once it's known how far they can be scrolled.
+///
+/// Must run after `bevy_ui` lays out nodes, and before transforms are propagated. Children are moved back to their layout
+/// by [`restore_scroll_positions`] before the layout runs, so translations are only changed when either the layout or the offset changes,
+/// and children are moved back once the entity is no longer scrolled.
+pub(crate) fn apply_scroll_positions(
+    mut q_scrolled: QueryScrolledNodes,
+    mut q_content: QueryScrolledContent,
+    mut scrolled: ResMut<ScrolledChildren>,
+) {
+    let mut translated = HashMap::new();
+
+    for (node, mut scroll, children, styled) in q_scrolled.iter_mut()
+    {
+        let size = node.size();
+        let max_offset = Vec2::select(scroll.axes, content_extent(size, children, &q_content) - size, Vec2::ZERO);
+        if scroll.max_offset != max_offset
+        {
+            scroll.max_offset = max_offset;
+        }
+
+        if let Some(mut styled) = styled.filter(|styled| styled.has_pending())
+        {
+            let offset = styled.take_pending(scroll.offset, max_offset);
+            if scroll.offset != offset
+            {
+                scroll.offset = offset;
+            }
+        }
+
+        let offset = scroll.applied_offset();
+        translated.extend(children.iter().map(|&child| (child, offset)));
+    }
+
+    for (&child, &offset) in translated.iter()
+    {
+        let Ok((_node, mut transform, _children, _style)) = q_content.get_mut(child)
+        else {
+            continue;
+        };
+
+        let base = transform.translation.truncate();
+        let translation = (base - offset).extend(transform.translation.z);
+        let unchanged = !transform.is_changed() && scrolled.0.get(&child).is_some_and(|&(_base, applied)| applied == offset);
+
+        // Offsets already propagated are restored without triggering change detection
+        match unchanged
+        {
+            true => transform.bypass_change_detection().translation = translation,
+            false => if transform.translation != translation
+            {
+                transform.translation = translation;
+            },
+        }
+
+        scrolled.0.insert(child, (base, offset));
+    }
+
+    // Children no longer scrolled were already moved back to their layout, which must be propagated
+    scrolled.0.retain(|child, (_base, offset)|
+    {
+        if translated.contains_key(child)
+        {
+            return true;
+        }
+
+        if let Ok((_node, mut transform, _children, _style)) = q_content.get_mut(*child)
+        {
+            if *offset != Vec2::ZERO
+            {
+                transform.set_changed();
+            }
+        }
+
+        false
+    });
+}
@@ -0,0 +1,313 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyToken, PropertyValues, StyleSheetState, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    math::{BVec2, Vec2},
+    prelude::{
+        Commands,
+        Component,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Query,
+        Res,
+        With,
+    },
+    ui::Val,
+};
+
+/// Scroll offset declared by `scroll-left`, `scroll-top` or `scroll-position`, which is applied on [`ScrollPosition`](super::ScrollPosition)
+/// once the content of the entity is laid out.
+///
+/// Offsets are only applied when the declared value changes, like when switching to a class declaring another offset, so user scrolling isn't undone
+/// whenever the entity is styled again.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Component)]
+pub struct StyledScrollOffset
+{
+    /// Offset from the left edge of the content, in pixels or as a percentage of how far it can be scrolled.
+    pub left: Option<Val>,
+    /// Offset from the top edge of the content, in pixels or as a percentage of how far it can be scrolled.
+    pub top: Option<Val>,
+    /// Axes which offset changed and wasn't applied yet.
+    pending: BVec2,
+}
+
+impl StyledScrollOffset
+{
+    /// Gets the declared offset of the given axis.
+    fn get(
+        &self,
+        axis: ScrollAxis
+    ) -> Option<Val> {
+        match axis
+        {
+            ScrollAxis::Left => self.left,
+            ScrollAxis::Top => self.top,
+        }
+    }
+
+    /// Sets the declared offset of the given axis, which is applied only if it changed.
+    fn set(
+        &mut self,
+        axis: ScrollAxis,
+        value: Option<Val>
+    ) {
+        let (current, pending) = match axis
+        {
+            ScrollAxis::Left => (&mut self.left, &mut self.pending.x),
+            ScrollAxis::Top => (&mut self.top, &mut self.pending.y),
+        };
+
+        if *current != value
+        {
+            *current = value;
+            *pending = value.is_some();
+        }
+    }
+
+    /// Checks if any declared offset changed and wasn't applied yet.
+    pub(crate) fn has_pending(
+        &self
+    ) -> bool {
+        self.pending.any()
+    }
+
+    /// Takes the offsets which must be applied, resolving percentages against the given maximum offset.
+    ///
+    /// Axes without a pending offset keep the given current offset.
+    pub(crate) fn take_pending(
+        &mut self,
+        current: Vec2,
+        max_offset: Vec2
+    ) -> Vec2 {
+        let resolve = |value: Option<Val>, max: f32, current: f32| match value
+        {
+            Some(Val::Px(px)) => px.clamp(0.0, max),
+            Some(Val::Percent(percent)) => (max * percent / 100.0).clamp(0.0, max),
+            _ => current,
+        };
+
+        let offset = Vec2::new(
+            if self.pending.x { resolve(self.left, max_offset.x, current.x) } else { current.x },
+            if self.pending.y { resolve(self.top, max_offset.y, current.y) } else { current.y },
+        );
+
+        self.pending = BVec2::FALSE;
+        offset
+    }
+}
+
+/// Axis of a scroll offset property.
+#[derive(Debug, Clone, Copy)]
+enum ScrollAxis
+{
+    Left,
+    Top,
+}
+
+/// Parses a single scroll offset, which must be a length or a percentage.
+fn parse_offset(
+    token: &PropertyToken
+) -> Option<Val> {
+    token.to_val()
+        .filter(|val| matches!(val, Val::Px(_) | Val::Percent(_)))
+}
+
+/// Applies the `scroll-left` property, which sets the horizontal scroll offset of an entity styled with `overflow: scroll`,
+/// see [`StyledScrollOffset`].
+///
+/// Percentages are relative to how far the content can be scrolled, so `100%` scrolls to the right edge.
+#[derive(Default)]
+pub struct ScrollLeftProperty;
+
+/// Applies the `scroll-top` property, which sets the vertical scroll offset of an entity styled with `overflow: scroll`,
+/// see [`StyledScrollOffset`].
+///
+/// Percentages are relative to how far the content can be scrolled, so `0px` jumps back to the top and `100%` to the bottom.
+#[derive(Default)]
+pub struct ScrollTopProperty;
+
+macro_rules! impl_scroll_axis_property {
+    ($name:expr, $struct:ident, $axis:expr) => {
+        impl Property
+        for $struct
+        {
+            type Cache = Val;
+            type Components = &'static mut StyledScrollOffset;
+            type Filters = With<Node>;
+            type Param = ();
+
+            fn name(
+                // no args
+            ) -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError> {
+                values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+                match values.as_slice()
+                {
+                    [token] => parse_offset(token),
+                    _ => None,
+                }
+                .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                mut offset: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                if offset.get($axis) != Some(*cache)
+                {
+                    offset.set($axis, Some(*cache));
+                }
+            }
+
+            fn reset<'w>(
+                mut offset: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                _commands: &mut Commands,
+            ) {
+                // The entity stays where it was scrolled to
+                if offset.get($axis).is_some()
+                {
+                    offset.set($axis, None);
+                }
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_scroll_offset(entity)
+            }
+        }
+    };
+}
+
+impl_scroll_axis_property!("scroll-left", ScrollLeftProperty, ScrollAxis::Left);
+impl_scroll_axis_property!("scroll-top", ScrollTopProperty, ScrollAxis::Top);
+
+/// Inserts [`StyledScrollOffset`] on UI nodes, which is all scroll offset properties need.
+fn insert_scroll_offset(
+    entity: &mut EntityWorldMut
+) -> bool {
+    if !entity.contains::<Node>()
+    {
+        return false;
+    }
+
+    entity.insert(StyledScrollOffset::default());
+    true
+}
+
+/// Applies the `scroll-position` shorthand property, which sets both scroll offsets of an entity styled with `overflow: scroll`,
+/// like `scroll-position: 0px 120px;`, see [`StyledScrollOffset`].
+///
+/// Like on `scroll-left` and `scroll-top`, each value is a length or a percentage, and a single value is used by both axes.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ui::Val;
+/// # use tomt_bevycss::{prelude::*, property::impls::style::ScrollPositionProperty};
+/// let (left, top) = ScrollPositionProperty::parse(&PropertyValues::parse("0px 50%").unwrap()).unwrap();
+///
+/// assert_eq!(left, Val::Px(0.0));
+/// assert_eq!(top, Val::Percent(50.0));
+/// assert!(ScrollPositionProperty::parse(&PropertyValues::parse("auto").unwrap()).is_err());
+/// ```
+#[derive(Default)]
+pub struct ScrollPositionProperty;
+
+impl Property
+for ScrollPositionProperty
+{
+    type Cache = (Val, Val);
+    type Components = &'static mut StyledScrollOffset;
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "scroll-position"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        let offsets = match values.as_slice()
+        {
+            [both] => parse_offset(both).map(|both| (both, both)),
+            [left, top] => parse_offset(left).zip(parse_offset(top)),
+            _ => None,
+        };
+
+        offsets.ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        (left, top): &Self::Cache,
+        mut offset: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        if offset.left != Some(*left) || offset.top != Some(*top)
+        {
+            offset.set(ScrollAxis::Left, Some(*left));
+            offset.set(ScrollAxis::Top, Some(*top));
+        }
+    }
+
+    fn reset<'w>(
+        mut offset: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        if offset.left.is_some() || offset.top.is_some()
+        {
+            offset.set(ScrollAxis::Left, None);
+            offset.set(ScrollAxis::Top, None);
+        }
+    }
+
+    fn insert_missing(
+        entity: &mut EntityWorldMut
+    ) -> bool {
+        insert_scroll_offset(entity)
+    }
+}
+
+/// Forgets the declared offsets of entities styled again without any rule declaring them, like after removing the class which did,
+/// so adding the class back scrolls the entity again.
+///
+/// The entity stays where it was scrolled to, like when the property is reverted.
+pub(crate) fn forget_unmatched_scroll_offsets(
+    state: Res<StyleSheetState>,
+    mut q_offsets: Query<(Entity, &mut StyledScrollOffset)>,
+) {
+    for (entity, mut offset) in q_offsets.iter_mut()
+    {
+        let Some(style) = state.computed.get(&entity)
+        else {
+            continue;
+        };
+
+        let position = style.contains_key(ScrollPositionProperty::name());
+        for (axis, name) in [(ScrollAxis::Left, ScrollLeftProperty::name()), (ScrollAxis::Top, ScrollTopProperty::name())]
+        {
+            if offset.get(axis).is_some() && !position && !style.contains_key(name)
+            {
+                offset.set(axis, None);
+            }
+        }
+    }
+}
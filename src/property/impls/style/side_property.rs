@@ -0,0 +1,113 @@
+use super::insert_missing_style;
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues, set_if_changed, UnitCategory},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Commands,
+        Entity,
+        EntityWorldMut,
+        Node,
+        Style,
+        With,
+        World,
+    },
+    ui::Val,
+};
+
+/// Sets the given value on the side of the rect returned by `side`.
+///
+/// This is deferred to when commands are applied, so it's done after shorthands applied on the same frame, like `margin`.
+fn set_side(
+    entity: Entity,
+    value: Val,
+    side: fn(&mut Style) -> &mut Val,
+    commands: &mut Commands
+) {
+    commands.add(move |world: &mut World|
+    {
+        if let Some(mut style) = world.get_mut::<Style>(entity)
+        {
+            set_if_changed(&mut style, side, value);
+        }
+    });
+}
+
+/// Implements a new property which is applied on a single side of a [`Style`] rect field, like `margin-left`.
+macro_rules! impl_style_side
+{
+    ($name:expr, $struct:ident, $style_prop:ident, $side:ident) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` property on [Style::"]
+        #[doc = stringify!($style_prop)]
+        #[doc = concat!("::", stringify!($side))]
+        #[doc = "](`Style`) field of matched entities."]
+        ///
+        /// It's applied after shorthands, so it takes precedence over them, like `margin: 0px; margin-left: auto;`.
+        #[derive(Default)]
+        pub struct $struct;
+
+        impl Property
+        for $struct
+        {
+            type Cache = Val;
+            type Components = Entity;
+            type Filters = (With<Node>, With<Style>);
+            type Param = ();
+
+            fn name(
+                // no args
+            ) -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError> {
+                values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+                match values.val()
+                {
+                    Some(val) => Ok(val),
+                    None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+                }
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                commands: &mut Commands,
+            ) {
+                set_side(components, *cache, |style| &mut style.$style_prop.$side, commands);
+            }
+
+            fn reset<'w>(
+                components: QueryItem<Self::Components>,
+                _context: &PropertyContext<Self>,
+                commands: &mut Commands,
+            ) {
+                let value = Style::DEFAULT.$style_prop.$side;
+                set_side(components, value, |style| &mut style.$style_prop.$side, commands);
+            }
+
+            fn insert_missing(
+                entity: &mut EntityWorldMut
+            ) -> bool {
+                insert_missing_style(entity)
+            }
+        }
+    };
+}
+
+impl_style_side!("margin-left", MarginLeftProperty, margin, left);
+impl_style_side!("margin-right", MarginRightProperty, margin, right);
+impl_style_side!("margin-top", MarginTopProperty, margin, top);
+impl_style_side!("margin-bottom", MarginBottomProperty, margin, bottom);
+impl_style_side!("padding-left", PaddingLeftProperty, padding, left);
+impl_style_side!("padding-right", PaddingRightProperty, padding, right);
+impl_style_side!("padding-top", PaddingTopProperty, padding, top);
+impl_style_side!("padding-bottom", PaddingBottomProperty, padding, bottom);
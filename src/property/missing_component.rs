@@ -0,0 +1,77 @@
+use super::{Property, PropertyContext, PropertyValues};
+use crate::selector::Selector;
+
+use bevy::{
+    ecs::system::{StaticSystemParam, SystemState},
+    log::warn,
+    prelude::{
+        Commands,
+        Entity,
+        Name,
+        Query,
+        World,
+    },
+    utils::get_short_name,
+};
+use std::any::type_name;
+
+/// Handles an entity matched by a rule with the property `T`, but which is missing the components required by it.
+///
+/// The missing components are inserted by [`Property::insert_missing`] and the property is applied right away.
+/// If the property doesn't insert them, a warning naming the entity, the selector and the required components is logged instead.
+pub(super) fn handle_missing_component<T: Property>(
+    entity: Entity,
+    selector: &Selector,
+    values: PropertyValues,
+    commands: &mut Commands
+) {
+    let selector = selector.to_string();
+
+    commands.add(move |world: &mut World|
+    {
+        let inserted = match world.get_entity_mut(entity)
+        {
+            Some(mut entity_mut) => T::insert_missing(&mut entity_mut),
+            // Despawned in the meantime, so there is nothing to report
+            None => return,
+        };
+
+        if !inserted
+        {
+            let name = world.get::<Name>(entity)
+                .map(|name| format!(" ({name})"))
+                .unwrap_or_default();
+
+            warn!(
+                "Property '{}' matched by '{selector}' has no effect on entity {entity:?}{name}, \
+                since it's missing components required by `Query<{}, {}>`",
+                T::name(),
+                get_short_name(type_name::<T::Components>()),
+                get_short_name(type_name::<T::Filters>()),
+            );
+            return;
+        }
+
+        let cache = match T::parse(&values)
+        {
+            Ok(cache) => cache,
+            Err(_) => return,
+        };
+
+        let mut state = SystemState::<(
+            Query<T::Components, T::Filters>,
+            StaticSystemParam<T::Param>,
+            Commands,
+        )>::new(world);
+
+        let (mut q_nodes, param, mut commands) = state.get_mut(world);
+        if let Ok(components) = q_nodes.get_mut(entity)
+        {
+            T::apply(&cache, components, &PropertyContext::new(&*param), &mut commands);
+        }
+        // Param items may borrow the world until dropped
+        drop(param);
+
+        state.apply(world);
+    });
+}
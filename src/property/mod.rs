@@ -1,3 +1,9 @@
+mod bare_number_policy;
+pub use bare_number_policy::*;
+
+mod cascade_audit;
+pub use cascade_audit::*;
+
 mod cache_state;
 pub use cache_state::*;
 
@@ -6,24 +12,43 @@ pub use cached_properties::*;
 
 mod colors;
 
+mod missing_component;
+use missing_component::handle_missing_component;
+
+mod property_context;
+pub use property_context::*;
+
 mod property_meta;
 pub use property_meta::*;
 
+mod property_name;
+pub use property_name::*;
+
 mod property_token;
 pub use property_token::*;
 
 mod property_values;
 pub use property_values::*;
 
+mod root_font_size;
+pub use root_font_size::*;
+
+mod rule_match_tracking;
+pub use rule_match_tracking::*;
+
 mod selected_entities;
 pub use selected_entities::*;
 
+mod set_if_changed;
+pub(crate) use set_if_changed::*;
+
 mod stylesheet_state;
 pub use stylesheet_state::*;
 
-pub(crate) mod impls;
-/// Impls for `bevy_text` [`Text`] component
-pub(crate) mod text;
+/// Impls for `bevy_ui` components
+pub mod impls;
+/// Impls for `bevy_text` [`Text`](bevy::prelude::Text) component
+pub mod text;
 
 
 use crate::prelude::{
@@ -32,14 +57,24 @@ use crate::prelude::{
 };
 
 use bevy::{
-    ecs::query::{
-        QueryItem,
-        ReadOnlyWorldQuery,
-        WorldQuery,
+    ecs::{
+        query::{
+            QueryEntityError,
+            QueryItem,
+            ReadOnlyWorldQuery,
+            WorldQuery,
+        },
+        system::{
+            ReadOnlySystemParam,
+            StaticSystemParam,
+        },
     },
+    log::{debug, error},
     prelude::{
-        Assets, AssetServer,
+        Assets,
         Commands,
+        Entity,
+        EntityWorldMut,
         Local,
         Query,
         Res,
@@ -47,6 +82,9 @@ use bevy::{
 };
 use std::any::Any;
 
+#[cfg(test)]
+mod tests;
+
 /// Determines how a property should interact and modify the [ecs world](`bevy::prelude::World`).
 ///
 /// Each implementation of this trait should be registered with [`RegisterProperty`](crate::RegisterProperty) trait, where
@@ -62,12 +100,14 @@ use std::any::Any;
 /// Entities are first filtered by [`selectors`](`crate::selector::Selector`), but it can be useful to also ensure some behavior for safety reasons,
 /// like only inserting [`TextAlignment`](bevy::prelude::TextAlignment) if the entity also has a [`Text`](bevy::prelude::Text) component.
 ///  Check [`WorldQuery`] for more.
+/// - [`Param`](Property::Param) is a read only [`SystemParam`](bevy::ecs::system::SystemParam) given to [`apply`](Property::apply)
+///   through [`PropertyContext`], like `Res<'static, AssetServer>`, or `()` when nothing else is needed.
 ///
 /// These are tree functions required to be implemented:
 /// - [`name`](Property::name) indicates which property name should matched for.
 /// - [`parse`](Property::parse) parses the [`PropertyValues`] into the [`Cache`](Property::Cache) value to be reused across multiple entities.
 /// - [`apply`](Property::apply) applies on the given [`Components`](Property::Components) the [`Cache`](Property::Cache) value.
-/// Additionally, an [`AssetServer`] and [`Commands`] parameters are provided for more complex use cases.
+/// Additionally, a [`PropertyContext`] and [`Commands`] parameters are provided for more complex use cases.
 ///
 /// Also, there one function which have default implementations:
 /// - [`apply_system`](Property::apply_system) is a [`system`](https://docs.rs/bevy_ecs/0.8.1/bevy_ecs/system/index.html) which interacts with
@@ -78,9 +118,16 @@ pub trait Property:
     /// The cached value type to be applied by property.
     type Cache: Default + Any + Send + Sync;
     /// Which components should be queried when applying the modification. Check [`WorldQuery`] for more.
+    ///
+    /// A tuple writes many components in one declaration, like `(&'static mut Style, Option<&'static mut BorderColor>)`,
+    /// with [`Option`] for the ones which aren't required, see [`BorderProperty`](impls::style::BorderProperty).
     type Components: WorldQuery;
     /// Filters conditions to be applied when querying entities by this property. Check [`ReadOnlyWorldQuery`] for more.
     type Filters: ReadOnlyWorldQuery;
+    /// Resources or queries read while applying the property, given through [`PropertyContext`], like `Res<'static, AssetServer>`.
+    ///
+    /// It must not read any of the [`Components`](Property::Components) mutably queried by this property.
+    type Param: ReadOnlySystemParam + 'static;
 
     /// Indicates which property name should matched for. Must match the same property name as on `css` file.
     ///
@@ -93,34 +140,88 @@ pub trait Property:
     ///
     /// This function is called only once, on the first time a matching property is found while applying style rule.
     /// If an error is returned, it is also cached so no more attempt are made.
+    ///
+    /// Since it doesn't depend on the [ecs world](`bevy::prelude::World`), it can be called directly to unit test a property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::ui::Val;
+    /// # use tomt_bevycss::{prelude::*, property::impls::style::WidthProperty};
+    /// let values = PropertyValues::parse("120px").unwrap();
+    /// assert_eq!(WidthProperty::parse(&values).unwrap(), Val::Px(120.0));
+    ///
+    /// let values = PropertyValues::parse("red").unwrap();
+    /// assert!(WidthProperty::parse(&values).is_err());
+    /// ```
     fn parse(
         values: &PropertyValues
     ) -> Result<Self::Cache, BevyCssError>;
 
     /// Applies on the given [`Components`](Property::Components) the [`Cache`](Property::Cache) value.
-    /// Additionally, an [`AssetServer`] and [`Commands`] parameters are provided for more complex use cases.
+    /// Additionally, a [`PropertyContext`] with the [`Param`](Property::Param) of the property and [`Commands`] are provided for more complex use cases.
     ///
     /// If mutability is desired while applying the changes, declare [`Components`](Property::Components) as mutable.
+    /// Prefer `try_insert` over `insert` on [`Commands`], since the entity may be despawned before commands are applied.
     fn apply(
         cache: &Self::Cache,
         components: QueryItem<Self::Components>,
-        asset_server: &AssetServer,
+        context: &PropertyContext<Self>,
         commands: &mut Commands,
     );
 
+    /// Reverts on the given [`Components`](Property::Components) any modification made by [`apply`](Property::apply),
+    /// usually by setting the affected fields back to their default values.
+    ///
+    /// This is called by [`clear_styles`](crate::system::clear_styles), and does nothing by default.
+    fn reset(
+        _components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        // Nothing to revert by default
+    }
+
+    /// Inserts on the given entity the components required by this property, when it's sensible to do so.
+    ///
+    /// This is called by [`apply_system`](Property::apply_system) when a matched entity doesn't match
+    /// [`Components`](Property::Components) and [`Filters`](Property::Filters), and the property is applied right after.
+    /// Returns `false` if nothing was inserted, which is the default, so a warning is logged instead.
+    fn insert_missing(
+        _entity: &mut EntityWorldMut
+    ) -> bool {
+        false
+    }
+
     /// The [`system`](https://docs.rs/bevy_ecs/0.8.1/bevy_ecs/system/index.html) which interacts with
     /// [ecs world](`bevy::prelude::World`) and call [`apply`](Property::apply) function on every matched entity.
     ///
-    /// The default implementation will cover most use cases, by just implementing [`apply`](Property::apply)
+    /// The default implementation will cover most use cases, by just implementing [`apply`](Property::apply).
+    /// Entities missing the required components are handled once by [`insert_missing`](Property::insert_missing),
+    /// except for inherited properties, like `color`, which are also declared on containers to be passed on to their descendants.
+    ///
+    /// Entities are applied parents first, see [`StyleSheetState::iter_parents_first`], so commands queued by `apply`
+    /// for an entity run after the ones queued for its ancestors. Entities despawned since they were selected are skipped.
     fn apply_system(
         mut local: Local<PropertyMeta<Self>>,
         assets: Res<Assets<StyleSheetAsset>>,
         apply_sheets: Res<StyleSheetState>,
+        bare_number_policy: Option<Res<BareNumberPolicy>>,
         mut q_nodes: Query<Self::Components, Self::Filters>,
-        asset_server: Res<AssetServer>,
+        param: StaticSystemParam<Self::Param>,
         mut commands: Commands,
     ) {
-        for (entity, style) in apply_sheets.iter()
+        let property_context = PropertyContext::new(&*param);
+        let bare_number_policy = bare_number_policy.map_or_else(BareNumberPolicy::default, |policy| *policy);
+        // Inherited properties are also declared on containers, only to be passed on to their descendants
+        let handles_missing = !INHERITED_PROPERTIES.contains(&Self::name());
+
+        // Entities despawned, or which property was reverted, are handled again if the property is applied later
+        local.retain_missing(|entity| apply_sheets.applied(entity)
+            .is_some_and(|applied| applied.contains(Self::name()))
+        );
+
+        for (entity, style) in apply_sheets.iter_parents_first()
         {
             let source = match style.get_key_value(Self::name())
             {
@@ -128,13 +229,56 @@ pub trait Property:
                 None => continue,
             };
 
-            let rules = match assets.get(&source.styleheet)
+            let rules = match assets.get(&source.stylesheet)
             {
                 Some(asset) => asset,
                 None => continue,
             };
 
-            let cached_value = match local.get_or_parse(rules, &source.selector)
+            // Values relative to font size depends on the entity, so they are resolved and parsed for each entity
+            if let Some(values) = rules.get_property_value(&source.selector, Self::name())
+                .filter(|values| match Self::name()
+                {
+                    "font-size" => values.is_font_size_relative(),
+                    _ => values.is_font_relative(),
+                })
+            {
+                let context = match apply_sheets.font_size(*entity)
+                {
+                    Some(context) => context,
+                    None => continue,
+                };
+
+                let values = match Self::name()
+                {
+                    "font-size" => values.resolve_font_size(context.parent, context.root),
+                    _ => values.resolve_font_relative(context.own, context.root),
+                };
+                let values = values.with_bare_number_policy(bare_number_policy);
+
+                let cache = match Self::parse(&values)
+                {
+                    Ok(cache) => cache,
+                    Err(err) => {
+                        error!("Failed to parse property {} of rule '{}'. Error: {}", Self::name(), source.selector, err);
+                        continue;
+                    }
+                };
+
+                match q_nodes.get_mut(*entity)
+                {
+                    Ok(components) => Self::apply(&cache, components, &property_context, &mut commands),
+                    Err(QueryEntityError::NoSuchEntity(_)) => log_despawned::<Self>(*entity),
+                    Err(_) => if handles_missing && local.mark_missing(*entity)
+                    {
+                        handle_missing_component::<Self>(*entity, &source.selector, values, &mut commands);
+                    },
+                }
+
+                continue;
+            }
+
+            let cached_value = match local.get_or_parse(rules, &source.selector, bare_number_policy)
             {
                 CacheState::Ok(cached) => cached,
                 _other => continue,
@@ -143,10 +287,30 @@ pub trait Property:
             let components = match q_nodes.get_mut(*entity)
             {
                 Ok(cmp) => cmp,
-                Err(_) => continue,
+                Err(QueryEntityError::NoSuchEntity(_)) => {
+                    log_despawned::<Self>(*entity);
+                    continue;
+                }
+                Err(_) => {
+                    if let (true, Some(values)) = (
+                        handles_missing && local.mark_missing(*entity),
+                        rules.get_property_value(&source.selector, Self::name())
+                    ) {
+                        let values = values.with_bare_number_policy(bare_number_policy);
+                        handle_missing_component::<Self>(*entity, &source.selector, values, &mut commands);
+                    }
+                    continue;
+                }
             };
 
-            Self::apply(cached_value, components, &asset_server, &mut commands);
+            Self::apply(cached_value, components, &property_context, &mut commands);
         }
     }
 }
+
+/// Logs that the given entity was despawned after being selected, like by gameplay code on the same frame, so it's skipped.
+fn log_despawned<T: Property>(
+    entity: Entity
+) {
+    debug!("Property '{}' not applied on entity {entity:?}, since it was despawned after being selected", T::name());
+}
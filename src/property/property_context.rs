@@ -0,0 +1,36 @@
+use super::Property;
+
+use bevy::ecs::system::SystemParamItem;
+use std::ops::Deref;
+
+/// Read access to the [`Param`](Property::Param) declared by a property, given to [`apply`](Property::apply) and [`reset`](Property::reset).
+///
+/// It dereferences to the param item, so a property declaring `type Param = Res<'static, AssetServer>;` can call
+/// `context.load(path)` directly, while tuples are accessed by field, like `context.0`.
+pub struct PropertyContext<'a, 'w, 's, T: Property>
+{
+    param: &'a SystemParamItem<'w, 's, T::Param>,
+}
+
+impl<'a, 'w, 's, T: Property> PropertyContext<'a, 'w, 's, T>
+{
+    pub(crate) fn new(
+        param: &'a SystemParamItem<'w, 's, T::Param>
+    ) -> Self {
+        Self{
+            param,
+        }
+    }
+}
+
+impl<'w, 's, T: Property> Deref
+for PropertyContext<'_, 'w, 's, T>
+{
+    type Target = SystemParamItem<'w, 's, T::Param>;
+
+    fn deref(
+        &self
+    ) -> &Self::Target {
+        self.param
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    BareNumberPolicy,
     CacheState, CachedProperties,
     Property,
 };
@@ -9,16 +10,20 @@ use crate::{
 
 use bevy::{
     log::error,
-    prelude::{Deref, DerefMut},
-    utils::HashMap,
+    prelude::{Deref, DerefMut, Entity},
+    utils::{HashMap, HashSet},
 };
 
 
 /// Internal property cache map. Used by [`Property::apply_system`] to keep track of which properties was already parsed.
+///
+/// It also keeps track of entities which were reported as missing the components required by the property.
 #[derive(Debug, Default)]
 #[derive(Deref, DerefMut)]
 pub struct PropertyMeta<T: Property>(
-    HashMap<u64, CachedProperties<T::Cache>>
+    #[deref]
+    HashMap<u64, CachedProperties<T::Cache>>,
+    HashSet<Entity>,
 );
 
 impl<T: Property> PropertyMeta<T>
@@ -30,6 +35,7 @@ impl<T: Property> PropertyMeta<T>
         &mut self,
         rules: &StyleSheetAsset,
         selector: &Selector,
+        bare_number_policy: BareNumberPolicy,
     ) -> &CacheState<T::Cache> {
         let cached_properties = self.entry(rules.hash()).or_default();
 
@@ -42,11 +48,11 @@ impl<T: Property> PropertyMeta<T>
         {
             let new_cache = rules
                 .get_property_value(selector, T::name())
-                .map(|values| match T::parse(values)
+                .map(|values| match T::parse(&values.with_bare_number_policy(bare_number_policy))
                 {
                     Ok(cache) => CacheState::Ok(cache),
                     Err(err) => {
-                        error!("Failed to parse property {}. Error: {}", T::name(), err);
+                        error!("Failed to parse property {} of rule '{}'. Error: {}", T::name(), selector, err);
                         // TODO: Clear cache state when the asset is reloaded, since values may be changed.
                         CacheState::Error
                     }
@@ -57,4 +63,23 @@ impl<T: Property> PropertyMeta<T>
             cached_properties.get(selector).unwrap()
         }
     }
+
+    /// Marks the given entity as missing the components required by the property.
+    ///
+    /// Returns `false` if it was already marked, so it's only handled once.
+    pub(super) fn mark_missing(
+        &mut self,
+        entity: Entity
+    ) -> bool {
+        self.1.insert(entity)
+    }
+
+    /// Forgets entities marked as missing the components required by the property, unless `keep` returns `true`,
+    /// like entities despawned or which no longer have the property, so they are handled again if it's applied later.
+    pub(super) fn retain_missing(
+        &mut self,
+        keep: impl Fn(Entity) -> bool
+    ) {
+        self.1.retain(|entity| keep(*entity));
+    }
 }
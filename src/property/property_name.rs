@@ -0,0 +1,32 @@
+use bevy::utils::HashSet;
+use std::sync::Arc;
+
+/// Name of a property, like `width`.
+///
+/// Names are interned when a sheet is parsed, so all rules of the sheet using the same property share it,
+/// and it can be cloned while styling entities without allocating. Since it derefs to `str`, maps keyed by it
+/// can still be queried with a `&str`, like `style.get("width")`.
+pub type PropertyName = Arc<str>;
+
+/// Interns the [`PropertyName`]s of a single sheet while it's parsed, so they are dropped along with the sheet.
+#[derive(Default)]
+pub(crate) struct PropertyNameInterner(HashSet<PropertyName>);
+
+impl PropertyNameInterner
+{
+    /// Returns the interned [`PropertyName`] for the given name, interning it if this is the first time it's seen.
+    pub fn intern(
+        &mut self,
+        name: &str
+    ) -> PropertyName {
+        match self.0.get(name)
+        {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned = PropertyName::from(name);
+                self.0.insert(interned.clone());
+                interned
+            }
+        }
+    }
+}
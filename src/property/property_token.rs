@@ -1,4 +1,11 @@
+use super::PropertyValues;
+
+use bevy::ui::Val;
 use cssparser::Token;
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
 
 /// A property value token which was parsed from a CSS rule.
 #[derive(Clone, Debug)]
@@ -8,11 +15,39 @@ pub enum PropertyToken
     /// A value which was parsed percent value, like `100%` or `73.23%`.
     Percentage(f32),
 
-    /// A value which was parsed dimension value, like `10px` or `35em.
+    /// A value which was parsed dimension value, like `10px`.
     ///
-    /// Currently there is no distinction between [`length-values`](https://developer.mozilla.org/en-US/docs/Web/CSS/length).
+    /// Only pixels are supported as absolute [`length-values`](https://developer.mozilla.org/en-US/docs/Web/CSS/length),
+    /// while angles and durations are parsed as [`Angle`](PropertyToken::Angle) and [`Time`](PropertyToken::Time).
+    /// Values on any other unit, like `10foo` or `1in`, are rejected when the sheet is parsed.
     Dimension(f32),
 
+    /// An angle, like `90deg`, `0.5turn` or `1rad`, kept on the unit it was written with, see [`degrees`](PropertyToken::degrees).
+    Angle(f32, AngleUnit),
+
+    /// A duration, like `1s` or `200ms`, kept on the unit it was written with, see [`seconds`](PropertyToken::seconds).
+    Time(f32, TimeUnit),
+
+    /// A dimension relative to the font size of the entity, like `1.5em`.
+    ///
+    /// Resolved into a [`Dimension`](PropertyToken::Dimension) before being parsed, see [`PropertyValues::resolve_font_relative`].
+    Em(f32),
+
+    /// A dimension relative to the [`RootFontSize`](super::RootFontSize), like `2rem`.
+    ///
+    /// Resolved into a [`Dimension`](PropertyToken::Dimension) before being parsed, see [`PropertyValues::resolve_font_relative`].
+    Rem(f32),
+
+    /// A dimension relative to the width of the primary window, like `50vw`, where `100vw` is the whole width.
+    ///
+    /// Converted to [`Val::Vw`], which `bevy_ui` resolves on layout, so resizing the window doesn't restyle anything.
+    Vw(f32),
+
+    /// A dimension relative to the height of the primary window, like `10vh`, where `100vh` is the whole height.
+    ///
+    /// Converted to [`Val::Vh`], like [`Vw`](PropertyToken::Vw).
+    Vh(f32),
+
     /// A numeric float value, like `31.1` or `43`.
     Number(f32),
 
@@ -24,6 +59,233 @@ pub enum PropertyToken
 
     /// A quoted string, like `"some value"`.
     String(String),
+
+    /// A function with its lower-case name and comma separated arguments, like `color-mix(in srgb, red 30%, blue)`.
+    Function(String, Vec<PropertyValues>),
+}
+
+impl PropertyToken
+{
+    /// Converts this token into a [`Val`], like custom properties usually need.
+    ///
+    /// A [`Dimension`](PropertyToken::Dimension) is converted to [`Val::Px`], a [`Percentage`](PropertyToken::Percentage)
+    /// to [`Val::Percent`], [`Vw`](PropertyToken::Vw) and [`Vh`](PropertyToken::Vh) to [`Val::Vw`] and [`Val::Vh`],
+    /// and the `auto` keyword to [`Val::Auto`]. A bare `0` is also accepted and converted to `Val::Px(0.0)`,
+    /// but any other bare number is rejected, see [`BareNumberPolicy`](super::BareNumberPolicy) for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::ui::Val;
+    /// # use tomt_bevycss::property::PropertyToken;
+    /// assert_eq!(PropertyToken::Dimension(10.0).to_val(), Some(Val::Px(10.0)));
+    /// assert_eq!(PropertyToken::Vw(50.0).to_val(), Some(Val::Vw(50.0)));
+    /// assert_eq!(PropertyToken::Identifier("auto".to_string()).to_val(), Some(Val::Auto));
+    /// assert_eq!(PropertyToken::Number(10.0).to_val(), None);
+    /// ```
+    pub fn to_val(
+        &self
+    ) -> Option<Val> {
+        match self
+        {
+            PropertyToken::Percentage(val) => Some(Val::Percent(*val)),
+            PropertyToken::Dimension(val) => Some(Val::Px(*val)),
+            PropertyToken::Vw(val) => Some(Val::Vw(*val)),
+            PropertyToken::Vh(val) => Some(Val::Vh(*val)),
+            PropertyToken::Number(val) if *val == 0.0 => Some(Val::Px(0.0)),
+            token => parse_keyword(token, &["auto"]).map(|_| Val::Auto),
+        }
+    }
+}
+
+/// Unit of an [`Angle`](PropertyToken::Angle) token.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Hash)]
+pub enum AngleUnit
+{
+    /// Degrees, like `90deg`.
+    Deg,
+    /// Gradians, like `100grad`, where `400grad` is a full turn.
+    Grad,
+    /// Radians, like `1.57rad`.
+    Rad,
+    /// Turns, like `0.25turn`.
+    Turn,
+}
+
+impl AngleUnit
+{
+    /// Name of the unit, as written on a `css` value, like `deg`.
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self
+        {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Grad => "grad",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+}
+
+/// Unit of a [`Time`](PropertyToken::Time) token.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Hash)]
+pub enum TimeUnit
+{
+    /// Seconds, like `1s`.
+    S,
+    /// Milliseconds, like `200ms`.
+    Ms,
+}
+
+impl TimeUnit
+{
+    /// Name of the unit, as written on a `css` value, like `ms`.
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self
+        {
+            TimeUnit::S => "s",
+            TimeUnit::Ms => "ms",
+        }
+    }
+}
+
+/// Category of the unit of a [`PropertyToken`], which properties check to report values on the wrong unit, like `width: 10deg;`.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+pub enum UnitCategory
+{
+    /// A length, like `10px`, `1.5em` or `2rem`.
+    Length,
+    /// An angle, like `90deg`.
+    Angle,
+    /// A duration, like `200ms`.
+    Time,
+}
+
+impl Display
+for UnitCategory
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        match self
+        {
+            UnitCategory::Length => write!(formatter, "length"),
+            UnitCategory::Angle => write!(formatter, "angle"),
+            UnitCategory::Time => write!(formatter, "time"),
+        }
+    }
+}
+
+impl PropertyToken
+{
+    /// Gets the category of the unit of this token, if it has a unit, like [`UnitCategory::Angle`] for `90deg`.
+    ///
+    /// Percentages and bare numbers have no unit category, since they are accepted by properties of any category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::property::{PropertyToken, TimeUnit, UnitCategory};
+    /// assert_eq!(PropertyToken::Dimension(10.0).unit_category(), Some(UnitCategory::Length));
+    /// assert_eq!(PropertyToken::Time(200.0, TimeUnit::Ms).unit_category(), Some(UnitCategory::Time));
+    /// assert_eq!(PropertyToken::Number(1.0).unit_category(), None);
+    /// ```
+    pub fn unit_category(
+        &self
+    ) -> Option<UnitCategory> {
+        match self
+        {
+            PropertyToken::Dimension(_)
+            | PropertyToken::Em(_)
+            | PropertyToken::Rem(_)
+            | PropertyToken::Vw(_)
+            | PropertyToken::Vh(_) => Some(UnitCategory::Length),
+            PropertyToken::Angle(..) => Some(UnitCategory::Angle),
+            PropertyToken::Time(..) => Some(UnitCategory::Time),
+            _ => None,
+        }
+    }
+
+    /// Converts an [`Angle`](PropertyToken::Angle) into degrees, like `90.0` for `0.25turn`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::property::{AngleUnit, PropertyToken};
+    /// assert_eq!(PropertyToken::Angle(0.25, AngleUnit::Turn).degrees(), Some(90.0));
+    /// assert_eq!(PropertyToken::Number(90.0).degrees(), None);
+    /// ```
+    pub fn degrees(
+        &self
+    ) -> Option<f32> {
+        match self
+        {
+            PropertyToken::Angle(val, AngleUnit::Deg) => Some(*val),
+            PropertyToken::Angle(val, AngleUnit::Grad) => Some(val * 0.9),
+            PropertyToken::Angle(val, AngleUnit::Rad) => Some(val.to_degrees()),
+            PropertyToken::Angle(val, AngleUnit::Turn) => Some(val * 360.0),
+            _ => None,
+        }
+    }
+
+    /// Converts a [`Time`](PropertyToken::Time) into seconds, like `0.2` for `200ms`.
+    pub fn seconds(
+        &self
+    ) -> Option<f32> {
+        match self
+        {
+            PropertyToken::Time(val, TimeUnit::S) => Some(*val),
+            PropertyToken::Time(val, TimeUnit::Ms) => Some(val / 1000.0),
+            _ => None,
+        }
+    }
+}
+
+/// Hashes numbers by their bits, so tokens can be interned, like by [`StyleSheetAsset::deduplicate`](crate::prelude::StyleSheetAsset::deduplicate).
+///
+/// `-0.0` is hashed as `0.0`, since both are equal.
+impl Hash
+for PropertyToken
+{
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H
+    ) {
+        std::mem::discriminant(self).hash(state);
+
+        match self
+        {
+            Self::Percentage(val)
+            | Self::Dimension(val)
+            | Self::Em(val)
+            | Self::Rem(val)
+            | Self::Vw(val)
+            | Self::Vh(val)
+            | Self::Number(val) => (val + 0.0).to_bits().hash(state),
+            Self::Angle(val, unit) => {
+                (val + 0.0).to_bits().hash(state);
+                unit.hash(state);
+            }
+            Self::Time(val, unit) => {
+                (val + 0.0).to_bits().hash(state);
+                unit.hash(state);
+            }
+            Self::Identifier(val)
+            | Self::Hash(val)
+            | Self::String(val) => val.hash(state),
+            Self::Function(name, args) => {
+                name.hash(state);
+                args.hash(state);
+            }
+        }
+    }
 }
 
 impl<'i> TryFrom<Token<'i>>
@@ -42,8 +304,50 @@ for PropertyToken
             Token::QuotedString(val) => Ok(Self::String(val.to_string())),
             Token::Number { value, .. } => Ok(Self::Number(value)),
             Token::Percentage { unit_value, .. } => Ok(Self::Percentage(unit_value * 100.0)),
-            Token::Dimension { value, .. } => Ok(Self::Dimension(value)),
+            // Unknown units, like `10foo`, aren't read as pixels, so they are rejected
+            Token::Dimension { value, unit, .. } => match unit.to_ascii_lowercase().as_str()
+            {
+                "px" => Ok(Self::Dimension(value)),
+                "em" => Ok(Self::Em(value)),
+                "rem" => Ok(Self::Rem(value)),
+                "vw" => Ok(Self::Vw(value)),
+                "vh" => Ok(Self::Vh(value)),
+                "deg" => Ok(Self::Angle(value, AngleUnit::Deg)),
+                "grad" => Ok(Self::Angle(value, AngleUnit::Grad)),
+                "rad" => Ok(Self::Angle(value, AngleUnit::Rad)),
+                "turn" => Ok(Self::Angle(value, AngleUnit::Turn)),
+                "s" => Ok(Self::Time(value, TimeUnit::S)),
+                "ms" => Ok(Self::Time(value, TimeUnit::Ms)),
+                _ => Err(()),
+            },
             _ => Err(()),
         }
     }
 }
+
+/// Checks if the given token is one of the `allowed` keywords, like `auto`, `none` or `normal`.
+///
+/// Keywords are compared ignoring ASCII case, as `css` does, and the matching entry of `allowed` is returned,
+/// so callers can match on its canonical form. Properties should use this helper, or [`PropertyValues::keyword`],
+/// instead of comparing identifiers by hand, so all of them accept keywords the same way.
+///
+/// # Examples
+///
+/// ```
+/// # use tomt_bevycss::property::{parse_keyword, PropertyToken};
+/// let token = PropertyToken::Identifier("AUTO".to_string());
+/// assert_eq!(parse_keyword(&token, &["auto", "none"]), Some("auto"));
+/// assert_eq!(parse_keyword(&token, &["none"]), None);
+/// ```
+pub fn parse_keyword<'a>(
+    token: &PropertyToken,
+    allowed: &[&'a str]
+) -> Option<&'a str> {
+    match token
+    {
+        PropertyToken::Identifier(ident) => allowed.iter()
+            .find(|keyword| keyword.eq_ignore_ascii_case(ident))
+            .copied(),
+        _ => None,
+    }
+}
@@ -1,6 +1,13 @@
 use super::{
     colors,
+    parse_keyword,
+    BareNumberPolicy,
     PropertyToken,
+    UnitCategory,
+};
+use crate::{
+    parser::{to_error, PropertyParser},
+    prelude::BevyCssError,
 };
 
 use bevy::{
@@ -14,17 +21,158 @@ use bevy::{
         Val,
     },
 };
+use cssparser::{Parser, ParserInput};
 use smallvec::SmallVec;
 
 /// A list of [`PropertyToken`] which was parsed from a single property.
 #[derive(Clone, Debug, Default)]
-#[derive(Deref)]
+#[derive(PartialEq, PartialOrd)]
+#[derive(Deref, Hash)]
 pub struct PropertyValues(
     pub(crate) SmallVec<[PropertyToken; 8]>
 );
 
 impl PropertyValues
 {
+    /// Parses a single property value, like `10px 5%` or `"some text"`, the same way it would be parsed on a `css` rule.
+    ///
+    /// This is useful to test [`Property::parse`](crate::Property::parse) implementations without a running app.
+    /// A trailing `!important` is ignored, since it has no meaning outside a style sheet.
+    ///
+    /// Returns the error of the first invalid token, the same one which would be reported for the rule on a style sheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let values = PropertyValues::parse("10px 5%")?;
+    /// assert_eq!(values.len(), 2);
+    ///
+    /// assert_eq!(PropertyValues::parse("10foo"), Err(BevyCssError::UnsupportedPropertyValue("10foo".to_string())));
+    /// # Ok::<(), BevyCssError>(())
+    /// ```
+    pub fn parse(
+        content: &str
+    ) -> Result<Self, BevyCssError> {
+        let mut input = ParserInput::new(content);
+        let mut parser = Parser::new(&mut input);
+
+        PropertyParser::parse_property_values(&mut parser)
+            .map(|(values, _important)| values)
+            .map_err(|err| to_error(&err))
+    }
+
+    /// Checks if any value depends on the font size, like `1.5em` or `2rem`, including arguments of functions like `rect(0, 2em, 1em, 0)`.
+    ///
+    /// Those values can't be parsed directly, since they must be resolved for each entity with [`resolve_font_relative`](PropertyValues::resolve_font_relative).
+    pub fn is_font_relative(
+        &self
+    ) -> bool {
+        self.0.iter()
+            .any(|token| match token
+            {
+                PropertyToken::Em(_) | PropertyToken::Rem(_) => true,
+                PropertyToken::Function(_name, args) => args.iter().any(PropertyValues::is_font_relative),
+                _ => false,
+            })
+    }
+
+    /// Converts [`Em`](PropertyToken::Em) and [`Rem`](PropertyToken::Rem) values into [`Dimension`](PropertyToken::Dimension),
+    /// using the given font size and root font size, respectively. Arguments of functions are converted too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let values = PropertyValues::parse("1.5em 2rem").unwrap().resolve_font_relative(20.0, 16.0);
+    /// assert_eq!(values, PropertyValues::parse("30px 32px").unwrap());
+    ///
+    /// let values = PropertyValues::parse("rect(0, 1em, 1rem, 0)").unwrap().resolve_font_relative(20.0, 16.0);
+    /// assert_eq!(values, PropertyValues::parse("rect(0, 20px, 16px, 0)").unwrap());
+    /// ```
+    pub fn resolve_font_relative(
+        &self,
+        font_size: f32,
+        root_font_size: f32
+    ) -> Self {
+        Self(self.0.iter()
+            .map(|token| match token
+            {
+                PropertyToken::Em(val) => PropertyToken::Dimension(val * font_size),
+                PropertyToken::Rem(val) => PropertyToken::Dimension(val * root_font_size),
+                PropertyToken::Function(name, args) => PropertyToken::Function(
+                    name.clone(),
+                    args.iter().map(|arg| arg.resolve_font_relative(font_size, root_font_size)).collect(),
+                ),
+                other => other.clone(),
+            })
+            .collect()
+        )
+    }
+
+    /// Same as [`is_font_relative`](PropertyValues::is_font_relative), but for values of `font-size` property,
+    /// where a [`Percentage`](PropertyToken::Percentage) is also relative to the font size of the parent, like `150%`.
+    pub fn is_font_size_relative(
+        &self
+    ) -> bool {
+        self.is_font_relative()
+            || self.0.iter().any(|token| matches!(token, PropertyToken::Percentage(_)))
+    }
+
+    /// Same as [`resolve_font_relative`](PropertyValues::resolve_font_relative), but for values of `font-size` property,
+    /// where [`Em`](PropertyToken::Em) and [`Percentage`](PropertyToken::Percentage) are resolved against the font size of the parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let values = PropertyValues::parse("150%").unwrap().resolve_font_size(20.0, 16.0);
+    /// assert_eq!(values, PropertyValues::parse("30px").unwrap());
+    /// ```
+    pub fn resolve_font_size(
+        &self,
+        parent_font_size: f32,
+        root_font_size: f32
+    ) -> Self {
+        Self(self.resolve_font_relative(parent_font_size, root_font_size).0
+            .into_iter()
+            .map(|token| match token
+            {
+                PropertyToken::Percentage(val) => PropertyToken::Dimension(val / 100.0 * parent_font_size),
+                other => other,
+            })
+            .collect()
+        )
+    }
+
+    /// Applies the given [`BareNumberPolicy`], converting bare [`Number`](PropertyToken::Number) values
+    /// into [`Dimension`](PropertyToken::Dimension) when it is [`BareNumberPolicy::Px`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let values = PropertyValues::parse("10").unwrap().with_bare_number_policy(BareNumberPolicy::Px);
+    /// assert_eq!(values, PropertyValues::parse("10px").unwrap());
+    /// ```
+    pub fn with_bare_number_policy(
+        &self,
+        policy: BareNumberPolicy
+    ) -> Self {
+        match policy
+        {
+            BareNumberPolicy::Reject => self.clone(),
+            BareNumberPolicy::Px => Self(self.0.iter()
+                .map(|token| match token
+                {
+                    PropertyToken::Number(val) => PropertyToken::Dimension(*val),
+                    other => other.clone(),
+                })
+                .collect()
+            ),
+        }
+    }
+
     /// Tries to parses the current values as a single [`String`].
     pub fn string(
         &self
@@ -43,19 +191,15 @@ impl PropertyValues
 
     /// Tries to parses the current values as a single [`Color`].
     ///
-    /// Currently only [named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color)
-    /// and [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color) are supported.
+    /// Currently only [named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color),
+    /// [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color)
+    /// and [`color-mix()`](https://developer.mozilla.org/en-US/docs/Web/CSS/color_value/color-mix) are supported.
     pub fn color(
         &self
     ) -> Option<Color> {
         if self.0.len() == 1
         {
-            match &self.0[0]
-            {
-                PropertyToken::Identifier(name) => colors::parse_named_color(name.as_str()),
-                PropertyToken::Hash(hash) => colors::parse_hex_color(hash.as_str()),
-                _ => None,
-            }
+            colors::parse_color(&self.0[0])
         }
         else
         {
@@ -104,21 +248,100 @@ impl PropertyValues
             })
     }
 
+    /// Tries to parses the first identifier of current values as one of the `allowed` keywords, see [`parse_keyword`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// assert_eq!(PropertyValues::parse("None").unwrap().keyword(&["auto", "none"]), Some("none"));
+    /// assert_eq!(PropertyValues::parse("normal").unwrap().keyword(&["auto", "none"]), None);
+    /// ```
+    pub fn keyword<'a>(
+        &self,
+        allowed: &[&'a str]
+    ) -> Option<&'a str> {
+        self.0.iter()
+            .find(|token| matches!(token, PropertyToken::Identifier(_)))
+            .and_then(|token| parse_keyword(token, allowed))
+    }
+
+    /// Tries to find a function which name is one of the `allowed` ones, ignoring case, like `fit-content(200px)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// assert_eq!(PropertyValues::parse("fit-content(200px)").unwrap().function(&["fit-content"]), Some("fit-content"));
+    /// assert_eq!(PropertyValues::parse("fit-content").unwrap().function(&["fit-content"]), None);
+    /// ```
+    pub fn function<'a>(
+        &self,
+        allowed: &[&'a str]
+    ) -> Option<&'a str> {
+        self.0.iter()
+            .find_map(|token| match token
+            {
+                PropertyToken::Function(name, _args) => allowed.iter()
+                    .find(|function| function.eq_ignore_ascii_case(name))
+                    .copied(),
+                _ => None,
+            })
+    }
+
+    /// Checks that all values with a unit are of the `expected` category, like lengths on `width`,
+    /// so values on another unit, like `width: 10deg;`, are reported as [`BevyCssError::MismatchedUnit`] instead of being misread.
+    /// The value is reported on the unit it was written with, like `200ms`. Arguments of functions aren't checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::{prelude::*, property::UnitCategory};
+    /// assert!(PropertyValues::parse("10px 50%").unwrap().expect_unit("margin", UnitCategory::Length).is_ok());
+    /// assert_eq!(
+    ///     PropertyValues::parse("10deg").unwrap().expect_unit("width", UnitCategory::Length),
+    ///     Err(BevyCssError::MismatchedUnit {
+    ///         property: "width".to_string(),
+    ///         value: "10deg".to_string(),
+    ///         expected: UnitCategory::Length,
+    ///     })
+    /// );
+    /// ```
+    pub fn expect_unit(
+        &self,
+        property: &str,
+        expected: UnitCategory
+    ) -> Result<(), BevyCssError> {
+        let mismatched = self.0.iter()
+            .find(|token| token.unit_category().is_some_and(|category| category != expected));
+
+        let value = match mismatched
+        {
+            None => return Ok(()),
+            Some(PropertyToken::Angle(val, unit)) => format!("{val}{}", unit.name()),
+            Some(PropertyToken::Time(val, unit)) => format!("{val}{}", unit.name()),
+            Some(PropertyToken::Em(val)) => format!("{val}em"),
+            Some(PropertyToken::Rem(val)) => format!("{val}rem"),
+            Some(PropertyToken::Vw(val)) => format!("{val}vw"),
+            Some(PropertyToken::Vh(val)) => format!("{val}vh"),
+            Some(PropertyToken::Dimension(val)) => format!("{val}px"),
+            Some(token) => format!("{token:?}"),
+        };
+
+        Err(BevyCssError::MismatchedUnit { property: property.to_string(), value, expected })
+    }
+
     /// Tries to parses the current values as a single [`Val`].
     ///
     /// Only [`Percentage`](PropertyToken::Percentage) and [`Dimension`](PropertyToken::Dimension`) are considered valid values,
     /// where former is converted to [`Val::Percent`] and latter is converted to [`Val::Px`].
+    /// A bare `0` is also accepted and converted to `Val::Px(0.0)`, but any other bare number is rejected,
+    /// see [`BareNumberPolicy`] for more.
     pub fn val(
         &self
     ) -> Option<Val> {
         self.0.iter()
-            .find_map(|token| match token
-            {
-                PropertyToken::Percentage(val) => Some(Val::Percent(*val)),
-                PropertyToken::Dimension(val) => Some(Val::Px(*val)),
-                PropertyToken::Identifier(val) if val == "auto" => Some(Val::Auto),
-                _ => None,
-            })
+            .find_map(PropertyToken::to_val)
     }
 
     /// Tries to parses the current values as a single [`f32`].
@@ -157,13 +380,7 @@ impl PropertyValues
                 | PropertyToken::Dimension(val)
                 | PropertyToken::Number(val) => Some(Some(*val)),
 
-                PropertyToken::Identifier(ident) => match ident.as_str()
-                {
-                    "none" => Some(None),
-                    _ => None,
-                },
-
-                _ => None,
+                token => parse_keyword(token, &["none"]).map(|_| None),
             })
     }
 
@@ -185,12 +402,10 @@ impl PropertyValues
             self.0.iter()
                 .fold((None, 0), |(rect, idx), token|
                 {
-                    let val = match token
+                    let val = match token.to_val()
                     {
-                        PropertyToken::Percentage(val) => Val::Percent(*val),
-                        PropertyToken::Dimension(val) => Val::Px(*val),
-                        PropertyToken::Identifier(val) if val == "auto" => Val::Auto,
-                        _ => return (rect, idx),
+                        Some(val) => val,
+                        None => return (rect, idx),
                     };
                     let mut rect: UiRect = rect.unwrap_or_default();
 
@@ -0,0 +1,42 @@
+use bevy::prelude::Resource;
+
+/// Font size used to resolve `rem` values, and `em` values on entities without any styled ancestor.
+///
+/// Defaults to `16.0`, like on most web browsers.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn setup(
+///     mut commands: Commands
+/// ) {
+///     // `1rem` is now `20px`
+///     commands.insert_resource(RootFontSize(20.0));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Resource)]
+pub struct RootFontSize(pub f32);
+
+impl Default
+for RootFontSize
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self(16.0)
+    }
+}
+
+/// Font sizes an entity resolves relative values against, computed top-down for each entity selected on the current frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontSizeContext
+{
+    /// The [`RootFontSize`] value, used by `rem` values.
+    pub root: f32,
+    /// Computed font size of the parent entity, used by `em` values on `font-size` property.
+    pub parent: f32,
+    /// Computed font size of the entity itself, used by `em` values on any other property.
+    pub own: f32,
+}
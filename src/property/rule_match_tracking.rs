@@ -0,0 +1,11 @@
+use bevy::prelude::Resource;
+
+/// Enables counting how many entities each style sheet rule matches while styling, so rules which never matched any entity
+/// can be listed with [`StyleSheetState::unmatched_rules`](super::StyleSheetState::unmatched_rules), to prune dead `css`.
+///
+/// The initial value is set by [`BevyCssPlugin::with_rule_match_tracking`](crate::prelude::BevyCssPlugin::with_rule_match_tracking),
+/// and it can be changed at any time, like from a dev console. Disabled by default, since counts are kept for every evaluated rule.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct RuleMatchTracking(pub bool);
@@ -0,0 +1,74 @@
+use bevy::prelude::{DetectChangesMut, Mut, Text, TextSection};
+
+/// Writes `value` on the field of `component` returned by `field`, only when it differs from the current value.
+///
+/// Properties are applied again whenever an entity is styled, so writing only real changes keeps `Changed<T>` filters,
+/// like the ones used by `bevy_ui` layout, from firing on every styled frame.
+pub(crate) fn set_if_changed<C, T>(
+    component: &mut Mut<C>,
+    field: impl FnOnce(&mut C) -> &mut T,
+    value: T
+) where
+    T: PartialEq,
+{
+    update_if_changed(component, |component|
+    {
+        let field = field(component);
+        let changed = *field != value;
+        if changed
+        {
+            *field = value;
+        }
+
+        changed
+    });
+}
+
+/// Calls `update` without triggering change detection, and only marks `component` as changed when `update` returns `true`.
+///
+/// Useful when many fields are written at once, like the style of all sections of a `Text`.
+pub(crate) fn update_if_changed<C>(
+    component: &mut Mut<C>,
+    update: impl FnOnce(&mut C) -> bool
+) {
+    if update(component.bypass_change_detection())
+    {
+        component.set_changed();
+    }
+}
+
+/// Writes `value` on the field returned by `field` of all sections of `text`, like [`set_if_changed`],
+/// so it's only marked as changed when any section differs.
+///
+/// A text without sections, like [`Text::default`], gets an empty section first, so properties like `font-size`
+/// or `text-content` aren't silently dropped on it.
+pub(crate) fn set_sections<T>(
+    text: &mut Mut<Text>,
+    field: impl Fn(&mut TextSection) -> &mut T,
+    value: T
+) where
+    T: PartialEq + Clone,
+{
+    update_if_changed(text, |text|
+    {
+        let created = text.sections.is_empty();
+        if created
+        {
+            text.sections.push(TextSection::default());
+        }
+
+        text.sections
+            .iter_mut()
+            .fold(created, |changed, section|
+            {
+                let field = field(section);
+                if *field == value
+                {
+                    return changed;
+                }
+
+                *field = value.clone();
+                true
+            })
+    });
+}
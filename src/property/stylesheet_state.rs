@@ -1,10 +1,21 @@
-use super::SelectedEntities;
+use super::{
+    BareNumberPolicy,
+    CascadeConflict,
+    CascadeDeclaration,
+    FontSizeContext,
+    PropertyName,
+    PropertyValues,
+    SelectedEntities,
+};
 use crate::{
+    prelude::StyleOverride,
     selector::Selector,
-    stylesheet::StyleSheetAsset,
+    stylesheet::{StyleRule, StyleSheetAsset},
 };
 
 use bevy::{
+    asset::AssetId,
+    log::error,
     prelude::{
         Assets,
         Deref, DerefMut,
@@ -12,86 +23,1118 @@ use bevy::{
         Handle,
         Resource,
     },
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
+use std::cmp::Reverse;
 
+/// Rule which won the cascade for a single property of an entity on the current frame.
 #[derive(Debug, Clone)]
 pub struct StyleSource
 {
-    pub styleheet: Handle<StyleSheetAsset>,
+    /// Sheet which declared the rule.
+    pub stylesheet: Handle<StyleSheetAsset>,
+    /// Selector of the rule, used to look up its properties on the sheet.
     pub selector: Selector,
+    /// If the declaration was marked as `!important`.
+    pub important: bool,
 }
 
+/// Winning rule of each property computed for a single entity, by property name.
 #[derive(Debug, Clone, Default, Deref, DerefMut)]
 pub struct ComputedStyle(
     HashMap<
-        String,
+        PropertyName,
         StyleSource
     >
 );
 
-/// Maps sheets for each [`StyleSheetAsset`].
+/// Properties which are inherited by descendants, like on `css`, where `color` on a container also applies to text inside it.
+///
+/// Descendants which have their own value for the property don't inherit it. Since containers usually can't apply these properties,
+/// entities which don't match the query of the property aren't warned about, see [`Property::apply_system`](crate::Property::apply_system).
+pub(crate) const INHERITED_PROPERTIES: &[&str] = &["color"];
+
+/// Maps sheets for each [`StyleSheetAsset`], along with the root entity it's attached to.
+///
+/// Each sheet also keeps track of its depth on the style sheet tree, where `0` is the top-most sheet.
+/// This is used by [`StyleSheetStateBuilder::build`] to apply sheets closer to the entity last.
+/// The same sheet may be attached to many roots at different depths, so both are keyed by the root and the sheet.
 #[derive(Debug, Clone, Default, Deref, DerefMut, Resource)]
-pub struct StyleSheetStateBuilder(
-    HashMap<
-        Handle<StyleSheetAsset>,
+pub struct StyleSheetStateBuilder
+{
+    #[deref]
+    selected: HashMap<
+        (Entity, Handle<StyleSheetAsset>),
         SelectedEntities
-    >
-);
+    >,
+    sheet_order: HashMap<
+        (Entity, Handle<StyleSheetAsset>),
+        usize
+    >,
+    /// Sheet of the [`InlineStyle`](crate::prelude::InlineStyle) of selected entities, applied after all other sheets.
+    inline: HashMap<
+        Entity,
+        Handle<StyleSheetAsset>
+    >,
+    /// Whether [`build`](StyleSheetStateBuilder::build) looks for [`CascadeConflict`]s, see [`CascadeAuditEnabled`](super::CascadeAuditEnabled).
+    cascade_audit: bool,
+    /// Conflicts found by the last [`build`](StyleSheetStateBuilder::build), when auditing.
+    conflicts: Vec<CascadeConflict>,
+    /// Whether [`record_matches`](StyleSheetStateBuilder::record_matches) counts matches, see [`RuleMatchTracking`](super::RuleMatchTracking).
+    match_tracking: bool,
+    /// Entities matched by each rule of each sheet on the current pass, when tracking matches.
+    match_counts: HashMap<
+        AssetId<StyleSheetAsset>,
+        HashMap<Selector, usize>
+    >,
+    /// Buffers lent to each built state, see [`reclaim`](StyleSheetStateBuilder::reclaim).
+    scratch: StateScratch,
+}
 
-#[derive(Debug, Clone, Default, Deref, DerefMut, Resource)]
-pub struct StyleSheetState(
-    HashMap<
+/// Layer rank, specificity, importance and whether it's inline, of a declaration.
+/// Declarations with the same precedence are applied only by the order of their sheets and rules.
+type CascadePrecedence = (usize, usize, bool, bool);
+
+/// Property and rules of a conflict, used to report conflicts on many entities only once.
+type ConflictKey<'a> = (&'a str, Vec<(&'a Handle<StyleSheetAsset>, &'a Selector)>);
+
+/// Which rule won the cascade for a single property of an entity.
+///
+/// Only available with the `provenance` feature, see [`StyleSheetState::computed_property`].
+#[cfg(feature = "provenance")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleProvenance
+{
+    /// Asset path of the sheet which declared the winning rule.
+    pub sheet: String,
+    /// Selector of the winning rule, like `button.enabled`.
+    pub selector: String,
+    /// If the winning declaration was marked as `!important`.
+    pub important: bool,
+}
+
+/// Maps the [`ComputedStyle`] of each entity selected on the current frame.
+///
+/// Computed styles are filled on [`BevyCssSet::Prepare`](crate::prelude::BevyCssSet::Prepare) and cleared on
+/// [`BevyCssSet::Cleanup`](crate::prelude::BevyCssSet::Cleanup), so systems of crates with their own properties,
+/// which don't implement [`Property`](crate::prelude::Property), can read them in between, like on
+/// [`BevyCssSet::Apply`](crate::prelude::BevyCssSet::Apply), see [`iter_pending`](StyleSheetState::iter_pending).
+///
+/// The read methods [`iter_pending`](StyleSheetState::iter_pending), [`iter_parents_first`](StyleSheetState::iter_parents_first)
+/// and [`font_size`](StyleSheetState::font_size), along with [`ComputedStyle`] and [`StyleSource`], are part of the stable API
+/// and only change on major versions. Other fields are internal.
+///
+/// With the `provenance` feature, it also keeps track of which rule won the cascade for each property,
+/// which is kept across frames so it can be inspected at any time.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct StyleSheetState
+{
+    /// Computed style of each entity selected on the current frame.
+    pub(crate) computed: HashMap<
         Entity,
         ComputedStyle
-    >
-);
+    >,
+
+    /// Name of all properties applied on each entity, kept across frames so they can be reverted later.
+    applied: HashMap<
+        Entity,
+        HashSet<PropertyName>
+    >,
+
+    /// Resolved values of properties applied on each entity, kept across frames so they can be snapshotted,
+    /// see [`snapshot_styles`](crate::system::snapshot_styles).
+    values: HashMap<
+        Entity,
+        HashMap<PropertyName, PropertyValues>
+    >,
+
+    /// Properties which were applied with another value, or reverted, on each entity since changes were last taken,
+    /// see [`StyleChanged`](crate::prelude::StyleChanged).
+    changed: HashMap<
+        Entity,
+        Vec<PropertyName>
+    >,
+
+    /// Font sizes of each entity, kept across frames so unchanged ancestors can still be used to resolve `em` values.
+    font_sizes: HashMap<
+        Entity,
+        FontSizeContext
+    >,
+
+    /// Entities with computed styles, sorted so parents always come before their children, see [`iter_parents_first`](StyleSheetState::iter_parents_first).
+    order: Vec<Entity>,
+
+    #[cfg(feature = "provenance")]
+    provenance: HashMap<
+        Entity,
+        HashMap<PropertyName, StyleProvenance>
+    >,
+
+    /// Entities matched by each rule of each sheet, by selector, summed over every time it was evaluated,
+    /// kept across frames while [`RuleMatchTracking`](super::RuleMatchTracking) is enabled.
+    match_counts: HashMap<
+        AssetId<StyleSheetAsset>,
+        HashMap<String, usize>
+    >,
+
+    /// Whether [`reset_match_counts`](StyleSheetState::reset_match_counts) was called since rules were last evaluated on the whole tree.
+    match_counts_reset: bool,
+
+    /// Buffers used while computing this state, handed back to the [`StyleSheetStateBuilder`] once it's done.
+    scratch: StateScratch,
+}
+
+/// Buffers used while computing a [`StyleSheetState`], kept by the [`StyleSheetStateBuilder`] across frames,
+/// so they don't have to be allocated again every time entities are styled.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StateScratch
+{
+    /// Entities walked up from an entity, until one which depth or font size is already known.
+    chain: Vec<Entity>,
+    /// Depth of each entity on the tree, see [`StyleSheetState::sort_parents_first`].
+    depths: HashMap<Entity, usize>,
+    /// Font sizes resolved on the current pass, see [`StyleSheetState::resolve_font_sizes`].
+    font_sizes: HashMap<Entity, FontSizeContext>,
+    /// Descendants waiting to inherit a property, see [`StyleSheetState::inherit_properties`].
+    pending: Vec<Entity>,
+}
+
+impl StyleSheetState
+{
+    /// Replaces the computed styles with the given state, and merges the font sizes of the given state into the current ones.
+    ///
+    /// With the `provenance` feature, the winners of the given state are merged into the current ones.
+    pub(crate) fn replace(
+        &mut self,
+        other: StyleSheetState
+    ) {
+        self.computed = other.computed;
+        self.order = other.order;
+        self.font_sizes.extend(other.font_sizes);
+
+        for (entity, values) in other.values
+        {
+            let previous = self.values.entry(entity).or_default();
+            let mut changed: Vec<PropertyName> = values.iter()
+                .filter(|(name, value)| previous.get(*name) != Some(*value))
+                .map(|(name, _value)| name.clone())
+                .collect();
+
+            if !changed.is_empty()
+            {
+                changed.sort();
+                self.changed.entry(entity)
+                    .or_default()
+                    .extend(changed);
+            }
+
+            previous.extend(values);
+        }
+
+        for (entity, style) in self.computed.iter()
+        {
+            self.applied.entry(*entity)
+                .or_default()
+                .extend(style.keys().cloned());
+        }
+
+        #[cfg(feature = "provenance")]
+        for (entity, provenance) in other.provenance
+        {
+            self.provenance.entry(entity)
+                .or_default()
+                .extend(provenance);
+        }
+    }
+
+    /// Adds the rule matches counted by the given state to the ones counted so far, see [`unmatched_rules`](StyleSheetState::unmatched_rules).
+    pub(crate) fn merge_match_counts(
+        &mut self,
+        other: &mut StyleSheetState
+    ) {
+        for (sheet, counts) in other.match_counts.drain()
+        {
+            let sheet_counts = self.match_counts.entry(sheet).or_default();
+            for (selector, count) in counts
+            {
+                *sheet_counts.entry(selector).or_default() += count;
+            }
+        }
+    }
+
+    /// Adds the entities matched by a rule of the given sheet, evaluated on the whole tree of the sheet
+    /// by the same system which warns about unmatched rules.
+    pub(crate) fn add_rule_matches(
+        &mut self,
+        sheet: AssetId<StyleSheetAsset>,
+        selector: String,
+        count: usize
+    ) {
+        *self.match_counts.entry(sheet)
+            .or_default()
+            .entry(selector)
+            .or_default() += count;
+    }
+
+    /// Returns whether [`reset_match_counts`](StyleSheetState::reset_match_counts) was called since it was last checked,
+    /// so rules are evaluated on the whole tree again.
+    pub(crate) fn take_match_counts_reset(
+        &mut self
+    ) -> bool {
+        std::mem::take(&mut self.match_counts_reset)
+    }
+
+    /// Returns the selector of every rule which didn't match any entity on all the times it was evaluated,
+    /// sorted and listed once, like `[".nonexistent"]`. Only available while [`RuleMatchTracking`](super::RuleMatchTracking) is enabled.
+    ///
+    /// Rules are evaluated on the whole tree of their sheet like by
+    /// [`with_unmatched_rule_warnings`](crate::prelude::BevyCssPlugin::with_unmatched_rule_warnings), whenever the sheet changes or loads,
+    /// and matches found while styling are added to them, so a rule is no longer listed once it matches any entity.
+    /// Rules with pseudo-classes, or matching a component variant, aren't listed, and neither are rules of `@when` and `@supports`
+    /// blocks which don't hold. Removed rules are kept until [`reset_match_counts`](StyleSheetState::reset_match_counts) is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use tomt_bevycss::{prelude::*, property::StyleSheetState};
+    /// fn report_dead_css(
+    ///     state: Res<StyleSheetState>
+    /// ) {
+    ///     for selector in state.unmatched_rules()
+    ///     {
+    ///         info!("Rule '{selector}' never matched any entity");
+    ///     }
+    /// }
+    /// ```
+    pub fn unmatched_rules(
+        &self
+    ) -> Vec<String> {
+        let mut unmatched: Vec<String> = self.match_counts.values()
+            .flat_map(|counts| counts.iter())
+            .filter(|(_selector, count)| **count == 0)
+            .map(|(selector, _count)| selector.clone())
+            .collect();
+
+        unmatched.sort();
+        unmatched.dedup();
+        unmatched
+    }
+
+    /// Forgets all rule matches counted so far, so [`unmatched_rules`](StyleSheetState::unmatched_rules) only considers
+    /// the ones counted from now on, like after the UI changed. Rules are evaluated on the whole tree again on the next frame.
+    pub fn reset_match_counts(
+        &mut self
+    ) {
+        self.match_counts.clear();
+        self.match_counts_reset = true;
+    }
+
+    /// Iterates over the computed styles of each entity, always visiting parents before their children.
+    ///
+    /// Entities are visited breadth-first, from the top-most entity of the tree down to the leaves,
+    /// so when a style is applied on an entity, it was already applied on all of its ancestors styled on the same frame.
+    pub fn iter_parents_first(
+        &self
+    ) -> impl Iterator<Item = (&Entity, &ComputedStyle)> {
+        self.order.iter()
+            .filter_map(|entity| self.computed.get_key_value(entity))
+    }
+
+    /// Iterates over each entity with the given property pending on the current frame, along with the selector of the rule
+    /// which won the cascade and its resolved values, where `em` and `rem` values are already converted to pixels.
+    ///
+    /// Entities are visited parents first, like on [`iter_parents_first`](StyleSheetState::iter_parents_first),
+    /// and only entities selected on the current frame are visited, so unchanged entities aren't styled again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use tomt_bevycss::{prelude::*, property::StyleSheetState};
+    /// // Registered with `app.add_systems(Update, apply_outline_glow.in_set(BevyCssSet::Apply))`
+    /// fn apply_outline_glow(
+    ///     state: Res<StyleSheetState>
+    /// ) {
+    ///     for (entity, selector, values) in state.iter_pending("outline-glow")
+    ///     {
+    ///         info!("Entity {entity:?} matched '{selector}' with glow {:?}", values.f32());
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_pending<'a>(
+        &'a self,
+        property: &'a str
+    ) -> impl Iterator<Item = (Entity, &'a Selector, &'a PropertyValues)> + 'a {
+        self.iter_parents_first()
+            .filter_map(move |(entity, style)| {
+                let source = style.get(property)?;
+                let values = self.values.get(entity)?.get(property)?;
+                Some((*entity, &source.selector, values))
+            })
+    }
+
+    /// Sorts the entities of computed styles by their depth on the tree, so parents are visited before their children
+    /// by [`iter_parents_first`](StyleSheetState::iter_parents_first). `parent_of` returns the parent of the given entity, if any.
+    ///
+    /// This must be called once all computed styles are known, since entities computed later aren't visited.
+    pub(crate) fn sort_parents_first(
+        &mut self,
+        parent_of: impl Fn(Entity) -> Option<Entity>
+    ) {
+        let StateScratch { chain, depths, .. } = &mut self.scratch;
+        depths.clear();
+
+        for entity in self.computed.keys()
+        {
+            // Walk up until an entity with a known depth, or the root, then resolve back down
+            chain.clear();
+            chain.push(*entity);
+            let mut depth = 0;
+            while let Some(current) = chain.last().copied()
+            {
+                if let Some(known) = depths.get(&current)
+                {
+                    chain.pop();
+                    depth = known + 1;
+                    break;
+                }
+
+                match parent_of(current)
+                {
+                    Some(parent) => chain.push(parent),
+                    None => break,
+                }
+            }
+
+            while let Some(current) = chain.pop()
+            {
+                depths.insert(current, depth);
+                depth += 1;
+            }
+        }
+
+        self.order.clear();
+        self.order.extend(self.computed.keys().copied());
+        self.order.sort_by_key(|entity| (depths[entity], *entity));
+    }
+
+    /// Removes and returns the name of all properties applied on the given entity so far.
+    pub(crate) fn take_applied(
+        &mut self,
+        entity: Entity
+    ) -> HashSet<PropertyName> {
+        #[cfg(feature = "provenance")]
+        self.provenance.remove(&entity);
+
+        self.font_sizes.remove(&entity);
+        self.values.remove(&entity);
+        self.applied.remove(&entity).unwrap_or_default()
+    }
+
+    /// Forgets everything kept across frames about entities which no longer exist, according to `exists`,
+    /// like entities despawned without ever having a [`Node`](bevy::prelude::Node).
+    pub(crate) fn forget_despawned(
+        &mut self,
+        exists: impl Fn(Entity) -> bool
+    ) {
+        #[cfg(feature = "provenance")]
+        self.provenance.retain(|entity, _provenance| exists(*entity));
+
+        self.font_sizes.retain(|entity, _font_size| exists(*entity));
+        self.values.retain(|entity, _values| exists(*entity));
+        self.changed.retain(|entity, _changed| exists(*entity));
+        self.applied.retain(|entity, _applied| exists(*entity));
+    }
+
+    /// Name of all properties applied on the given entity so far.
+    pub(crate) fn applied(
+        &self,
+        entity: Entity
+    ) -> Option<&HashSet<PropertyName>> {
+        self.applied.get(&entity)
+    }
+
+    /// Forgets the given properties applied on the given entity, like after they are reverted because no rule applies them anymore.
+    pub(crate) fn forget_applied(
+        &mut self,
+        entity: Entity,
+        properties: &[PropertyName]
+    ) {
+        if !properties.is_empty()
+        {
+            self.mark_changed(entity, properties.iter().cloned());
+        }
+
+        for property in properties
+        {
+            #[cfg(feature = "provenance")]
+            if let Some(provenance) = self.provenance.get_mut(&entity)
+            {
+                provenance.remove(property);
+            }
+
+            if let Some(values) = self.values.get_mut(&entity)
+            {
+                values.remove(property);
+            }
+
+            if let Some(applied) = self.applied.get_mut(&entity)
+            {
+                applied.remove(property);
+            }
+        }
+    }
+
+    /// Records the given properties as changed on the given entity, like after they are reverted by [`clear_styles`](crate::system::clear_styles).
+    pub(crate) fn mark_changed(
+        &mut self,
+        entity: Entity,
+        properties: impl IntoIterator<Item = PropertyName>
+    ) {
+        let changed = self.changed.entry(entity).or_default();
+        changed.extend(properties);
+        changed.sort();
+        changed.dedup();
+    }
+
+    /// Checks if any property changed since [`take_changed`](StyleSheetState::take_changed) was last called.
+    pub(crate) fn has_pending_changes(
+        &self
+    ) -> bool {
+        !self.changed.is_empty()
+    }
+
+    /// Removes and returns the properties which changed on each entity since this was last called, see [`StyleChanged`](crate::prelude::StyleChanged).
+    pub(crate) fn take_changed(
+        &mut self
+    ) -> HashMap<Entity, Vec<PropertyName>> {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Resolved values of all properties applied on the given entity so far, by property name.
+    pub(crate) fn applied_values(
+        &self,
+        entity: Entity
+    ) -> Option<&HashMap<PropertyName, PropertyValues>> {
+        self.values.get(&entity)
+    }
+
+    /// Replaces all properties applied on the given entity with the given ones, like when they are restored from a snapshot.
+    pub(crate) fn set_applied_values(
+        &mut self,
+        entity: Entity,
+        values: HashMap<PropertyName, PropertyValues>
+    ) {
+        self.applied.insert(entity, values.keys().cloned().collect());
+        self.values.insert(entity, values);
+    }
+
+    /// Resolves the values of each computed property, as they are applied by [`Property::apply_system`](crate::Property::apply_system),
+    /// so they can be applied again later, even without the sheet which declared them.
+    ///
+    /// This must be called after [`resolve_font_sizes`](StyleSheetState::resolve_font_sizes), since values relative to font size are resolved too.
+    pub(crate) fn resolve_values(
+        &mut self,
+        assets: &Assets<StyleSheetAsset>,
+        bare_number_policy: BareNumberPolicy
+    ) {
+        for (entity, style) in self.computed.iter()
+        {
+            let context = self.font_sizes.get(entity).copied();
+            let resolved = self.values.entry(*entity).or_default();
+
+            for (name, source) in style.iter()
+            {
+                let values = match assets.get(&source.stylesheet)
+                    .and_then(|sheet| sheet.get_property_value(&source.selector, name))
+                {
+                    Some(values) => values,
+                    None => continue,
+                };
+
+                let values = match (&**name, context)
+                {
+                    ("font-size", Some(context)) => values.resolve_font_size(context.parent, context.root),
+                    (_, Some(context)) => values.resolve_font_relative(context.own, context.root),
+                    (_, None) => values.clone(),
+                };
+
+                resolved.insert(name.clone(), values.with_bare_number_policy(bare_number_policy));
+            }
+        }
+    }
+
+    /// Returns the font sizes which `em` and `rem` values on the given entity are resolved against.
+    pub fn font_size(
+        &self,
+        entity: Entity
+    ) -> Option<FontSizeContext> {
+        self.font_sizes.get(&entity).copied()
+    }
+
+    /// Computes the font size of each entity top-down, resolving `em` and `rem` values on `font-size` property.
+    /// `parent_of` returns the parent of the given entity, if any, and `previous` is used to find the font size of
+    /// parents which weren't selected on the current frame.
+    ///
+    /// Entities without a `font-size` property inherit the font size of their parent, and entities without a parent
+    /// use `root_font_size`. Since there is nothing to be relative to, `em` and `rem` aren't allowed on entities without a parent,
+    /// while percentages on those entities are relative to `root_font_size`.
+    pub(crate) fn resolve_font_sizes(
+        &mut self,
+        assets: &Assets<StyleSheetAsset>,
+        root_font_size: f32,
+        previous: Option<&StyleSheetState>,
+        parent_of: impl Fn(Entity) -> Option<Entity>
+    ) {
+        // Taken while resolving, since font sizes are computed from the state itself
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let StateScratch { chain, font_sizes: resolved, .. } = &mut scratch;
+        resolved.clear();
+        let mut rejected = Vec::new();
+
+        for entity in self.computed.keys()
+        {
+            // Walk up until an entity with a known font size, then resolve back down
+            chain.clear();
+            chain.push(*entity);
+            let mut parent_size = root_font_size;
+            while let Some(current) = chain.last().copied()
+            {
+                if let Some(context) = resolved.get(&current)
+                {
+                    chain.pop();
+                    parent_size = context.own;
+                    break;
+                }
+
+                match parent_of(current)
+                {
+                    Some(parent) if self.computed.contains_key(&parent) => chain.push(parent),
+                    Some(parent) => {
+                        parent_size = previous.and_then(|previous| previous.font_size(parent))
+                            .map_or(root_font_size, |context| context.own);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+
+            while let Some(current) = chain.pop()
+            {
+                let own = self.computed_font_size(assets, current, parent_size, root_font_size, &parent_of)
+                    .unwrap_or_else(|| {
+                        rejected.push(current);
+                        root_font_size
+                    });
+                resolved.insert(current, FontSizeContext
+                    {
+                        root: root_font_size,
+                        parent: parent_size,
+                        own,
+                    });
+                parent_size = own;
+            }
+        }
+
+        self.font_sizes.extend(resolved.drain());
+        self.scratch = scratch;
+
+        // Rejected values are reported once resolving, so they must not be applied either
+        for entity in rejected
+        {
+            if let Some(style) = self.computed.get_mut(&entity)
+            {
+                style.remove("font-size");
+            }
+        }
+    }
+
+    /// Computes the font size of the given entity, given the font size of its parent.
+    ///
+    /// Returns `None` if the declared font size is invalid, like a relative one on an entity without parent.
+    fn computed_font_size(
+        &self,
+        assets: &Assets<StyleSheetAsset>,
+        entity: Entity,
+        parent_size: f32,
+        root_font_size: f32,
+        parent_of: &impl Fn(Entity) -> Option<Entity>
+    ) -> Option<f32> {
+        let values = self.computed.get(&entity)
+            .and_then(|style| style.get("font-size"))
+            .and_then(|source| assets.get(&source.stylesheet)?
+                .get_property_value(&source.selector, "font-size")
+            );
+
+        let values = match values
+        {
+            Some(values) => values,
+            None => return Some(parent_size),
+        };
+
+        if values.is_font_relative() && parent_of(entity).is_none()
+        {
+            error!("Relative font-size isn't allowed on entity {entity:?}, since it has no parent to be relative to");
+            return None;
+        }
+
+        Some(values.resolve_font_size(parent_size, root_font_size)
+            .f32()
+            .unwrap_or(parent_size)
+        )
+    }
+
+    /// Removes from computed styles the properties listed on the [`StyleOverride`] of each entity, if any,
+    /// so they aren't applied. `override_of` returns the override of the given entity.
+    pub(crate) fn remove_overridden<'a>(
+        &mut self,
+        override_of: impl Fn(Entity) -> Option<&'a StyleOverride>
+    ) {
+        for (entity, style) in self.computed.iter_mut()
+        {
+            if let Some(style_override) = override_of(*entity)
+            {
+                style.retain(|property, _source| !style_override.overrides(property));
+            }
+        }
+    }
+
+    /// Propagates [`INHERITED_PROPERTIES`] top-down, from each entity to all of its descendants which don't set
+    /// the property themselves. `children_of` returns the direct children of the given entity.
+    ///
+    /// Only descendants for which `accepts` returns `true`, like the ones with a [`Text`](bevy::prelude::Text), take the inherited value,
+    /// while the others only pass it on to their own descendants.
+    ///
+    /// This must be called after all entities have been matched, since only then is known which ones set the property themselves.
+    pub(crate) fn inherit_properties<'a>(
+        &mut self,
+        children_of: impl Fn(Entity) -> Option<&'a [Entity]>,
+        accepts: impl Fn(Entity) -> bool
+    ) {
+        let mut pending = std::mem::take(&mut self.scratch.pending);
+
+        for property in INHERITED_PROPERTIES
+        {
+            let owners: HashMap<Entity, (PropertyName, StyleSource)> = self.computed.iter()
+                .filter_map(|(entity, style)| style.get_key_value(*property)
+                    .map(|(name, source)| (*entity, (name.clone(), source.clone())))
+                )
+                .collect();
+
+            for (owner, (name, source)) in owners.iter()
+            {
+                pending.clear();
+                pending.extend_from_slice(children_of(*owner).unwrap_or_default());
+
+                while let Some(entity) = pending.pop()
+                {
+                    // Explicit values override the inherited one, also for their own descendants
+                    if owners.contains_key(&entity)
+                    {
+                        continue;
+                    }
+
+                    if accepts(entity)
+                    {
+                        self.computed.entry(entity)
+                            .or_default()
+                            .insert(name.clone(), source.clone());
+
+                        #[cfg(feature = "provenance")]
+                        if let Some(provenance) = self.provenance.get(owner).and_then(|provenance| provenance.get(name)).cloned()
+                        {
+                            self.provenance.entry(entity)
+                                .or_default()
+                                .insert(name.clone(), provenance);
+                        }
+                    }
+
+                    if let Some(children) = children_of(entity)
+                    {
+                        pending.extend_from_slice(children);
+                    }
+                }
+            }
+        }
+
+        self.scratch.pending = pending;
+    }
+
+    /// Returns which rule won the cascade for the given property on the given entity,
+    /// on the last time a style sheet was applied on it.
+    #[cfg(feature = "provenance")]
+    pub fn computed_property(
+        &self,
+        entity: Entity,
+        property: &str
+    ) -> Option<&StyleProvenance> {
+        self.provenance.get(&entity)
+            .and_then(|provenance| provenance.get(property))
+    }
+}
 
 impl StyleSheetStateBuilder
 {
+    /// Removes all selected entities and sheets, keeping the allocated buffers, so the builder can be reused on the next frame.
+    pub(crate) fn clear(
+        &mut self
+    ) {
+        self.selected.clear();
+        self.sheet_order.clear();
+        self.inline.clear();
+        self.conflicts.clear();
+        self.match_counts.clear();
+    }
+
+    /// Takes back the buffers lent to a state built by [`build`](StyleSheetStateBuilder::build), once it's done being computed.
+    pub(crate) fn reclaim(
+        &mut self,
+        state: &mut StyleSheetState
+    ) {
+        self.scratch = std::mem::take(&mut state.scratch);
+    }
+
+    /// Sets the depth of the given sheet, attached to the given root entity, on the style sheet tree.
+    ///
+    /// Sheets with greater depth are applied later, so their properties override the ones from ancestor sheets.
+    pub(crate) fn set_sheet_order(
+        &mut self,
+        root: Entity,
+        handle: &Handle<StyleSheetAsset>,
+        order: usize
+    ) {
+        self.sheet_order.insert((root, handle.clone()), order);
+    }
+
+    /// Sets the sheet of the [`InlineStyle`](crate::prelude::InlineStyle) of the given entity.
+    pub(crate) fn set_inline(
+        &mut self,
+        entity: Entity,
+        handle: Handle<StyleSheetAsset>
+    ) {
+        self.inline.insert(entity, handle);
+    }
+
+    /// Enables looking for [`CascadeConflict`]s while building the state, which can be taken with
+    /// [`take_conflicts`](StyleSheetStateBuilder::take_conflicts).
+    pub(crate) fn set_cascade_audit(
+        &mut self,
+        enabled: bool
+    ) {
+        self.cascade_audit = enabled;
+    }
+
+    /// Enables counting the entities matched by each rule, which are passed to the built state, see [`record_matches`](StyleSheetStateBuilder::record_matches).
+    pub(crate) fn set_match_tracking(
+        &mut self,
+        enabled: bool
+    ) {
+        self.match_tracking = enabled;
+    }
+
+    /// Counts the entities matched by a rule of the given sheet, if tracking matches.
+    ///
+    /// Styling passes only evaluate rules on the entities which changed, so rules which matched nothing aren't counted,
+    /// they are only listed once they are evaluated on the whole tree, see [`add_rule_matches`](StyleSheetState::add_rule_matches).
+    pub(crate) fn record_matches(
+        &mut self,
+        sheet: AssetId<StyleSheetAsset>,
+        selector: &Selector,
+        count: usize
+    ) {
+        if !self.match_tracking || count == 0
+        {
+            return;
+        }
+
+        *self.match_counts.entry(sheet)
+            .or_default()
+            .entry(selector.clone())
+            .or_default() += count;
+    }
+
+    /// Takes the conflicts found by the last [`build`](StyleSheetStateBuilder::build), if the cascade audit is enabled.
+    pub(crate) fn take_conflicts(
+        &mut self
+    ) -> Vec<CascadeConflict> {
+        std::mem::take(&mut self.conflicts)
+    }
+
+    /// All entities selected by any rule.
+    pub(crate) fn entities(
+        &self
+    ) -> HashSet<Entity> {
+        self.selected.values()
+            .flat_map(|selected| selected.values())
+            .flat_map(|entities| entities.iter().copied())
+            .collect()
+    }
+
+    /// Keeps only the selected entities which match the given predicate.
+    pub(crate) fn retain_entities(
+        &mut self,
+        predicate: impl Fn(&Entity) -> bool
+    ) {
+        for selected in self.selected.values_mut()
+        {
+            for entities in selected.values_mut()
+            {
+                entities.retain(|entity| predicate(entity));
+            }
+        }
+    }
+
+    /// Ranks the cascade layers of all selected sheets, on the order they were first declared.
+    ///
+    /// Sheets are visited from ancestor sheets down, so a layer declared by an ancestor sheet keeps its position
+    /// when it's used again by a sheet closer to the entity.
+    fn layer_ranks<'a>(
+        &self,
+        assets: &'a Assets<StyleSheetAsset>
+    ) -> HashMap<&'a str, usize> {
+        let mut sheets: Vec<_> = self.selected.keys()
+            .filter_map(|key| assets.get(&key.1).map(|sheet| (self.sheet_order.get(key), sheet)))
+            .collect();
+        sheets.sort_by_key(|(order, _sheet)| order.copied().unwrap_or_default());
+
+        let mut ranks = HashMap::new();
+        for layer in sheets.iter().flat_map(|(_order, sheet)| sheet.layers())
+        {
+            let rank = ranks.len();
+            ranks.entry(layer.as_str()).or_insert(rank);
+        }
+
+        ranks
+    }
+
+    /// Merges all selected rules into a single [`ComputedStyle`] for each entity.
+    ///
+    /// When more than one rule sets the same property on the same entity, the declaration with the highest precedence wins:
+    /// 1. `!important` declarations of the [`InlineStyle`](crate::prelude::InlineStyle).
+    /// 2. `!important` declarations of sheet rules.
+    /// 3. Declarations of the [`InlineStyle`](crate::prelude::InlineStyle).
+    /// 4. Declarations of sheet rules.
+    ///
+    /// Between declarations of the same precedence, the last one applied wins. Rules are applied in the following order:
+    /// 1. Cascade layer, rules on layers declared first come first and rules outside of any layer last.
+    ///    Layers are shared by all sheets, so a layer is declared by the first sheet which names it, from ancestor sheets down.
+    ///    The layer order is inverted for `!important` declarations, so the ones on layers declared first win.
+    /// 2. Sheet order, ancestor sheets first and sheets closer to the entity last.
+    /// 3. Selector specificity, rules with fewer selector elements first.
+    /// 4. File order, rules which appears first on the sheet first.
+    ///
+    /// The [`InlineStyle`](crate::prelude::InlineStyle) of each entity is applied after all of them.
+    ///
+    /// When auditing, declarations which override another one with the same layer, specificity and importance,
+    /// and a different value, are kept as [`CascadeConflict`]s, see [`take_conflicts`](StyleSheetStateBuilder::take_conflicts).
     pub(crate) fn build(
         &mut self,
         assets: &Assets<StyleSheetAsset>
     ) -> StyleSheetState {
-        let mut result = StyleSheetState::default();
+        let mut result = StyleSheetState{
+            scratch: std::mem::take(&mut self.scratch),
+            ..Default::default()
+        };
+
+        // Selectors are shared by rules with the same text, so their matches are summed
+        for (sheet, counts) in self.match_counts.drain()
+        {
+            let sheet_counts = result.match_counts.entry(sheet).or_default();
+            for (selector, count) in counts
+            {
+                *sheet_counts.entry(selector.to_string()).or_default() += count;
+            }
+        }
 
-        for (handle, selected) in self.iter()
+        // Invert list of entities for each selector, into a list of selectors for each entity
+        let mut inverted = HashMap::<Entity, Vec<(usize, &Handle<StyleSheetAsset>, &Selector)>>::new();
+        for (key, selected) in self.selected.iter()
         {
-            if let Some(sheet) = assets.get(handle)
+            let (_root, handle) = key;
+            if assets.get(handle).is_none()
             {
-                // Invert list of entities for each selector, into a list of selectors for each entity
-                let mut inverted = HashMap::<Entity, Vec<Selector>>::new();
-                for (selector, entities) in selected.iter()
+                continue;
+            }
+
+            let order = self.sheet_order.get(key)
+                .copied()
+                .unwrap_or_default();
+
+            for (selector, entities) in selected.iter()
+            {
+                for entity in entities.iter()
                 {
-                    for entity in entities.iter()
-                    {
-                        inverted.entry(*entity)
-                            .or_default()
-                            .push(selector.clone());
-                    }
+                    inverted.entry(*entity)
+                        .or_default()
+                        .push((order, handle, selector));
                 }
+            }
+        }
 
-                // "Pre-apply" the selectors to get a list of properties without duplicates
-                for (entity, mut selectors) in inverted
+        for entity in self.inline.keys()
+        {
+            inverted.entry(*entity).or_default();
+        }
+
+        let layer_ranks = self.layer_ranks(assets);
+        let layer_rank = |handle: &Handle<StyleSheetAsset>, rule: &StyleRule| assets.get(handle)
+            .and_then(|sheet| sheet.layer_of(rule))
+            .and_then(|layer| layer_ranks.get(layer).copied())
+            // Rules outside of any layer are applied last
+            .unwrap_or(usize::MAX);
+
+        let mut conflicts = HashMap::<ConflictKey, Vec<Entity>>::new();
+        // Rules selecting each entity, reused by all of them
+        let mut sources = Vec::new();
+
+        // "Pre-apply" the selectors to get a list of properties without duplicates
+        for (entity, selected) in inverted
+        {
+            let style = result.computed.entry(entity).or_default();
+
+            sources.clear();
+            sources.extend(selected.into_iter()
+                .filter_map(|(order, handle, selector)|
+                    assets.get(handle)
+                        .and_then(|sheet| sheet.get_rule(selector))
+                        .map(|rule| (order, handle, selector, rule))
+                )
+            );
+
+            sources.sort_by(|(lhs_order, lhs_handle, lhs, lhs_rule), (rhs_order, rhs_handle, rhs, rhs_rule)|
+                layer_rank(lhs_handle, lhs_rule).cmp(&layer_rank(rhs_handle, rhs_rule))
+                    .then_with(|| lhs_order.cmp(rhs_order))
+                    .then_with(|| lhs.cmp(rhs))
+            );
+
+            let inline_handle = self.inline.get(&entity);
+            let inline = inline_handle
+                .and_then(|handle| assets.get(handle).map(|sheet| (handle, sheet)))
+                .and_then(|(handle, sheet)| sheet.iter().next().map(|rule| (usize::MAX, handle, &rule.selector, rule)));
+            sources.extend(inline);
+
+            // Importance comes first, then inline styles over sheet rules, then layers declared first for `!important` declarations
+            let mut winners = HashMap::<&str, (bool, bool, Reverse<usize>)>::new();
+
+            // Declarations which won so far for each property, along with their precedence, when auditing
+            let mut ties = HashMap::<&str, (CascadePrecedence, Vec<usize>)>::new();
+
+            for (index, (_order, handle, selector, rule)) in sources.iter().enumerate()
+            {
+                let is_inline = Some(*handle) == inline_handle;
+
+                for prop in rule.properties.keys()
                 {
-                    let style = result.entry(entity).or_default();
+                    let important = rule.is_important(prop);
+                    let layer = match important
+                    {
+                        true => layer_rank(handle, rule),
+                        false => 0,
+                    };
+                    let precedence = (important, is_inline, Reverse(layer));
+                    if winners.get(&**prop).is_some_and(|winner| *winner > precedence)
+                    {
+                        continue;
+                    }
+                    winners.insert(&**prop, precedence);
+
+                    style.insert(prop.clone(), StyleSource
+                        {
+                            stylesheet: (*handle).clone(),
+                            selector: (*selector).clone(),
+                            important,
+                        });
 
-                    selectors.sort();
-                    for selector in selectors.iter()
+                    if self.cascade_audit
                     {
-                        for prop in sheet.get_property_names(selector).unwrap_or_default()
+                        let declared = (layer_rank(handle, rule), selector.elements().len(), important, is_inline);
+                        match ties.get_mut(&**prop)
                         {
-                            style.insert(prop, StyleSource
-                                {
-                                    styleheet: handle.clone(),
-                                    selector: selector.clone(),
-                                });
+                            Some((precedence, indices)) if *precedence == declared => indices.push(index),
+                            _ => {
+                                ties.insert(&**prop, (declared, vec![index]));
+                            }
                         }
                     }
                 }
             }
+
+            for (prop, (_precedence, indices)) in ties
+            {
+                let value_of = |index: &usize| sources[*index].3.properties.get(prop);
+                let winner = indices.last().and_then(value_of);
+
+                // Rules declaring the same value don't contradict each other
+                if indices.iter().all(|index| value_of(index) == winner)
+                {
+                    continue;
+                }
+
+                let rules = indices.iter()
+                    .map(|index| (sources[*index].1, sources[*index].2))
+                    .collect();
+                conflicts.entry((prop, rules)).or_default().push(entity);
+            }
+
+            #[cfg(feature = "provenance")]
+            for (prop, source) in style.iter()
+            {
+                let sheet = assets.get(&source.stylesheet)
+                    .map(|sheet| sheet.path().to_string())
+                    .unwrap_or_default();
+
+                result.provenance.entry(entity)
+                    .or_default()
+                    .insert(prop.clone(), StyleProvenance
+                    {
+                        sheet,
+                        selector: source.selector.to_string(),
+                        important: source.important,
+                    });
+            }
         }
 
+        let mut conflicts: Vec<CascadeConflict> = conflicts.into_iter()
+            .filter_map(|((prop, rules), mut entities)|
+            {
+                let declarations = rules.iter()
+                    .map(|(handle, selector)|
+                    {
+                        let sheet = assets.get(*handle)?;
+                        Some(CascadeDeclaration
+                            {
+                                sheet: sheet.path().to_string(),
+                                selector: selector.to_string(),
+                                location: sheet.get_rule(selector)
+                                    .map(|rule| rule.property_location(prop).unwrap_or(rule.location))?,
+                            })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                let (handle, selector) = rules.last()?;
+                let value = assets.get(*handle)?
+                    .get_property_value(selector, prop)?
+                    .clone();
+
+                entities.sort();
+                Some(CascadeConflict
+                    {
+                        property: prop.to_string(),
+                        entities,
+                        declarations,
+                        value,
+                    })
+            })
+            .collect();
+
+        conflicts.sort_by(|lhs, rhs| lhs.property.cmp(&rhs.property)
+            .then_with(|| lhs.winner().map(|winner| (&winner.sheet, winner.location))
+                .cmp(&rhs.winner().map(|winner| (&winner.sheet, winner.location)))
+            )
+        );
+        self.conflicts = conflicts;
+
         result
     }
 }
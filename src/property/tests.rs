@@ -0,0 +1,1199 @@
+use super::*;
+use crate::stylesheet::StyleSheetAsset;
+
+use bevy::{
+    prelude::{Assets, Entity, Handle},
+    ui::{UiRect, Val},
+};
+
+/// Root entity the sheets selecting entities on these tests are attached to.
+const ROOT: Entity = Entity::PLACEHOLDER;
+
+/// Selects the given entity with every rule on the sheet, as if all selectors matched it.
+fn select_all(
+    builder: &mut StyleSheetStateBuilder,
+    assets: &Assets<StyleSheetAsset>,
+    handle: &Handle<StyleSheetAsset>,
+    entity: Entity
+) {
+    let selected = builder.entry((ROOT, handle.clone())).or_default();
+    for rule in assets.get(handle).unwrap().iter()
+    {
+        selected.entry(rule.selector.clone())
+            .or_default()
+            .push(entity);
+    }
+}
+
+/// Returns the `width` value which would be applied on the given entity.
+fn computed_width(
+    state: &StyleSheetState,
+    assets: &Assets<StyleSheetAsset>,
+    entity: Entity
+) -> Option<Val> {
+    let source = state.computed.get(&entity)?.get("width")?;
+    assets.get(&source.stylesheet)?
+        .get_property_value(&source.selector, "width")?
+        .val()
+}
+
+#[test]
+fn later_rule_with_same_selector_wins(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", "a { width: 1px; } a { width: 2px; }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn builder_is_reused_across_passes(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", "a { width: 1px; } b { width: 2px; }"));
+    let (first, second) = (Entity::from_raw(1), Entity::from_raw(2));
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, first);
+    let mut state = builder.build(&assets);
+    builder.reclaim(&mut state);
+    assert_eq!(computed_width(&state, &assets, first), Some(Val::Px(2.0)));
+
+    builder.clear();
+    assert!(builder.is_empty(), "Should forget entities selected on the last pass");
+
+    select_all(&mut builder, &assets, &handle, second);
+    let state = builder.build(&assets);
+    assert!(state.computed.get(&first).is_none());
+    assert_eq!(computed_width(&state, &assets, second), Some(Val::Px(2.0)));
+
+    // Property names are shared with the rules which declared them
+    let (computed_name, _source) = state.computed.get(&second).unwrap().get_key_value("width").unwrap();
+    let (declared_name, _values) = assets.get(&handle).unwrap().iter().last().unwrap().properties.get_key_value("width").unwrap();
+    assert!(std::sync::Arc::ptr_eq(computed_name, declared_name));
+}
+
+#[test]
+fn later_rule_with_same_specificity_wins(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", ".b { width: 1px; } .a { width: 2px; }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn closer_sheet_wins(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let parent = assets.add(StyleSheetAsset::parse("", "a { width: 1px; }"));
+    let child = assets.add(StyleSheetAsset::parse("", "a { width: 2px; }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &child, entity);
+    select_all(&mut builder, &assets, &parent, entity);
+    builder.set_sheet_order(ROOT, &parent, 0);
+    builder.set_sheet_order(ROOT, &child, 1);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn cascade_audit_reports_conflicting_rules(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let base = assets.add(StyleSheetAsset::parse("base.css", ".panel { width: 200px; height: 10px; }\n.panel.wide { width: 300px; }"));
+    let theme = assets.add(StyleSheetAsset::parse("theme.css", "\n.panel { width: 240px; height: 10px; }"));
+    let panel = Entity::from_raw(1);
+    let other = Entity::from_raw(2);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    builder.set_cascade_audit(true);
+    for (handle, entity) in [(&base, panel), (&theme, panel), (&base, other), (&theme, other)]
+    {
+        builder.entry((ROOT, handle.clone())).or_default()
+            .entry(assets.get(handle).unwrap().iter().next().unwrap().selector.clone())
+            .or_default()
+            .push(entity);
+    }
+    builder.set_sheet_order(ROOT, &base, 0);
+    builder.set_sheet_order(ROOT, &theme, 1);
+    builder.build(&assets);
+
+    let conflicts = builder.take_conflicts();
+    assert_eq!(conflicts.len(), 1, "Rules declaring the same value shouldn't conflict");
+
+    let conflict = &conflicts[0];
+    assert_eq!(conflict.property, "width");
+    assert_eq!(conflict.entities, [panel, other], "Should report all entities at once");
+    assert_eq!(conflict.value, PropertyValues::parse("240px").unwrap());
+
+    let declarations: Vec<_> = conflict.declarations.iter()
+        .map(|declaration| (declaration.sheet.as_str(), declaration.location.line))
+        .collect();
+    assert_eq!(declarations, [("base.css", 1), ("theme.css", 2)]);
+}
+
+#[test]
+fn cascade_audit_reports_location_of_merged_declarations(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let mut base = StyleSheetAsset::parse("base.css", ".panel { width: 200px; }\n.other { left: 1px; }\n.panel { height: 10px; }");
+    base.deduplicate();
+    assert_eq!(base.rule_count(), 2);
+    let base = assets.add(base);
+    let theme = assets.add(StyleSheetAsset::parse("theme.css", ".panel { width: 240px; }"));
+    let panel = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    builder.set_cascade_audit(true);
+    select_all(&mut builder, &assets, &base, panel);
+    select_all(&mut builder, &assets, &theme, panel);
+    builder.set_sheet_order(ROOT, &base, 0);
+    builder.set_sheet_order(ROOT, &theme, 1);
+    builder.build(&assets);
+
+    let conflicts = builder.take_conflicts();
+    let lines: Vec<_> = conflicts[0].declarations.iter()
+        .map(|declaration| (declaration.sheet.as_str(), declaration.location.line))
+        .collect();
+    assert_eq!(lines, [("base.css", 1), ("theme.css", 1)], "Should keep the line of the merged declaration");
+}
+
+#[test]
+fn cascade_audit_ignores_rules_with_different_specificity(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", ".a { width: 1px; } .a.b { width: 2px; } @layer base { .b { width: 3px; } }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    builder.set_cascade_audit(true);
+    select_all(&mut builder, &assets, &handle, entity);
+    builder.build(&assets);
+
+    assert!(builder.take_conflicts().is_empty());
+}
+
+#[test]
+fn important_rule_wins_over_specificity(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", ".a { width: 1px !important; } .a.b { width: 2px; }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(1.0)));
+}
+
+#[test]
+fn later_layer_wins_over_specificity(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", r#"
+        @layer base, theme;
+        @layer theme { .a { width: 2px; } }
+        @layer base { .a.b { width: 1px; } }
+    "#));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn unlayered_rule_wins_over_layers(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", "a { width: 2px; } @layer theme { a.b { width: 1px; } }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn layer_order_is_shared_across_sheets(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let parent = assets.add(StyleSheetAsset::parse("", r#"
+        @layer base, overrides;
+        @layer overrides { .a { width: 2px; } }
+    "#));
+    let child = assets.add(StyleSheetAsset::parse("", "@layer base { .a.b { width: 1px; } }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &parent, entity);
+    select_all(&mut builder, &assets, &child, entity);
+    builder.set_sheet_order(ROOT, &parent, 0);
+    builder.set_sheet_order(ROOT, &child, 1);
+    let state = builder.build(&assets);
+
+    assert_eq!(
+        computed_width(&state, &assets, entity),
+        Some(Val::Px(2.0)),
+        "Later layer should win over a closer sheet"
+    );
+}
+
+#[test]
+fn unlayered_rule_wins_over_layers_of_other_sheets(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let parent = assets.add(StyleSheetAsset::parse("", ".a { width: 2px; }"));
+    let child = assets.add(StyleSheetAsset::parse("", "@layer overrides { .a.b { width: 1px; } }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &parent, entity);
+    select_all(&mut builder, &assets, &child, entity);
+    builder.set_sheet_order(ROOT, &parent, 0);
+    builder.set_sheet_order(ROOT, &child, 1);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)));
+}
+
+#[test]
+fn anonymous_layers_of_other_sheets_are_distinct(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let parent = assets.add(StyleSheetAsset::parse("", "@layer { .b { width: 3px; } } @layer theme { .a { width: 1px; } }"));
+    let child = assets.add(StyleSheetAsset::parse("", "@layer { .a { width: 2px; } }"));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &parent, entity);
+    select_all(&mut builder, &assets, &child, entity);
+    builder.set_sheet_order(ROOT, &parent, 0);
+    builder.set_sheet_order(ROOT, &child, 1);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(2.0)), "Should declare a new layer after theme");
+}
+
+#[test]
+fn important_on_earlier_layer_wins(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", r#"
+        @layer base, theme;
+        @layer base { .a { width: 1px !important; } }
+        @layer theme { .a.b { width: 2px !important; } }
+        .a.b.c { width: 3px !important; }
+    "#));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+    let state = builder.build(&assets);
+
+    assert_eq!(computed_width(&state, &assets, entity), Some(Val::Px(1.0)));
+}
+
+#[cfg(feature = "provenance")]
+#[test]
+fn records_cascade_winner(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse(
+        "sheets/test.css",
+        ".a { width: 1px; color: red !important; } .a.b { width: 2px; color: blue; }"
+    ));
+    let entity = Entity::from_raw(1);
+
+    let mut builder = StyleSheetStateBuilder::default();
+    select_all(&mut builder, &assets, &handle, entity);
+
+    let mut state = StyleSheetState::default();
+    state.replace(builder.build(&assets));
+    // Provenance is kept after the computed styles are cleared on cleanup
+    state.computed.clear();
+
+    let width = state.computed_property(entity, "width").expect("Should record width winner");
+    assert_eq!(width.sheet, "sheets/test.css");
+    assert_eq!(width.selector, ".a.b");
+    assert!(!width.important);
+
+    let color = state.computed_property(entity, "color").expect("Should record color winner");
+    assert_eq!(color.selector, ".a");
+    assert!(color.important);
+}
+
+#[test]
+fn color_is_inherited_by_descendants(
+    // no args
+) {
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    let handle = assets.add(StyleSheetAsset::parse("", ".card { color: red; } .title { color: blue; }"));
+
+    // card -> body -> text, card -> title -> title_text
+    let [card, body, text, title, title_text] = [1, 2, 3, 4, 5].map(Entity::from_raw);
+    let children = bevy::utils::HashMap::from([
+        (card, vec![body, title]),
+        (body, vec![text]),
+        (title, vec![title_text]),
+    ]);
+
+    let rules: Vec<_> = assets.get(&handle).unwrap().iter().collect();
+    let mut builder = StyleSheetStateBuilder::default();
+    let selected = builder.entry((ROOT, handle.clone())).or_default();
+    selected.entry(rules[0].selector.clone()).or_default().push(card);
+    selected.entry(rules[1].selector.clone()).or_default().push(title);
+
+    let mut state = builder.build(&assets);
+    state.inherit_properties(|entity| children.get(&entity).map(Vec::as_slice), |entity| entity != body);
+
+    let color_selector = |entity| state.computed.get(&entity)
+        .and_then(|style| style.get("color"))
+        .map(|source| source.selector.to_string());
+
+    assert_eq!(color_selector(body), None, "Entities which can't take the value should only pass it on");
+    assert_eq!(color_selector(text).as_deref(), Some(".card"));
+    assert_eq!(color_selector(title).as_deref(), Some(".title"), "Explicit color should override the inherited one");
+    assert_eq!(color_selector(title_text).as_deref(), Some(".title"));
+}
+
+#[test]
+fn color_mix_even(
+    // no args
+) {
+    let color = PropertyValues::parse("color-mix(in srgb, red, blue)").unwrap()
+        .color()
+        .expect("Should parse color-mix");
+
+    assert_eq!(color.as_rgba_f32(), [0.5, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn color_mix_weighted(
+    // no args
+) {
+    let color = PropertyValues::parse("color-mix(in srgb, #000 30%, #fff)").unwrap()
+        .color()
+        .expect("Should parse color-mix");
+
+    let [r, g, b, a] = color.as_rgba_f32();
+    for channel in [r, g, b]
+    {
+        assert!((channel - 0.7).abs() < 1e-5, "Should have 70% of white, got {channel}");
+    }
+    assert_eq!(a, 1.0);
+}
+
+#[test]
+fn color_lighten_darken(
+    // no args
+) {
+    let rgb8 = |value: &str| PropertyValues::parse(value).unwrap()
+        .color()
+        .expect("Should parse color function")
+        .as_rgba_f32()
+        .map(|channel| (channel * 255.0).round() as u8);
+
+    // Same results as Sass, which also adds the amount to the HSL lightness
+    assert_eq!(rgb8("lighten(#3050ff, 10%)"), [99, 123, 255, 255]);
+    assert_eq!(rgb8("darken(#3050ff, 10%)"), [0, 39, 252, 255]);
+    assert_eq!(rgb8("darken(red, 20%)"), [153, 0, 0, 255]);
+
+    // Lightness is clamped
+    assert_eq!(rgb8("lighten(red, 80%)"), [255, 255, 255, 255]);
+    assert_eq!(rgb8("darken(#3050ff80, 100%)"), [0, 0, 0, 128]);
+
+    // Functions can be nested
+    assert_eq!(rgb8("lighten(darken(#3050ff, 10%), 10%)"), [48, 80, 255, 255]);
+    assert_eq!(rgb8("darken(color-mix(in srgb, red, blue), 0%)"), [128, 0, 128, 255]);
+}
+
+#[test]
+fn color_alpha(
+    // no args
+) {
+    let rgba = |value: &str| PropertyValues::parse(value).unwrap()
+        .color()
+        .expect("Should parse alpha")
+        .as_rgba_f32();
+
+    assert_eq!(rgba("alpha(#3050ff, 0.5)")[3], 0.5);
+    assert_eq!(rgba("alpha(#3050ff80, 100%)")[3], 1.0);
+    assert_eq!(rgba("alpha(lighten(red, 0%), 25%)"), [1.0, 0.0, 0.0, 0.25]);
+    assert_eq!(rgba("alpha(red, 2)")[3], 1.0, "Alpha should be clamped");
+}
+
+#[test]
+fn color_functions_invalid(
+    // no args
+) {
+    assert!(PropertyValues::parse("lighten(#3050ff)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("lighten(#3050ff, 0.1)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("darken(10%, #3050ff)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("alpha(#3050ff, 0.5, 0.5)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("alpha(#3050ff, auto)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("alpha(nope, 0.5)").unwrap().color().is_none());
+}
+
+#[test]
+fn color_mix_invalid(
+    // no args
+) {
+    assert!(PropertyValues::parse("color-mix(in hsl, red, blue)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("color-mix(in srgb, red)").unwrap().color().is_none());
+    assert!(PropertyValues::parse("color-mix(in srgb, red 0%, blue 0%)").unwrap().color().is_none());
+}
+
+#[test]
+fn inset_single_value(
+    // no args
+) {
+    let rect = impls::style::InsetProperty::parse(&PropertyValues::parse("10px").unwrap()).unwrap();
+    assert_eq!(rect, UiRect::all(Val::Px(10.0)));
+}
+
+#[test]
+fn inset_two_values(
+    // no args
+) {
+    let rect = impls::style::InsetProperty::parse(&PropertyValues::parse("10px 5%").unwrap()).unwrap();
+    assert_eq!(rect, UiRect::new(Val::Percent(5.0), Val::Percent(5.0), Val::Px(10.0), Val::Px(10.0)));
+}
+
+#[test]
+fn inset_three_values(
+    // no args
+) {
+    let rect = impls::style::InsetProperty::parse(&PropertyValues::parse("1px 2px 3px").unwrap()).unwrap();
+    assert_eq!(rect, UiRect::new(Val::Px(2.0), Val::Px(2.0), Val::Px(1.0), Val::Px(3.0)));
+}
+
+#[test]
+fn inset_four_values(
+    // no args
+) {
+    let rect = impls::style::InsetProperty::parse(&PropertyValues::parse("1px 2px 3px 4px").unwrap()).unwrap();
+    assert_eq!(rect, UiRect::new(Val::Px(4.0), Val::Px(2.0), Val::Px(1.0), Val::Px(3.0)));
+}
+
+#[test]
+fn inset_auto_mix(
+    // no args
+) {
+    let rect = impls::style::InsetProperty::parse(&PropertyValues::parse("auto 10% auto 0px").unwrap()).unwrap();
+    assert_eq!(rect, UiRect::new(Val::Px(0.0), Val::Percent(10.0), Val::Auto, Val::Auto));
+}
+
+#[test]
+fn inset_invalid(
+    // no args
+) {
+    use impls::style::InsetProperty;
+
+    assert!(InsetProperty::parse(&PropertyValues::parse("").unwrap()).is_err());
+    assert!(InsetProperty::parse(&PropertyValues::parse("1px 2px 3px 4px 5px").unwrap()).is_err());
+    assert!(InsetProperty::parse(&PropertyValues::parse("1px red").unwrap()).is_err());
+}
+
+#[test]
+fn invalid_values_are_reported_by_parse(
+    // no args
+) {
+    use crate::prelude::BevyCssError;
+
+    assert_eq!(PropertyValues::parse("1in"), Err(BevyCssError::UnsupportedPropertyValue("1in".to_string())));
+    assert_eq!(PropertyValues::parse("10px { red }"), Err(BevyCssError::UnexpectedToken("{".to_string())));
+    assert_eq!(PropertyValues::parse("").map(|values| values.len()), Ok(0), "Empty values should still be valid");
+}
+
+#[test]
+fn token_to_val(
+    // no args
+) {
+    let token = |value: &str| PropertyValues::parse(value).unwrap().iter().next().cloned().expect("Should have a token");
+
+    assert_eq!(token("12.5px").to_val(), Some(Val::Px(12.5)));
+    assert_eq!(token("40%").to_val(), Some(Val::Percent(40.0)));
+    assert_eq!(token("auto").to_val(), Some(Val::Auto));
+    assert_eq!(token("AUTO").to_val(), Some(Val::Auto), "Keywords should ignore case");
+    assert_eq!(token("0").to_val(), Some(Val::Px(0.0)));
+
+    assert_eq!(token("10").to_val(), None, "Bare numbers need a unit");
+    assert_eq!(token("none").to_val(), None);
+    assert_eq!(token("\"10px\"").to_val(), None);
+}
+
+#[test]
+fn length_requires_unit_except_zero(
+    // no args
+) {
+    use impls::style::WidthProperty;
+
+    assert!(WidthProperty::parse(&PropertyValues::parse("10").unwrap()).is_err());
+    assert_eq!(WidthProperty::parse(&PropertyValues::parse("0").unwrap()).unwrap(), Val::Px(0.0));
+}
+
+#[test]
+fn bare_number_as_px(
+    // no args
+) {
+    use impls::style::{FlexGrowProperty, WidthProperty};
+
+    let values = PropertyValues::parse("10").unwrap().with_bare_number_policy(BareNumberPolicy::Px);
+    assert_eq!(WidthProperty::parse(&values).unwrap(), Val::Px(10.0));
+    assert_eq!(FlexGrowProperty::parse(&values).unwrap(), 10.0, "Number properties should be kept working");
+}
+
+#[test]
+fn font_list(
+    // no args
+) {
+    use text::FontProperty;
+
+    let parse = |value| FontProperty::parse(&PropertyValues::parse(value).unwrap()).ok();
+    assert_eq!(parse(r#""main.ttf""#), Some(vec!["main.ttf".to_string()]));
+    assert_eq!(parse(r#""main.ttf", "cjk.ttf","emoji.ttf""#), Some(vec!["main.ttf".to_string(), "cjk.ttf".to_string(), "emoji.ttf".to_string()]));
+    assert_eq!(parse(r#""main.ttf", sans-serif"#), None);
+    assert_eq!(parse(r#""""#), None);
+    assert_eq!(parse("none"), None);
+}
+
+#[test]
+fn text_transform_keywords(
+    // no args
+) {
+    use text::{TextTransform, TextTransformProperty};
+
+    let parse = |value| TextTransformProperty::parse(&PropertyValues::parse(value).unwrap()).ok();
+    assert_eq!(parse("none"), Some(TextTransform::None));
+    assert_eq!(parse("uppercase"), Some(TextTransform::Uppercase));
+    assert_eq!(parse("lowercase"), Some(TextTransform::Lowercase));
+    assert_eq!(parse("capitalize"), Some(TextTransform::Capitalize));
+    assert_eq!(parse("shout"), None);
+}
+
+#[test]
+fn text_transform_none(
+    // no args
+) {
+    assert_eq!(text::TextTransform::None.transform("Hello wOrld"), "Hello wOrld");
+}
+
+#[test]
+fn text_transform_uppercase(
+    // no args
+) {
+    assert_eq!(text::TextTransform::Uppercase.transform("Hello wOrld"), "HELLO WORLD");
+}
+
+#[test]
+fn text_transform_lowercase(
+    // no args
+) {
+    assert_eq!(text::TextTransform::Lowercase.transform("Hello wOrld"), "hello world");
+}
+
+#[test]
+fn text_transform_capitalize(
+    // no args
+) {
+    assert_eq!(text::TextTransform::Capitalize.transform("hello  wOrld\nagain"), "Hello  WOrld\nAgain");
+}
+
+#[test]
+fn text_shadow_values(
+    // no args
+) {
+    use bevy::{math::Vec2, prelude::Color};
+    use text::{TextShadow, TextShadowProperty};
+
+    let parse = |value| TextShadowProperty::parse(&PropertyValues::parse(value).unwrap()).ok();
+    assert_eq!(parse("1px 2px"), Some(Some(TextShadow { offset: Vec2::new(1.0, 2.0), color: Color::BLACK })));
+    assert_eq!(parse("1px 2px red"), Some(Some(TextShadow { offset: Vec2::new(1.0, 2.0), color: Color::RED })));
+    assert_eq!(parse("red -1px 0"), Some(Some(TextShadow { offset: Vec2::new(-1.0, 0.0), color: Color::RED })));
+    assert_eq!(parse("1px 2px 0 #000"), Some(Some(TextShadow { offset: Vec2::new(1.0, 2.0), color: Color::BLACK })));
+    assert_eq!(parse("none"), Some(None));
+    assert_eq!(parse("1px"), None);
+    assert_eq!(parse("1px 2px 3px"), None);
+    assert_eq!(parse("1px 50%"), None);
+}
+
+#[test]
+fn mismatched_units_are_reported(
+    // no args
+) {
+    use impls::{
+        style::{BorderProperty, MarginProperty, ScrollTopProperty, WidthProperty},
+        ClipProperty,
+        ImageSliceProperty,
+        OverflowClipMarginProperty,
+    };
+    use text::FontSizeProperty;
+
+    let mismatched = |property: &str, value: &str, expected| BevyCssError::MismatchedUnit {
+        property: property.to_string(),
+        value: value.to_string(),
+        expected,
+    };
+
+    assert_eq!(WidthProperty::parse(&PropertyValues::parse("10deg").unwrap()), Err(mismatched("width", "10deg", UnitCategory::Length)));
+    assert_eq!(MarginProperty::parse(&PropertyValues::parse("5px 200ms").unwrap()), Err(mismatched("margin", "200ms", UnitCategory::Length)));
+    assert_eq!(FontSizeProperty::parse(&PropertyValues::parse("1s").unwrap()), Err(mismatched("font-size", "1s", UnitCategory::Length)));
+    assert_eq!(BorderProperty::parse(&PropertyValues::parse("1turn solid red").unwrap()), Err(mismatched("border", "1turn", UnitCategory::Length)));
+    assert_eq!(
+        mismatched("width", "10deg", UnitCategory::Length).to_string(),
+        "Mismatched unit on property width: 10deg, expected a length"
+    );
+
+    assert_eq!(ClipProperty::parse(&PropertyValues::parse("rect(0, 1s, 0, 0)").unwrap()), Err(mismatched("clip", "1s", UnitCategory::Length)));
+    assert_eq!(OverflowClipMarginProperty::parse(&PropertyValues::parse("10deg").unwrap()), Err(mismatched("overflow-clip-margin", "10deg", UnitCategory::Length)));
+    assert_eq!(ScrollTopProperty::parse(&PropertyValues::parse("0.5turn").unwrap()), Err(mismatched("scroll-top", "0.5turn", UnitCategory::Length)));
+    assert_eq!(ImageSliceProperty::parse(&PropertyValues::parse("8px 2s").unwrap()), Err(mismatched("image-slice", "2s", UnitCategory::Length)));
+
+    assert_eq!(WidthProperty::parse(&PropertyValues::parse("50%").unwrap()), Ok(Val::Percent(50.0)), "Should accept values without unit");
+}
+
+#[test]
+fn text_align_keywords(
+    // no args
+) {
+    use bevy::prelude::TextAlignment;
+    use text::TextAlignProperty;
+
+    let parse = |value| TextAlignProperty::parse(&PropertyValues::parse(value).unwrap());
+    assert_eq!(parse("left").unwrap(), Some(TextAlignment::Left));
+    assert_eq!(parse("center").unwrap(), Some(TextAlignment::Center));
+    assert_eq!(parse("right").unwrap(), Some(TextAlignment::Right));
+    assert!(matches!(parse("justify"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse("middle"), Err(BevyCssError::InvalidPropertyValue(_))));
+}
+
+#[test]
+fn white_space_keywords(
+    // no args
+) {
+    use bevy::text::BreakLineOn;
+    use text::WhiteSpaceProperty;
+
+    let parse = |value| WhiteSpaceProperty::parse(&PropertyValues::parse(value).unwrap());
+    assert_eq!(parse("normal").unwrap(), Some(BreakLineOn::WordBoundary));
+    assert_eq!(parse("pre-wrap").unwrap(), Some(BreakLineOn::WordBoundary));
+    assert_eq!(parse("pre").unwrap(), Some(BreakLineOn::NoWrap));
+    assert_eq!(parse("nowrap").unwrap(), Some(BreakLineOn::NoWrap));
+    assert!(matches!(parse("break-spaces"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse("wrap"), Err(BevyCssError::InvalidPropertyValue(_))));
+}
+
+#[test]
+fn display_keywords(
+    // no args
+) {
+    use bevy::prelude::Display;
+    use impls::style::DisplayProperty;
+
+    let parse = |value| DisplayProperty::parse(&PropertyValues::parse(value).unwrap());
+    assert_eq!(parse("flex").unwrap(), Display::Flex);
+    assert_eq!(parse("grid").unwrap(), Display::Grid);
+    assert_eq!(parse("none").unwrap(), Display::None);
+    assert!(matches!(parse("inline"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse("block"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse("hidden"), Err(BevyCssError::InvalidPropertyValue(_))));
+}
+
+#[test]
+fn alignment_keywords(
+    // no args
+) {
+    use bevy::prelude::{AlignContent, AlignItems, AlignSelf, JustifyContent};
+    use impls::style::{
+        AlignContentProperty,
+        AlignItemsProperty,
+        AlignSelfProperty,
+        JustifyContentProperty,
+    };
+
+    fn parse<P: Property>(
+        value: &str
+    ) -> Result<P::Cache, BevyCssError> {
+        P::parse(&PropertyValues::parse(value).unwrap())
+    }
+
+    let keywords = [
+        ("normal", Some(AlignItems::Default), None, Some(AlignContent::Default), Some(JustifyContent::Default)),
+        ("start", Some(AlignItems::Start), Some(AlignSelf::Start), Some(AlignContent::Start), Some(JustifyContent::Start)),
+        ("end", Some(AlignItems::End), Some(AlignSelf::End), Some(AlignContent::End), Some(JustifyContent::End)),
+        ("flex-start", Some(AlignItems::FlexStart), Some(AlignSelf::FlexStart), Some(AlignContent::FlexStart), Some(JustifyContent::FlexStart)),
+        ("flex-end", Some(AlignItems::FlexEnd), Some(AlignSelf::FlexEnd), Some(AlignContent::FlexEnd), Some(JustifyContent::FlexEnd)),
+        ("center", Some(AlignItems::Center), Some(AlignSelf::Center), Some(AlignContent::Center), Some(JustifyContent::Center)),
+        ("stretch", Some(AlignItems::Stretch), Some(AlignSelf::Stretch), Some(AlignContent::Stretch), Some(JustifyContent::Stretch)),
+        ("baseline", Some(AlignItems::Baseline), Some(AlignSelf::Baseline), None, None),
+        ("space-between", None, None, Some(AlignContent::SpaceBetween), Some(JustifyContent::SpaceBetween)),
+        ("space-around", None, None, Some(AlignContent::SpaceAround), Some(JustifyContent::SpaceAround)),
+        ("space-evenly", None, None, Some(AlignContent::SpaceEvenly), Some(JustifyContent::SpaceEvenly)),
+    ];
+
+    for (keyword, items, align_self, content, justify) in keywords
+    {
+        assert_eq!(parse::<AlignItemsProperty>(keyword).ok(), items, "align-items: {keyword}");
+        assert_eq!(parse::<AlignSelfProperty>(keyword).ok(), align_self, "align-self: {keyword}");
+        assert_eq!(parse::<AlignContentProperty>(keyword).ok(), content, "align-content: {keyword}");
+        assert_eq!(parse::<JustifyContentProperty>(keyword).ok(), justify, "justify-content: {keyword}");
+    }
+
+    // Valid `css`, but not supported by Bevy
+    assert!(matches!(parse::<AlignItemsProperty>("self-start"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse::<AlignSelfProperty>("normal"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse::<AlignContentProperty>("baseline"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse::<JustifyContentProperty>("left"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+    assert!(matches!(parse::<JustifyContentProperty>("safe center"), Err(BevyCssError::UnsupportedPropertyValue(_))));
+
+    // Not `css` at all
+    assert!(matches!(parse::<AlignItemsProperty>("space-between"), Err(BevyCssError::InvalidPropertyValue(_))));
+    assert!(matches!(parse::<JustifyContentProperty>("middle"), Err(BevyCssError::InvalidPropertyValue(_))));
+}
+
+#[test]
+fn keywords_are_accepted_consistently(
+    // no args
+) {
+    use bevy::prelude::{AlignSelf, Display};
+    use impls::style::{
+        AlignSelfProperty,
+        AspectRatioProperty,
+        DisplayProperty,
+        InsetProperty,
+        LeftProperty,
+        MarginProperty,
+        WidthProperty,
+    };
+
+    // `auto` is accepted by every length property, ignoring case
+    for value in ["auto", "AUTO", "Auto"]
+    {
+        let values = PropertyValues::parse(value).unwrap();
+        assert_eq!(WidthProperty::parse(&values).unwrap(), Val::Auto, "width: {value}");
+        assert_eq!(LeftProperty::parse(&values).unwrap(), Val::Auto, "left: {value}");
+        assert_eq!(MarginProperty::parse(&values).unwrap(), UiRect::all(Val::Auto), "margin: {value}");
+        assert_eq!(InsetProperty::parse(&values).unwrap(), UiRect::all(Val::Auto), "inset: {value}");
+        assert_eq!(AlignSelfProperty::parse(&values).unwrap(), AlignSelf::Auto, "align-self: {value}");
+    }
+
+    // `none` is accepted by properties which can be disabled, ignoring case
+    for value in ["none", "NONE"]
+    {
+        let values = PropertyValues::parse(value).unwrap();
+        assert_eq!(AspectRatioProperty::parse(&values).unwrap(), None, "aspect-ratio: {value}");
+        assert_eq!(DisplayProperty::parse(&values).unwrap(), Display::None, "display: {value}");
+    }
+
+    // Keywords of other properties are rejected
+    assert!(WidthProperty::parse(&PropertyValues::parse("none").unwrap()).is_err());
+    assert!(InsetProperty::parse(&PropertyValues::parse("normal").unwrap()).is_err());
+    assert!(AspectRatioProperty::parse(&PropertyValues::parse("auto").unwrap()).is_err());
+    assert!(DisplayProperty::parse(&PropertyValues::parse("auto").unwrap()).is_err());
+
+    // A bare `0` is a valid length on shorthands too
+    assert_eq!(InsetProperty::parse(&PropertyValues::parse("0").unwrap()).unwrap(), UiRect::all(Val::Px(0.0)));
+}
+
+#[test]
+fn box_sizing_keywords(
+    // no args
+) {
+    use impls::style::{BoxSizing, BoxSizingProperty};
+
+    let parse = |value: &str| BoxSizingProperty::parse(&PropertyValues::parse(value).unwrap());
+
+    assert_eq!(parse("content-box").unwrap(), BoxSizing::ContentBox);
+    assert_eq!(parse("border-box").unwrap(), BoxSizing::BorderBox);
+    assert!(parse("padding-box").is_err());
+}
+
+#[test]
+fn clip_rect_four_values(
+    // no args
+) {
+    use impls::{ClipProperty, ClipRect};
+
+    let parse = |value: &str| ClipProperty::parse(&PropertyValues::parse(value).unwrap());
+    let expected = Some(ClipRect { top: Val::Px(0.0), right: Val::Px(100.0), bottom: Val::Px(50.0), left: Val::Px(0.0) });
+
+    // Sides can be either comma or space separated
+    assert_eq!(parse("rect(0px, 100px, 50px, 0px)").unwrap(), expected);
+    assert_eq!(parse("rect(0px 100px 50px 0px)").unwrap(), expected);
+    assert_eq!(parse("RECT(0, 100px, 50px, 0)").unwrap(), expected);
+
+    assert_eq!(
+        parse("rect(auto, 50%, 25%, auto)").unwrap(),
+        Some(ClipRect { top: Val::Auto, right: Val::Percent(50.0), bottom: Val::Percent(25.0), left: Val::Auto })
+    );
+    assert_eq!(parse("auto").unwrap(), None);
+
+    assert!(parse("rect(0px, 100px, 50px)").is_err());
+    assert!(parse("rect(0px, 100px, 50px, 0px, 0px)").is_err());
+    assert!(parse("rect(0px 100px, 50px 0px)").is_err());
+    assert!(parse("rect(0px, red, 50px, 0px)").is_err());
+    assert!(parse("inset(0px, 100px, 50px, 0px)").is_err());
+    assert!(parse("auto auto").is_err());
+}
+
+#[test]
+fn overflow_shorthand(
+    // no args
+) {
+    use bevy::{math::BVec2, ui::{Overflow, OverflowAxis}};
+    use impls::style::OverflowProperty;
+
+    let parse = |value: &str| OverflowProperty::parse(&PropertyValues::parse(value).unwrap());
+
+    let visible = parse("visible").unwrap();
+    assert_eq!(visible.overflow, Overflow::visible());
+    assert!(!visible.scroll.any());
+
+    assert_eq!(parse("hidden").unwrap().overflow, Overflow::clip());
+    assert_eq!(parse("clip visible").unwrap().overflow, Overflow::clip_x());
+
+    // Scrolled axes are clipped
+    let scroll = parse("scroll").unwrap();
+    assert_eq!(scroll.overflow, Overflow::clip());
+    assert_eq!(scroll.scroll, BVec2::TRUE);
+
+    let scroll_y = parse("visible scroll").unwrap();
+    assert_eq!(scroll_y.overflow, Overflow { x: OverflowAxis::Visible, y: OverflowAxis::Clip });
+    assert_eq!(scroll_y.scroll, BVec2::new(false, true));
+
+    assert!(parse("auto").is_err());
+    assert!(parse("scroll scroll scroll").is_err());
+    assert!(parse("10px").is_err());
+}
+
+#[test]
+fn border_shorthand(
+    // no args
+) {
+    use bevy::prelude::Color;
+    use impls::style::{Border, BorderProperty};
+
+    let parse = |value: &str| BorderProperty::parse(&PropertyValues::parse(value).unwrap());
+
+    // Width, line style and color are all optional, and on any order
+    let expected = Border { width: Some(UiRect::all(Val::Px(2.0))), color: Some(Color::WHITE) };
+    assert_eq!(parse("2px solid #fff").unwrap(), expected);
+    assert_eq!(parse("#fff 2px solid").unwrap(), expected);
+    assert_eq!(parse("red").unwrap(), Border { width: None, color: Some(Color::RED) });
+    assert_eq!(parse("solid").unwrap(), Border::default());
+
+    // The width-only syntax keeps working, with up to four sides
+    assert_eq!(parse("1px 2px 3px 4px").unwrap().width, Some(UiRect::new(Val::Px(4.0), Val::Px(2.0), Val::Px(1.0), Val::Px(3.0))));
+    assert_eq!(parse("1px 2px 3px 4px blue").unwrap().color, Some(Color::BLUE));
+
+    // `none` removes the border
+    assert_eq!(parse("none").unwrap().width, Some(UiRect::all(Val::Px(0.0))));
+    assert_eq!(parse("NONE red").unwrap(), Border { width: Some(UiRect::all(Val::Px(0.0))), color: Some(Color::RED) });
+
+    assert!(parse("2px none").is_err());
+    assert!(parse("solid dashed").is_err());
+    assert!(parse("red blue").is_err());
+    assert!(parse("1px 1px 1px 1px 1px").is_err());
+    assert!(parse("thick").is_err());
+}
+
+#[test]
+fn auto_is_accepted_by_every_length_property(
+    // no args
+) {
+    use impls::style::*;
+
+    fn parse<P: Property<Cache = Val>>(
+        // no args
+    ) -> Val {
+        P::parse(&PropertyValues::parse("auto").unwrap()).unwrap()
+    }
+
+    let values = [
+        parse::<LeftProperty>(), parse::<RightProperty>(), parse::<TopProperty>(), parse::<BottomProperty>(),
+        parse::<WidthProperty>(), parse::<HeightProperty>(),
+        parse::<MinWidthProperty>(), parse::<MinHeightProperty>(),
+        parse::<MaxWidthProperty>(), parse::<MaxHeightProperty>(),
+        parse::<FlexBasisProperty>(),
+        parse::<MarginLeftProperty>(), parse::<MarginRightProperty>(),
+        parse::<MarginTopProperty>(), parse::<MarginBottomProperty>(),
+    ];
+
+    assert!(values.iter().all(|val| *val == Val::Auto), "{values:?}");
+}
+
+#[test]
+fn content_sizing_keywords_are_unsupported(
+    // no args
+) {
+    use impls::style::{FlexBasisProperty, MinWidthProperty, WidthProperty};
+
+    let parse = |value| WidthProperty::parse(&PropertyValues::parse(value).unwrap());
+    assert_eq!(parse("max-content"), Err(BevyCssError::UnsupportedPropertyValue("width: max-content".to_string())));
+    assert_eq!(parse("fit-content(200px)"), Err(BevyCssError::UnsupportedPropertyValue("width: fit-content".to_string())));
+    assert!(matches!(
+        MinWidthProperty::parse(&PropertyValues::parse("min-content").unwrap()),
+        Err(BevyCssError::UnsupportedPropertyValue(_))
+    ));
+    assert!(matches!(
+        FlexBasisProperty::parse(&PropertyValues::parse("FIT-CONTENT").unwrap()),
+        Err(BevyCssError::UnsupportedPropertyValue(_))
+    ));
+
+    // Other properties don't know those keywords
+    assert!(matches!(parse("more-content"), Err(BevyCssError::InvalidPropertyValue(_))));
+    assert!(matches!(
+        impls::style::LeftProperty::parse(&PropertyValues::parse("max-content").unwrap()),
+        Err(BevyCssError::InvalidPropertyValue(_))
+    ));
+}
+
+#[test]
+fn deduplicated_rules_keep_declaration_order(
+    // no args
+) {
+    let mut sheet = StyleSheetAsset::parse("", ".a { width: 1px; height: 2px; } .a { left: 3px; width: 4px; }");
+    sheet.deduplicate();
+
+    let rule = sheet.iter().next().expect("Should merge both rules");
+    assert!(rule.properties.keys().map(|name| &**name).eq(["height", "left", "width"]), "{:?}", rule.properties.keys());
+}
+
+#[test]
+fn deduplicated_sheet_keeps_cascade_results(
+    // no args
+) {
+    // Small linear congruential generator, so the generated sheet is always the same
+    let mut seed = 7_u32;
+    let mut next = |max: usize| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) as usize % max
+    };
+
+    // Mostly of the same specificity, so rules of different selectors are interleaved on cascade order
+    let selectors = [".a", ".b", ".c", "#n", ".a.b"];
+    let properties = ["width", "height", "left", "top"];
+    let values = ["1px", "2px", "3px"];
+
+    let mut content = String::from("@layer base, theme;\n");
+    for _ in 0..300
+    {
+        let declarations: String = (0..1 + next(3))
+            .map(|_| format!(
+                "{}: {}{};",
+                properties[next(properties.len())],
+                values[next(values.len())],
+                if next(8) == 0 { " !important" } else { "" },
+            ))
+            .collect();
+
+        let rule = format!("{} {{ {declarations} }}", selectors[next(selectors.len())]);
+        match next(4)
+        {
+            0 => content.push_str(&format!("@layer base {{ {rule} }}\n")),
+            1 => content.push_str(&format!("@layer theme {{ {rule} }}\n")),
+            _ => content.push_str(&format!("{rule}\n")),
+        }
+    }
+
+    let sheet = StyleSheetAsset::parse("", &content);
+    let mut deduplicated = sheet.clone();
+    deduplicated.deduplicate();
+
+    let before = sheet.stats();
+    let after = deduplicated.stats();
+    assert_eq!(before.rules, 300);
+    assert!(after.rules < before.rules, "{after:?}");
+    assert_eq!(after.unique_selectors, before.unique_selectors);
+    assert!(after.interned_values <= values.len() * 2, "{after:?}");
+    assert!(after.heap_bytes < before.heap_bytes, "{after:?}");
+
+    let computed = |sheet: StyleSheetAsset| {
+        let mut assets = Assets::<StyleSheetAsset>::default();
+        let handle = assets.add(sheet);
+        let entity = Entity::from_raw(1);
+
+        let mut builder = StyleSheetStateBuilder::default();
+        select_all(&mut builder, &assets, &handle, entity);
+        let state = builder.build(&assets);
+
+        properties.map(|name| state.computed.get(&entity)
+            .and_then(|style| style.get(name))
+            .and_then(|source| assets.get(&source.stylesheet)?.get_property_value(&source.selector, name))
+            .cloned()
+        )
+    };
+
+    assert_eq!(computed(sheet), computed(deduplicated));
+}
+
+#[test]
+fn normalized_sheet_drops_empty_rules_and_keeps_cascade_results(
+    // no args
+) {
+    let sheet = StyleSheetAsset::parse("", r#"
+        @layer base;
+        .a { }
+        .a { width: 1px; height: 1px; }
+        .a { height: 2px; }
+        @layer base { .a { width: 4px !important; } }
+        .b { width: 2px; }
+        .a { width: 3px; }
+        .c { & .d { left: 1px; } }
+        .a { }
+    "#);
+    let mut normalized = sheet.clone();
+    normalized.normalize();
+
+    assert_eq!(sheet.rule_count(), 9);
+    // Empty rules are dropped, and the adjacent `.a` rules are merged, but not with the last one, since `.b` is between them
+    assert_eq!(normalized.rule_count(), 5);
+    assert_eq!(normalized.declaration_count(), 6);
+
+    let computed = |sheet: StyleSheetAsset| {
+        let mut assets = Assets::<StyleSheetAsset>::default();
+        let handle = assets.add(sheet);
+        let entity = Entity::from_raw(1);
+
+        let mut builder = StyleSheetStateBuilder::default();
+        select_all(&mut builder, &assets, &handle, entity);
+        let state = builder.build(&assets);
+
+        ["width", "height", "left"].map(|name| state.computed.get(&entity)
+            .and_then(|style| style.get(name))
+            .and_then(|source| assets.get(&source.stylesheet)?.get_property_value(&source.selector, name))
+            .cloned()
+        )
+    };
+
+    assert_eq!(computed(sheet), computed(normalized));
+}
+
+#[test]
+fn focus_policy_keywords(
+    // no args
+) {
+    use impls::FocusPolicyProperty;
+    use bevy::ui::FocusPolicy;
+
+    let parse = |value: &str| FocusPolicyProperty::parse(&PropertyValues::parse(value).unwrap());
+    assert_eq!(parse("block"), Ok(FocusPolicy::Block));
+    assert_eq!(parse("pass"), Ok(FocusPolicy::Pass));
+    assert_eq!(parse("BLOCK"), Ok(FocusPolicy::Block), "Keywords should be case insensitive");
+    assert!(parse("auto").is_err());
+    assert!(parse("block pass").is_err());
+}
+
+#[test]
+fn image_slicing_is_parsed_but_unsupported(
+    // no args
+) {
+    use impls::{ImageScaleModeProperty, ImageSliceProperty, ImageTileProperty};
+
+    let unsupported = |error: Option<BevyCssError>| matches!(error, Some(BevyCssError::UnsupportedPropertyValue(_)));
+    let invalid = |error: Option<BevyCssError>| matches!(error, Some(BevyCssError::InvalidPropertyValue(_)));
+
+    assert!(unsupported(ImageScaleModeProperty::parse(&PropertyValues::parse("SLICED").unwrap()).err()));
+    assert!(unsupported(ImageScaleModeProperty::parse(&PropertyValues::parse("tiled").unwrap()).err()));
+    assert!(invalid(ImageScaleModeProperty::parse(&PropertyValues::parse("sliced tiled").unwrap()).err()));
+
+    assert!(unsupported(ImageSliceProperty::parse(&PropertyValues::parse("16px").unwrap()).err()));
+    assert!(unsupported(ImageSliceProperty::parse(&PropertyValues::parse("16px 8px 4px 0").unwrap()).err()));
+    assert!(invalid(ImageSliceProperty::parse(&PropertyValues::parse("10%").unwrap()).err()), "Insets should be pixels");
+    assert!(invalid(ImageSliceProperty::parse(&PropertyValues::parse("-1px").unwrap()).err()));
+    assert!(invalid(ImageSliceProperty::parse(&PropertyValues::parse("1px 2px 3px 4px 5px").unwrap()).err()));
+
+    assert!(unsupported(ImageTileProperty::parse(&PropertyValues::parse("repeat stretch").unwrap()).err()));
+    assert!(invalid(ImageTileProperty::parse(&PropertyValues::parse("round").unwrap()).err()));
+}
+
+#[test]
+fn background_linear_gradient(
+    // no args
+) {
+    use impls::{Background, BackgroundProperty, ColorStop};
+    use bevy::prelude::Color;
+
+    let gradient = |value: &str| match BackgroundProperty::parse(&PropertyValues::parse(value).unwrap())
+    {
+        Ok(Background::LinearGradient(gradient)) => Some(gradient),
+        _ => None,
+    };
+
+    let vertical = gradient("linear-gradient(to bottom, #202030, #101018)").unwrap();
+    assert_eq!(vertical.angle, 180.0);
+    assert_eq!(vertical.stops, [
+        ColorStop { color: Color::hex("202030").unwrap(), position: 0.0 },
+        ColorStop { color: Color::hex("101018").unwrap(), position: 1.0 },
+    ]);
+
+    assert_eq!(gradient("linear-gradient(red, blue)").unwrap().angle, 180.0, "Should default to `to bottom`");
+    assert_eq!(gradient("linear-gradient(to top left, red, blue)").unwrap().angle, 315.0);
+    assert_eq!(gradient("linear-gradient(45deg, red, blue)").unwrap().angle, 45.0);
+    assert_eq!(gradient("linear-gradient(0.25turn, red, blue)").unwrap().angle, 90.0);
+    assert!(gradient("linear-gradient(45px, red, blue)").is_none(), "Should require an angle");
+
+    // Stops without a position are spread evenly, and no stop comes before the previous one
+    let positions = |value: &str| gradient(value).unwrap().stops.iter().map(|stop| stop.position).collect::<Vec<_>>();
+    assert_eq!(positions("linear-gradient(red, green, blue 50%, white, black)"), [0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(positions("linear-gradient(red 60%, blue 20%)"), [0.6, 0.6]);
+
+    assert_eq!(BackgroundProperty::parse(&PropertyValues::parse("#fff").unwrap()).unwrap(), Background::Color(Color::WHITE));
+    assert!(gradient("linear-gradient(red)").is_none(), "Should require at least two stops");
+    assert!(gradient("linear-gradient(to middle, red, blue)").is_none());
+    assert!(gradient("radial-gradient(red, blue)").is_none());
+}
+
+#[test]
+fn flex_factors_are_fractional_and_non_negative(
+    // no args
+) {
+    use impls::style::{FlexGrowProperty, FlexShrinkProperty};
+
+    let grow = |value| FlexGrowProperty::parse(&PropertyValues::parse(value).unwrap());
+    let shrink = |value| FlexShrinkProperty::parse(&PropertyValues::parse(value).unwrap());
+
+    assert_eq!(grow("0.5").unwrap(), 0.5);
+    assert_eq!(grow("2").unwrap(), 2.0);
+    assert_eq!(shrink("2").unwrap(), 2.0);
+    assert_eq!(shrink("0.25").unwrap(), 0.25);
+
+    assert_eq!(grow("-1").unwrap(), 0.0, "Negative factors should be clamped to zero");
+    assert_eq!(shrink("-0.5").unwrap(), 0.0);
+    assert!(grow("auto").is_err());
+}
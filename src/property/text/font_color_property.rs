@@ -1,15 +1,14 @@
 use crate::{
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{Property, PropertyContext, PropertyValues, set_sections},
 };
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
-        AssetServer,
         Color,
         Commands,
         Node,
-        Text,
+        Text, TextStyle,
         With,
     },
 };
@@ -24,6 +23,7 @@ for FontColorProperty
     type Cache = Color;
     type Components = &'static mut Text;
     type Filters = With<Node>;
+    type Param = ();
 
     fn name(
         // no args
@@ -44,12 +44,17 @@ for FontColorProperty
     fn apply<'w>(
         cache: &Self::Cache,
         mut components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
+        _context: &PropertyContext<Self>,
         _commands: &mut Commands,
     ) {
-        for section in components.sections.iter_mut()
-        {
-            section.style.color = *cache;
-        }
+        set_sections(&mut components, |section| &mut section.style.color, *cache);
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_sections(&mut components, |section| &mut section.style.color, TextStyle::default().color);
     }
 }
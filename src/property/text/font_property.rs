@@ -1,28 +1,71 @@
 use crate::{
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{Property, PropertyContext, PropertyToken, PropertyValues, set_sections},
 };
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
         AssetServer,
         Commands,
+        Component,
+        Deref,
+        DetectChangesMut,
+        Entity,
+        Font,
+        Handle,
         Node,
-        Text,
+        Res,
+        Text, TextStyle,
         With,
     },
 };
 
+/// Fonts listed by the `font` property of an entity, in the same order they were declared, like `font: "main.ttf", "noto-cjk.ttf";`.
+///
+/// Bevy text can't fall back to another font when a glyph is missing, so only the first font is used by [`Text`],
+/// while this list can be consumed by user systems, like to pick a font for each section based on its content.
+/// All handles are kept alive while the property is applied, so every listed font stays loaded and is hot reloaded.
+#[derive(Debug, Default, Clone)]
+#[derive(PartialEq)]
+#[derive(Component, Deref)]
+pub struct FontList(
+    Vec<Handle<Font>>
+);
+
+impl FontList
+{
+    /// Font used by [`Text`], which is the first one listed.
+    pub fn primary(
+        &self
+    ) -> Option<&Handle<Font>> {
+        self.0.first()
+    }
+}
+
 /// Applies the `font` property on [`TextStyle::font`](`TextStyle`) property of all sections on matched [`Text`] components.
+///
+/// It accepts a comma separated list of font paths, like `font: "fonts/main.ttf", "fonts/noto-cjk.ttf";`.
+/// The first font is applied on [`Text`], and all of them are loaded and kept on [`FontList`] as fallbacks.
+///
+/// # Examples
+///
+/// ```
+/// # use tomt_bevycss::{prelude::*, property::text::FontProperty};
+/// let fonts = FontProperty::parse(&PropertyValues::parse(r#""fonts/main.ttf", "fonts/noto-cjk.ttf""#).unwrap()).unwrap();
+/// assert_eq!(fonts, ["fonts/main.ttf", "fonts/noto-cjk.ttf"]);
+///
+/// assert!(FontProperty::parse(&PropertyValues::parse(r#""fonts/main.ttf", serif"#).unwrap()).is_err());
+/// ```
 #[derive(Default)]
 pub struct FontProperty;
 
 impl Property
 for FontProperty
 {
-    type Cache = String;
-    type Components = &'static mut Text;
+    type Cache = Vec<String>;
+    type Components = (Entity, &'static mut Text, Option<&'static mut FontList>);
     type Filters = With<Node>;
+    type Param = Res<'static, AssetServer>;
 
     fn name(
         // no args
@@ -33,22 +76,58 @@ for FontProperty
     fn parse<'a>(
         values: &PropertyValues
     ) -> Result<Self::Cache, BevyCssError> {
-        match values.string()
+        let paths: Option<Vec<String>> = values.iter()
+            .map(|token| match token
+            {
+                PropertyToken::String(path) if !path.is_empty() => Some(path.clone()),
+                _ => None,
+            })
+            .collect();
+
+        match paths
         {
-            Some(path) => Ok(path),
-            None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+            Some(paths) if !paths.is_empty() => Ok(paths),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
         }
     }
 
     fn apply<'w>(
         cache: &Self::Cache,
-        mut components: QueryItem<Self::Components>,
-        asset_server: &AssetServer,
-        _commands: &mut Commands,
+        (entity, mut text, font_list): QueryItem<Self::Components>,
+        asset_server: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        let fonts = FontList(cache.iter()
+            .map(|path| asset_server.load(path))
+            .collect()
+        );
+
+        if let Some(font) = fonts.primary()
+        {
+            set_sections(&mut text, |section| &mut section.style.font, font.clone());
+        }
+
+        match font_list
+        {
+            Some(mut font_list) => {
+                font_list.set_if_neq(fonts);
+            }
+            None => {
+                commands.entity(entity).try_insert(fonts);
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (entity, mut text, font_list): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
     ) {
-        for section in components.sections.iter_mut()
+        set_sections(&mut text, |section| &mut section.style.font, TextStyle::default().font);
+
+        if font_list.is_some()
         {
-            section.style.font = asset_server.load(cache);
+            commands.entity(entity).remove::<FontList>();
         }
     }
 }
@@ -1,14 +1,13 @@
 use crate::{
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{Property, PropertyContext, PropertyValues, set_sections, UnitCategory},
 };
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
-        AssetServer,
         Commands,
         Node,
-        Text,
+        Text, TextStyle,
         With,
     },
 };
@@ -23,6 +22,7 @@ for FontSizeProperty
     type Cache = f32;
     type Components = &'static mut Text;
     type Filters = With<Node>;
+    type Param = ();
 
     fn name(
         // no args
@@ -33,6 +33,8 @@ for FontSizeProperty
     fn parse<'a>(
         values: &PropertyValues
     ) -> Result<Self::Cache, BevyCssError> {
+        values.expect_unit(Self::name(), UnitCategory::Length)?;
+
         match values.f32()
         {
             Some(size) => Ok(size),
@@ -43,12 +45,17 @@ for FontSizeProperty
     fn apply<'w>(
         cache: &Self::Cache,
         mut components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
+        _context: &PropertyContext<Self>,
         _commands: &mut Commands,
     ) {
-        for section in components.sections.iter_mut()
-        {
-            section.style.font_size = *cache
-        }
+        set_sections(&mut components, |section| &mut section.style.font_size, *cache);
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_sections(&mut components, |section| &mut section.style.font_size, TextStyle::default().font_size);
     }
 }
@@ -10,5 +10,18 @@ pub use font_size_property::*;
 mod text_align_property;
 pub use text_align_property::*;
 
+mod text_binding;
+pub use text_binding::TextTemplate;
+pub(crate) use text_binding::{sync_text_bindings, TextBindingRegistry};
+
 mod text_content_property;
 pub use text_content_property::*;
+
+mod text_shadow_property;
+pub use text_shadow_property::*;
+
+mod text_transform_property;
+pub use text_transform_property::*;
+
+mod white_space_property;
+pub use white_space_property::*;
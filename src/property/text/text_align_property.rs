@@ -1,12 +1,11 @@
 use crate::{
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{Property, PropertyContext, PropertyValues, set_if_changed},
 };
 
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
-        AssetServer,
         Commands,
         Node,
         Text, TextAlignment,
@@ -14,7 +13,10 @@ use bevy::{
     },
 };
 
-/// Applies the `text-align` property on [`Text::horizontal`](`TextAlignment`) components.
+/// Applies the `text-align` property on [`Text::alignment`](`TextAlignment`) components.
+///
+/// Alignment applies to the whole text block, so all sections share it.
+/// Supports `left`, `center` and `right`, while `justify` is reported as unsupported, since Bevy can't justify text.
 #[derive(Default)]
 pub struct TextAlignProperty;
 
@@ -25,6 +27,7 @@ for TextAlignProperty
     type Cache = Option<TextAlignment>;
     type Components = &'static mut Text;
     type Filters = With<Node>;
+    type Param = ();
 
     fn name(
         // no args
@@ -40,6 +43,7 @@ for TextAlignProperty
             Some("left") => Ok(Some(TextAlignment::Left)),
             Some("center") => Ok(Some(TextAlignment::Center)),
             Some("right") => Ok(Some(TextAlignment::Right)),
+            Some("justify") => Err(BevyCssError::UnsupportedPropertyValue(format!("{}: justify", Self::name()))),
             _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
         }
     }
@@ -47,9 +51,17 @@ for TextAlignProperty
     fn apply<'w>(
         cache: &Self::Cache,
         mut components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
+        _context: &PropertyContext<Self>,
         _commands: &mut Commands,
     ) {
-        components.alignment = cache.expect("Should always have a inner value");
+        set_if_changed(&mut components, |text| &mut text.alignment, cache.expect("Should always have a inner value"));
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut components, |text| &mut text.alignment, TextAlignment::default());
     }
 }
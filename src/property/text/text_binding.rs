@@ -0,0 +1,344 @@
+use super::text_content_property::TextTemplateSource;
+use crate::property::set_sections;
+
+use bevy::{
+    ecs::{
+        component::ComponentTicks,
+        system::SystemChangeTick,
+        world::EntityRef,
+    },
+    prelude::{
+        Component,
+        DetectChanges,
+        DetectChangesMut,
+        Entity,
+        ParamSet,
+        Parent,
+        Query,
+        Ref,
+        Res,
+        Resource,
+        Text,
+    },
+    reflect::{GetPath, Reflect, ReflectRef},
+    utils::HashMap,
+};
+use std::marker::PhantomData;
+
+/// Part of a [`TextTemplate`], either plain text or a placeholder.
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart
+{
+    Text(String),
+    /// Name of the binding and path of the field on its component, which is empty to use the component itself.
+    Binding(String, String),
+}
+
+/// Content of the `text-content` property, which may reference fields of components registered with
+/// [`register_text_binding`](crate::RegisterTextBinding::register_text_binding), like `"HP: {health.current}"`.
+///
+/// Placeholders are written as `{name}`, to use the component itself, or `{name.path}`, to use one of its fields,
+/// and `{{` and `}}` are written as plain braces.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextTemplate
+{
+    parts: Vec<TemplatePart>,
+}
+
+impl TextTemplate
+{
+    /// Parses the given content, which is kept as plain text where braces aren't closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::property::text::TextTemplate;
+    /// let template = TextTemplate::parse("HP: {health.current} {{max}}");
+    ///
+    /// assert!(template.is_bound());
+    /// assert_eq!(template.resolve(|name, path| Some(format!("{name}/{path}"))), "HP: health/current {max}");
+    /// assert_eq!(TextTemplate::parse("Score {").resolve(|_name, _path| None), "Score {");
+    /// ```
+    pub fn parse(
+        content: &str
+    ) -> Self {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut chars = content.chars().peekable();
+
+        while let Some(c) = chars.next()
+        {
+            match (c, chars.peek())
+            {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    text.push(c);
+                }
+                ('{', _) => {
+                    let placeholder: String = chars.clone().take_while(|c| *c != '}').collect();
+                    let closed = chars.clone().nth(placeholder.chars().count()) == Some('}');
+                    let name = placeholder.split('.').next().unwrap_or_default();
+
+                    if !closed || name.is_empty() || placeholder.contains(char::is_whitespace)
+                    {
+                        text.push(c);
+                        continue;
+                    }
+
+                    chars.nth(placeholder.chars().count());
+                    if !text.is_empty()
+                    {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+
+                    let path = placeholder[name.len()..].trim_start_matches('.');
+                    parts.push(TemplatePart::Binding(name.to_string(), path.to_string()));
+                }
+                _ => text.push(c),
+            }
+        }
+
+        if !text.is_empty()
+        {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        Self{ parts }
+    }
+
+    /// Checks if this template has any placeholder, so it must be resolved again whenever the bound components change.
+    pub fn is_bound(
+        &self
+    ) -> bool {
+        self.parts.iter()
+            .any(|part| matches!(part, TemplatePart::Binding(..)))
+    }
+
+    /// Names of the bindings referenced by placeholders.
+    pub(crate) fn bindings(
+        &self
+    ) -> impl Iterator<Item = &str> {
+        self.parts.iter()
+            .filter_map(|part| match part
+            {
+                TemplatePart::Binding(name, _path) => Some(name.as_str()),
+                TemplatePart::Text(_) => None,
+            })
+    }
+
+    /// Builds the text, replacing each placeholder with the value returned by `value` for its binding name and field path.
+    ///
+    /// Placeholders without a value are kept as they were written, so missing bindings are visible.
+    pub fn resolve(
+        &self,
+        mut value: impl FnMut(&str, &str) -> Option<String>
+    ) -> String {
+        let mut result = String::new();
+        for part in self.parts.iter()
+        {
+            match part
+            {
+                TemplatePart::Text(text) => result.push_str(text),
+                TemplatePart::Binding(name, path) => match value(name, path)
+                {
+                    Some(value) => result.push_str(&value),
+                    None if path.is_empty() => result.push_str(&format!("{{{name}}}")),
+                    None => result.push_str(&format!("{{{name}.{path}}}")),
+                },
+            }
+        }
+
+        result
+    }
+}
+
+/// Gets the reflected component of a binding registered with
+/// [`register_text_binding`](crate::RegisterTextBinding::register_text_binding).
+pub(crate) trait TextBinding: Send + Sync
+{
+    fn get<'w>(
+        &self,
+        entity: &EntityRef<'w>
+    ) -> Option<&'w dyn Reflect>;
+
+    /// Gets when the bound component of the given entity was added and last changed.
+    fn ticks(
+        &self,
+        entity: &EntityRef
+    ) -> Option<ComponentTicks>;
+}
+
+struct ComponentBinding<T>(PhantomData<T>);
+
+impl<T> TextBinding
+for ComponentBinding<T>
+where
+    T: Component + Reflect,
+{
+    fn get<'w>(
+        &self,
+        entity: &EntityRef<'w>
+    ) -> Option<&'w dyn Reflect> {
+        entity.get::<T>().map(T::as_reflect)
+    }
+
+    fn ticks(
+        &self,
+        entity: &EntityRef
+    ) -> Option<ComponentTicks> {
+        entity.get_change_ticks::<T>()
+    }
+}
+
+/// Components which fields can be referenced by `text-content` placeholders, by their binding name.
+#[derive(Default, Resource)]
+pub(crate) struct TextBindingRegistry(HashMap<String, Box<dyn TextBinding>>);
+
+impl TextBindingRegistry
+{
+    pub fn register<T>(
+        &mut self,
+        name: &str
+    ) where
+        T: Component + Reflect,
+    {
+        self.0.insert(name.to_string(), Box::new(ComponentBinding::<T>(PhantomData)));
+    }
+
+    /// Formats the field on the given path of the bound component, found on the given entity or its closest ancestor which has it.
+    fn value(
+        &self,
+        q_entities: &Query<EntityRef>,
+        entity: Entity,
+        name: &str,
+        path: &str
+    ) -> Option<String> {
+        let binding = self.0.get(name)?;
+
+        let mut current = q_entities.get(entity).ok();
+        while let Some(entity) = current
+        {
+            if let Some(component) = binding.get(&entity)
+            {
+                return match path.is_empty()
+                {
+                    true => format_value(component),
+                    false => component.reflect_path(path).ok().and_then(format_value),
+                };
+            }
+
+            current = entity.get::<Parent>().and_then(|parent| q_entities.get(parent.get()).ok());
+        }
+
+        None
+    }
+
+    /// Checks if the bound component found for the given entity, like by [`value`](TextBindingRegistry::value),
+    /// changed since the system last ran, or if the entity or any ancestor on the way was moved to another parent.
+    fn binding_changed(
+        &self,
+        q_entities: &Query<EntityRef>,
+        entity: Entity,
+        name: &str,
+        ticks: &SystemChangeTick
+    ) -> bool {
+        let Some(binding) = self.0.get(name)
+        else {
+            return false;
+        };
+
+        let is_changed = |component: ComponentTicks| component.is_changed(ticks.last_run(), ticks.this_run());
+        let mut current = q_entities.get(entity).ok();
+        while let Some(entity) = current
+        {
+            if let Some(component) = binding.ticks(&entity)
+            {
+                return is_changed(component);
+            }
+
+            if entity.get_change_ticks::<Parent>().is_some_and(is_changed)
+            {
+                return true;
+            }
+
+            current = entity.get::<Parent>().and_then(|parent| q_entities.get(parent.get()).ok());
+        }
+
+        false
+    }
+}
+
+/// Formats a reflected value, like numbers, booleans, strings, enum variants and tuple structs with a single field, like `Health(10)`.
+fn format_value(
+    value: &dyn Reflect
+) -> Option<String> {
+    if let Some(value) = value.downcast_ref::<String>()
+    {
+        return Some(value.clone());
+    }
+
+    macro_rules! format_as
+    {
+        ($($type:ty),*) => {
+            $(
+                if let Some(value) = value.downcast_ref::<$type>()
+                {
+                    return Some(value.to_string());
+                }
+            )*
+        };
+    }
+
+    format_as!(bool, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+    match value.reflect_ref()
+    {
+        ReflectRef::Enum(value) => Some(value.variant_name().to_string()),
+        ReflectRef::TupleStruct(value) if value.field_len() == 1 => value.field(0).and_then(format_value),
+        _ => None,
+    }
+}
+
+/// Templates of bound texts along with all entities, to read bound components from, and the texts to write.
+type QueryBoundTexts<'w, 's> = ParamSet<
+    'w, 's,
+    (
+        (Query<'static, 'static, (Entity, Ref<'static, TextTemplateSource>)>, Query<'static, 'static, EntityRef<'static>>),
+        Query<'static, 'static, (&'static mut TextTemplateSource, &'static mut Text)>,
+    ),
+>;
+
+/// Resolves again the `text-content` of entities with placeholders, writing the text only when the resolved value changes.
+///
+/// Templates are only resolved again when they change, or when a bound component they reference changes,
+/// so the text follows them without styling the entity again.
+pub(crate) fn sync_text_bindings(
+    registry: Res<TextBindingRegistry>,
+    ticks: SystemChangeTick,
+    mut queries: QueryBoundTexts,
+) {
+    let (q_sources, q_entities) = queries.p0();
+    let changes: Vec<(Entity, String)> = q_sources.iter()
+        .filter(|(entity, source)| source.is_changed()
+            || source.template.bindings().any(|name| registry.binding_changed(&q_entities, *entity, name, &ticks))
+        )
+        .filter_map(|(entity, source)|
+        {
+            let value = source.template.resolve(|name, path| registry.value(&q_entities, entity, name, path));
+            (source.resolved.as_ref() != Some(&value)).then_some((entity, value))
+        })
+        .collect();
+
+    let mut q_texts = queries.p1();
+    for (entity, value) in changes
+    {
+        let Ok((mut source, mut text)) = q_texts.get_mut(entity)
+        else {
+            continue;
+        };
+
+        // Only the template is set by the property, so remembering the text doesn't resolve it again
+        source.bypass_change_detection().resolved = Some(value.clone());
+        set_sections(&mut text, |section| &mut section.value, value);
+    }
+}
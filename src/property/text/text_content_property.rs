@@ -1,29 +1,58 @@
+use super::TextTemplate;
 use crate::{
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{Property, PropertyContext, PropertyValues, set_sections},
 };
 
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
-        AssetServer,
         Commands,
+        Component,
+        Entity,
         Node,
         Text,
         With,
     },
 };
 
+/// Keeps the [`TextTemplate`] of an entity which `text-content` has placeholders, so it can be resolved again
+/// whenever the bound components change.
+#[derive(Debug, Default, Clone, Component)]
+pub struct TextTemplateSource
+{
+    pub(super) template: TextTemplate,
+    /// Last text written from the template, so the text is only written when it changes.
+    pub(super) resolved: Option<String>,
+}
+
+impl TextTemplateSource
+{
+    /// Gets the template applied.
+    pub fn template(
+        &self
+    ) -> &TextTemplate {
+        &self.template
+    }
+}
+
 /// Apply a custom `text-content` which updates [`TextSection::value`](`TextSection`) of all sections on matched [`Text`] components
+///
+/// The content may reference fields of components registered with [`register_text_binding`](crate::RegisterTextBinding::register_text_binding),
+/// like `text-content: "HP: {health.current}";`, see [`TextTemplate`]. Those are kept on [`TextTemplateSource`] and resolved
+/// again on every frame, so the text follows the bound components.
+///
+/// Since the original text isn't known, it isn't reverted by [`clear_styles`](crate::system::clear_styles).
 #[derive(Default)]
 pub struct TextContentProperty;
 
 impl Property
 for TextContentProperty
 {
-    type Cache = String;
-    type Components = &'static mut Text;
+    type Cache = TextTemplate;
+    type Components = (Entity, &'static mut Text, Option<&'static TextTemplateSource>);
     type Filters = With<Node>;
+    type Param = ();
 
     fn name(
         // no args
@@ -36,21 +65,45 @@ for TextContentProperty
     ) -> Result<Self::Cache, BevyCssError> {
         match values.string()
         {
-            Some(content) => Ok(content),
+            Some(content) => Ok(TextTemplate::parse(&content)),
             None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
         }
     }
 
     fn apply<'w>(
         cache: &Self::Cache,
-        mut components: QueryItem<Self::Components>,
-        _asset_server: &AssetServer,
-        _commands: &mut Commands,
+        (entity, mut text, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
     ) {
+        if cache.is_bound()
+        {
+            // Placeholders are resolved along with the world, once commands are applied
+            if source.map_or(true, |source| source.template != *cache)
+            {
+                commands.entity(entity).try_insert(TextTemplateSource { template: cache.clone(), resolved: None });
+            }
+            return;
+        }
+
+        if source.is_some()
+        {
+            commands.entity(entity).remove::<TextTemplateSource>();
+        }
+
         // TODO: Maybe change this so each line break is a new section
-        for section in components.sections.iter_mut()
+        set_sections(&mut text, |section| &mut section.value, cache.resolve(|_name, _path| None));
+    }
+
+    fn reset<'w>(
+        (entity, _text, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        // The text stops following the bound components, but it's kept since the original text isn't known
+        if source.is_some()
         {
-            section.value = cache.clone()
+            commands.entity(entity).remove::<TextTemplateSource>();
         }
     }
 }
@@ -0,0 +1,432 @@
+use crate::{
+    prelude::{BevyCssError, CssIgnore},
+    property::{colors, Property, PropertyContext, PropertyToken, PropertyValues, UnitCategory},
+};
+
+use bevy::{
+    ecs::query::QueryItem,
+    log::warn,
+    math::Vec2,
+    prelude::{
+        BuildWorldChildren,
+        Changed,
+        Children,
+        Color,
+        Commands,
+        Component,
+        DespawnRecursiveExt,
+        Entity,
+        Node,
+        Local,
+        Or,
+        ParamSet,
+        Parent,
+        PositionType,
+        Query,
+        RemovedComponents,
+        Style,
+        Text,
+        TextBundle,
+        TextSection,
+        TextStyle,
+        Transform,
+        Visibility,
+        With,
+        Without,
+        World,
+    },
+    ui::{UiRect, Val},
+    utils::HashSet,
+};
+
+/// Shadow declared by `text-shadow`, drawn behind the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextShadow
+{
+    /// Offset of the shadow from the text, in pixels, where positive values move it right and down.
+    pub offset: Vec2,
+    /// Color of all sections of the shadow.
+    pub color: Color,
+}
+
+/// Kept on entities styled with `text-shadow`, along with the entity spawned to render their shadow, see [`TextShadowOf`].
+#[derive(Debug, Clone, Copy)]
+#[derive(Component)]
+pub struct StyledTextShadow
+{
+    /// Shadow currently applied.
+    pub shadow: TextShadow,
+    /// Entity rendering the shadow.
+    entity: Entity,
+}
+
+impl StyledTextShadow
+{
+    /// Gets the entity rendering the shadow.
+    pub fn entity(
+        &self
+    ) -> Entity {
+        self.entity
+    }
+}
+
+/// Marks an entity spawned by `text-shadow` to render the shadow of the given entity.
+///
+/// It's owned by `tomt_bevycss`, which keeps it in sync with the text of its owner and despawns it once the property
+/// is reverted or the owner is despawned. It also has [`CssIgnore`], so it's never styled by sheets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Component)]
+pub struct TextShadowOf(pub Entity);
+
+/// Copies the sections of the given text with the given color.
+fn shadow_sections(
+    text: &Text,
+    color: Color
+) -> Vec<TextSection> {
+    text.sections.iter()
+        .map(|section| TextSection {
+            value: section.value.clone(),
+            style: TextStyle { color, ..section.style.clone() },
+        })
+        .collect()
+}
+
+/// Copies the style of the owner of a shadow, so it's laid out the same way, like hidden by `display: none`.
+///
+/// Shadows are positioned with [`PositionType::Absolute`], so they don't change the layout of their siblings,
+/// and they keep the given size, which is the one of their owner once it's laid out, see [`place_text_shadows`].
+fn shadow_style(
+    owner: &Style,
+    width: Val,
+    height: Val
+) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        margin: UiRect::DEFAULT,
+        left: Val::Auto,
+        right: Val::Auto,
+        top: Val::Auto,
+        bottom: Val::Auto,
+        width,
+        height,
+        ..owner.clone()
+    }
+}
+
+/// Marks texts which shadow can't be drawn since they have no parent, so it's only warned about once.
+#[derive(Component)]
+pub(crate) struct UnparentedTextShadow;
+
+/// Spawns the entity rendering the shadow of the given entity, right before it on its parent, so it's drawn behind it.
+///
+/// Shadows are styled like their owner, see [`shadow_style`], and moved over it once it's laid out, see [`place_text_shadows`].
+fn spawn_text_shadow(
+    world: &mut World,
+    owner: Entity,
+    shadow: TextShadow
+) {
+    let Some(owner_ref) = world.get_entity(owner)
+    else {
+        return;
+    };
+
+    // Shadow may be applied again before the spawned one is seen
+    if owner_ref.contains::<StyledTextShadow>()
+    {
+        return;
+    }
+
+    let Some(parent) = owner_ref.get::<Parent>().map(Parent::get)
+    else {
+        if !owner_ref.contains::<UnparentedTextShadow>()
+        {
+            warn!("Text shadow of entity {owner:?} can't be drawn behind it, since it has no parent");
+            world.entity_mut(owner).insert(UnparentedTextShadow);
+        }
+        return;
+    };
+
+    let Some(text) = owner_ref.get::<Text>()
+    else {
+        return;
+    };
+
+    let text = Text {
+        sections: shadow_sections(text, shadow.color),
+        ..text.clone()
+    };
+    let style = owner_ref.get::<Style>()
+        .map_or_else(Style::default, |style| shadow_style(style, style.width, style.height));
+
+    let index = world.get::<Children>(parent)
+        .and_then(|children| children.iter().position(|child| *child == owner))
+        .unwrap_or_default();
+
+    let entity = world.spawn((TextBundle { text, style, ..Default::default() }, TextShadowOf(owner), CssIgnore))
+        .id();
+    world.entity_mut(parent).insert_children(index, &[entity]);
+    world.entity_mut(owner)
+        .insert(StyledTextShadow { shadow, entity })
+        .remove::<UnparentedTextShadow>();
+}
+
+/// Despawns the given shadow entity, if it still exists.
+fn despawn_text_shadow(
+    world: &mut World,
+    shadow: Entity
+) {
+    if let Some(shadow) = world.get_entity_mut(shadow)
+    {
+        shadow.despawn_recursive();
+    }
+}
+
+/// Parses a single shadow offset, which must be a length.
+fn parse_offset(
+    token: &PropertyToken
+) -> Option<f32> {
+    match token.to_val()
+    {
+        Some(Val::Px(offset)) => Some(offset),
+        _ => None,
+    }
+}
+
+/// Applies the `text-shadow` property, like `text-shadow: 1px 1px #000;`, which draws a copy of the text behind matched [`Text`] components.
+///
+/// Bevy can't draw text shadows, so a [`TextShadowOf`] entity with a copy of the text is spawned right before the styled entity,
+/// see [`StyledTextShadow`]. The copy follows any change on the text, like by `text-content`, and it's despawned once the property is reverted.
+///
+/// The offsets are lengths, and the color is optional, defaulting to black. Since shadows can't be blurred,
+/// only a blur radius of `0` is accepted, and `none` removes the shadow.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::{math::Vec2, prelude::Color};
+/// # use tomt_bevycss::{prelude::*, property::text::TextShadowProperty};
+/// let shadow = TextShadowProperty::parse(&PropertyValues::parse("1px 2px #f00").unwrap()).unwrap().unwrap();
+///
+/// assert_eq!(shadow.offset, Vec2::new(1.0, 2.0));
+/// assert_eq!(shadow.color, Color::RED);
+/// assert!(TextShadowProperty::parse(&PropertyValues::parse("1px 2px 4px").unwrap()).is_err());
+/// ```
+#[derive(Default)]
+pub struct TextShadowProperty;
+
+impl Property
+for TextShadowProperty
+{
+    type Cache = Option<TextShadow>;
+    type Components = (Entity, Option<&'static mut StyledTextShadow>);
+    type Filters = (With<Node>, With<Text>, Without<TextShadowOf>);
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "text-shadow"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        if values.len() == 1 && values.keyword(&["none"]).is_some()
+        {
+            return Ok(None);
+        }
+
+        values.expect_unit(Self::name(), UnitCategory::Length)?;
+
+        // The color may be either the first or the last value, like on `css`
+        let (color, lengths) = match values.as_slice()
+        {
+            [first, rest @ ..] if parse_offset(first).is_none() => (colors::parse_color(first), rest),
+            [rest @ .., last] if parse_offset(last).is_none() => (colors::parse_color(last), rest),
+            all => (Some(Color::BLACK), all),
+        };
+
+        let Some(color) = color
+        else {
+            return Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()));
+        };
+
+        let lengths: Option<Vec<f32>> = lengths.iter().map(parse_offset).collect();
+        match lengths.as_deref()
+        {
+            Some([x, y]) => Ok(Some(TextShadow { offset: Vec2::new(*x, *y), color })),
+            Some([x, y, blur]) if *blur == 0.0 => Ok(Some(TextShadow { offset: Vec2::new(*x, *y), color })),
+            Some([_x, _y, _blur]) => Err(BevyCssError::UnsupportedPropertyValue(format!("{}: blur radius", Self::name()))),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, styled): QueryItem<Self::Components>,
+        context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match (cache, styled)
+        {
+            (Some(shadow), Some(mut styled)) => if styled.shadow != *shadow
+            {
+                styled.shadow = *shadow;
+            },
+            (Some(shadow), None) => {
+                let shadow = *shadow;
+                commands.add(move |world: &mut World| spawn_text_shadow(world, entity, shadow));
+            }
+            (None, styled) => Self::reset((entity, styled), context, commands),
+        }
+    }
+
+    fn reset<'w>(
+        (entity, styled): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if let Some(styled) = styled
+        {
+            let shadow = styled.entity;
+            commands.entity(entity).remove::<StyledTextShadow>();
+            commands.add(move |world: &mut World| despawn_text_shadow(world, shadow));
+        }
+    }
+}
+
+/// Texts with a shadow which changed since it was last synced.
+type QueryChangedShadowedTexts<'w, 's> = Query<
+    'w, 's,
+    (&'static Text, &'static StyledTextShadow),
+    (Or<(Changed<Text>, Changed<StyledTextShadow>)>, Without<TextShadowOf>),
+>;
+
+/// Styled entities with a shadow which style changed since it was last synced.
+type QueryChangedShadowedStyles<'w, 's> = Query<
+    'w, 's,
+    (&'static Style, &'static StyledTextShadow),
+    (Or<(Changed<Style>, Changed<StyledTextShadow>)>, Without<TextShadowOf>),
+>;
+
+/// Copies the text and style of entities with a shadow to their [`TextShadowOf`] entity, whenever either changes,
+/// and despawns shadows which owner no longer has [`StyledTextShadow`], like when it's despawned.
+pub(crate) fn sync_text_shadows(
+    q_changed: QueryChangedShadowedTexts,
+    q_changed_styles: QueryChangedShadowedStyles,
+    q_owners: Query<&StyledTextShadow>,
+    mut q_shadows: Query<(Entity, &TextShadowOf, &mut Text, &mut Style)>,
+    mut removed: RemovedComponents<StyledTextShadow>,
+    mut commands: Commands,
+) {
+    for (text, styled) in q_changed.iter()
+    {
+        let Ok((_entity, _owner, mut shadow_text, _style)) = q_shadows.get_mut(styled.entity)
+        else {
+            continue;
+        };
+
+        shadow_text.sections = shadow_sections(text, styled.shadow.color);
+        shadow_text.alignment = text.alignment;
+        shadow_text.linebreak_behavior = text.linebreak_behavior;
+    }
+
+    for (style, styled) in q_changed_styles.iter()
+    {
+        let Ok((_entity, _owner, _text, mut current)) = q_shadows.get_mut(styled.entity)
+        else {
+            continue;
+        };
+
+        let synced = shadow_style(style, current.width, current.height);
+        if *current != synced
+        {
+            *current = synced;
+        }
+    }
+
+    // Owners despawned also lose their `StyledTextShadow`
+    let removed: HashSet<Entity> = removed.read().collect();
+    if removed.is_empty()
+    {
+        return;
+    }
+
+    for (entity, owner, _text, _style) in q_shadows.iter()
+    {
+        if removed.contains(&owner.0) && !q_owners.get(owner.0).is_ok_and(|styled| styled.entity == entity)
+        {
+            commands.add(move |world: &mut World| despawn_text_shadow(world, entity));
+        }
+    }
+}
+
+/// Laid out entities with a shadow.
+type QueryShadowedNodes<'w, 's> = Query<
+    'w, 's,
+    (&'static StyledTextShadow, &'static Node, &'static Transform, Option<&'static Visibility>),
+    Without<TextShadowOf>,
+>;
+
+/// Entities with a shadow which layout, visibility or shadow changed since it was last placed.
+type QueryChangedShadowedNodes<'w, 's> = Query<
+    'w, 's,
+    Entity,
+    (
+        With<StyledTextShadow>,
+        Without<TextShadowOf>,
+        Or<(Changed<Node>, Changed<Transform>, Changed<Visibility>, Changed<StyledTextShadow>)>,
+    ),
+>;
+
+/// Shadows placed again by `bevy_ui`, which lays out their translation every frame, and the shadows themselves.
+type QueryShadows<'w, 's> = ParamSet<'w, 's, (
+    Query<'static, 'static, &'static TextShadowOf, Changed<Transform>>,
+    Query<'static, 'static, (&'static mut Style, &'static mut Transform, &'static mut Visibility), With<TextShadowOf>>,
+)>;
+
+/// Moves shadows over their owner, offset by [`TextShadow::offset`], and keeps them with the same size and [`Visibility`].
+///
+/// Must run after `bevy_ui` lays out nodes, which places shadows where they'd be without an offset, and before transforms are propagated.
+/// Since shadows share the parent of their owner, both are translated from the same origin.
+/// Only shadows which owner changed, or which were laid out again, are placed.
+pub(crate) fn place_text_shadows(
+    q_owners: QueryShadowedNodes,
+    q_changed: QueryChangedShadowedNodes,
+    mut q_shadows: QueryShadows,
+    mut owners: Local<Vec<Entity>>,
+) {
+    owners.clear();
+    owners.extend(q_changed.iter());
+    owners.extend(q_shadows.p0().iter().map(|owner| owner.0));
+
+    let mut q_shadows = q_shadows.p1();
+    for (styled, node, transform, visibility) in owners.drain(..).filter_map(|owner| q_owners.get(owner).ok())
+    {
+        let Ok((mut style, mut shadow_transform, mut shadow_visibility)) = q_shadows.get_mut(styled.entity)
+        else {
+            continue;
+        };
+
+        let translation = transform.translation + styled.shadow.offset.extend(0.0);
+        if shadow_transform.translation != translation
+        {
+            shadow_transform.translation = translation;
+        }
+
+        // Shadows are laid out on their own, so the size of the owner is kept to wrap the text on the same places
+        let size = node.size();
+        if size != Vec2::ZERO && (style.width != Val::Px(size.x) || style.height != Val::Px(size.y))
+        {
+            style.width = Val::Px(size.x);
+            style.height = Val::Px(size.y);
+        }
+
+        let visibility = visibility.copied().unwrap_or_default();
+        if *shadow_visibility != visibility
+        {
+            *shadow_visibility = visibility;
+        }
+    }
+}
+
@@ -0,0 +1,222 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues},
+};
+
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Added,
+        Changed,
+        Commands,
+        Component,
+        DetectChangesMut,
+        Entity,
+        Mut,
+        Node,
+        Or,
+        Query,
+        Text,
+        With,
+    },
+};
+
+/// Transformation applied on the displayed text by `text-transform` property.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextTransform
+{
+    /// Keeps the text as it is.
+    #[default]
+    None,
+    /// Converts all characters to upper case.
+    Uppercase,
+    /// Converts all characters to lower case.
+    Lowercase,
+    /// Converts the first character of each word to upper case.
+    Capitalize,
+}
+
+impl TextTransform
+{
+    /// Transforms the given text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::property::text::TextTransform;
+    /// assert_eq!(TextTransform::Capitalize.transform("hello world"), "Hello World");
+    /// ```
+    pub fn transform(
+        &self,
+        text: &str
+    ) -> String {
+        match self
+        {
+            TextTransform::None => text.to_string(),
+            TextTransform::Uppercase => text.to_uppercase(),
+            TextTransform::Lowercase => text.to_lowercase(),
+            TextTransform::Capitalize => {
+                let mut result = String::with_capacity(text.len());
+                let mut word_start = true;
+
+                for c in text.chars()
+                {
+                    match word_start
+                    {
+                        true => result.extend(c.to_uppercase()),
+                        false => result.push(c),
+                    }
+
+                    word_start = c.is_whitespace();
+                }
+
+                result
+            }
+        }
+    }
+}
+
+/// Keeps the untransformed [`TextSection::value`](bevy::prelude::TextSection) of an entity styled with `text-transform`,
+/// so the transformation can be changed or reverted without losing the original text.
+#[derive(Debug, Default, Clone, Component)]
+pub struct TextTransformSource
+{
+    transform: TextTransform,
+    sections: Vec<String>,
+}
+
+impl TextTransformSource
+{
+    /// Gets the untransformed value of each section.
+    pub fn sections(
+        &self
+    ) -> &[String] {
+        &self.sections
+    }
+
+    /// Updates the untransformed values with any section changed since it was last transformed,
+    /// like when the text is updated by [`TextContentProperty`](super::TextContentProperty) or by user code.
+    fn sync(
+        &mut self,
+        text: &Text
+    ) {
+        self.sections.resize(text.sections.len(), Default::default());
+
+        for (source, section) in self.sections.iter_mut().zip(text.sections.iter())
+        {
+            if self.transform.transform(source) != section.value
+            {
+                *source = section.value.clone();
+            }
+        }
+    }
+
+    /// Writes the transformed values on the given text, only touching the sections which differ.
+    fn write(
+        &self,
+        text: &mut Mut<Text>
+    ) {
+        for (index, source) in self.sections.iter().enumerate()
+        {
+            let value = self.transform.transform(source);
+            if text.sections[index].value != value
+            {
+                text.sections[index].value = value;
+            }
+        }
+    }
+}
+
+/// Applies the `text-transform` property on [`TextSection::value`](bevy::prelude::TextSection) of all sections on matched [`Text`] components.
+///
+/// The original text is kept on [`TextTransformSource`] and the transformation is applied again whenever the text changes.
+/// Supports `none`, `uppercase`, `lowercase` and `capitalize`.
+#[derive(Default)]
+pub struct TextTransformProperty;
+
+impl Property
+for TextTransformProperty
+{
+    type Cache = TextTransform;
+    type Components = (Entity, &'static mut Text, Option<&'static mut TextTransformSource>);
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "text-transform"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        match values.keyword(&["none", "uppercase", "lowercase", "capitalize"])
+        {
+            Some("none") => Ok(TextTransform::None),
+            Some("uppercase") => Ok(TextTransform::Uppercase),
+            Some("lowercase") => Ok(TextTransform::Lowercase),
+            Some("capitalize") => Ok(TextTransform::Capitalize),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, mut text, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        match source
+        {
+            Some(mut source) => {
+                // The source only keeps track of the original text, so only changes on the text itself are detected
+                let source = source.bypass_change_detection();
+                source.sync(&text);
+                source.transform = *cache;
+                source.write(&mut text);
+            }
+            None => {
+                let source = TextTransformSource {
+                    transform: *cache,
+                    sections: text.sections.iter().map(|section| section.value.clone()).collect(),
+                };
+
+                source.write(&mut text);
+                commands.entity(entity).try_insert(source);
+            }
+        }
+    }
+
+    fn reset<'w>(
+        (entity, mut text, source): QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        commands: &mut Commands,
+    ) {
+        if let Some(mut source) = source
+        {
+            source.sync(&text);
+            source.transform = TextTransform::None;
+            source.write(&mut text);
+            commands.entity(entity).remove::<TextTransformSource>();
+        }
+    }
+}
+
+/// Texts which were transformed for the first time or changed since the last transformation.
+type QueryTransformedTexts<'w, 's> = Query<
+    'w, 's,
+    (&'static mut Text, &'static mut TextTransformSource),
+    Or<(Added<TextTransformSource>, Changed<Text>)>,
+>;
+
+/// Applies again the `text-transform` of entities which text was changed, like by [`TextContentProperty`](super::TextContentProperty).
+pub(crate) fn sync_text_transform(
+    mut q_texts: QueryTransformedTexts
+) {
+    for (mut text, mut source) in q_texts.iter_mut()
+    {
+        source.sync(&text);
+        source.write(&mut text);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues, set_if_changed},
+};
+
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        Commands,
+        Node,
+        Text,
+        With,
+    },
+    text::BreakLineOn,
+};
+
+/// Applies the `white-space` property on [`Text::linebreak_behavior`](`BreakLineOn`) of matched components.
+///
+/// Bevy always breaks lines on explicit newlines, like the ones written with the `\A` escape on `text-content: "first\A second";`,
+/// so `pre` and `pre-wrap` only choose whether long lines are also wrapped. `nowrap` and `pre` map to [`BreakLineOn::NoWrap`],
+/// while `normal`, `pre-wrap` and `pre-line` map to [`BreakLineOn::WordBoundary`]. Since Bevy never collapses whitespace,
+/// newlines are kept even with `normal`, and `break-spaces` is reported as unsupported.
+#[derive(Default)]
+pub struct WhiteSpaceProperty;
+
+impl Property
+for WhiteSpaceProperty
+{
+    // Using Option since Cache must impl Default, which BreakLineOn doesn't
+    type Cache = Option<BreakLineOn>;
+    type Components = &'static mut Text;
+    type Filters = With<Node>;
+    type Param = ();
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "white-space"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        match values.identifier()
+        {
+            Some("normal" | "pre-wrap" | "pre-line") => Ok(Some(BreakLineOn::WordBoundary)),
+            Some("nowrap" | "pre") => Ok(Some(BreakLineOn::NoWrap)),
+            Some("break-spaces") => Err(BevyCssError::UnsupportedPropertyValue(format!("{}: break-spaces", Self::name()))),
+            _ => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        // Only the default cache is empty, since parsing always sets a value
+        let Some(linebreak_behavior) = *cache
+        else {
+            return;
+        };
+
+        set_if_changed(&mut components, |text| &mut text.linebreak_behavior, linebreak_behavior);
+    }
+
+    fn reset<'w>(
+        mut components: QueryItem<Self::Components>,
+        _context: &PropertyContext<Self>,
+        _commands: &mut Commands,
+    ) {
+        set_if_changed(&mut components, |text| &mut text.linebreak_behavior, Text::default().linebreak_behavior);
+    }
+}
@@ -0,0 +1,545 @@
+use bevy::utils::AHasher;
+use cssparser::CowRcStr;
+use smallvec::{smallvec, SmallVec};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+#[cfg(test)]
+mod tests;
+
+static RULE_COUNTER: Mutex<usize> = Mutex::new(0);
+
+/// Represents a selector element on a style sheet rule.
+/// A single selector can have multiple elements, for instance a selector of `button.enabled`
+/// Would generated two elements, one for `button` and another for `.enabled`.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Hash)]
+pub enum SelectorElement
+{
+    /// A name selector element, like `#score_window`. On CSS used on web, this is as known as id.
+    Name(String),
+
+    /// A component selector element, like `window` or `button`
+    Component(String),
+
+    /// A component selector element which also matches the current variant of a reflected enum component, like `interaction(hovered)`
+    ComponentVariant(String, String),
+
+    /// A component selector element which also matches the value of a reflected field, like `health[current=0]`, with the component,
+    /// field path and expected value. Unit enum variants are matched by the `variant` attribute, like `interaction[variant=pressed]`.
+    ComponentAttribute(String, String, String),
+
+    /// A class name component selector element, `.border`
+    Class(String),
+
+    /// A class selector element matching any class starting with the given prefix, like `.slot-*`
+    ClassPrefix(String),
+
+    /// A class selector element matching any class ending with the given suffix, like `.*-active`
+    ClassSuffix(String),
+
+    #[cfg(feature = "pseudo_class")]
+    /// A class name component selector element, like `:hover` or `:first-child` or `:empty`
+    PseudoClass(String),
+
+    #[cfg(feature = "pseudo_prop")]
+    /// A class name component selector element, like `::first-line` or `::first-letter` or `::marker`
+    PseudoProp(String),
+
+    /// The entity holding the [`StyleSheet`](crate::prelude::StyleSheet) which rule is being applied, `:scope`
+    Scope,
+
+    /// Matches the `an+b`-th sibling having the component of the same compound selector, like `button:nth-of-type(2n+1)`,
+    /// with `a` and `b` respectively.
+    NthOfType(i32, i32),
+
+    /// Indicates a parent-child relation between previous elements and next elements, like `window .border`
+    Child,
+
+    /// Indicates a direct parent-child relation between previous elements and next elements, like `window > .border`
+    DirectChild,
+}
+
+impl SelectorElement
+{
+    /// Checks if this element selects the given class, either exactly, like `.slot`, or by prefix or suffix, like `.slot-*` or `.*-active`.
+    ///
+    /// Elements which aren't classes never match.
+    pub fn matches_class(
+        &self,
+        class: &str
+    ) -> bool {
+        match self
+        {
+            SelectorElement::Class(name) => name == class,
+            SelectorElement::ClassPrefix(prefix) => class.starts_with(prefix.as_str()),
+            SelectorElement::ClassSuffix(suffix) => class.ends_with(suffix.as_str()),
+            _ => false,
+        }
+    }
+}
+
+/// A selector parsed from a `css` rule. Each selector has a internal hash used to differentiate between many rules in the same sheet.
+///
+/// Two rules with the same selector text are still considered different selectors, since the rule loading order is
+/// also taken into account on equality. This allows later rules to override earlier ones, like `a { width: 1px; } a { width: 2px; }`.
+///
+/// Elements are shared by all clones of a selector, so cloning it doesn't allocate.
+#[derive(Clone, Debug)]
+pub struct Selector
+{
+    hash: u64,
+    elements: Arc<[SelectorElement]>,
+    /// Range of elements of each node of the [`get_parent_tree`](Selector::get_parent_tree), computed once when the selector is created.
+    nodes: Arc<[Range<usize>]>,
+    /// Rule loading order from parser
+    load_order: usize,
+}
+
+impl Default
+for Selector
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self{
+            hash: 0,
+            elements: Arc::default(),
+            nodes: std::iter::once(0..0).collect(),
+            load_order: 0,
+        }
+    }
+}
+
+impl Selector
+{
+    /// Creates a new selector for the given elements.
+    pub fn new(
+        elements: SmallVec<[SelectorElement; 8]>
+    ) -> Self {
+        let hasher = AHasher::default();
+
+        let hasher = elements.iter().fold(hasher, |mut hasher, el|
+        {
+            el.hash(&mut hasher);
+            hasher
+        });
+
+        let hash = hasher.finish();
+
+        // Nodes are split by both descendant and child combinators
+        let mut nodes = SmallVec::<[Range<usize>; 8]>::new();
+        let mut start = 0;
+        for (index, element) in elements.iter().enumerate()
+        {
+            if matches!(element, SelectorElement::Child | SelectorElement::DirectChild)
+            {
+                nodes.push(start..index);
+                start = index + 1;
+            }
+        }
+        nodes.push(start..elements.len());
+
+        Self{
+            elements: elements.into_iter().collect(),
+            nodes: nodes.into_iter().collect(),
+            hash,
+            load_order: RULE_COUNTER
+                .lock()
+                .map(|mut lock|
+                {
+                    *lock += 1;
+                    *lock
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Creates a selector matching entities with the given class name, like `.btn`.
+    ///
+    /// Selectors built in code can be combined with [`descendant`](Selector::descendant) and the `with_` functions,
+    /// and are ordered on the cascade like a rule declared after all rules loaded so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::selector::Selector;
+    /// let selector = Selector::id("menu")
+    ///     .descendant(Selector::component("button").with_class("enabled"));
+    ///
+    /// assert_eq!(selector.to_string(), "#menu button.enabled");
+    /// ```
+    pub fn class(
+        name: impl Into<String>
+    ) -> Self {
+        Self::new(smallvec![SelectorElement::Class(name.into())])
+    }
+
+    /// Creates a selector matching entities with the given [`Name`](bevy::prelude::Name), like `#ok`.
+    pub fn id(
+        name: impl Into<String>
+    ) -> Self {
+        Self::new(smallvec![SelectorElement::Name(name.into())])
+    }
+
+    /// Creates a selector matching only the entity holding the [`StyleSheet`](crate::prelude::StyleSheet), like `:scope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::selector::Selector;
+    /// let selector = Selector::scope().child(Selector::class("column"));
+    ///
+    /// assert_eq!(selector.to_string(), ":scope > .column");
+    /// ```
+    pub fn scope(
+        // no args
+    ) -> Self {
+        Self::new(smallvec![SelectorElement::Scope])
+    }
+
+    /// Creates a selector matching entities with the component registered with the given name, like `button`.
+    ///
+    /// See [`register_component_selector`](crate::RegisterComponentSelector::register_component_selector).
+    pub fn component(
+        name: impl Into<String>
+    ) -> Self {
+        Self::new(smallvec![SelectorElement::Component(name.into())])
+    }
+
+    /// Also requires the given class name on the last entity matched by this selector, like `button.enabled`.
+    pub fn with_class(
+        self,
+        name: impl Into<String>
+    ) -> Self {
+        self.with(SelectorElement::Class(name.into()))
+    }
+
+    /// Also requires the given [`Name`](bevy::prelude::Name) on the last entity matched by this selector, like `button#ok`.
+    pub fn with_id(
+        self,
+        name: impl Into<String>
+    ) -> Self {
+        self.with(SelectorElement::Name(name.into()))
+    }
+
+    /// Also requires the given pseudo-class on the last entity matched by this selector, like `button:hover`.
+    #[cfg(feature = "pseudo_class")]
+    pub fn with_pseudo_class(
+        self,
+        name: impl Into<String>
+    ) -> Self {
+        self.with(SelectorElement::PseudoClass(name.into()))
+    }
+
+    /// Matches entities matched by `descendant` which are also descendants of any entity matched by this selector, like `#menu .btn`.
+    ///
+    /// Descendants are matched at any depth, use [`child`](Selector::child) to match only direct children.
+    pub fn descendant(
+        self,
+        descendant: Selector
+    ) -> Self {
+        self.combine(SelectorElement::Child, descendant)
+    }
+
+    /// Matches entities matched by `child` which are also direct children of any entity matched by this selector, like `#menu > .btn`.
+    pub fn child(
+        self,
+        child: Selector
+    ) -> Self {
+        self.combine(SelectorElement::DirectChild, child)
+    }
+
+    fn combine(
+        self,
+        combinator: SelectorElement,
+        next: Selector
+    ) -> Self {
+        let mut elements: SmallVec<[SelectorElement; 8]> = self.elements.iter().cloned().collect();
+        if !elements.is_empty() && !next.elements.is_empty()
+        {
+            elements.push(combinator);
+        }
+        elements.extend(next.elements.iter().cloned());
+
+        Self::new(elements)
+    }
+
+    fn with(
+        self,
+        element: SelectorElement
+    ) -> Self {
+        let mut elements: SmallVec<[SelectorElement; 8]> = self.elements.iter().cloned().collect();
+        elements.push(element);
+
+        Self::new(elements)
+    }
+
+    /// Elements of this selector, in the same order they were declared.
+    pub fn elements(
+        &self
+    ) -> &[SelectorElement] {
+        &self.elements
+    }
+
+    /// Checks if this selector depends on the [`Interaction`](bevy::prelude::Interaction) state, like `button:hover .icon`.
+    ///
+    /// `:root` only depends on the UI hierarchy, which is tracked like classes, so it isn't taken into account.
+    #[cfg(feature = "pseudo_class")]
+    pub fn has_pseudo_class(
+        &self
+    ) -> bool {
+        self.elements.iter()
+            .any(|element| matches!(element, SelectorElement::PseudoClass(class) if class != "root"))
+    }
+
+    /// Checks if this selector matches the variant, or a field, of a component, like `interaction(hovered) .icon` or `health[current=0]`.
+    pub fn has_component_variant(
+        &self
+    ) -> bool {
+        self.elements.iter()
+            .any(|element| matches!(element, SelectorElement::ComponentVariant(..) | SelectorElement::ComponentAttribute(..)))
+    }
+
+    /// Combinators between each node of the [`get_parent_tree`](Selector::get_parent_tree), either
+    /// [`SelectorElement::Child`] or [`SelectorElement::DirectChild`].
+    pub(crate) fn combinators(
+        &self
+    ) -> impl Iterator<Item = &SelectorElement> {
+        self.elements.iter()
+            .filter(|element| matches!(element, SelectorElement::Child | SelectorElement::DirectChild))
+    }
+
+    /// Elements of each node of the [`get_parent_tree`](Selector::get_parent_tree), from the top-most ancestor down,
+    /// without allocating a new tree.
+    pub(crate) fn nodes(
+        &self
+    ) -> impl Iterator<Item = &[SelectorElement]> {
+        self.nodes.iter()
+            .map(|range| &self.elements[range.clone()])
+    }
+
+    /// Builds a selector tree for this selector.
+    /// Each node in the tree is composed of many elements, also each node is parent of the next one.
+    ///
+    /// Nodes are split by both descendant and child combinators, see [`combinators`](Selector::combinators).
+    pub fn get_parent_tree(
+        &self
+    ) -> SmallVec<[SmallVec<[&SelectorElement; 8]>; 8]> {
+        self.nodes()
+            .map(|node| node.iter().collect())
+            .collect()
+    }
+}
+
+impl std::fmt::Display
+for Selector
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        let mut buffer = String::new();
+        let mut previous: Option<&SelectorElement> = None;
+
+        for element in self.elements.iter()
+        {
+            match element
+            {
+                SelectorElement::Name(n) => {
+                    buffer.push('#');
+                    buffer.push_str(n);
+                }
+
+                SelectorElement::Component(c) => {
+                    buffer.push_str(c);
+                }
+
+                SelectorElement::ComponentVariant(c, v) => {
+                    buffer.push_str(c);
+                    buffer.push('(');
+                    buffer.push_str(v);
+                    buffer.push(')');
+                }
+
+                SelectorElement::ComponentAttribute(c, field, value) => {
+                    // Chained attributes of the same component are written once, like `health[current=0][name="hero"]`
+                    if !matches!(previous, Some(SelectorElement::ComponentAttribute(p, _, _)) if p == c)
+                    {
+                        buffer.push_str(c);
+                    }
+
+                    buffer.push('[');
+                    buffer.push_str(field);
+                    buffer.push('=');
+                    match value.parse::<f32>().is_ok()
+                    {
+                        true => buffer.push_str(value),
+                        false => cssparser::serialize_string(value, &mut buffer)?,
+                    }
+                    buffer.push(']');
+                }
+
+                SelectorElement::Class(c) => {
+                    buffer.push('.');
+                    buffer.push_str(c);
+                }
+
+                SelectorElement::ClassPrefix(c) => {
+                    buffer.push('.');
+                    buffer.push_str(c);
+                    buffer.push('*');
+                }
+
+                SelectorElement::ClassSuffix(c) => {
+                    buffer.push_str(".*");
+                    buffer.push_str(c);
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::PseudoClass(c) => {
+                    buffer.push(':');
+                    buffer.push_str(c);
+                }
+
+                #[cfg(feature = "pseudo_prop")]
+                SelectorElement::PseudoProp(p) => {
+                    buffer.push_str("::");
+                    buffer.push_str(p);
+                }
+
+                SelectorElement::Scope => {
+                    buffer.push_str(":scope");
+                }
+
+                SelectorElement::NthOfType(a, b) => {
+                    let nth = match (a, b)
+                    {
+                        (0, b) => b.to_string(),
+                        (a, 0) => format!("{a}n"),
+                        (a, b) => format!("{a}n{b:+}"),
+                    };
+                    buffer.push_str(":nth-of-type(");
+                    buffer.push_str(&nth);
+                    buffer.push(')');
+                }
+
+                SelectorElement::Child => {
+                    buffer.push(' ');
+                }
+
+                SelectorElement::DirectChild => {
+                    buffer.push_str(" > ");
+                }
+            }
+
+            previous = Some(element);
+        }
+
+        write!(formatter, "{}", buffer)
+    }
+}
+
+impl PartialEq
+for Selector
+{
+    fn eq(
+        &self,
+        other: &Self
+    ) -> bool {
+        self.hash == other.hash
+            && self.load_order == other.load_order
+    }
+}
+
+impl Eq
+for Selector
+{
+    // nothing to do
+}
+
+impl PartialOrd
+for Selector
+{
+    fn partial_cmp(
+        &self,
+        other: &Self
+    ) -> Option<Ordering> {
+        match self.elements.len().partial_cmp(&other.elements.len())
+        {
+            Some(Ordering::Equal) => self.load_order.partial_cmp(&other.load_order),
+            not_eq => not_eq,
+        }
+    }
+}
+
+impl Ord
+for Selector
+{
+    fn cmp(
+        &self,
+        other: &Self
+    ) -> std::cmp::Ordering {
+        match self.elements.len().cmp(&other.elements.len())
+        {
+            Ordering::Equal => self.load_order.cmp(&other.load_order),
+            not_eq => not_eq,
+        }
+    }
+}
+
+impl Hash
+for Selector
+{
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H
+    ) {
+        self.hash.hash(state);
+        self.load_order.hash(state);
+    }
+}
+
+impl<'i> From<Vec<CowRcStr<'i>>>
+for Selector
+{
+    fn from(
+        input: Vec<CowRcStr<'i>>
+    ) -> Self {
+        let mut elements = smallvec![];
+        let mut next_is_class = false;
+
+        for value in input.into_iter()
+            .filter(|v| !v.is_empty())
+        {
+            if value.as_ref() == "."
+            {
+                next_is_class = true;
+                continue;
+            }
+
+            if let Some(value) = value.strip_prefix('#')
+            {
+                elements.push(SelectorElement::Name(value.to_string()));
+            }
+            else if next_is_class
+            {
+                elements.push(SelectorElement::Class(value.to_string()))
+            }
+            else
+            {
+                elements.push(SelectorElement::Component(value.to_string()))
+            }
+
+            next_is_class = false;
+        }
+
+        Self::new(elements)
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+/// Formats each node of the parent tree of the given selector, like `["#menu", "button.enabled"]`.
+fn parent_tree(
+    selector: &Selector
+) -> Vec<Vec<SelectorElement>> {
+    selector.get_parent_tree()
+        .into_iter()
+        .map(|node| node.into_iter().cloned().collect())
+        .collect()
+}
+
+#[test]
+fn single_element_constructors(
+    // no args
+) {
+    assert_eq!(Selector::class("btn").elements(), [SelectorElement::Class("btn".to_string())]);
+    assert_eq!(Selector::id("ok").elements(), [SelectorElement::Name("ok".to_string())]);
+    assert_eq!(Selector::component("button").elements(), [SelectorElement::Component("button".to_string())]);
+}
+
+#[test]
+fn compound_selector_is_a_single_node(
+    // no args
+) {
+    let selector = Selector::component("button")
+        .with_class("enabled")
+        .with_id("ok");
+
+    assert_eq!(selector.to_string(), "button.enabled#ok");
+    assert_eq!(parent_tree(&selector), [[
+        SelectorElement::Component("button".to_string()),
+        SelectorElement::Class("enabled".to_string()),
+        SelectorElement::Name("ok".to_string()),
+    ]]);
+}
+
+#[test]
+fn descendant_adds_a_node(
+    // no args
+) {
+    let selector = Selector::id("menu")
+        .descendant(Selector::class("row"))
+        .descendant(Selector::component("button").with_class("enabled"));
+
+    assert_eq!(selector.to_string(), "#menu .row button.enabled");
+    assert_eq!(parent_tree(&selector), [
+        vec![SelectorElement::Name("menu".to_string())],
+        vec![SelectorElement::Class("row".to_string())],
+        vec![SelectorElement::Component("button".to_string()), SelectorElement::Class("enabled".to_string())],
+    ]);
+
+    // Nested descendants build the same tree
+    let nested = Selector::id("menu")
+        .descendant(Selector::class("row").descendant(Selector::component("button").with_class("enabled")));
+    assert_eq!(parent_tree(&nested), parent_tree(&selector));
+}
+
+#[test]
+fn child_adds_a_node(
+    // no args
+) {
+    let selector = Selector::scope()
+        .child(Selector::class("column"))
+        .descendant(Selector::class("cell"));
+
+    assert_eq!(selector.to_string(), ":scope > .column .cell");
+    assert_eq!(parent_tree(&selector), [
+        vec![SelectorElement::Scope],
+        vec![SelectorElement::Class("column".to_string())],
+        vec![SelectorElement::Class("cell".to_string())],
+    ]);
+    assert!(selector.combinators().eq([&SelectorElement::DirectChild, &SelectorElement::Child]));
+}
+
+#[test]
+fn built_selectors_match_parsed_ones(
+    // no args
+) {
+    let sheet = crate::prelude::StyleSheetAsset::parse("", "#menu button.enabled { width: 1px; }");
+    let parsed = &sheet.iter().next().unwrap().selector;
+    let built = Selector::id("menu").descendant(Selector::component("button").with_class("enabled"));
+
+    assert_eq!(built.elements(), parsed.elements());
+}
+
+#[test]
+fn nodes_are_computed_once_and_shared_by_clones(
+    // no args
+) {
+    let selector = Selector::id("menu")
+        .child(Selector::class("row"))
+        .descendant(Selector::component("button").with_class("enabled"));
+
+    let nodes: Vec<&[SelectorElement]> = selector.nodes().collect();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes.iter().map(|node| node.to_vec()).collect::<Vec<_>>(), parent_tree(&selector));
+
+    let clone = selector.clone();
+    assert!(std::ptr::eq(clone.elements(), selector.elements()), "Clones should share elements");
+    assert!(clone.nodes().eq(selector.nodes()));
+
+    // Empty selectors still have a single empty node, like an empty parent tree
+    assert_eq!(Selector::default().nodes().count(), 1);
+    assert_eq!(parent_tree(&Selector::default()), vec![vec![]]);
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn pseudo_class_is_on_the_last_node(
+    // no args
+) {
+    let selector = Selector::class("menu").descendant(Selector::component("button").with_pseudo_class("hover"));
+
+    assert!(selector.has_pseudo_class());
+    assert_eq!(selector.to_string(), ".menu button:hover");
+}
@@ -1,8 +1,14 @@
 mod style_rule;
-pub use style_rule::StyleRule;
+pub use style_rule::{SourceLocation, StyleRule};
 
 mod style_sheet_asset;
 pub use style_sheet_asset::StyleSheetAsset;
 
+mod sheet_diagnostic;
+pub use sheet_diagnostic::{KnownNames, SheetDiagnostic};
+
+mod style_sheet_stats;
+pub use style_sheet_stats::StyleSheetStats;
+
 mod style_sheet_loader;
 pub(crate) use style_sheet_loader::StyleSheetLoader;
@@ -0,0 +1,27 @@
+use super::SourceLocation;
+use crate::error::BevyCssError;
+
+use bevy::utils::HashSet;
+
+/// An error found on a style sheet, along with where it was found, see [`StyleSheetAsset::diagnostics`](super::StyleSheetAsset::diagnostics).
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub struct SheetDiagnostic
+{
+    /// Where the error was found on the sheet.
+    pub location: SourceLocation,
+    /// What went wrong.
+    pub error: BevyCssError,
+}
+
+/// Classes and properties which style sheets are expected to use, checked by [`StyleSheetAsset::validate`](super::StyleSheetAsset::validate).
+///
+/// An empty list isn't checked, so only classes or only properties can be validated.
+#[derive(Clone, Debug, Default)]
+pub struct KnownNames
+{
+    /// Classes which selectors may use, like `button` for `.button`.
+    pub classes: HashSet<String>,
+    /// Properties which rules may declare, like `width`.
+    pub properties: HashSet<String>,
+}
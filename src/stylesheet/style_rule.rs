@@ -1,10 +1,11 @@
 use crate::{
-    property::PropertyValues,
+    property::{PropertyName, PropertyValues},
     selector::Selector,
 };
 
-use bevy::utils::HashMap;
-use std::fmt;
+use bevy::utils::{HashMap, HashSet};
+use indexmap::IndexMap;
+use std::{fmt, sync::Arc};
 
 /// Represents a single rule inside a style sheet with a [`Selector`] which determines which entities
 /// should be applied the [`PropertyValues`].
@@ -17,8 +18,75 @@ pub struct StyleRule
     /// Selector used to match entities to apply properties.
     pub selector: Selector,
 
-    /// Properties values to be applied on selected entities.
-    pub properties: HashMap<String, PropertyValues>,
+    /// Properties values to be applied on selected entities, on the order their winning declarations were declared.
+    ///
+    /// Values are shared, so identical values can be stored only once, see [`StyleSheetAsset::deduplicate`](crate::prelude::StyleSheetAsset::deduplicate).
+    pub properties: IndexMap<PropertyName, Arc<PropertyValues>>,
+
+    /// Name of properties which were declared as `!important`.
+    pub important: HashSet<String>,
+
+    /// Index of the cascade layer declared with `@layer` this rule belongs to, on the
+    /// [`layers`](crate::prelude::StyleSheetAsset::layers) of its sheet.
+    ///
+    /// Rules outside of any layer have `None` and take precedence over all layered rules.
+    pub layer: Option<usize>,
+
+    /// Names of the conditions of the `@when` blocks this rule was declared on, which must all hold for it to be applied,
+    /// see [`CssConditions`](crate::prelude::CssConditions).
+    ///
+    /// Rules outside of any `@when` block have no conditions and are always applied.
+    pub conditions: Vec<String>,
+
+    /// Names of the flags of the `@when` blocks this rule was declared on, like `experiment-b` on `@when experiment-b`,
+    /// which must all be enabled for it to be applied, see [`CssFlags`](crate::prelude::CssFlags).
+    pub flags: Vec<String>,
+
+    /// Names of the capabilities checked by the `@supports` blocks this rule was declared on, which must all be supported
+    /// for it to be applied, see [`CssCapabilities`](crate::prelude::CssCapabilities).
+    pub capabilities: Vec<String>,
+
+    /// Where the selector of this rule was declared on its sheet, used to report it, like on [`CascadeAuditReport`](crate::prelude::CascadeAuditReport).
+    pub location: SourceLocation,
+
+    /// Where the winning declaration of each property was declared on the sheet, see [`property_location`](StyleRule::property_location).
+    pub property_locations: HashMap<String, SourceLocation>,
+}
+
+/// Line and column where a rule was declared on its style sheet, both starting at `1`.
+///
+/// Rules which weren't parsed from a sheet, like the ones created with [`StyleRule::new`], are at line `0`.
+#[derive(Debug, Clone, Copy, Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceLocation
+{
+    pub line: u32,
+    pub column: u32,
+}
+
+impl fmt::Display
+for SourceLocation
+{
+    fn fmt(
+        &self,
+        formatter: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        write!(formatter, "{}:{}", self.line, self.column)
+    }
+}
+
+impl From<cssparser::SourceLocation>
+for SourceLocation
+{
+    fn from(
+        location: cssparser::SourceLocation
+    ) -> Self {
+        // Lines of `cssparser` start at `0`, while columns already start at `1`
+        Self{
+            line: location.line + 1,
+            column: location.column,
+        }
+    }
 }
 
 impl StyleRule
@@ -29,8 +97,66 @@ impl StyleRule
         Self{
             selector,
             properties: Default::default(),
+            important: Default::default(),
+            layer: None,
+            conditions: Default::default(),
+            flags: Default::default(),
+            capabilities: Default::default(),
+            location: Default::default(),
+            property_locations: Default::default(),
         }
     }
+
+    /// Gets where the given property was declared on the sheet, pointing to the property name, if this rule declares it.
+    ///
+    /// When a property is declared more than once, it's the location of the declaration which won, like the `!important` one.
+    pub fn property_location(
+        &self,
+        name: &str
+    ) -> Option<SourceLocation> {
+        self.property_locations.get(name).copied()
+    }
+
+    /// Checks if the property with the given name was declared as `!important`.
+    pub fn is_important(
+        &self,
+        name: &str
+    ) -> bool {
+        self.important.contains(name)
+    }
+
+    /// Merges the declarations of an earlier rule with the same selector into this one, as if both were applied.
+    ///
+    /// Declarations of this rule win, unless the earlier one was `!important` and this one isn't.
+    pub(crate) fn merge_earlier(
+        &mut self,
+        earlier: StyleRule
+    ) {
+        // Declarations of the earlier rule come first, so the declaration order is kept
+        let mut properties = IndexMap::with_capacity(self.properties.len() + earlier.properties.len());
+        for (name, values) in earlier.properties
+        {
+            let important = earlier.important.contains(&*name);
+            if self.properties.contains_key(&name) && (!important || self.is_important(&name))
+            {
+                continue;
+            }
+
+            if important
+            {
+                self.important.insert(name.to_string());
+            }
+            if let Some(location) = earlier.property_locations.get(&*name)
+            {
+                self.property_locations.insert(name.to_string(), *location);
+            }
+            self.properties.shift_remove(&name);
+            properties.insert(name, values);
+        }
+
+        properties.extend(self.properties.drain(..));
+        self.properties = properties;
+    }
 }
 
 impl fmt::Display
@@ -1,20 +1,25 @@
-use super::StyleRule;
+use super::{KnownNames, SheetDiagnostic, SourceLocation, StyleRule, StyleSheetStats};
 use crate::{
-    parser::StyleSheetParser,
-    property::PropertyValues,
-    selector::Selector,
+    error::BevyCssError,
+    parser::{ParsedStyleSheet, StyleSheetParser},
+    property::{PropertyName, PropertyToken, PropertyValues},
+    selector::{Selector, SelectorElement},
 };
 
 use bevy::{
     log::trace,
     prelude::*,
     reflect::{TypePath, TypeUuid},
-    utils::AHasher,
+    utils::{AHasher, HashMap, HashSet},
 };
 use smallvec::SmallVec;
-use std::hash::{Hash, Hasher};
+use std::{
+    collections::BTreeSet,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[derive(Asset, TypePath, TypeUuid)]
 #[uuid = "14b98dd6-5425-4692-a561-5e6ae9180554"]
 /// A cascading style sheet (`css`) asset file.
@@ -27,6 +32,23 @@ pub struct StyleSheetAsset
     path: String,
     hash: u64,
     rules: SmallVec<[StyleRule; 8]>,
+    /// Paths of style sheets imported with `@import`, as written on the sheet.
+    import_paths: Vec<String>,
+    /// Names of cascade layers declared with `@layer`, indexed by [`StyleRule::layer`].
+    layers: Vec<String>,
+    /// Handles of imported style sheets, resolved by the asset loader.
+    pub(crate) imports: Vec<Handle<StyleSheetAsset>>,
+    /// Index of rules which must be evaluated again when an [`Interaction`], or the variant of a component, changes.
+    #[cfg(feature = "pseudo_class")]
+    interaction_rules: SmallVec<[usize; 8]>,
+    /// Index of rules which must be evaluated again when a pseudo-class state changes, by the name of the pseudo-class,
+    /// see [`find_pseudo_class_rules`](StyleSheetAsset::find_pseudo_class_rules).
+    #[cfg(feature = "pseudo_class")]
+    pseudo_class_rules: HashMap<String, SmallVec<[usize; 8]>>,
+    /// Id of the selector of each rule, shared by all rules with an identical selector, see [`find_selector_ids`](StyleSheetAsset::find_selector_ids).
+    selector_ids: SmallVec<[usize; 8]>,
+    /// Rules and declarations which failed to parse and were skipped.
+    diagnostics: Vec<SheetDiagnostic>,
 }
 
 impl StyleSheetAsset
@@ -41,15 +63,196 @@ impl StyleSheetAsset
     ) -> Self {
         trace!("StyleSheetAsset::parse");
 
+        Self::from_parsed(path, content, StyleSheetParser::parse_sheet(content))
+    }
+
+    /// Parses a style sheet like [`parse`](StyleSheetAsset::parse), but a rule with any invalid declaration is skipped as a whole,
+    /// and reported once on [`diagnostics`](StyleSheetAsset::diagnostics), as used by the `bevycss-check` binary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse_strict("", ".a { width: 10px; height 10px; }\n.b { width: 10px; }");
+    /// assert_eq!(sheet.rule_count(), 1);
+    /// assert_eq!(sheet.diagnostics()[0].location, SourceLocation { line: 1, column: 25 });
+    /// ```
+    pub fn parse_strict(
+        path: &str,
+        content: &str
+    ) -> Self {
+        trace!("StyleSheetAsset::parse_strict");
+
+        Self::from_parsed(path, content, StyleSheetParser::parse_sheet_strict(content))
+    }
+
+    fn from_parsed(
+        path: &str,
+        content: &str,
+        parsed: ParsedStyleSheet
+    ) -> Self {
         let mut hasher = AHasher::default();
         content.hash(&mut hasher);
         let hash = hasher.finish();
 
+        let ParsedStyleSheet { rules, imports: import_paths, layers, errors: diagnostics } = parsed;
+
         Self{
             path: path.to_string(),
             hash,
-            rules: StyleSheetParser::parse(content),
+            import_paths,
+            layers,
+            imports: Default::default(),
+            #[cfg(feature = "pseudo_class")]
+            interaction_rules: Self::find_interaction_rules(&rules),
+            #[cfg(feature = "pseudo_class")]
+            pseudo_class_rules: Self::find_pseudo_class_rules(&rules),
+            selector_ids: Self::find_selector_ids(&rules),
+            diagnostics,
+            rules,
+        }
+    }
+
+    /// Parses the UTF-8 content of a style sheet, like one embedded with `include_bytes!`, see [`parse`](StyleSheetAsset::parse).
+    ///
+    /// Sheets created this way aren't loaded by the [`AssetServer`], so `@import`ed sheets are only loaded when added by
+    /// [`AddStyleSheet::add_style_sheet`](crate::AddStyleSheet::add_style_sheet).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::from_bytes("embedded.css", b".panel { width: 10px; }").unwrap();
+    /// assert_eq!(sheet.rule_count(), 1);
+    ///
+    /// assert!(StyleSheetAsset::from_bytes("embedded.css", &[0xff, 0xfe]).is_err());
+    /// ```
+    pub fn from_bytes(
+        path: &str,
+        bytes: &[u8]
+    ) -> Result<Self, std::str::Utf8Error> {
+        std::str::from_utf8(bytes)
+            .map(|content| Self::parse(path, content))
+    }
+
+    /// Creates a sheet with a single rule made of the given declarations, like `width: 10px; color: red;`,
+    /// as used by [`InlineStyle`](crate::prelude::InlineStyle).
+    ///
+    /// Invalid declarations are skipped and returned along with the sheet.
+    pub(crate) fn parse_inline(
+        content: &str
+    ) -> (Self, Vec<BevyCssError>) {
+        let mut hasher = AHasher::default();
+        content.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let (rule, diagnostics) = StyleSheetParser::parse_declarations(content);
+        let errors = diagnostics.iter()
+            .map(|diagnostic| diagnostic.error.clone())
+            .collect();
+        let rules = smallvec::smallvec![rule];
+
+        let sheet = Self{
+            path: "inline style".to_string(),
+            hash,
+            import_paths: Default::default(),
+            layers: Default::default(),
+            imports: Default::default(),
+            #[cfg(feature = "pseudo_class")]
+            interaction_rules: Self::find_interaction_rules(&rules),
+            #[cfg(feature = "pseudo_class")]
+            pseudo_class_rules: Self::find_pseudo_class_rules(&rules),
+            selector_ids: Self::find_selector_ids(&rules),
+            diagnostics,
+            rules,
+        };
+
+        (sheet, errors)
+    }
+
+    /// Finds which rules are sensitive to [`Interaction`] changes.
+    ///
+    /// Those are the rules with a pseudo-class or component variant selector, like `button:hover` or `interaction(hovered)`, but also any other rule which
+    /// declares the same properties, since they must be applied again once those no longer match.
+    #[cfg(feature = "pseudo_class")]
+    fn find_interaction_rules(
+        rules: &[StyleRule]
+    ) -> SmallVec<[usize; 8]> {
+        let is_sensitive = |rule: &StyleRule| rule.selector.has_pseudo_class() || rule.selector.has_component_variant();
+        let properties: HashSet<&PropertyName> = rules.iter()
+            .filter(|rule| is_sensitive(rule))
+            .flat_map(|rule| rule.properties.keys())
+            .collect();
+
+        rules.iter()
+            .enumerate()
+            .filter(|(_idx, rule)| is_sensitive(rule)
+                || rule.properties.keys().any(|name| properties.contains(name))
+            )
+            .map(|(idx, _rule)| idx)
+            .collect()
+    }
+
+    /// Finds which rules are sensitive to changes of each pseudo-class, like `:selected` set by [`PseudoClasses`](crate::prelude::PseudoClasses).
+    ///
+    /// Those are the rules which selector references the pseudo-class, but also any other rule which declares the same properties,
+    /// like on [`find_interaction_rules`](StyleSheetAsset::find_interaction_rules).
+    #[cfg(feature = "pseudo_class")]
+    fn find_pseudo_class_rules(
+        rules: &[StyleRule]
+    ) -> HashMap<String, SmallVec<[usize; 8]>> {
+        let mut names: HashMap<&String, HashSet<&PropertyName>> = HashMap::new();
+        for rule in rules.iter()
+        {
+            for element in rule.selector.elements()
+            {
+                if let SelectorElement::PseudoClass(name) = element
+                {
+                    names.entry(name)
+                        .or_default()
+                        .extend(rule.properties.keys());
+                }
+            }
         }
+
+        names.into_iter()
+            .map(|(name, properties)|
+            {
+                let indices = rules.iter()
+                    .enumerate()
+                    .filter(|(_idx, rule)| rule.selector.elements().iter()
+                            .any(|element| matches!(element, SelectorElement::PseudoClass(class) if class == name))
+                        || rule.properties.keys().any(|property| properties.contains(property))
+                    )
+                    .map(|(idx, _rule)| idx)
+                    .collect();
+
+                (name.clone(), indices)
+            })
+            .collect()
+    }
+
+    /// Gives rules with identical selectors, like two `.btn` rules, the same id, so entities matched by them are selected only once.
+    ///
+    /// Ids are the index of the first rule with that selector.
+    fn find_selector_ids(
+        rules: &[StyleRule]
+    ) -> SmallVec<[usize; 8]> {
+        let mut ids = HashMap::<&[SelectorElement], usize>::new();
+
+        rules.iter()
+            .enumerate()
+            .map(|(idx, rule)| *ids.entry(rule.selector.elements()).or_insert(idx))
+            .collect()
+    }
+
+    /// Returns the [`StyleRule`] with the given [`crate::selector::Selector`].
+    pub fn get_rule(
+        &self,
+        selector: &Selector
+    ) -> Option<&StyleRule> {
+        self.rules.iter()
+            .find(|&rule| rule.selector == *selector)
     }
 
     /// Returns the list of properties defined by the given [`crate::selector::Selector`].
@@ -60,7 +263,7 @@ impl StyleSheetAsset
         self.rules.iter()
             .find(|&rule| rule.selector == *selector)
             .map(|rule| rule.properties.iter()
-                .map(|(prop, _val)| prop.clone())
+                .map(|(prop, _val)| prop.to_string())
                 .collect::<Vec<String>>()
             )
     }
@@ -74,6 +277,569 @@ impl StyleSheetAsset
         self.rules.iter()
             .find(|&rule| &rule.selector == selector)
             .and_then(|rule| rule.properties.get(name))
+            .map(Arc::as_ref)
+    }
+
+    /// Iterates over all rules which selector references the given class, on any position of the selector.
+    /// Classes matched by a prefix or suffix, like `.slot-*` or `.*-active`, are included too.
+    ///
+    /// Useful to find dead or conflicting rules while refactoring a style sheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", r#"
+    ///     .button { width: 10px; }
+    ///     .panel .button.primary { color: red; }
+    ///     .panel { height: 10px; }
+    ///     #button { width: 5px; }
+    ///     .*ton { height: 5px; }
+    /// "#);
+    ///
+    /// let selectors: Vec<_> = sheet.rules_for_class("button")
+    ///     .map(|rule| rule.selector.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(selectors, [".button", ".panel .button.primary", ".*ton"]);
+    /// ```
+    pub fn rules_for_class<'a>(
+        &'a self,
+        class: &'a str
+    ) -> impl Iterator<Item = &'a StyleRule> {
+        self.rules.iter()
+            .filter(move |rule| rule.selector.elements()
+                .iter()
+                .any(|element| element.matches_class(class))
+            )
+    }
+
+    /// Distinct class names referenced by any rule, like `button` on `.panel .button`, sorted by name.
+    ///
+    /// Useful for authoring tools, like to check which classes an UI must provide for the sheet to be fully used.
+    /// Classes matched by a prefix or suffix, like `.slot-*`, aren't names, so they are listed by
+    /// [`required_class_patterns`](StyleSheetAsset::required_class_patterns) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", r#"
+    ///     .panel .button { width: 10px; }
+    ///     #title.button { color: red; }
+    ///     text { color: blue; }
+    ///     interaction(hovered) { color: white; }
+    /// "#);
+    ///
+    /// assert_eq!(sheet.required_classes().into_iter().collect::<Vec<_>>(), ["button", "panel"]);
+    /// assert_eq!(sheet.required_names().into_iter().collect::<Vec<_>>(), ["title"]);
+    /// assert_eq!(sheet.required_components().into_iter().collect::<Vec<_>>(), ["interaction", "text"]);
+    /// ```
+    pub fn required_classes(
+        &self
+    ) -> BTreeSet<&str> {
+        self.required(|element| match element
+        {
+            SelectorElement::Class(class) => Some(class),
+            _ => None,
+        })
+    }
+
+    /// Distinct class prefixes and suffixes referenced by any rule, like `slot-*` on `.slot-*` or `*-active` on `.*-active`, sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", ".slot-* .*-active.slot { width: 10px; }");
+    ///
+    /// assert_eq!(sheet.required_classes().into_iter().collect::<Vec<_>>(), ["slot"]);
+    /// assert_eq!(sheet.required_class_patterns().into_iter().collect::<Vec<_>>(), ["*-active", "slot-*"]);
+    /// ```
+    pub fn required_class_patterns(
+        &self
+    ) -> BTreeSet<String> {
+        self.rules.iter()
+            .flat_map(|rule| rule.selector.elements())
+            .filter_map(class_pattern)
+            .collect()
+    }
+
+    /// Distinct names referenced by any rule, like `title` on `#title`, sorted by name.
+    ///
+    /// See [`required_classes`](StyleSheetAsset::required_classes) for an example.
+    pub fn required_names(
+        &self
+    ) -> BTreeSet<&str> {
+        self.required(|element| match element
+        {
+            SelectorElement::Name(name) => Some(name),
+            _ => None,
+        })
+    }
+
+    /// Distinct component selectors referenced by any rule, like `text` on `text` or `interaction` on `interaction(hovered)`, sorted by name.
+    ///
+    /// See [`required_classes`](StyleSheetAsset::required_classes) for an example.
+    pub fn required_components(
+        &self
+    ) -> BTreeSet<&str> {
+        self.required(|element| match element
+        {
+            SelectorElement::Component(component)
+            | SelectorElement::ComponentVariant(component, _)
+            | SelectorElement::ComponentAttribute(component, _, _) => Some(component),
+            _ => None,
+        })
+    }
+
+    fn required<'a>(
+        &'a self,
+        filter: impl Fn(&'a SelectorElement) -> Option<&'a String>
+    ) -> BTreeSet<&'a str> {
+        self.rules.iter()
+            .flat_map(|rule| rule.selector.elements())
+            .filter_map(filter)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Iterates over rules which must be evaluated again when an [`Interaction`], or the variant of a component, changes,
+    /// along with the id of their selector, see [`iter_with_selector_ids`](StyleSheetAsset::iter_with_selector_ids).
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn interaction_rules(
+        &self
+    ) -> impl Iterator<Item = (usize, &StyleRule)> {
+        self.interaction_rules.iter()
+            .map(|idx| (self.selector_ids[*idx], &self.rules[*idx]))
+    }
+
+    /// Names of the pseudo-classes referenced by the selectors of rules, like `selected` on `.item:selected`.
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn pseudo_classes(
+        &self
+    ) -> impl Iterator<Item = &str> {
+        self.pseudo_class_rules.keys()
+            .map(|name| name.as_str())
+    }
+
+    /// Iterates over rules which must be evaluated again when any of the given pseudo-classes changes, on the order they were declared,
+    /// along with the id of their selector, see [`iter_with_selector_ids`](StyleSheetAsset::iter_with_selector_ids).
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn pseudo_class_rules<'a>(
+        &'a self,
+        names: &[String]
+    ) -> impl Iterator<Item = (usize, &'a StyleRule)> {
+        let indices: BTreeSet<usize> = names.iter()
+            .filter_map(|name| self.pseudo_class_rules.get(name))
+            .flatten()
+            .copied()
+            .collect();
+
+        indices.into_iter()
+            .map(|idx| (self.selector_ids[idx], &self.rules[idx]))
+    }
+
+    /// Iterates over rules which declare any of the given properties, on the order they were declared,
+    /// along with the id of their selector, see [`iter_with_selector_ids`](StyleSheetAsset::iter_with_selector_ids).
+    #[cfg(feature = "pseudo_class")]
+    pub(crate) fn rules_declaring<'a>(
+        &'a self,
+        properties: &'a HashSet<PropertyName>
+    ) -> impl Iterator<Item = (usize, &'a StyleRule)> {
+        self.iter_with_selector_ids()
+            .filter(|(_selector_id, rule)| rule.properties.keys().any(|name| properties.contains(name)))
+    }
+
+    /// Iterates over all rules along with the id of their selector, which is the same for all rules with an identical selector.
+    pub(crate) fn iter_with_selector_ids(
+        &self
+    ) -> impl Iterator<Item = (usize, &StyleRule)> {
+        self.selector_ids.iter()
+            .copied()
+            .zip(self.rules.iter())
+    }
+
+    /// Paths of style sheets imported with `@import`, as written on the sheet.
+    pub fn import_paths(
+        &self
+    ) -> &[String] {
+        &self.import_paths
+    }
+
+    /// Handles of style sheets imported with `@import`.
+    ///
+    /// Those are only resolved when the sheet is loaded by the [`AssetServer`], so it's empty for sheets created with [`parse`](StyleSheetAsset::parse).
+    /// Rules of imported sheets are applied before the rules of the importing sheet.
+    pub fn imports(
+        &self
+    ) -> &[Handle<StyleSheetAsset>] {
+        &self.imports
+    }
+
+    /// Names of cascade layers declared with `@layer`, on the order they were declared, and indexed by [`StyleRule::layer`].
+    pub fn layers(
+        &self
+    ) -> &[String] {
+        &self.layers
+    }
+
+    /// Gets the name of the cascade layer the given rule belongs to, if any.
+    pub fn layer_of(
+        &self,
+        rule: &StyleRule
+    ) -> Option<&str> {
+        rule.layer
+            .and_then(|layer| self.layers.get(layer))
+            .map(|name| name.as_str())
+    }
+
+    /// Reduces the memory used by the sheet, without changing which properties are applied on any entity.
+    ///
+    /// Rules with the same selector, on the same layer, are merged into a single rule, as long as no rule between them
+    /// declares any of the same properties. Identical [`PropertyValues`] are then shared by all rules declaring them.
+    ///
+    /// This is meant for big generated sheets, and can be done on load with [`BevyCssPlugin::with_rule_deduplication`](crate::BevyCssPlugin::with_rule_deduplication).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let mut sheet = StyleSheetAsset::parse("", r#"
+    ///     .a { width: 10px; }
+    ///     .b { height: 10px; }
+    ///     .a { height: 10px; }
+    /// "#);
+    /// sheet.deduplicate();
+    ///
+    /// let stats = sheet.stats();
+    /// assert_eq!(stats.rules, 2);
+    /// assert_eq!(stats.interned_values, 1);
+    /// ```
+    pub fn deduplicate(
+        &mut self
+    ) {
+        let merges = self.find_merges();
+        let merged = merges.iter().filter(|target| target.is_some()).count();
+
+        if merged > 0
+        {
+            let mut rules: Vec<Option<StyleRule>> = self.rules.drain(..).map(Some).collect();
+
+            // Sources are always before their target on cascade order, so chained merges are carried along
+            for (source, target) in merges.into_iter().enumerate()
+            {
+                if let Some(target) = target
+                {
+                    let source = rules[source].take().expect("Each rule is merged only once");
+                    rules[target].as_mut()
+                        .expect("Merge targets are never merged before their sources")
+                        .merge_earlier(source);
+                }
+            }
+
+            self.rules = rules.into_iter().flatten().collect();
+            self.reindex(merged);
+        }
+
+        let mut interned = HashMap::<u64, Vec<Arc<PropertyValues>>>::new();
+        for values in self.rules.iter_mut().flat_map(|rule| rule.properties.values_mut())
+        {
+            let mut hasher = AHasher::default();
+            values.hash(&mut hasher);
+
+            let bucket = interned.entry(hasher.finish()).or_default();
+            match bucket.iter().find(|existing| *existing == values)
+            {
+                Some(existing) => *values = existing.clone(),
+                None => bucket.push(values.clone()),
+            }
+        }
+
+        trace!("Deduplicated sheet {}, merging {merged} rules", self.path);
+    }
+
+    /// Normalizes the sheet for faster matching and smaller memory, without changing which properties are applied on any entity.
+    ///
+    /// Rules without any declaration, like `.a { }` or the parent of nested rules, are dropped, and the sheet is then
+    /// [`deduplicate`](StyleSheetAsset::deduplicate)d, which merges rules with the same selector and shares identical values.
+    /// Each property is declared only once per rule, so merged rules keep only the declaration which wins.
+    ///
+    /// This can be done on load with [`BevyCssPlugin::with_normalization`](crate::BevyCssPlugin::with_normalization).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let mut sheet = StyleSheetAsset::parse("", r#"
+    ///     .a { width: 10px; }
+    ///     .a { width: 20px; height: 10px; }
+    ///     .b { }
+    ///     .c { & .d { width: 5px; } }
+    /// "#);
+    /// sheet.normalize();
+    ///
+    /// assert_eq!(sheet.rule_count(), 2);
+    /// assert_eq!(sheet.declaration_count(), 3);
+    /// ```
+    pub fn normalize(
+        &mut self
+    ) {
+        let before = self.rules.len();
+        self.rules.retain(|rule| !rule.properties.is_empty());
+
+        let dropped = before - self.rules.len();
+        if dropped > 0
+        {
+            self.reindex(dropped);
+            trace!("Dropped {dropped} empty rules from sheet {}", self.path);
+        }
+
+        self.deduplicate();
+    }
+
+    /// Builds again the indices of rules, after rules were merged or removed, and changes the hash of the sheet.
+    ///
+    /// Values cached by the previous hash must not be reused, since they may belong to other rules now.
+    fn reindex(
+        &mut self,
+        changes: usize
+    ) {
+        #[cfg(feature = "pseudo_class")]
+        {
+            self.interaction_rules = Self::find_interaction_rules(&self.rules);
+            self.pseudo_class_rules = Self::find_pseudo_class_rules(&self.rules);
+        }
+        self.selector_ids = Self::find_selector_ids(&self.rules);
+
+        let mut hasher = AHasher::default();
+        (self.hash, changes, self.rules.len()).hash(&mut hasher);
+        self.hash = hasher.finish();
+    }
+
+    /// Finds, for each rule, the later rule it can be merged into, see [`deduplicate`](StyleSheetAsset::deduplicate).
+    fn find_merges(
+        &self
+    ) -> Vec<Option<usize>> {
+        // Rules of the same specificity are applied on load order, and rules of other specificities are never
+        // between two rules with the same selector, so only rules of the same layer are checked.
+        // Rules on different `@when` or `@supports` blocks are applied independently, so they are never merged
+        let mut order: Vec<usize> = (0..self.rules.len()).collect();
+        order.sort_by(|&lhs, &rhs| self.rules[lhs].selector.cmp(&self.rules[rhs].selector));
+
+        let mut merges = vec![None; self.rules.len()];
+        let mut last_rule = HashMap::<(Option<usize>, &[String], &[String], &[String], &[SelectorElement]), usize>::new();
+        let mut last_declared = HashMap::<(Option<usize>, &str), usize>::new();
+        // Properties of each rule, including those of rules merged into it
+        let mut properties: Vec<Vec<&str>> = self.rules.iter()
+            .map(|rule| rule.properties.keys().map(|name| &**name).collect())
+            .collect();
+
+        for index in order
+        {
+            let rule = &self.rules[index];
+
+            if let Some(previous) = last_rule.insert((rule.layer, rule.conditions.as_slice(), rule.flags.as_slice(), rule.capabilities.as_slice(), rule.selector.elements()), index)
+            {
+                let overridden = properties[previous].iter()
+                    .any(|name| last_declared.get(&(rule.layer, *name)) != Some(&previous));
+
+                if !overridden
+                {
+                    merges[previous] = Some(index);
+                    let previous_properties = std::mem::take(&mut properties[previous]);
+                    properties[index].extend(previous_properties);
+                }
+            }
+
+            for name in properties[index].iter()
+            {
+                last_declared.insert((rule.layer, name), index);
+            }
+        }
+
+        merges
+    }
+
+    /// Counts rules, selectors and values of this sheet, and estimates its heap usage.
+    ///
+    /// See [`deduplicate`](StyleSheetAsset::deduplicate) for an example.
+    pub fn stats(
+        &self
+    ) -> StyleSheetStats {
+        let unique_selectors: HashSet<&[SelectorElement]> = self.rules.iter()
+            .map(|rule| rule.selector.elements())
+            .collect();
+
+        // Shared values are counted only once
+        let values: HashMap<*const PropertyValues, &PropertyValues> = self.rules.iter()
+            .flat_map(|rule| rule.properties.values())
+            .map(|values| (Arc::as_ptr(values), values.as_ref()))
+            .collect();
+
+        let rule_bytes: usize = self.rules.iter()
+            .map(|rule| rule.selector.elements().iter().map(element_heap_bytes).sum::<usize>()
+                + rule.properties.capacity() * std::mem::size_of::<(PropertyName, Arc<PropertyValues>)>()
+                + rule.properties.keys().map(|name| name.len()).sum::<usize>()
+                + rule.important.iter().map(String::capacity).sum::<usize>()
+            )
+            .sum();
+
+        let value_bytes: usize = values.values()
+            .map(|values| std::mem::size_of::<PropertyValues>()
+                + values.iter().map(token_heap_bytes).sum::<usize>()
+            )
+            .sum();
+
+        let sheet_bytes = match self.rules.spilled()
+        {
+            true => self.rules.capacity() * std::mem::size_of::<StyleRule>(),
+            false => 0,
+        };
+
+        StyleSheetStats {
+            rules: self.rules.len(),
+            unique_selectors: unique_selectors.len(),
+            interned_values: values.len(),
+            heap_bytes: sheet_bytes + rule_bytes + value_bytes,
+        }
+    }
+
+    /// Number of rules on this sheet, counting each nested rule on its own, like `.a { & .b { ... } }` which has two rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", r#"
+    ///     .panel { width: 10px; height: 10px; & .title { color: red; } }
+    ///     .button { width 10px; height: 5px; }
+    ///     .broken# { color: red; }
+    /// "#);
+    ///
+    /// assert_eq!(sheet.rule_count(), 3);
+    /// assert_eq!(sheet.declaration_count(), 4);
+    /// assert_eq!(sheet.error_count(), 2);
+    /// ```
+    pub fn rule_count(
+        &self
+    ) -> usize {
+        self.rules.len()
+    }
+
+    /// Number of declarations of all rules, where a property declared twice on the same rule is counted once,
+    /// since only the winning declaration is kept.
+    ///
+    /// See [`rule_count`](StyleSheetAsset::rule_count) for an example.
+    pub fn declaration_count(
+        &self
+    ) -> usize {
+        self.rules.iter()
+            .map(|rule| rule.properties.len())
+            .sum()
+    }
+
+    /// Number of rules and declarations which failed to parse, and so were skipped, when this sheet was parsed.
+    ///
+    /// Property values are only validated when applied, so invalid values, like `width: red;`, aren't counted.
+    /// See [`rule_count`](StyleSheetAsset::rule_count) for an example.
+    pub fn error_count(
+        &self
+    ) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Rules and declarations which failed to parse, and so were skipped, along with where they were declared,
+    /// see [`error_count`](StyleSheetAsset::error_count).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", ".panel {\n  width: 10px;\n  height 5px;\n}");
+    /// let diagnostic = &sheet.diagnostics()[0];
+    ///
+    /// assert_eq!(sheet.describe_location(diagnostic.location), ":3:9");
+    /// assert_eq!(diagnostic.error, BevyCssError::UnexpectedToken("5px".to_string()));
+    /// ```
+    pub fn diagnostics(
+        &self
+    ) -> &[SheetDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Checks that rules only use the given classes and properties, returning a diagnostic for each unknown one.
+    ///
+    /// Unknown properties are reported where they are declared, while unknown classes are reported once,
+    /// at the first rule which selector uses them. Class prefixes and suffixes, like `.tit*`, are unknown when no known class matches them.
+    /// Parse errors aren't included, see [`diagnostics`](StyleSheetAsset::diagnostics).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("", ".panel .tit* { width: 10px; }\n.panel .titel { colour: red; }\n.pan-* { width: 5px; }");
+    ///
+    /// let mut known = KnownNames::default();
+    /// known.classes.extend(["panel".to_string(), "title".to_string()]);
+    /// known.properties.extend(["width".to_string(), "color".to_string()]);
+    ///
+    /// let errors: Vec<_> = sheet.validate(&known).into_iter().map(|diagnostic| diagnostic.error).collect();
+    /// assert_eq!(errors, [
+    ///     BevyCssError::UnknownClass("titel".to_string()),
+    ///     BevyCssError::UnsupportedProperty("colour".to_string()),
+    ///     BevyCssError::UnknownClass("pan-*".to_string()),
+    /// ]);
+    /// ```
+    pub fn validate(
+        &self,
+        known: &KnownNames
+    ) -> Vec<SheetDiagnostic> {
+        let mut reported_classes = HashSet::new();
+        let mut diagnostics = vec![];
+
+        for rule in self.rules.iter()
+        {
+            if !known.classes.is_empty()
+            {
+                for element in rule.selector.elements()
+                {
+                    // Prefixes and suffixes are unknown when no known class matches them, and are reported like `slot-*`
+                    let unknown = match element
+                    {
+                        SelectorElement::Class(class) if !known.classes.contains(class) => Some(class.clone()),
+                        SelectorElement::ClassPrefix(_) | SelectorElement::ClassSuffix(_)
+                            if !known.classes.iter().any(|class| element.matches_class(class)) => class_pattern(element),
+                        _ => None,
+                    };
+
+                    if let Some(class) = unknown.filter(|class| reported_classes.insert(class.clone()))
+                    {
+                        diagnostics.push(SheetDiagnostic {
+                            location: rule.location,
+                            error: BevyCssError::UnknownClass(class),
+                        });
+                    }
+                }
+            }
+
+            if !known.properties.is_empty()
+            {
+                for name in rule.properties.keys()
+                {
+                    if !known.properties.contains(&**name)
+                    {
+                        diagnostics.push(SheetDiagnostic {
+                            location: rule.property_location(name).unwrap_or(rule.location),
+                            error: BevyCssError::UnsupportedProperty(name.to_string()),
+                        });
+                    }
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.location);
+        diagnostics
     }
 
     /// Iterates over all existing rules
@@ -90,6 +856,25 @@ impl StyleSheetAsset
         self.hash
     }
 
+    /// Formats the given location on this sheet like `ui/menu.css:42:7`, to point to a rule or declaration on diagnostics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tomt_bevycss::prelude::*;
+    /// let sheet = StyleSheetAsset::parse("ui/menu.css", ".menu {\n  width: 10px;\n}");
+    /// let rule = sheet.iter().next().unwrap();
+    ///
+    /// assert_eq!(sheet.describe_location(rule.location), "ui/menu.css:1:1");
+    /// assert_eq!(sheet.describe_location(rule.property_location("width").unwrap()), "ui/menu.css:2:3");
+    /// ```
+    pub fn describe_location(
+        &self,
+        location: SourceLocation
+    ) -> String {
+        format!("{}:{location}", self.path)
+    }
+
     /// Asset path, for debug reasons only
     pub fn path(
         &self
@@ -97,3 +882,52 @@ impl StyleSheetAsset
         &self.path
     }
 }
+
+/// Class prefix or suffix of the given selector element, written like `slot-*` or `*-active`.
+fn class_pattern(
+    element: &SelectorElement
+) -> Option<String> {
+    match element
+    {
+        SelectorElement::ClassPrefix(prefix) => Some(format!("{prefix}*")),
+        SelectorElement::ClassSuffix(suffix) => Some(format!("*{suffix}")),
+        _ => None,
+    }
+}
+
+/// Bytes allocated on the heap by the given selector element.
+fn element_heap_bytes(
+    element: &SelectorElement
+) -> usize {
+    match element
+    {
+        SelectorElement::Name(value)
+        | SelectorElement::Component(value)
+        | SelectorElement::Class(value)
+        | SelectorElement::ClassPrefix(value)
+        | SelectorElement::ClassSuffix(value) => value.capacity(),
+        SelectorElement::ComponentVariant(name, variant) => name.capacity() + variant.capacity(),
+        SelectorElement::ComponentAttribute(name, field, value) => name.capacity() + field.capacity() + value.capacity(),
+        #[cfg(feature = "pseudo_class")]
+        SelectorElement::PseudoClass(value) => value.capacity(),
+        #[cfg(feature = "pseudo_prop")]
+        SelectorElement::PseudoProp(value) => value.capacity(),
+        SelectorElement::Scope | SelectorElement::NthOfType(..) | SelectorElement::Child | SelectorElement::DirectChild => 0,
+    }
+}
+
+/// Bytes allocated on the heap by the given token, including the arguments of functions.
+fn token_heap_bytes(
+    token: &PropertyToken
+) -> usize {
+    match token
+    {
+        PropertyToken::Identifier(value)
+        | PropertyToken::Hash(value)
+        | PropertyToken::String(value) => value.capacity(),
+        PropertyToken::Function(name, args) => name.capacity()
+            + args.capacity() * std::mem::size_of::<PropertyValues>()
+            + args.iter().flat_map(|values| values.iter()).map(token_heap_bytes).sum::<usize>(),
+        _ => 0,
+    }
+}
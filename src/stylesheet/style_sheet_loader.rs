@@ -15,7 +15,13 @@ use bevy::{
 use thiserror::Error;
 
 #[derive(Default)]
-pub(crate) struct StyleSheetLoader;
+pub(crate) struct StyleSheetLoader
+{
+    /// Deduplicates loaded sheets, see [`StyleSheetAsset::deduplicate`].
+    pub deduplicate: bool,
+    /// Normalizes loaded sheets, see [`StyleSheetAsset::normalize`].
+    pub normalize: bool,
+}
 
 #[non_exhaustive]
 #[derive(Debug, Error)]
@@ -46,11 +52,31 @@ for StyleSheetLoader
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
             
-            let content = std::str::from_utf8(&bytes)?;
-            let stylesheet = StyleSheetAsset::parse(
+            let mut stylesheet = StyleSheetAsset::from_bytes(
                 load_context.path().to_str().unwrap_or_default(),
-                content
-            );
+                &bytes
+            )?;
+
+            // Imported paths are relative to the importing sheet
+            let dir = load_context.path()
+                .parent()
+                .map(|dir| dir.to_path_buf())
+                .unwrap_or_default();
+
+            if self.normalize
+            {
+                stylesheet.normalize();
+            }
+            else if self.deduplicate
+            {
+                stylesheet.deduplicate();
+            }
+
+            stylesheet.imports = stylesheet.import_paths()
+                .iter()
+                .map(|path| load_context.load(dir.join(path)))
+                .collect();
+
             Ok(stylesheet)
         })
     }
@@ -0,0 +1,14 @@
+/// Size information of a [`StyleSheetAsset`](super::StyleSheetAsset), see [`stats`](super::StyleSheetAsset::stats).
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub struct StyleSheetStats
+{
+    /// Number of rules.
+    pub rules: usize,
+    /// Number of distinct selectors, like `.a .b`, ignoring rules which repeat the same selector.
+    pub unique_selectors: usize,
+    /// Number of distinct property values stored, since identical values can be shared across rules.
+    pub interned_values: usize,
+    /// Approximate number of bytes allocated on the heap by rules, selectors and values.
+    pub heap_bytes: usize,
+}
@@ -0,0 +1,233 @@
+use super::PropertyResetRegistry;
+use crate::{
+    component::{CssIgnore, StyleSheet},
+    property::{PropertyName, StyleSheetState},
+};
+
+use bevy::{
+    ecs::system::SystemState,
+    log::debug,
+    prelude::{
+        Added,
+        Children,
+        Entity,
+        Local,
+        Mut,
+        Node,
+        Parent,
+        Query,
+        RemovedComponents,
+        World,
+    },
+};
+
+/// Reverts every property applied by `tomt_bevycss` on the given entity, by calling [`Property::reset`](crate::Property::reset)
+/// for each one of them.
+///
+/// Only the given entity is reverted, its descendants are kept untouched.
+/// Properties will be applied again whenever a [`StyleSheet`] which matches the entity changes.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::system::clear_styles;
+/// #[derive(Component)]
+/// struct ResetStyle;
+///
+/// fn reset_styles(
+///     world: &mut World
+/// ) {
+///     let entities: Vec<Entity> = world.query_filtered::<Entity, With<ResetStyle>>()
+///         .iter(world)
+///         .collect();
+///
+///     for entity in entities
+///     {
+///         clear_styles(world, entity);
+///     }
+/// }
+/// ```
+pub fn clear_styles(
+    world: &mut World,
+    entity: Entity
+) {
+    let exists = world.get_entity(entity).is_some();
+    let properties = match world.get_resource_mut::<StyleSheetState>()
+    {
+        Some(mut state) => {
+            let properties = state.take_applied(entity);
+            if exists && !properties.is_empty()
+            {
+                state.mark_changed(entity, properties.iter().cloned());
+            }
+            properties
+        }
+        None => return,
+    };
+
+    if properties.is_empty() || !exists
+    {
+        return;
+    }
+
+    debug!("Clearing {} properties from entity {entity:?}", properties.len());
+    reset_properties(world, entity, properties.iter().map(|name| &**name));
+}
+
+/// Reverts the given properties on the given entity, by calling [`Property::reset`](crate::Property::reset) for each one of them.
+///
+/// Unlike [`clear_styles`], properties are still considered applied, so they are reverted again by it later.
+pub(crate) fn reset_properties<'a>(
+    world: &mut World,
+    entity: Entity,
+    properties: impl Iterator<Item = &'a str>
+) {
+    world.resource_scope(|world, mut registry: Mut<PropertyResetRegistry>|
+    {
+        for property in properties
+        {
+            if let Some(reset) = registry.0.get_mut(property)
+            {
+                reset.reset(world, entity);
+            }
+        }
+    });
+}
+
+/// Reverts the given properties on the given entity like [`reset_properties`], and forgets them, since nothing applies them anymore,
+/// like after the rules or inline style which declared them are changed.
+pub(crate) fn revert_properties(
+    world: &mut World,
+    entity: Entity,
+    properties: &[PropertyName]
+) {
+    reset_properties(world, entity, properties.iter().map(|name| &**name));
+
+    if let Some(mut state) = world.get_resource_mut::<StyleSheetState>()
+    {
+        state.forget_applied(entity, properties);
+    }
+}
+
+/// Exclusive system which reverts the styles applied by a [`StyleSheet`] when it's removed.
+///
+/// Since the removed sheet may have styled any descendant, the whole subtree is reverted and the remaining
+/// sheets on ancestors and descendants are flagged as changed, so they are applied again on the next frame.
+pub(crate) fn clear_removed_style_sheets(
+    world: &mut World,
+    mut removed: Local<SystemState<(RemovedComponents<StyleSheet>, RemovedComponents<Node>)>>,
+) {
+    let (mut removed_sheets, mut removed_nodes) = removed.get_mut(world);
+    let removed_sheets: Vec<Entity> = removed_sheets.read().collect();
+    let removed_nodes: Vec<Entity> = removed_nodes.read().collect();
+
+    // Despawned entities doesn't need to be reverted, just forgotten
+    world.resource_scope(|world, mut state: Mut<StyleSheetState>|
+    {
+        for entity in removed_nodes.iter()
+        {
+            state.take_applied(*entity);
+        }
+
+        // Styled entities without a node are only noticed by looking them up. The entity count can't tell if any was despawned,
+        // since another one may have been spawned on the same frame
+        state.forget_despawned(|entity| world.get_entity(entity).is_some());
+    });
+
+    for root in removed_sheets
+    {
+        if world.get_entity(root).is_none()
+        {
+            continue;
+        }
+
+        let mut pending = vec![root];
+        while let Some(entity) = pending.pop()
+        {
+            clear_styles(world, entity);
+
+            if let Some(children) = world.get::<Children>(entity)
+            {
+                pending.extend(children.iter());
+            }
+
+            if let Some(mut sheet) = world.get_mut::<StyleSheet>(entity)
+            {
+                sheet.refresh();
+            }
+        }
+
+        refresh_ancestor_sheets(world, root);
+    }
+}
+
+/// Entities which [`CssIgnore`] was added to, and removed from.
+type CssIgnoreChanges<'w, 's> = (Query<'w, 's, Entity, Added<CssIgnore>>, RemovedComponents<'w, 's, CssIgnore>);
+
+/// Exclusive system which reverts the styles applied on an entity and its descendants when [`CssIgnore`] is added,
+/// and applies style sheets on them again when it's removed.
+pub(crate) fn clear_ignored_styles(
+    world: &mut World,
+    mut changes: Local<SystemState<CssIgnoreChanges<'static, 'static>>>,
+) {
+    let (added, mut removed) = changes.get_mut(world);
+    let added: Vec<Entity> = added.iter().collect();
+    let removed: Vec<Entity> = removed.read().collect();
+
+    for root in added
+    {
+        let mut pending = vec![root];
+        while let Some(entity) = pending.pop()
+        {
+            clear_styles(world, entity);
+
+            if let Some(children) = world.get::<Children>(entity)
+            {
+                pending.extend(children.iter());
+            }
+        }
+    }
+
+    // Sheets which may match the subtree are reapplied, which also applies sheets of the subtree itself
+    for root in removed
+    {
+        if world.get_entity(root).is_none() || world.get::<CssIgnore>(root).is_some()
+        {
+            continue;
+        }
+
+        let mut pending = vec![root];
+        while let Some(entity) = pending.pop()
+        {
+            if let Some(mut sheet) = world.get_mut::<StyleSheet>(entity)
+            {
+                sheet.refresh();
+            }
+
+            if let Some(children) = world.get::<Children>(entity)
+            {
+                pending.extend(children.iter());
+            }
+        }
+
+        refresh_ancestor_sheets(world, root);
+    }
+}
+
+/// Flags the sheets on all ancestors of the given entity as changed, so they are applied again on the next frame.
+fn refresh_ancestor_sheets(
+    world: &mut World,
+    entity: Entity
+) {
+    let mut current = world.get::<Parent>(entity).map(Parent::get);
+    while let Some(entity) = current
+    {
+        if let Some(mut sheet) = world.get_mut::<StyleSheet>(entity)
+        {
+            sheet.refresh();
+        }
+
+        current = world.get::<Parent>(entity).map(Parent::get);
+    }
+}
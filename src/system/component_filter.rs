@@ -1,11 +1,18 @@
 use bevy::{
     ecs::system::SystemState,
     prelude::{
+        Changed,
         Component,
         Entity,
         Query,
         With, World,
     },
+    reflect::{
+        Enum,
+        GetPath,
+        Reflect,
+        ReflectRef,
+    },
 };
 use smallvec::SmallVec;
 
@@ -15,6 +22,40 @@ pub(crate) trait ComponentFilter
         &mut self,
         world: &World
     ) -> SmallVec<[Entity; 8]>;
+
+    /// Filters entities which component is currently on the given variant, like `interaction(hovered)`.
+    ///
+    /// Returns `None` when the component can't be matched by variant, which is the case unless it was
+    /// registered with [`register_component_selector_reflect`](crate::RegisterComponentSelector::register_component_selector_reflect).
+    fn filter_variant(
+        &mut self,
+        _world: &World,
+        _variant: &str
+    ) -> Option<SmallVec<[Entity; 8]>> {
+        None
+    }
+
+    /// Filters entities which component has the given value on a reflected field, like `health[current=0]`.
+    ///
+    /// Returns `None` when the component can't be matched by its fields, which is the case unless it was registered with
+    /// [`register_component_selector_reflect`](crate::RegisterComponentSelector::register_component_selector_reflect)
+    /// or [`register_component_selector_fields`](crate::RegisterComponentSelector::register_component_selector_fields).
+    fn filter_attribute(
+        &mut self,
+        _world: &World,
+        _field: &str,
+        _value: &str
+    ) -> Option<SmallVec<[Entity; 8]>> {
+        None
+    }
+
+    /// Entities which component changed since last call, so selectors matching its variant must be evaluated again.
+    fn changed(
+        &mut self,
+        _world: &World
+    ) -> SmallVec<[Entity; 8]> {
+        SmallVec::new()
+    }
 }
 
 impl<'w, 's, T: Component> ComponentFilter
@@ -27,3 +68,217 @@ for SystemState<Query<'w, 's, Entity, With<T>>>
         self.get(world).iter().collect()
     }
 }
+
+/// Filters entities by a reflected enum component, which can also be matched by its current variant.
+pub(crate) struct VariantFilter<T>
+where
+    T: Component + Enum,
+{
+    query: SystemState<Query<'static, 'static, (Entity, &'static T)>>,
+    changes: SystemState<Query<'static, 'static, Entity, Changed<T>>>,
+}
+
+impl<T> VariantFilter<T>
+where
+    T: Component + Enum,
+{
+    pub fn new(
+        world: &mut World
+    ) -> Self {
+        Self{
+            query: SystemState::new(world),
+            changes: SystemState::new(world),
+        }
+    }
+}
+
+impl<T> ComponentFilter
+for VariantFilter<T>
+where
+    T: Component + Enum,
+{
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> SmallVec<[Entity; 8]> {
+        self.query.get(world)
+            .iter()
+            .map(|(entity, _component)| entity)
+            .collect()
+    }
+
+    fn filter_variant(
+        &mut self,
+        world: &World,
+        variant: &str
+    ) -> Option<SmallVec<[Entity; 8]>> {
+        let entities = self.query.get(world)
+            .iter()
+            .filter(|(_entity, component)| variant_matches(component.variant_name(), variant))
+            .map(|(entity, _component)| entity)
+            .collect();
+
+        Some(entities)
+    }
+
+    fn filter_attribute(
+        &mut self,
+        world: &World,
+        field: &str,
+        value: &str
+    ) -> Option<SmallVec<[Entity; 8]>> {
+        let entities = self.query.get(world)
+            .iter()
+            .filter(|(_entity, component)| attribute_matches(component.as_reflect(), field, value))
+            .map(|(entity, _component)| entity)
+            .collect();
+
+        Some(entities)
+    }
+
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> SmallVec<[Entity; 8]> {
+        self.changes.get(world).iter().collect()
+    }
+}
+
+/// Filters entities by a reflected component, which can also be matched by the value of its fields.
+pub(crate) struct FieldFilter<T>
+where
+    T: Component + Reflect,
+{
+    query: SystemState<Query<'static, 'static, (Entity, &'static T)>>,
+    changes: SystemState<Query<'static, 'static, Entity, Changed<T>>>,
+}
+
+impl<T> FieldFilter<T>
+where
+    T: Component + Reflect,
+{
+    pub fn new(
+        world: &mut World
+    ) -> Self {
+        Self{
+            query: SystemState::new(world),
+            changes: SystemState::new(world),
+        }
+    }
+}
+
+impl<T> ComponentFilter
+for FieldFilter<T>
+where
+    T: Component + Reflect,
+{
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> SmallVec<[Entity; 8]> {
+        self.query.get(world)
+            .iter()
+            .map(|(entity, _component)| entity)
+            .collect()
+    }
+
+    fn filter_attribute(
+        &mut self,
+        world: &World,
+        field: &str,
+        value: &str
+    ) -> Option<SmallVec<[Entity; 8]>> {
+        let entities = self.query.get(world)
+            .iter()
+            .filter(|(_entity, component)| attribute_matches(component.as_reflect(), field, value))
+            .map(|(entity, _component)| entity)
+            .collect();
+
+        Some(entities)
+    }
+
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> SmallVec<[Entity; 8]> {
+        self.changes.get(world).iter().collect()
+    }
+}
+
+/// Checks if the field on the given path of a component, like `current` or `stats.level`, has the given value.
+///
+/// Unit enum variants have no fields, so the `variant` attribute matches the variant of enum components, like `interaction[variant=pressed]`.
+fn attribute_matches(
+    component: &dyn Reflect,
+    field: &str,
+    value: &str
+) -> bool {
+    match (component.reflect_path(field), component.reflect_ref())
+    {
+        (Ok(field), _) => value_matches(field, value),
+        (Err(_), ReflectRef::Enum(component)) if field == "variant" => variant_matches(component.variant_name(), value),
+        (Err(_), _) => false,
+    }
+}
+
+/// Checks if a reflected value matches the given selector value, like `true`, `10` or `fast-running`.
+///
+/// Enums are matched by variant, like [`variant_matches`], strings and booleans by their text, and numbers by their value.
+fn value_matches(
+    field: &dyn Reflect,
+    value: &str
+) -> bool {
+    if let ReflectRef::Enum(field) = field.reflect_ref()
+    {
+        return variant_matches(field.variant_name(), value);
+    }
+
+    if let Some(field) = field.downcast_ref::<String>()
+    {
+        return field == value;
+    }
+
+    if let Some(field) = field.downcast_ref::<bool>()
+    {
+        return value.parse::<bool>() == Ok(*field);
+    }
+
+    let number = match value.parse::<f64>()
+    {
+        Ok(number) => number,
+        Err(_) => return false,
+    };
+
+    macro_rules! number_matches
+    {
+        ($($number:ty),*) => {
+            $(
+                if let Some(field) = field.downcast_ref::<$number>()
+                {
+                    return *field as f64 == number;
+                }
+            )*
+        };
+    }
+
+    number_matches!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    false
+}
+
+/// Checks if the given selector variant, like `flex-start`, is the `kebab-case` form of a variant name, like `FlexStart`, ignoring case.
+fn variant_matches(
+    variant_name: &str,
+    selector_variant: &str
+) -> bool {
+    let mut kebab_case = String::with_capacity(variant_name.len() + 4);
+    for (index, c) in variant_name.char_indices()
+    {
+        if c.is_ascii_uppercase() && index > 0
+        {
+            kebab_case.push('-');
+        }
+        kebab_case.push(c.to_ascii_lowercase());
+    }
+
+    kebab_case.eq_ignore_ascii_case(selector_variant)
+}
@@ -0,0 +1,68 @@
+use crate::stylesheet::StyleRule;
+
+use bevy::{
+    prelude::Resource,
+    utils::HashSet,
+};
+
+/// Capabilities of the engine which `@supports` blocks of style sheets can check, like `@supports (grid) { ... }`.
+///
+/// Every registered property is a capability with its own name, like `text-transform`, along with the layouts
+/// supported by `bevy_ui`, which are `flex` and `grid`. Other capabilities can be added with [`insert`](CssCapabilities::insert),
+/// like to tell apart builds of your game.
+///
+/// Rules of a `@supports` block are only applied when the capability is supported. Capabilities are meant to be known on startup,
+/// so changing them doesn't apply or revert rules of entities already styled.
+#[derive(Debug, Clone)]
+#[derive(Resource)]
+pub struct CssCapabilities
+{
+    capabilities: HashSet<String>,
+}
+
+impl Default
+for CssCapabilities
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self{
+            capabilities: ["flex", "grid"].into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl CssCapabilities
+{
+    /// Adds a capability with the given name.
+    pub fn insert(
+        &mut self,
+        name: &str
+    ) {
+        self.capabilities.insert(name.to_string());
+    }
+
+    /// Removes the capability with the given name, so `@supports` blocks checking it are skipped.
+    pub fn remove(
+        &mut self,
+        name: &str
+    ) {
+        self.capabilities.remove(name);
+    }
+
+    /// Checks if the capability with the given name is supported.
+    pub fn supports(
+        &self,
+        name: &str
+    ) -> bool {
+        self.capabilities.contains(name)
+    }
+
+    /// Checks if all capabilities required by the given rule are supported, so it can be applied.
+    pub(crate) fn allows(
+        &self,
+        rule: &StyleRule
+    ) -> bool {
+        rule.capabilities.iter().all(|name| self.supports(name))
+    }
+}
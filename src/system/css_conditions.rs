@@ -0,0 +1,105 @@
+use super::{PendingRuleDiffs, RuleDiff};
+use crate::stylesheet::{StyleRule, StyleSheetAsset};
+
+use bevy::{
+    log::debug,
+    prelude::{
+        Assets,
+        Resource,
+        World,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Predicate of a condition used by `@when` blocks, which is evaluated against the [`World`] every frame.
+pub type CssConditionFn = fn(&World) -> bool;
+
+/// Conditions which `@when` blocks of style sheets can use, like `@when state("menu") { ... }`,
+/// along with the result of their last evaluation.
+///
+/// Conditions are added with [`RegisterCssCondition::register_css_condition`](crate::RegisterCssCondition::register_css_condition)
+/// and evaluated before entities are styled on every frame. Rules of a `@when` block are only applied while its condition holds,
+/// and conditions which weren't registered never hold.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct CssConditions
+{
+    conditions: HashMap<String, (CssConditionFn, bool)>,
+}
+
+impl CssConditions
+{
+    /// Adds a condition with the given name, replacing any condition with the same name.
+    ///
+    /// It doesn't hold until it's first evaluated.
+    pub(crate) fn register(
+        &mut self,
+        name: &str,
+        predicate: CssConditionFn
+    ) {
+        self.conditions.insert(name.to_string(), (predicate, false));
+    }
+
+    /// Checks if the condition with the given name held on its last evaluation.
+    pub fn is_active(
+        &self,
+        name: &str
+    ) -> bool {
+        self.conditions.get(name).is_some_and(|(_predicate, active)| *active)
+    }
+
+    /// Checks if all conditions of the given rule hold, so it can be applied.
+    pub(crate) fn allows(
+        &self,
+        rule: &StyleRule
+    ) -> bool {
+        rule.conditions.iter().all(|name| self.is_active(name))
+    }
+}
+
+/// Evaluates all [`CssConditions`], and reapplies rules of every sheet depending on conditions which changed.
+///
+/// Rules which now hold are applied, while properties of rules which no longer hold are reverted, see [`RuleDiff::conditional`].
+pub(crate) fn evaluate_css_conditions(
+    world: &mut World
+) {
+    let Some(conditions) = world.get_resource::<CssConditions>()
+    else {
+        return;
+    };
+
+    let changed: HashSet<String> = conditions.conditions.iter()
+        .filter(|(_name, (predicate, active))| predicate(world) != *active)
+        .map(|(name, _condition)| name.clone())
+        .collect();
+
+    if changed.is_empty()
+    {
+        return;
+    }
+
+    let mut conditions = world.resource_mut::<CssConditions>();
+    for name in changed.iter()
+    {
+        if let Some((_predicate, active)) = conditions.conditions.get_mut(name)
+        {
+            *active = !*active;
+            debug!("Condition '{name}' changed to {active}");
+        }
+    }
+
+    let conditions = world.resource::<CssConditions>();
+    let diffs: Vec<_> = world.resource::<Assets<StyleSheetAsset>>()
+        .iter()
+        .map(|(id, sheet)| (id, RuleDiff::conditional(sheet, &changed, conditions)))
+        .filter(|(_id, diff)| !diff.is_empty())
+        .collect();
+
+    let mut pending = world.resource_mut::<PendingRuleDiffs>();
+    for (id, diff) in diffs
+    {
+        pending.entry(id)
+            .or_default()
+            .merge(diff);
+    }
+}
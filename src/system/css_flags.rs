@@ -0,0 +1,171 @@
+use super::{PendingRuleDiffs, RuleDiff};
+use crate::stylesheet::{StyleRule, StyleSheetAsset};
+
+use bevy::{
+    log::{debug, warn},
+    prelude::{
+        Assets,
+        DetectChangesMut,
+        Local,
+        Resource,
+        World,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Flags which `@when` blocks of style sheets can check by name, like `@when experiment-b { ... }`, to toggle
+/// rule sets at runtime, like UI variants being A/B tested.
+///
+/// Rules of a `@when` block are only applied while its flag is enabled, and they are applied or reverted on the next frame
+/// after the flag changes. Flags which were never enabled nor disabled are unknown, so they are treated as disabled
+/// and a warning is logged once for each of them.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// // Used as `@when experiment-b { .cta { background-color: orange; } }`
+/// fn enable_experiment(
+///     mut flags: ResMut<CssFlags>
+/// ) {
+///     flags.enable("experiment-b");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[derive(Resource)]
+pub struct CssFlags
+{
+    /// Whether each known flag is enabled.
+    flags: HashMap<String, bool>,
+    /// Flags which were enabled when rules were last applied or reverted, see [`evaluate_css_flags`].
+    applied: HashSet<String>,
+}
+
+impl CssFlags
+{
+    /// Enables the flag with the given name, so rules of `@when` blocks checking it are applied.
+    pub fn enable(
+        &mut self,
+        name: &str
+    ) {
+        self.set(name, true);
+    }
+
+    /// Disables the flag with the given name, so rules of `@when` blocks checking it are reverted.
+    ///
+    /// It's also useful to declare flags which are disabled by default, so they aren't reported as unknown.
+    pub fn disable(
+        &mut self,
+        name: &str
+    ) {
+        self.set(name, false);
+    }
+
+    /// Enables or disables the flag with the given name.
+    pub fn set(
+        &mut self,
+        name: &str,
+        enabled: bool
+    ) {
+        self.flags.insert(name.to_string(), enabled);
+    }
+
+    /// Checks if the flag with the given name is enabled.
+    pub fn is_enabled(
+        &self,
+        name: &str
+    ) -> bool {
+        self.flags.get(name).copied().unwrap_or_default()
+    }
+
+    /// Checks if the flag with the given name was ever enabled or disabled.
+    pub fn is_known(
+        &self,
+        name: &str
+    ) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// Checks if all flags of the given rule are enabled, so it can be applied.
+    pub(crate) fn allows(
+        &self,
+        rule: &StyleRule
+    ) -> bool {
+        rule.flags.iter().all(|name| self.is_enabled(name))
+    }
+}
+
+/// Reapplies rules of every sheet on `@when` blocks which flags were enabled or disabled since last frame, see [`CssFlags`].
+///
+/// Rules which now have all flags enabled are applied, while properties of the other ones are reverted, see [`RuleDiff::flagged`].
+/// Whenever sheets change, flags they check which are unknown are also reported, once for each flag.
+pub(crate) fn evaluate_css_flags(
+    world: &mut World,
+    mut reported: Local<HashSet<String>>
+) {
+    let Some(flags) = world.get_resource::<CssFlags>()
+    else {
+        return;
+    };
+
+    let flags_changed = world.is_resource_changed::<CssFlags>();
+    if world.is_resource_changed::<Assets<StyleSheetAsset>>()
+    {
+        let unknown = world.resource::<Assets<StyleSheetAsset>>()
+            .iter()
+            .flat_map(|(_id, sheet)| sheet.iter())
+            .flat_map(|rule| rule.flags.iter())
+            .filter(|name| !flags.is_known(name));
+
+        for name in unknown
+        {
+            if reported.insert(name.clone())
+            {
+                warn!("Flag '{name}' of `@when` block is unknown, so it's disabled. Use `CssFlags` to enable or disable it");
+            }
+        }
+    }
+
+    // Changed sheets are applied again as a whole, which already skips rules of disabled flags, so only flag changes matter here
+    if !flags_changed
+    {
+        return;
+    }
+
+    let enabled: HashSet<String> = flags.flags.iter()
+        .filter(|(_name, enabled)| **enabled)
+        .map(|(name, _enabled)| name.clone())
+        .collect();
+
+    let changed: HashSet<String> = enabled.symmetric_difference(&flags.applied)
+        .cloned()
+        .collect();
+
+    if changed.is_empty()
+    {
+        return;
+    }
+
+    for name in changed.iter()
+    {
+        debug!("Flag '{name}' changed to {}", enabled.contains(name));
+    }
+    // Not a change made by users, so flags aren't compared again on the next frame
+    world.resource_mut::<CssFlags>().bypass_change_detection().applied = enabled;
+
+    let flags = world.resource::<CssFlags>();
+    let diffs: Vec<_> = world.resource::<Assets<StyleSheetAsset>>()
+        .iter()
+        .map(|(id, sheet)| (id, RuleDiff::flagged(sheet, &changed, flags)))
+        .filter(|(_id, diff)| !diff.is_empty())
+        .collect();
+
+    let mut pending = world.resource_mut::<PendingRuleDiffs>();
+    for (id, diff) in diffs
+    {
+        pending.entry(id)
+            .or_default()
+            .merge(diff);
+    }
+}
@@ -1,6 +1,9 @@
 use super::query;
 
-use crate::prelude::StyleSheetAsset;
+use crate::prelude::{
+    NonUiDescendants,
+    StyleSheetAsset,
+};
 
 use bevy::{
     ecs::system::SystemParam,
@@ -17,20 +20,73 @@ pub(crate) struct CssQueryParam<'w, 's>
     pub classes: query::QueryEntityClasses<'w, 's>,
     pub parent: query::QueryEntityParent<'w, 's>,
     pub children: query::QueryEntityChildren<'w, 's>,
+    pub ignored: query::QueryEntityIgnored<'w, 's>,
+    pub overrides: StyleOverrideParam<'w, 's>,
+    pub non_ui_descendants: Option<Res<'w, NonUiDescendants>>,
 
     #[cfg(feature = "pseudo_class")]
     pub pseudo_classes: PseudoClassParam<'w, 's>,
 }
 
+impl CssQueryParam<'_, '_>
+{
+    /// Checks if descendant traversal goes through entities without [`Node`], see [`NonUiDescendants`].
+    fn through_non_ui(
+        &self
+    ) -> bool {
+        self.non_ui_descendants.as_ref().is_some_and(|enabled| enabled.0)
+    }
+
+    /// [`Children`] followed by descendant selectors, which only exists on UI nodes unless [`NonUiDescendants`] is enabled.
+    pub fn descendant_children(
+        &self,
+        entity: Entity
+    ) -> Option<&Children> {
+        match self.through_non_ui()
+        {
+            true => self.ui_nodes.get(entity).ok().and_then(|(_entity, _parent, children, _stylesheet)| children),
+            false => self.children.get(entity).ok().map(|(_entity, children)| children),
+        }
+    }
+
+    /// [`Parent`] followed by ancestors of descendant selectors, which only exists on UI nodes unless [`NonUiDescendants`] is enabled.
+    pub fn ancestor_parent(
+        &self,
+        entity: Entity
+    ) -> Option<&Parent> {
+        match self.through_non_ui()
+        {
+            true => self.ui_nodes.get(entity).ok().and_then(|(_entity, parent, _children, _stylesheet)| parent),
+            false => self.parent.get(entity).ok().map(|(_entity, parent)| parent),
+        }
+    }
+}
+
 #[derive(Deref, SystemParam)]
 pub(crate) struct StyleSheetResource<'w>(
     Res<'w, Assets<StyleSheetAsset>>
 );
 
+#[derive(SystemParam)]
+pub(crate) struct StyleOverrideParam<'w, 's>
+{
+    pub overrides: query::QueryEntityOverrides<'w, 's>,
+    pub changes: query::QueryOverrideChanges<'w, 's>,
+    pub removed: RemovedComponents<'w, 's, crate::prelude::StyleOverride>,
+}
+
 #[cfg(feature = "pseudo_class")]
 #[derive(SystemParam)]
 pub(crate) struct PseudoClassParam<'w, 's>
 {
     pub interaction: query::QueryEntityInteraction<'w, 's>,
+    pub interaction_changes: query::QueryInteractionChanges<'w, 's>,
+    pub disabled: query::QueryEntityDisabled<'w, 's>,
+    pub removed_disabled: RemovedComponents<'w, 's, crate::prelude::Disabled>,
+    pub checked: query::QueryEntityChecked<'w, 's>,
+    pub removed_checked: RemovedComponents<'w, 's, crate::prelude::Checked>,
+    pub custom: query::QueryEntityPseudoClasses<'w, 's>,
+    pub removed_custom: RemovedComponents<'w, 's, crate::prelude::PseudoClasses>,
+    pub ui_parents: query::QueryEntityUiParent<'w, 's>,
     pub _children: query::QueryEntityChildren<'w, 's>,
 }
@@ -0,0 +1,77 @@
+use bevy::prelude::{
+    Res,
+    Resource,
+};
+
+/// Controls if style sheets are reapplied whenever their asset is modified.
+///
+/// The initial value is set by [`BevyCssPlugin`](crate::prelude::BevyCssPlugin), which keeps hot reloading disabled
+/// unless created with [`with_hot_reload`](crate::prelude::BevyCssPlugin::with_hot_reload). With the `debug_hot_reload`
+/// feature, it's enabled by default on debug builds instead, so the same setup hot reloads while developing and not on release.
+/// This resource can be changed at any time by user systems, like a dev console.
+///
+/// _Note_: Hot reloading also requires the `file_watcher` feature of `bevy` in order to receive asset modification events.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn toggle_hot_reload(
+///     keys: Res<Input<KeyCode>>,
+///     mut hot_reload: ResMut<HotReloadEnabled>,
+/// ) {
+///     if keys.just_pressed(KeyCode::F5) {
+///         hot_reload.toggle();
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct HotReloadEnabled(pub bool);
+
+impl HotReloadEnabled
+{
+    /// Checks if hot reloading is currently enabled.
+    pub fn is_enabled(
+        &self
+    ) -> bool {
+        self.0
+    }
+
+    /// Enables or disables hot reloading.
+    pub fn set(
+        &mut self,
+        enabled: bool
+    ) {
+        self.0 = enabled;
+    }
+
+    /// Flips the current hot reloading state.
+    pub fn toggle(
+        &mut self
+    ) {
+        self.0 = !self.0;
+    }
+}
+
+/// Whether hot reloading is enabled by default, which is only on debug builds with the `debug_hot_reload` feature.
+const ENABLED_BY_DEFAULT: bool = cfg!(all(feature = "debug_hot_reload", debug_assertions));
+
+impl Default
+for HotReloadEnabled
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self(ENABLED_BY_DEFAULT)
+    }
+}
+
+/// Run condition which checks if [`HotReloadEnabled`] is set.
+pub(crate) fn hot_reload_enabled(
+    enabled: Option<Res<HotReloadEnabled>>
+) -> bool {
+    enabled.is_some_and(|enabled| enabled.is_enabled())
+}
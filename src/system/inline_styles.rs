@@ -0,0 +1,111 @@
+use super::{
+    query,
+    revert_properties,
+    StyleDiagnostic,
+};
+use crate::{
+    prelude::InlineStyle,
+    property::PropertyName,
+    stylesheet::StyleSheetAsset,
+};
+
+use bevy::{
+    ecs::system::{SystemParam, SystemState},
+    log::error,
+    prelude::{
+        Assets,
+        Deref, DerefMut,
+        Entity,
+        Handle,
+        RemovedComponents,
+        Resource,
+        World,
+    },
+    utils::{HashMap, HashSet},
+};
+
+#[derive(SystemParam)]
+pub(crate) struct InlineStyleParam<'w, 's>
+{
+    pub changes: query::QueryInlineStyleChanges<'w, 's>,
+    pub removed: RemovedComponents<'w, 's, InlineStyle>,
+}
+
+/// Sheet made of the declarations of each [`InlineStyle`], by entity.
+///
+/// Those sheets are applied after all other sheets on their entity, see [`StyleSheetStateBuilder::build`](crate::property::StyleSheetStateBuilder::build).
+#[derive(Debug, Default)]
+#[derive(Deref, DerefMut, Resource)]
+pub(crate) struct InlineStyleSheets(
+    HashMap<Entity, Handle<StyleSheetAsset>>
+);
+
+/// Keeps [`InlineStyleSheets`] up to date with changed and removed [`InlineStyle`] components,
+/// and returns the entities which must be styled again.
+///
+/// Properties no longer declared by the inline style of an entity are reverted, and parse errors are sent as [`StyleDiagnostic`] events.
+pub(crate) fn sync_inline_styles(
+    world: &mut World,
+    params: &mut SystemState<InlineStyleParam<'static, 'static>>
+) -> HashSet<Entity> {
+    let (changed, removed) = {
+        let mut inline_query = params.get(world);
+
+        let changed: Vec<_> = inline_query.changes.iter()
+            .map(|(entity, style)| (entity, style.sheet().clone(), style.errors().to_vec()))
+            .collect();
+        let removed: Vec<Entity> = inline_query.removed.read().collect();
+
+        (changed, removed)
+    };
+
+    let mut restyled = HashSet::new();
+
+    for entity in removed
+    {
+        let previous = world.resource_mut::<InlineStyleSheets>().remove(&entity);
+        let properties: Vec<PropertyName> = declared_properties(world, previous.as_ref()).into_iter().collect();
+        revert_properties(world, entity, &properties);
+
+        restyled.insert(entity);
+    }
+
+    for (entity, sheet, errors) in changed
+    {
+        for error in errors
+        {
+            error!("Failed to parse inline style of entity {entity:?}. Error: {error}");
+            world.send_event(StyleDiagnostic { entity, error });
+        }
+
+        let handle = world.resource_mut::<Assets<StyleSheetAsset>>().add(sheet);
+        let previous = world.resource_mut::<InlineStyleSheets>().insert(entity, handle.clone());
+
+        // Properties which were only declared by the previous inline style must be reverted
+        let properties = declared_properties(world, Some(&handle));
+        let removed_properties: Vec<PropertyName> = declared_properties(world, previous.as_ref())
+            .into_iter()
+            .filter(|property| !properties.contains(property))
+            .collect();
+        revert_properties(world, entity, &removed_properties);
+
+        restyled.insert(entity);
+    }
+
+    // Despawned entities doesn't need to be styled again
+    restyled.retain(|entity| world.get_entity(*entity).is_some());
+    restyled
+}
+
+/// Names of all properties declared by the given sheet.
+fn declared_properties(
+    world: &World,
+    handle: Option<&Handle<StyleSheetAsset>>
+) -> HashSet<PropertyName> {
+    handle.and_then(|handle| world.resource::<Assets<StyleSheetAsset>>().get(handle))
+        .map(|sheet| sheet.iter()
+            .flat_map(|rule| rule.properties.keys().cloned())
+            .collect()
+        )
+        .unwrap_or_default()
+}
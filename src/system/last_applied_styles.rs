@@ -0,0 +1,42 @@
+use crate::property::{ComputedStyle, StyleSheetState};
+
+use bevy::{
+    prelude::{
+        Deref,
+        Entity,
+        Resource,
+    },
+    utils::HashMap,
+};
+
+/// Styles computed for each entity on the last frame which styled any entity, enabled by
+/// [`BevyCssPlugin::with_retained_state`](crate::prelude::BevyCssPlugin::with_retained_state).
+///
+/// [`StyleSheetState`] only holds the computed styles until [`BevyCssSet::Cleanup`](crate::prelude::BevyCssSet::Cleanup),
+/// so this resource allows systems running after it, like a devtools plugin, to inspect which rule set each property.
+/// It's kept until another frame styles any entity, so entities which weren't styled again aren't listed.
+///
+/// # Memory
+///
+/// Computed styles are swapped into this resource, instead of cloned, so the cost is keeping a second map alive,
+/// with an entry for each property of each entity styled on that frame, which is a [`Handle`](bevy::prelude::Handle)
+/// and a [`Selector`](crate::selector::Selector) each. After styling the whole UI, like on startup, that's a few hundred bytes per entity.
+#[derive(Clone, Debug, Default)]
+#[derive(Deref, Resource)]
+pub struct LastAppliedStyles(
+    HashMap<
+        Entity,
+        ComputedStyle
+    >
+);
+
+impl LastAppliedStyles
+{
+    /// Takes the computed styles of the given state, leaving it with the previous ones, which must be cleared by the caller.
+    pub(crate) fn swap(
+        &mut self,
+        state: &mut StyleSheetState
+    ) {
+        std::mem::swap(&mut self.0, &mut state.computed);
+    }
+}
@@ -1,3 +1,7 @@
+mod clear_styles;
+pub use clear_styles::clear_styles;
+pub(crate) use clear_styles::{clear_ignored_styles, clear_removed_style_sheets, reset_properties, revert_properties};
+
 mod component_filter;
 
 pub(crate) use component_filter::*;
@@ -5,13 +9,74 @@ pub(crate) use component_filter::*;
 mod component_filter_registry;
 pub(crate) use component_filter_registry::*;
 
+mod inline_styles;
+pub(crate) use inline_styles::*;
+
+mod property_reset_registry;
+pub(crate) use property_reset_registry::*;
+
+mod css_capabilities;
+pub use css_capabilities::CssCapabilities;
+
+mod css_conditions;
+pub use css_conditions::{CssConditionFn, CssConditions};
+pub(crate) use css_conditions::evaluate_css_conditions;
+
+mod css_flags;
+pub use css_flags::CssFlags;
+pub(crate) use css_flags::evaluate_css_flags;
+
 mod css_query_param;
 pub(crate) use css_query_param::*;
 
+mod hot_reload_enabled;
+pub use hot_reload_enabled::HotReloadEnabled;
+pub(crate) use hot_reload_enabled::hot_reload_enabled;
+
+mod non_ui_descendants;
+pub use non_ui_descendants::NonUiDescendants;
+
 pub(crate) mod query;
 
+mod last_applied_styles;
+pub use last_applied_styles::LastAppliedStyles;
+
+mod rule_diff;
+pub(crate) use rule_diff::*;
+
 pub mod sets;
 
+mod present_names;
+use present_names::{PresentNames, PresentNamesChanges};
+
+mod selector_match_cache;
+pub use selector_match_cache::SelectorMatchStats;
+use selector_match_cache::SelectorMatchCache;
+
+mod style_applied;
+pub use style_applied::StyleApplied;
+pub(crate) use style_applied::send_style_applied;
+
+mod style_changed;
+pub use style_changed::StyleChanged;
+pub(crate) use style_changed::send_style_changed;
+
+mod style_diagnostic;
+pub use style_diagnostic::StyleDiagnostic;
+
+mod style_sheet_dependencies;
+pub(crate) use style_sheet_dependencies::*;
+
+mod style_snapshot;
+pub use style_snapshot::{restore_styles, snapshot_styles, StyleSnapshot};
+
+mod unmatched_rules;
+pub(crate) use unmatched_rules::{report_unmatched_rules, UnmatchedRuleWarnings};
+
+mod style_roots;
+pub use style_roots::{style_sheet_roots, StyleRootProvider, StyleRoots};
+pub(crate) use style_roots::{StyleRootMap, StyleRootUpdate};
+
 mod style_tree;
 use style_tree::StyleTree;
 
@@ -20,39 +85,103 @@ use crate::{
         MatchSelectorElement,
         StyleSheet,
     },
-    property::{StyleSheetState, StyleSheetStateBuilder},
+    parser::LINT_TARGET,
+    property::{
+        BareNumberPolicy,
+        CascadeAuditEnabled, CascadeAuditReport, CascadeConflict,
+        PropertyName,
+        RootFontSize,
+        RuleMatchTracking,
+        StyleSheetState, StyleSheetStateBuilder,
+    },
     selector::{Selector, SelectorElement},
-    stylesheet::StyleSheetAsset,
+    stylesheet::{StyleRule, StyleSheetAsset},
 };
 
 use bevy::{
     ecs::system::SystemState,
-    log::{error, debug, trace},
+    log::{error, debug, trace, warn},
     prelude::{
-        AssetEvent, Assets,
+        AssetEvent, AssetId, Assets,
         Children, Component,
         Deref, DerefMut,
         Entity, EventReader,
         Mut,
         Parent,
+        Handle,
+        Local,
         Query,
-        ResMut, Resource,
+        Res, ResMut, Resource,
+        Text,
         World,
     },
+    utils::{HashMap, HashSet},
+};
+#[cfg(feature = "pseudo_class")]
+use crate::component::PseudoClasses;
+#[cfg(feature = "pseudo_class")]
+use bevy::{
+    ecs::query::QueryState,
+    prelude::DetectChangesMut,
 };
 use smallvec::{smallvec, SmallVec};
 
+#[cfg(test)]
+mod tests;
+
+/// Pseudo-classes matched by the plugin itself, any other name is a custom state set with [`PseudoClasses`].
+#[cfg(feature = "pseudo_class")]
+const BUILTIN_PSEUDO_CLASSES: [&str; 5] = ["hover", "click", "disabled", "checked", "root"];
+
+/// System states used by [`prepare`].
+///
+/// Each step has its own state, since getting the same state twice on a frame would miss changes made in between.
 #[derive(Deref, DerefMut, Resource)]
-pub(crate) struct PrepareParams(
-    SystemState<CssQueryParam<'static, 'static>>
-);
+pub(crate) struct PrepareParams
+{
+    #[deref]
+    query: SystemState<CssQueryParam<'static, 'static>>,
+    /// Used to select entities matched by rules changed on hot reload, see [`reload_changed_rules`].
+    reload: SystemState<CssQueryParam<'static, 'static>>,
+    /// Used to find changed inline styles, see [`sync_inline_styles`].
+    inline: SystemState<InlineStyleParam<'static, 'static>>,
+    /// Used to take pseudo-class states which changed, see [`take_flipped_pseudo_classes`].
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: QueryState<(Entity, &'static mut PseudoClasses)>,
+    /// Custom pseudo-classes already checked by [`find_unknown_pseudo_classes`], so each one is only warned about once.
+    #[cfg(feature = "pseudo_class")]
+    checked_pseudo_classes: HashSet<String>,
+    /// Used to find if classes or names changed, so [`PresentNames`] is only indexed again when they did.
+    present_changes: SystemState<PresentNamesChanges<'static, 'static>>,
+    buffers: PrepareBuffers,
+}
+
+/// State kept between [`prepare_state`] passes.
+#[derive(Default)]
+pub(crate) struct PrepareBuffers
+{
+    /// Reused by every pass, so its buffers are only allocated once.
+    builder: StyleSheetStateBuilder,
+    /// Classes and names present on the world, see [`PresentNames::begin_pass`].
+    present: PresentNames,
+}
 
 impl PrepareParams
 {
     pub fn new(
         world: &mut World
     ) -> Self {
-        Self(SystemState::new(world))
+        Self{
+            query: SystemState::new(world),
+            reload: SystemState::new(world),
+            inline: SystemState::new(world),
+            #[cfg(feature = "pseudo_class")]
+            pseudo_classes: world.query(),
+            #[cfg(feature = "pseudo_class")]
+            checked_pseudo_classes: HashSet::new(),
+            present_changes: SystemState::new(world),
+            buffers: PrepareBuffers::default(),
+        }
     }
 }
 
@@ -60,91 +189,676 @@ impl PrepareParams
 pub(crate) fn prepare(
     world: &mut World
 ) {
+    let roots = world.resource_scope(|world, mut provider: Mut<StyleRootProvider>| provider.update(world));
+
     world.resource_scope(|world, mut params: Mut<PrepareParams>|
     {
         world.resource_scope(|world, mut registry: Mut<ComponentFilterRegistry>|
         {
-            let assets = world.resource::<Assets<StyleSheetAsset>>();
-            let css_query = params.get(world);
-            let state = prepare_state(world, assets, css_query, &mut registry);
+            let mut restyled = reload_changed_rules(world, &mut params.reload, &mut registry, &roots.roots);
+            restyled.extend(sync_inline_styles(world, &mut params.inline));
+            #[cfg(feature = "pseudo_class")]
+            let flipped = take_flipped_pseudo_classes(world, &mut params.pseudo_classes);
+            #[cfg(feature = "pseudo_class")]
+            for name in find_unknown_pseudo_classes(world, &flipped, &mut params.checked_pseudo_classes)
+            {
+                warn!(
+                    target: LINT_TARGET,
+                    "Unknown pseudo-class :{name}, which no PseudoClasses component sets. It's matched as a custom state, so check for typos"
+                );
+            }
+
+            let params = &mut *params;
+            let names_changed = params.present_changes.get_mut(world).any();
+            params.buffers.present.begin_pass(names_changed);
+
+            let css_query = params.query.get(world);
+            let (mut state, match_stats, conflicts, stale) = prepare_state(
+                world,
+                css_query,
+                &mut registry,
+                &restyled,
+                &roots,
+                &mut params.buffers,
+                #[cfg(feature = "pseudo_class")]
+                &flipped,
+            );
+
+            if match_stats.rules > 0 || match_stats.skipped > 0
+            {
+                world.insert_resource(match_stats);
+            }
+
+            if !conflicts.is_empty()
+            {
+                for conflict in conflicts.iter()
+                {
+                    warn!(target: LINT_TARGET, "{conflict}");
+                }
+
+                world.send_event(CascadeAuditReport { conflicts });
+            }
 
-            if !state.is_empty()
+            for (entity, properties) in stale.iter()
             {
-                let mut state_res = world
-                    .get_resource_mut::<StyleSheetState>()
-                    .expect("Should be added by plugin");
+                debug!("Reverting {} properties of pseudo-class rules from entity {entity:?}", properties.len());
+                reset_properties(world, *entity, properties.iter().map(|name| &**name));
+            }
 
-                *state_res = state;
+            let mut state_res = world
+                .get_resource_mut::<StyleSheetState>()
+                .expect("Should be added by plugin");
+
+            for (entity, properties) in stale
+            {
+                state_res.forget_applied(entity, &properties);
+            }
+
+            // Matches are counted even when nothing was styled
+            state_res.merge_match_counts(&mut state);
+            if !state.computed.is_empty()
+            {
+                state_res.replace(state);
             }
         });
     });
 }
 
+/// Takes the names of the pseudo-class states which were set or unset on each entity since last frame, see [`PseudoClasses`].
+///
+/// Those are taken without triggering change detection, so systems watching [`PseudoClasses`] only see changes made by users.
+#[cfg(feature = "pseudo_class")]
+fn take_flipped_pseudo_classes(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &'static mut PseudoClasses)>
+) -> HashMap<Entity, Vec<String>> {
+    query.iter_mut(world)
+        .filter(|(_entity, pseudo_classes)| pseudo_classes.has_flipped())
+        .map(|(entity, mut pseudo_classes)| (entity, pseudo_classes.bypass_change_detection().take_flipped()))
+        .collect()
+}
+
+/// Finds pseudo-classes referenced by loaded style sheets which aren't built-in, like `:hover`, and which no [`PseudoClasses`]
+/// component sets, so typos like `:hovr` don't silently never match.
+///
+/// Each name is only returned once, since states like `:selected` may only be set later on.
+#[cfg(feature = "pseudo_class")]
+fn find_unknown_pseudo_classes(
+    world: &mut World,
+    flipped: &HashMap<Entity, Vec<String>>,
+    checked: &mut HashSet<String>
+) -> Vec<String> {
+    let Some(assets) = world.get_resource::<Assets<StyleSheetAsset>>()
+    else {
+        return vec![];
+    };
+
+    let unchecked: HashSet<String> = assets.iter()
+        .flat_map(|(_id, sheet)| sheet.pseudo_classes())
+        .filter(|name| !BUILTIN_PSEUDO_CLASSES.contains(name) && !checked.contains(*name))
+        .map(str::to_string)
+        .collect();
+
+    if unchecked.is_empty()
+    {
+        return vec![];
+    }
+
+    let mut query = world.query::<&PseudoClasses>();
+    let mut unknown: Vec<String> = unchecked.iter()
+        .filter(|name| !flipped.values().flatten().any(|flipped| flipped == *name)
+            && !query.iter(world).any(|pseudo_classes| pseudo_classes.contains(name))
+        )
+        .cloned()
+        .collect();
+
+    checked.extend(unchecked);
+    unknown.sort();
+    unknown
+}
+
+/// Reverts properties of rules changed by hot reload, and returns the entities which must be styled again.
+///
+/// Only entities matched by added, changed or removed rules, on any sheet of the given roots which uses the modified sheet,
+/// are returned. Their descendants are also returned when those rules declare inherited properties.
+fn reload_changed_rules(
+    world: &mut World,
+    params: &mut SystemState<CssQueryParam<'static, 'static>>,
+    registry: &mut ComponentFilterRegistry,
+    roots: &StyleRootMap
+) -> HashSet<Entity> {
+    let diffs = match world.get_resource_mut::<PendingRuleDiffs>()
+    {
+        Some(mut diffs) if !diffs.is_empty() => std::mem::take(&mut **diffs),
+        _ => return HashSet::new(),
+    };
+
+    let mut reloaded = HashSet::new();
+    let mut reverted: Vec<(Entity, &[PropertyName])> = Vec::new();
+
+    {
+        let css_query = params.get(world);
+        for (root, sheets) in roots.iter()
+        {
+            let root = *root;
+            let sheets: SmallVec<[Handle<StyleSheetAsset>; 4]> = sheets.iter()
+                .flat_map(|sheet| with_imports(sheet, &css_query.assets))
+                .collect();
+
+            for (_id, diff) in diffs.iter().filter(|(id, _diff)| sheets.iter().any(|handle| handle.id() == **id))
+            {
+                let selectors = diff.applied.iter()
+                    .map(|selector| (selector, Default::default()))
+                    .chain(diff.reverted.iter().map(|(selector, properties)| (selector, properties.as_slice())));
+
+                for (selector, properties) in selectors
+                {
+                    for entity in select_entities(root, root, selector, world, &css_query, registry)
+                    {
+                        reloaded.insert(entity);
+
+                        if diff.inherited
+                        {
+                            if let Some(children) = css_query.descendant_children(entity)
+                            {
+                                reloaded.extend(get_children_recursively(children, &css_query));
+                            }
+                        }
+
+                        if !properties.is_empty()
+                        {
+                            reverted.push((entity, properties));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (entity, properties) in reverted
+    {
+        debug!("Reverting {} properties of changed rules from entity {entity:?}", properties.len());
+        revert_properties(world, entity, properties);
+    }
+
+    reloaded
+}
+
+/// Properties which no longer apply on each entity, and must be reverted.
+type StaleProperties = Vec<(Entity, Vec<PropertyName>)>;
+
 /// Prepare state to be used by [`Property`](crate::Property) systems
+///
+/// Entities on `restyled` are styled again, without their ancestors and descendants,
+/// like the ones returned by [`reload_changed_rules`] and [`sync_inline_styles`].
+/// So are entities which [`StyleOverride`](crate::prelude::StyleOverride) changed or was removed.
+///
+/// Entities on `flipped` had the given [`PseudoClasses`] states set or unset, so only rules referencing them are evaluated again,
+/// and only for those entities and their descendants.
+///
+/// Sheets apply to the given `roots`, and changed roots are styled again along with their descendants, see [`StyleRootProvider`].
+///
+/// Returns the state along with how many selectors were evaluated to build it, the conflicts found by the cascade audit,
+/// when [`CascadeAuditEnabled`] is set, and the properties of pseudo-class rules which no longer apply on each entity,
+/// which must be reverted.
 pub(crate) fn prepare_state(
     world: &World,
-    assets: &Assets<StyleSheetAsset>,
-    params: CssQueryParam,
-    registry: &mut ComponentFilterRegistry
-) -> StyleSheetState {
-    let mut state = StyleSheetStateBuilder::default();
-    let mut style_tree: StyleTree = Default::default();
+    mut params: CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    restyled: &HashSet<Entity>,
+    roots: &StyleRootUpdate,
+    buffers: &mut PrepareBuffers,
+    #[cfg(feature = "pseudo_class")]
+    flipped: &HashMap<Entity, Vec<String>>
+) -> (StyleSheetState, SelectorMatchStats, Vec<CascadeConflict>, StaleProperties) {
+    let PrepareBuffers { builder: state, present } = buffers;
+    let assets = world.resource::<Assets<StyleSheetAsset>>();
+    state.clear();
+    state.set_cascade_audit(world.get_resource::<CascadeAuditEnabled>().is_some_and(|audit| audit.0));
+    state.set_match_tracking(world.get_resource::<RuleMatchTracking>().is_some_and(|tracking| tracking.0));
+    let mut caches = PrepareCaches {
+        style_tree: StyleTree::new(&roots.roots),
+        matches: Default::default(),
+        present,
+        ignored: ignored_entities(&params),
+        #[cfg(feature = "pseudo_class")]
+        pseudo_class_properties: Default::default(),
+    };
+
+    // Properties no longer overridden must be applied by sheets again
+    let changed_overrides: SmallVec<[Entity; 8]> = params.overrides.changes.iter()
+        .chain(params.overrides.removed.read())
+        .filter(|entity| params.ui_nodes.contains(*entity))
+        .collect();
+    let restyled: HashSet<Entity> = restyled.iter()
+        .copied()
+        .chain(changed_overrides)
+        .collect();
+
+    // Entities matched by rules changed on hot reload, or which inline style changed. Since selecting rules also
+    // selects ancestors and descendants, only those entities are kept, so unchanged parts of the tree aren't applied again.
+    for restyled_entity in restyled.iter()
+    {
+        debug!("Restyle requested for entity {}", restyled_entity.index());
+        select_rules(*restyled_entity, &RuleScope::All, world, &params, registry, &mut caches, state);
+    }
+
+    // Entities which pseudo-class states flipped are kept the same way, along with their descendants
+    #[cfg(feature = "pseudo_class")]
+    let flipped_scope = select_flipped_rules(flipped, world, &params, registry, &mut caches, state);
+    #[cfg(not(feature = "pseudo_class"))]
+    let flipped_scope: HashSet<Entity> = HashSet::new();
+    state.retain_entities(|entity| restyled.contains(entity) || flipped_scope.contains(entity));
 
     // Find only changed components
     for updated_entity in &params.ui_changes
     {
         debug!("Updated detected for entity {}", updated_entity.index());
+        select_rules(updated_entity, &RuleScope::All, world, &params, registry, &mut caches, state);
+    }
 
-        // Find list of stylesheets that apply to this component (and cache in style_tree for next iterations)
-        for (root_entity, sheet_handle) in style_tree
-            .get_style_roots_for(updated_entity, &params.ui_nodes)
-            .iter()
-        {
-            let style_sheet = match params.assets.get(sheet_handle)
-            {
-                Some(sheet) => sheet,
-                None => {
-                    error!("Failed to load stylesheet from handle {sheet_handle:?}");
-                    continue;
-                }
-            };
+    // Roots found for the first time, or which sheets changed, may not have any changed component
+    for root in roots.changed.iter().filter(|root| !params.ui_changes.contains(**root))
+    {
+        debug!("Root update detected for entity {}", root.index());
+        select_rules(*root, &RuleScope::All, world, &params, registry, &mut caches, state);
+    }
 
-            debug!("Applying style {}", style_sheet.path());
-            for rule in style_sheet.iter()
-            {
-                let mut entities = select_entities(
-                    *root_entity,
-                    updated_entity,
-                    &rule.selector,
-                    world,
-                    &params,
-                    registry,
-                );
+    // Entities which reflected enum components changed, like `interaction(hovered)`, may match other rules now
+    let variant_changes: HashSet<Entity> = registry.0.values_mut()
+        .flat_map(|filter| filter.changed(world))
+        .filter(|entity| !params.ui_changes.contains(*entity))
+        .collect();
 
-                trace!(
-                    "Applying rule '{}' on {} entities {entities:?}",
-                    rule.selector.to_string(),
-                    entities.len()
-                );
+    #[cfg(not(feature = "pseudo_class"))]
+    for updated_entity in variant_changes
+    {
+        debug!("Variant update detected for entity {}", updated_entity.index());
+        select_rules(updated_entity, &RuleScope::All, world, &params, registry, &mut caches, state);
+    }
 
-                let existing_state = state.entry(sheet_handle.clone())
-                    .or_default()
-                    .entry(rule.selector.clone())
-                    .or_default();
+    // Entities which only had its interaction changed need only interaction sensitive rules to be evaluated again.
+    // Removing `Disabled` or `Checked` also changes which pseudo-classes match, and so does changing the variant of a component,
+    // so both are handled the same way. Removing `PseudoClasses` unsets states which are no longer known, so it's handled
+    // the same way too.
+    #[cfg(feature = "pseudo_class")]
+    let removed_disabled: SmallVec<[Entity; 8]> = params.pseudo_classes.removed_disabled.read()
+        .chain(params.pseudo_classes.removed_checked.read())
+        .chain(params.pseudo_classes.removed_custom.read())
+        .collect();
 
-                entities = entities.into_iter()
-                    .filter(|e| !existing_state.contains(e))
-                    .collect();
-                existing_state.append(&mut entities);
-            }
+    #[cfg(feature = "pseudo_class")]
+    let interaction_changes: HashSet<Entity> = params.pseudo_classes.interaction_changes.iter()
+        .chain(removed_disabled)
+        .chain(variant_changes)
+        .collect();
+
+    // Entities which pseudo-class rules were evaluated for, so the ones no longer matching can be reverted
+    #[cfg(feature = "pseudo_class")]
+    let mut pseudo_class_scope = flipped_scope;
+
+    #[cfg(feature = "pseudo_class")]
+    for updated_entity in interaction_changes
+    {
+        if params.ui_changes.contains(updated_entity)
+        {
+            continue;
         }
+
+        debug!("Interaction update detected for entity {}", updated_entity.index());
+        select_rules(updated_entity, &RuleScope::Interaction, world, &params, registry, &mut caches, state);
+
+        pseudo_class_scope.insert(updated_entity);
+        if let Some(children) = params.descendant_children(updated_entity)
+        {
+            pseudo_class_scope.extend(get_children_recursively(children, &params));
+        }
+    }
+
+    // Inline styles must override the rules selected for their entity, so they are included whenever it's styled
+    if let Some(inline_sheets) = world.get_resource::<InlineStyleSheets>()
+    {
+        let selected = state.entities();
+        inline_sheets.iter()
+            .filter(|(entity, _handle)| restyled.contains(*entity) || selected.contains(*entity))
+            .for_each(|(entity, handle)| state.set_inline(*entity, handle.clone()));
     }
 
     if state.len() > 0
     {
         trace!("PreProcess result: {state:?}");
     }
-    state.build(assets)
+    let built = state.build(assets);
+    let conflicts = state.take_conflicts();
+    let builder = state;
+
+    let mut state = built;
+    state.inherit_properties(
+        |entity| params.children
+            .get(entity)
+            .ok()
+            .map(|(_entity, children)| &**children),
+        // Inherited properties are only applied on texts
+        |entity| world.get::<Text>(entity).is_some(),
+    );
+    // Ignored entities may still be selected by rules without ancestors, or inherit properties from their parent
+    state.computed.retain(|entity, _style| !caches.ignored.contains(entity));
+    state.remove_overridden(|entity| params.overrides.overrides
+        .get(entity)
+        .ok()
+        .map(|(_entity, style_override)| style_override)
+    );
+
+    let previous_state = world.get_resource::<StyleSheetState>();
+    #[cfg(feature = "pseudo_class")]
+    let stale = stale_pseudo_class_properties(&state, previous_state, &pseudo_class_scope, &caches, &params);
+    #[cfg(not(feature = "pseudo_class"))]
+    let stale = Vec::new();
+
+    let root_font_size = world.get_resource::<RootFontSize>()
+        .copied()
+        .unwrap_or_default();
+    state.resolve_font_sizes(assets, root_font_size.0, previous_state, |entity| params.parent
+        .get(entity)
+        .ok()
+        .map(|(_entity, parent)| parent.get())
+    );
+
+    let bare_number_policy = world.get_resource::<BareNumberPolicy>()
+        .copied()
+        .unwrap_or_default();
+    state.resolve_values(assets, bare_number_policy);
+
+    state.sort_parents_first(|entity| params.parent
+        .get(entity)
+        .ok()
+        .map(|(_entity, parent)| parent.get())
+    );
+    builder.reclaim(&mut state);
+
+    (state, caches.matches.stats(), conflicts, stale)
+}
+
+/// Lists properties applied on entities of the given scope by pseudo-class rules which no longer match them,
+/// like `display: none` of a `:hover` rule after the pointer leaves, so they can be reverted.
+///
+/// Rules evaluated for pseudo-class changes include every other rule declaring the same properties, so a property
+/// missing from the new state isn't applied by any rule anymore, unless it's overridden by [`StyleOverride`](crate::prelude::StyleOverride).
+#[cfg(feature = "pseudo_class")]
+fn stale_pseudo_class_properties(
+    state: &StyleSheetState,
+    previous_state: Option<&StyleSheetState>,
+    scope: &HashSet<Entity>,
+    caches: &PrepareCaches,
+    params: &CssQueryParam
+) -> StaleProperties {
+    let Some(previous_state) = previous_state
+    else {
+        return Vec::new();
+    };
+
+    scope.iter()
+        .filter(|entity| !caches.ignored.contains(*entity))
+        .filter_map(|entity| {
+            let applied = previous_state.applied(*entity)?;
+            let computed = state.computed.get(entity);
+            let style_override = params.overrides.overrides
+                .get(*entity)
+                .ok()
+                .map(|(_entity, style_override)| style_override);
+
+            let stale: Vec<PropertyName> = applied.iter()
+                .filter(|name| caches.pseudo_class_properties.contains(*name))
+                .filter(|name| !computed.is_some_and(|style| style.contains_key(*name)))
+                .filter(|name| !style_override.is_some_and(|style_override| style_override.overrides(name)))
+                .cloned()
+                .collect();
+
+            (!stale.is_empty()).then_some((*entity, stale))
+        })
+        .collect()
+}
+
+/// Selects entities matched by rules referencing the pseudo-classes which flipped on each entity, see [`PseudoClasses`].
+///
+/// Those rules can't match other entities than the flipped ones and their descendants, which are returned, so only them
+/// must be kept and the rest of the tree is left untouched.
+#[cfg(feature = "pseudo_class")]
+fn select_flipped_rules(
+    flipped: &HashMap<Entity, Vec<String>>,
+    world: &World,
+    params: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    caches: &mut PrepareCaches,
+    state: &mut StyleSheetStateBuilder
+) -> HashSet<Entity> {
+    let mut scope = HashSet::new();
+
+    for (flipped_entity, names) in flipped.iter()
+    {
+        // Changed entities are fully styled anyway
+        if params.ui_changes.contains(*flipped_entity)
+        {
+            continue;
+        }
+
+        debug!("Pseudo-class update detected for entity {}", flipped_entity.index());
+        select_rules(*flipped_entity, &RuleScope::PseudoClasses(names.clone()), world, params, registry, caches, state);
+
+        scope.insert(*flipped_entity);
+        if let Some(children) = params.descendant_children(*flipped_entity)
+        {
+            scope.extend(get_children_recursively(children, params));
+        }
+    }
+
+    scope
+}
+
+/// Caches shared by all [`select_rules`] calls of a single [`prepare_state`] pass.
+struct PrepareCaches<'a>
+{
+    style_tree: StyleTree<'a>,
+    matches: SelectorMatchCache,
+    present: &'a mut PresentNames,
+    /// Entities with [`CssIgnore`](crate::prelude::CssIgnore) and their descendants, see [`ignored_entities`].
+    ignored: HashSet<Entity>,
+    /// Properties declared by rules evaluated for pseudo-class changes, which are reverted where they no longer apply,
+    /// see [`stale_pseudo_class_properties`].
+    #[cfg(feature = "pseudo_class")]
+    pseudo_class_properties: HashSet<PropertyName>,
+}
+
+/// Which rules of each sheet are evaluated by [`select_rules`].
+enum RuleScope
+{
+    /// All rules of the sheet.
+    All,
+    /// Only rules sensitive to [`Interaction`](bevy::prelude::Interaction) changes, see [`StyleSheetAsset::interaction_rules`].
+    #[cfg(feature = "pseudo_class")]
+    Interaction,
+    /// Only rules sensitive to changes of the given pseudo-classes, see [`StyleSheetAsset::pseudo_class_rules`].
+    #[cfg(feature = "pseudo_class")]
+    PseudoClasses(Vec<String>),
+}
+
+/// Selects entities matched by rules of every sheet which applies to the updated entity.
+///
+/// Only rules on the given scope are evaluated, and rules sharing a selector reuse the entities it matched, see [`SelectorMatchCache`].
+fn select_rules(
+    updated_entity: Entity,
+    scope: &RuleScope,
+    world: &World,
+    params: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    caches: &mut PrepareCaches,
+    state: &mut StyleSheetStateBuilder
+) {
+    if caches.ignored.contains(&updated_entity)
+    {
+        return;
+    }
+
+    // Find list of stylesheets that apply to this component (and cache in style_tree for next iterations)
+    // Sheets are returned from the top-most ancestor down to the closest one, which is used as the sheet order.
+    // Imported sheets are placed right before the sheet importing them.
+    let sheets: SmallVec<[(Entity, Handle<StyleSheetAsset>); 8]> = caches.style_tree
+        .get_style_roots_for(updated_entity, &params.ui_nodes)
+        .iter()
+        .flat_map(|(root_entity, sheet_handle)| with_imports(sheet_handle, &params.assets)
+            .into_iter()
+            .map(|handle| (*root_entity, handle))
+        )
+        .collect();
+
+    // Sheets only know which of their own rules declare the same properties as sensitive rules, so rules of other sheets
+    // must also be evaluated again, since they may win once the sensitive rules no longer match
+    #[cfg(feature = "pseudo_class")]
+    let sensitive_properties: HashSet<PropertyName> = match scope
+    {
+        RuleScope::All => HashSet::new(),
+        _ if sheets.len() < 2 => HashSet::new(),
+        scope => sheets.iter()
+            .filter_map(|(_root_entity, sheet_handle)| params.assets.get(sheet_handle))
+            .flat_map(|sheet| scoped_rules(sheet, scope))
+            .flat_map(|(_selector_id, rule)| rule.properties.keys().cloned())
+            .collect(),
+    };
+
+    for (order, (root_entity, sheet_handle)) in sheets.iter().enumerate()
+    {
+        let style_sheet = match params.assets.get(sheet_handle)
+        {
+            Some(sheet) => sheet,
+            None => {
+                error!("Failed to load stylesheet from handle {sheet_handle:?}");
+                continue;
+            }
+        };
+
+        debug!("Applying style {}", style_sheet.path());
+        state.set_sheet_order(*root_entity, sheet_handle, order);
+
+        #[cfg(feature = "pseudo_class")]
+        let rules: SmallVec<[(usize, &StyleRule); 8]> = match sensitive_properties.is_empty()
+        {
+            true => scoped_rules(style_sheet, scope).collect(),
+            // Sensitive rules also declare those properties, so they are included too
+            false => style_sheet.rules_declaring(&sensitive_properties).collect(),
+        };
+        #[cfg(not(feature = "pseudo_class"))]
+        let rules: SmallVec<[(usize, &StyleRule); 8]> = scoped_rules(style_sheet, scope).collect();
+
+        #[cfg(feature = "pseudo_class")]
+        if !matches!(scope, RuleScope::All)
+        {
+            caches.pseudo_class_properties.extend(rules.iter().flat_map(|(_selector_id, rule)| rule.properties.keys().cloned()));
+        }
+
+        // Rules on `@when` blocks are only applied while their conditions hold and their flags are enabled,
+        // and rules on `@supports` blocks only when their capabilities are supported
+        let rules = rules.into_iter()
+            .filter(|(_selector_id, rule)| rule.conditions.is_empty()
+                || world.get_resource::<CssConditions>().is_some_and(|conditions| conditions.allows(rule))
+            )
+            .filter(|(_selector_id, rule)| rule.flags.is_empty()
+                || world.get_resource::<CssFlags>().is_some_and(|flags| flags.allows(rule))
+            )
+            .filter(|(_selector_id, rule)| rule.capabilities.is_empty()
+                || world.get_resource::<CssCapabilities>().is_some_and(|capabilities| capabilities.allows(rule))
+            );
+
+        for (selector_id, rule) in rules
+        {
+            // Selectors referencing a class, name or component no entity has can't match anything
+            if !caches.present.may_match(&rule.selector, world, params, registry)
+            {
+                caches.matches.skip();
+                continue;
+            }
+
+            let mut entities = caches.matches.get_or_select(
+                *root_entity,
+                updated_entity,
+                sheet_handle.id(),
+                selector_id,
+                || select_entities(*root_entity, updated_entity, &rule.selector, world, params, registry),
+            );
+
+            trace!(
+                "Applying rule '{}' on {} entities {entities:?}",
+                rule.selector.to_string(),
+                entities.len()
+            );
+            state.record_matches(sheet_handle.id(), &rule.selector, entities.len());
+
+            let existing_state = state.entry((*root_entity, sheet_handle.clone()))
+                .or_default()
+                .entry(rule.selector.clone())
+                .or_default();
+
+            entities = entities.into_iter()
+                .filter(|e| !existing_state.contains(e))
+                .collect();
+            existing_state.append(&mut entities);
+        }
+    }
+}
+
+/// Iterates over the rules of the given sheet which are on the given scope, along with the id of their selector.
+fn scoped_rules<'a>(
+    style_sheet: &'a StyleSheetAsset,
+    scope: &'a RuleScope
+) -> Box<dyn Iterator<Item = (usize, &'a StyleRule)> + 'a> {
+    match scope
+    {
+        RuleScope::All => Box::new(style_sheet.iter_with_selector_ids()),
+        #[cfg(feature = "pseudo_class")]
+        RuleScope::Interaction => Box::new(style_sheet.interaction_rules()),
+        #[cfg(feature = "pseudo_class")]
+        RuleScope::PseudoClasses(names) => Box::new(style_sheet.pseudo_class_rules(names)),
+    }
+}
+
+/// Lists the given sheet preceded by all sheets it imports, recursively, on the order they are applied.
+///
+/// Imported sheets which aren't loaded are skipped with a warning, and each sheet is listed only once, even on import cycles.
+fn with_imports(
+    handle: &Handle<StyleSheetAsset>,
+    assets: &Assets<StyleSheetAsset>
+) -> SmallVec<[Handle<StyleSheetAsset>; 4]> {
+    fn visit(
+        handle: &Handle<StyleSheetAsset>,
+        assets: &Assets<StyleSheetAsset>,
+        result: &mut SmallVec<[Handle<StyleSheetAsset>; 4]>,
+        visiting: &mut HashSet<AssetId<StyleSheetAsset>>
+    ) {
+        if !visiting.insert(handle.id())
+        {
+            return;
+        }
+
+        if let Some(sheet) = assets.get(handle)
+        {
+            for import in sheet.imports()
+            {
+                match assets.get(import)
+                {
+                    Some(_) => visit(import, assets, result, visiting),
+                    None => warn!(
+                        "Style sheet {import:?} imported by '{}' isn't loaded, so its rules are skipped",
+                        sheet.path()
+                    ),
+                }
+            }
+        }
+
+        result.push(handle.clone());
+    }
+
+    let mut result = smallvec::smallvec![];
+    visit(handle, assets, &mut result, &mut HashSet::new());
+    result
 }
 
 fn build_entity_filter(
@@ -153,18 +867,18 @@ fn build_entity_filter(
     css_query: &CssQueryParam
 ) -> Option<SmallVec<[Entity; 8]>> {
     css_query.ui_nodes.get(updated_node)
-        .map(|(_entity, parent, children, _stylesheet)|
+        .map(|_node|
         {
             // Add parents recursively
-            parent.map_or_else(SmallVec::default, |parent| 
-                    get_parents_recursively(root, parent, &css_query.parent)
+            css_query.ancestor_parent(updated_node).map_or_else(SmallVec::default, |parent|
+                    get_parents_recursively(root, parent, css_query)
                 )
                 .into_iter()
                 // Add the entity that triggered the change
                 .chain(std::iter::once(updated_node))
                 // Add children recursively
-                .chain(children.map_or_else(SmallVec::default, |children|
-                    get_children_recursively(children, &css_query.children)
+                .chain(css_query.descendant_children(updated_node).map_or_else(SmallVec::default, |children|
+                    get_children_recursively(children, css_query)
                 ))
                 .collect()
         })
@@ -182,43 +896,50 @@ fn select_entities(
     css_query: &CssQueryParam,
     registry: &mut ComponentFilterRegistry
 ) -> SmallVec<[Entity; 8]> {
-    let mut parent_tree = selector.get_parent_tree();
-
-    if parent_tree.is_empty()
-    {
-        return SmallVec::new();
-    }
+    let mut nodes = selector.nodes().peekable();
+    let mut combinators = selector.combinators();
 
     let mut filter = build_entity_filter(root_node, updated_node, css_query);
-    loop
+    while let Some(node) = nodes.next()
     {
-        // TODO: Rework this to use a index to avoid recreating parent_tree every time the systems runs.
-        // This is has little to no impact on performance, since this system doesn't runs often.
-        let node = parent_tree.remove(0);
-        let entities = select_entities_node(node, world, css_query, registry, filter.clone());
+        let entities = select_entities_node(node, root_node, world, css_query, registry, filter);
 
-        if parent_tree.is_empty()
+        if nodes.peek().is_none()
         {
-            break entities;
+            return entities;
         }
-        else
-        {
-            let children = entities.into_iter()
-                .filter_map(|e| css_query.children.get(e).ok())
-                .flat_map(|(_e, ch)|
-                    get_children_recursively(ch, &css_query.children)
-                )
-                .collect();
 
-            filter = Some(children);
-        }
+        let direct = combinators.next() == Some(&SelectorElement::DirectChild);
+
+        // Nested matches share descendants, so skip duplicates to avoid checking them more than once
+        let mut visited = HashSet::new();
+        let children = entities.into_iter()
+            .flat_map(|e| match direct
+            {
+                // `>` always means the direct parent, even when descendants go through non UI entities
+                true => css_query.children.get(e).map_or_else(|_err| SmallVec::new(), |(_e, ch)| ch.iter()
+                    .filter(|e| !css_query.ignored.contains(**e))
+                    .copied()
+                    .collect()
+                ),
+                false => css_query.descendant_children(e).map_or_else(SmallVec::new, |ch|
+                    get_children_recursively(ch, css_query)
+                ),
+            })
+            .filter(|e| visited.insert(*e))
+            .collect();
+
+        filter = Some(children);
     }
+
+    SmallVec::new()
 }
 
 /// Filter entities matching the given selectors.
 /// This function is called once per node on tree returned by [`get_parent_tree`](Selector::get_parent_tree)
 fn select_entities_node(
-    node: SmallVec<[&SelectorElement; 8]>,
+    node: &[SelectorElement],
+    root_node: Entity,
     world: &World,
     css_query: &CssQueryParam,
     registry: &mut ComponentFilterRegistry,
@@ -242,6 +963,18 @@ fn select_entities_node(
                 filter
             ),
 
+            SelectorElement::ClassPrefix(prefix) => get_entities_matching(
+                &css_query.classes,
+                filter,
+                |class| class.matches_prefix(prefix)
+            ),
+
+            SelectorElement::ClassSuffix(suffix) => get_entities_matching(
+                &css_query.classes,
+                filter,
+                |class| class.matches_suffix(suffix)
+            ),
+
             #[cfg(feature = "pseudo_class")]
             SelectorElement::PseudoClass(class) => get_entities_with_pseudo_class(
                 class.as_str(),
@@ -254,6 +987,22 @@ fn select_entities_node(
                 "Implement PseudoProperty selection"
             ),
 
+            SelectorElement::NthOfType(a, b) => get_entities_nth_of_type(
+                node,
+                (*a, *b),
+                world,
+                css_query,
+                registry,
+                filter
+            ),
+
+            // The entity holding the sheet is known, so there is no need to query for it
+            SelectorElement::Scope => match filter
+            {
+                Some(filter) if !filter.contains(&root_node) => SmallVec::new(),
+                _ => smallvec![root_node],
+            },
+
             SelectorElement::Component(component) => get_entities_with_component(
                 component.as_str(),
                 world,
@@ -261,14 +1010,31 @@ fn select_entities_node(
                 filter
             ),
 
+            SelectorElement::ComponentVariant(component, variant) => get_entities_with_component_variant(
+                component.as_str(),
+                variant.as_str(),
+                world,
+                registry,
+                filter
+            ),
+
+            SelectorElement::ComponentAttribute(component, field, value) => get_entities_with_component_attribute(
+                component.as_str(),
+                field.as_str(),
+                value.as_str(),
+                world,
+                registry,
+                filter
+            ),
+
             // All child elements are filtered by [`get_parent_tree`](Selector::get_parent_tree)
-            SelectorElement::Child => unreachable!(),
+            SelectorElement::Child | SelectorElement::DirectChild => unreachable!(),
         };
 
         Some(result)
     };
 
-    node.into_iter()
+    node.iter()
         .fold(filter, fold_fn)
         .unwrap_or_default()
 }
@@ -281,6 +1047,72 @@ fn get_entities_with_pseudo_class(
 ) -> SmallVec<[Entity; 8]> {
     use bevy::prelude::Interaction;
 
+    if name == "disabled"
+    {
+        return match filter
+        {
+            Some(filter) => filter.into_iter()
+                .filter(|e| query.disabled.contains(*e))
+                .collect(),
+            None => query.disabled.iter().collect(),
+        };
+    }
+
+    if name == "checked"
+    {
+        // The `checked` custom state was the only way to match it before `Checked`, so both are matched
+        let is_checked = |entity: Entity| query.checked.contains(entity)
+            || query.custom.get(entity).is_ok_and(|(_e, pseudo_classes)| pseudo_classes.contains(name));
+
+        return match filter
+        {
+            Some(filter) => filter.into_iter()
+                .filter(|e| is_checked(*e))
+                .collect(),
+            None => {
+                let mut visited = HashSet::new();
+                query.checked.iter()
+                    .chain(query.custom.iter().filter_map(|(e, pseudo_classes)| pseudo_classes.contains(name).then_some(e)))
+                    .filter(|e| visited.insert(*e))
+                    .collect()
+            }
+        };
+    }
+
+    if name == "root"
+    {
+        // A UI root is a node which has no parent, or which parent isn't a UI node, like a node spawned under a 2d scene
+        let is_root = |entity: Entity| query.ui_parents.get(entity)
+            .is_ok_and(|(_e, parent)| parent.map_or(true, |parent| !query.ui_parents.contains(parent.get())));
+
+        return match filter
+        {
+            Some(filter) => filter.into_iter()
+                .filter(|e| is_root(*e))
+                .collect(),
+            None => query.ui_parents.iter()
+                .filter_map(|(e, _parent)| is_root(e).then_some(e))
+                .collect(),
+        };
+    }
+
+    if !BUILTIN_PSEUDO_CLASSES.contains(&name)
+    {
+        // Any other name is a custom state, set with `PseudoClasses`
+        let is_set = |entity: Entity| query.custom.get(entity)
+            .is_ok_and(|(_e, pseudo_classes)| pseudo_classes.contains(name));
+
+        return match filter
+        {
+            Some(filter) => filter.into_iter()
+                .filter(|e| is_set(*e))
+                .collect(),
+            None => query.custom.iter()
+                .filter_map(|(e, pseudo_classes)| pseudo_classes.contains(name).then_some(e))
+                .collect(),
+        };
+    }
+
     let mut buffer: SmallVec<[Entity; 8]> = Default::default();
     for (entity, action) in query.interaction.iter()
     {
@@ -313,18 +1145,96 @@ fn get_entities_with<T>(
 where
     T: Component + MatchSelectorElement,
 {
-    query.iter()
-        .filter_map(|(e, rhs)| match rhs.matches(name)
+    get_entities_matching(query, filter, |rhs| rhs.matches(name))
+}
+
+/// Filters entities which component matches the given predicate, like classes starting with a prefix.
+fn get_entities_matching<T>(
+    query: &Query<(Entity, &'static T)>,
+    filter: Option<SmallVec<[Entity; 8]>>,
+    predicate: impl Fn(&T) -> bool
+) -> SmallVec<[Entity; 8]>
+where
+    T: Component,
+{
+    // Filters are usually way smaller than the whole query, so check only filtered entities when there is one
+    match filter
+    {
+        Some(filter) => filter.into_iter()
+            .filter(|e| query.get(*e).is_ok_and(|(_e, rhs)| predicate(rhs)))
+            .collect(),
+
+        None => query.iter()
+            .filter_map(|(e, rhs)| match predicate(rhs)
+            {
+                true => Some(e),
+                false => None,
+            })
+            .collect(),
+    }
+}
+
+/// Filters entities which are the `an+b`-th of their siblings having the component of the same compound, like `button:nth-of-type(2)`.
+///
+/// Siblings are counted from `1`, skipping the ones without the component, like text between buttons, and the ones with [`CssIgnore`](crate::prelude::CssIgnore).
+fn get_entities_nth_of_type(
+    node: &[SelectorElement],
+    (a, b): (i32, i32),
+    world: &World,
+    css_query: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    filter: Option<SmallVec<[Entity; 8]>>
+) -> SmallVec<[Entity; 8]> {
+    // The parser only accepts it on a compound with a component
+    let Some(component) = node.iter()
+        .find_map(|element| match element
         {
-            true => Some(e),
-            false => None,
+            SelectorElement::Component(name)
+            | SelectorElement::ComponentVariant(name, _)
+            | SelectorElement::ComponentAttribute(name, _, _) => Some(name.as_str()),
+            _ => None,
         })
-        .filter(|e| match &filter
+    else {
+        return SmallVec::new();
+    };
+
+    let of_type: HashSet<Entity> = get_entities_with_component(component, world, registry, None)
+        .into_iter()
+        .collect();
+
+    let is_nth = |entity: Entity| -> bool {
+        let Some(siblings) = css_query.parent.get(entity).ok()
+            .and_then(|(_e, parent)| css_query.children.get(parent.get()).ok())
+        else {
+            return false;
+        };
+
+        let Some(index) = siblings.1.iter()
+            .filter(|sibling| of_type.contains(*sibling) && !css_query.ignored.contains(**sibling))
+            .position(|sibling| *sibling == entity)
+        else {
+            return false;
+        };
+
+        // Matches when `index = a * n + b` for any `n >= 0`
+        let offset = index as i32 + 1 - b;
+        match a
         {
-            Some(filter) => filter.contains(e),
-            None => true,
-        })
-        .collect()
+            0 => offset == 0,
+            a => offset % a == 0 && offset / a >= 0,
+        }
+    };
+
+    match filter
+    {
+        Some(filter) => filter.into_iter()
+            .filter(|e| of_type.contains(e) && is_nth(*e))
+            .collect(),
+        None => of_type.iter()
+            .copied()
+            .filter(|e| is_nth(*e))
+            .collect(),
+    }
 }
 
 /// Filters entities which have the components specified on selector, like "a" or "button".
@@ -356,78 +1266,235 @@ fn get_entities_with_component(
     }
 }
 
+/// Filters entities which have the component specified on selector on the given variant, like "interaction(hovered)".
+///
+/// The component must be registered on [`ComponentFilterRegistry`] with
+/// [`register_component_selector_reflect`](crate::RegisterComponentSelector::register_component_selector_reflect).
+fn get_entities_with_component_variant(
+    name: &str,
+    variant: &str,
+    world: &World,
+    components: &mut ComponentFilterRegistry,
+    filter: Option<SmallVec<[Entity; 8]>>
+) -> SmallVec<[Entity; 8]> {
+    let buffer = match components.0.get_mut(name)
+    {
+        Some(query) => query.filter_variant(world, variant),
+        None => {
+            error!("Unregistered component selector {}", name);
+            return SmallVec::new();
+        }
+    };
+
+    match (buffer, filter)
+    {
+        (Some(buffer), Some(filter)) => buffer.into_iter()
+            .filter(|e| filter.contains(e))
+            .collect(),
+        (Some(buffer), None) => buffer,
+        (None, _) => {
+            error!("Component selector {name} can't be matched by variant, register it with `register_component_selector_reflect`");
+            SmallVec::new()
+        }
+    }
+}
+
+/// Utility function to filter any entities by the value of a reflected field of a component, like `health[current=0]`
+fn get_entities_with_component_attribute(
+    name: &str,
+    field: &str,
+    value: &str,
+    world: &World,
+    components: &mut ComponentFilterRegistry,
+    filter: Option<SmallVec<[Entity; 8]>>
+) -> SmallVec<[Entity; 8]> {
+    let buffer = match components.0.get_mut(name)
+    {
+        Some(query) => query.filter_attribute(world, field, value),
+        None => {
+            error!("Unregistered component selector {}", name);
+            return SmallVec::new();
+        }
+    };
+
+    match (buffer, filter)
+    {
+        (Some(buffer), Some(filter)) => buffer.into_iter()
+            .filter(|e| filter.contains(e))
+            .collect(),
+        (Some(buffer), None) => buffer,
+        (None, _) => {
+            error!("Component selector {name} can't be matched by its fields, register it with `register_component_selector_fields`");
+            SmallVec::new()
+        }
+    }
+}
+
 /// Starting with the provided [Parent], collect all UI parent entities, recurisevely up the entity tree
 /// # Arguments
 /// `root` - The top-level [Entity] which contains the stylesheet, passed in to provide early stop when root hit
 /// `parent` - First [Parent] component to start search with (appears last in returned list)
-/// `query_parent` - Bevy [Query] paramter to perform recursive searching
+/// `css_query` - Bevy [Query] parameters to perform recursive searching, see [`ancestor_parent`](CssQueryParam::ancestor_parent)
 fn get_parents_recursively(
     root: Entity,
     parent: &Parent,
-    query_parent: &query::QueryEntityParent
+    css_query: &CssQueryParam
 ) -> SmallVec<[Entity; 8]> {
-    let mut result = match query_parent.get(parent.get())
+    let entity = parent.get();
+    let mut result = match css_query.ancestor_parent(entity)
     {
-        Ok((entity, parent)) => match entity == root
+        Some(parent) => match entity == root
         {
             true => smallvec![entity],
-            false => get_parents_recursively(root, parent, query_parent),
+            false => get_parents_recursively(root, parent, css_query),
         },
-        Err(_err) => Default::default(),
+        None => Default::default(),
     };
 
-    result.push(parent.get());
+    result.push(entity);
     result
 }
 
 /// Starting with the provided [Children] component, collect all UI children entities, recursively down the entity tree
 /// # Arguments
 /// `children` First [Children] component to start search with (children appear depth first in returned list)
-/// `query_children` - Bevy [Query] parameter to perform recursive searching with
+/// `css_query` - Bevy [Query] parameters to perform recursive searching with, see [`descendant_children`](CssQueryParam::descendant_children).
+/// Entities with [`CssIgnore`](crate::prelude::CssIgnore) are skipped along with their descendants
 fn get_children_recursively(
     children: &Children,
-    query_childs: &query::QueryEntityChildren,
+    css_query: &CssQueryParam,
 ) -> SmallVec<[Entity; 8]> {
     children
         .iter()
+        .filter(|e| !css_query.ignored.contains(**e))
         .flat_map(|&e|
             std::iter::once(e).chain(
-                query_childs.get(e)
-                    .map_or(SmallVec::new(), |(_c, gc)|
-                        get_children_recursively(gc, query_childs)
+                css_query.descendant_children(e)
+                    .map_or(SmallVec::new(), |gc|
+                        get_children_recursively(gc, css_query)
                     )
             )
         )
         .collect()
 }
 
-/// Auto reapply style sheets when hot reloading is enabled
+/// Collects all entities with [`CssIgnore`](crate::prelude::CssIgnore) along with their descendants, which must not be styled.
+fn ignored_entities(
+    params: &CssQueryParam
+) -> HashSet<Entity> {
+    let mut ignored = HashSet::new();
+    let mut pending: Vec<Entity> = params.ignored.iter().collect();
+
+    while let Some(entity) = pending.pop()
+    {
+        if ignored.insert(entity)
+        {
+            if let Some(children) = params.descendant_children(entity)
+            {
+                pending.extend(children.iter());
+            }
+        }
+    }
+
+    ignored
+}
+
+/// Auto reapply style sheets when hot reloading is enabled.
+///
+/// The modified sheet is compared with the version last seen, and only rules which were added, changed or removed
+/// are applied again on the next [`prepare`], see [`RuleDiff`]. Properties of removed rules are reverted.
+/// When changes can't be isolated to some rules, or the previous version is unknown, the whole sheet is reapplied instead.
+/// Since the previous version is taken when the sheet is first loaded, this is also the case for sheets modified on the same frame they are loaded.
+///
+/// Sheets importing the modified sheet, directly or through other imports, are also reapplied.
+/// This system only runs while [`HotReloadEnabled`] is set, so it can be enabled late and still pick up modified assets.
 pub(crate) fn hot_reload_style_sheets(
     mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    dependencies: Res<StyleSheetDependencies>,
+    mut previous: Local<HashMap<AssetId<StyleSheetAsset>, StyleSheetAsset>>,
+    mut pending: ResMut<PendingRuleDiffs>,
     mut q_sheets: Query<&mut StyleSheet>,
 ) {
     for evt in assets_events.read()
     {
-        if let AssetEvent::Modified { id } = evt
+        match evt
         {
-            q_sheets.iter_mut()
-                .filter(|sheet| &sheet.handle().id() == id)
-                .for_each(|mut sheet|
+            AssetEvent::Added { id } => {
+                if let Some(sheet) = assets.get(*id)
                 {
-                    debug!("Refreshing sheet {:?}", sheet);
-                    sheet.refresh();
-                });
+                    previous.insert(*id, sheet.clone());
+                }
+            }
+
+            AssetEvent::Modified { id } => {
+                let current = match assets.get(*id)
+                {
+                    Some(sheet) => sheet,
+                    None => continue,
+                };
+
+                // A sheet with the same content may have been modified before the previous version was seen
+                let diff = previous.insert(*id, current.clone())
+                    .filter(|previous| previous.hash() != current.hash())
+                    .and_then(|previous| RuleDiff::between(&previous, current));
+
+                match diff
+                {
+                    Some(diff) if diff.is_empty() => debug!("Sheet {} modified, but no rule changed", current.path()),
+                    Some(diff) => {
+                        debug!("Reapplying {} rules of sheet {}", diff.applied.len() + diff.reverted.len(), current.path());
+                        pending.entry(*id)
+                            .or_default()
+                            .merge(diff);
+                    }
+                    None => {
+                        let dependents = dependencies.dependents(*id);
+
+                        q_sheets.iter_mut()
+                            .filter(|sheet| dependents.contains(&sheet.handle().id()))
+                            .for_each(|mut sheet|
+                            {
+                                debug!("Refreshing sheet {:?}", sheet);
+                                sheet.refresh();
+                            });
+                    }
+                }
+            }
+
+            AssetEvent::Removed { id } => {
+                previous.remove(id);
+            }
+
+            _ => (),
         }
     }
 }
 
+/// Reapplies all style sheets when [`RootFontSize`] changes, so `rem` values, and relative font sizes, are resolved again.
+pub(crate) fn refresh_on_root_font_size_change(
+    mut q_sheets: Query<&mut StyleSheet>
+) {
+    debug!("Root font size changed, refreshing all sheets");
+    q_sheets.iter_mut()
+        .for_each(|mut sheet| sheet.refresh());
+}
+
 /// Clear temporary state
+///
+/// When retained, the computed styles are moved to [`LastAppliedStyles`] first.
 pub(crate) fn clear_state(
-    mut sheet_rule: ResMut<StyleSheetState>
+    mut sheet_rule: ResMut<StyleSheetState>,
+    last_applied: Option<ResMut<LastAppliedStyles>>
 ) {
-    if sheet_rule.len() > 0
+    if !sheet_rule.computed.is_empty()
     {
         debug!("Finished applying style sheet.");
-        sheet_rule.clear();
+
+        if let Some(mut last_applied) = last_applied
+        {
+            last_applied.swap(&mut sheet_rule);
+        }
+        sheet_rule.computed.clear();
     }
 }
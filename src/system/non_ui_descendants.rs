@@ -0,0 +1,26 @@
+use bevy::prelude::Resource;
+
+/// Controls if descendant selectors, like `#root .item`, match UI nodes placed below entities without [`Node`](bevy::prelude::Node),
+/// like a [`SpatialBundle`](bevy::prelude::SpatialBundle) used to group them, so nesting on `css` matches nesting on the scene tree.
+///
+/// The initial value is set by [`BevyCssPlugin`](crate::prelude::BevyCssPlugin), which disables it by default.
+/// When disabled, descendant traversal stops at the first entity without [`Node`](bevy::prelude::Node).
+///
+/// _Note_: The direct child combinator, like `#root > .item`, always matches the direct [`Parent`](bevy::prelude::Parent) only,
+/// so `.item` doesn't match if there is any entity between them, UI node or not.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn match_through_groups(
+///     mut non_ui_descendants: ResMut<NonUiDescendants>,
+/// ) {
+///     non_ui_descendants.0 = true;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct NonUiDescendants(pub bool);
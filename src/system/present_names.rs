@@ -0,0 +1,121 @@
+use super::{ComponentFilterRegistry, CssQueryParam};
+use crate::selector::{Selector, SelectorElement};
+
+use crate::prelude::Class;
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Changed, Name, Query, RemovedComponents, World},
+    utils::{HashMap, HashSet},
+};
+use std::{collections::BTreeSet, ops::Bound};
+
+/// Classes, names and components present on the world, kept by [`prepare`](super::prepare) between passes.
+///
+/// Selectors referencing any of them which no entity has can't match anything, so they are skipped without being evaluated
+/// against the tree. Classes and names are indexed the first time a selector is checked after any [`Class`] or [`Name`] changed,
+/// see [`PresentNamesChanges`], while components are checked once per component selector on each pass.
+///
+/// Classes are kept sorted, so class prefixes are found with a range lookup, and also reversed, so suffixes are found the same way.
+#[derive(Default)]
+pub(crate) struct PresentNames
+{
+    indexed: bool,
+    classes: BTreeSet<String>,
+    /// Present classes with their characters reversed, like `evitca-` for `-active`.
+    reversed_classes: BTreeSet<String>,
+    names: HashSet<String>,
+    components: HashMap<String, bool>,
+}
+
+/// Classes and names added, changed or removed since the last [`prepare`](super::prepare) pass.
+#[derive(SystemParam)]
+pub(super) struct PresentNamesChanges<'w, 's>
+{
+    classes: Query<'w, 's, (), Changed<Class>>,
+    names: Query<'w, 's, (), Changed<Name>>,
+    removed_classes: RemovedComponents<'w, 's, Class>,
+    removed_names: RemovedComponents<'w, 's, Name>,
+}
+
+impl PresentNamesChanges<'_, '_>
+{
+    /// Checks if any class or name was added, changed or removed.
+    pub fn any(
+        &mut self
+    ) -> bool {
+        // Removed components are read anyway, so they aren't reported again on the next pass
+        let removed = self.removed_classes.read().count() + self.removed_names.read().count();
+        removed > 0 || !self.classes.is_empty() || !self.names.is_empty()
+    }
+}
+
+impl PresentNames
+{
+    /// Starts a new pass, indexing classes and names again only if any of them changed.
+    ///
+    /// Components are always checked again, since they aren't tracked.
+    pub fn begin_pass(
+        &mut self,
+        changed: bool
+    ) {
+        self.indexed &= !changed;
+        self.components.clear();
+    }
+
+    /// Checks if every class, name and component referenced by the selector is present on any entity, so it may match.
+    ///
+    /// Unregistered components are assumed present, so they are still reported when the selector is evaluated.
+    pub fn may_match(
+        &mut self,
+        selector: &Selector,
+        world: &World,
+        params: &CssQueryParam,
+        registry: &mut ComponentFilterRegistry
+    ) -> bool {
+        if !self.indexed
+        {
+            self.classes = params.classes.iter()
+                .flat_map(|(_entity, class)| class.split_ascii_whitespace())
+                .map(String::from)
+                .collect();
+            self.reversed_classes = self.classes.iter()
+                .map(|class| class.chars().rev().collect())
+                .collect();
+            self.names = params.names.iter()
+                .map(|(_entity, name)| name.as_str().to_string())
+                .collect();
+            self.indexed = true;
+        }
+
+        selector.elements()
+            .iter()
+            .all(|element| match element
+            {
+                SelectorElement::Class(class) => self.classes.contains(class),
+                SelectorElement::ClassPrefix(prefix) => has_prefix(&self.classes, prefix),
+                SelectorElement::ClassSuffix(suffix) => has_prefix(&self.reversed_classes, &suffix.chars().rev().collect::<String>()),
+                SelectorElement::Name(name) => self.names.contains(name),
+                SelectorElement::Component(name)
+                | SelectorElement::ComponentVariant(name, _)
+                | SelectorElement::ComponentAttribute(name, _, _) => *self.components
+                    .entry(name.clone())
+                    .or_insert_with(|| match registry.0.get_mut(name.as_str())
+                    {
+                        Some(filter) => !filter.filter(world).is_empty(),
+                        None => true,
+                    }),
+                _ => true,
+            })
+    }
+}
+
+/// Checks if any of the sorted values starts with the given prefix, which is the first value not lower than it, if any.
+fn has_prefix(
+    values: &BTreeSet<String>,
+    prefix: &str
+) -> bool {
+    values.range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+        .next()
+        .is_some_and(|value| value.starts_with(prefix))
+}
@@ -0,0 +1,121 @@
+use crate::{
+    prelude::BevyCssError,
+    property::{Property, PropertyContext, PropertyValues},
+};
+
+use bevy::{
+    ecs::system::{StaticSystemParam, SystemState},
+    prelude::{
+        Commands,
+        Entity,
+        Query,
+        Res,
+        Resource,
+        World,
+    },
+    utils::HashMap,
+};
+use std::any::TypeId;
+
+/// Reverts a single [`Property`] on a given entity, by calling [`Property::reset`].
+pub(crate) trait PropertyReset
+{
+    fn reset(
+        &mut self,
+        world: &mut World,
+        entity: Entity
+    );
+
+    /// Applies the given values of the property on a given entity, outside of [`Property::apply_system`],
+    /// like when restoring a [`StyleSnapshot`](super::StyleSnapshot).
+    fn apply(
+        &mut self,
+        world: &mut World,
+        entity: Entity,
+        values: &PropertyValues
+    ) -> Result<(), BevyCssError>;
+
+    /// Type of the reverted [`Property`], to tell which one was registered last with a given name.
+    fn property(
+        &self
+    ) -> TypeId;
+}
+
+type PropertyResetParams<T> = (
+    Query<'static, 'static, <T as Property>::Components, <T as Property>::Filters>,
+    StaticSystemParam<'static, 'static, <T as Property>::Param>,
+    Commands<'static, 'static>,
+);
+
+/// Keeps the [`SystemState`] needed to query the [`Property::Components`] of a property outside of its system.
+pub(crate) struct PropertyResetState<T: Property>(
+    SystemState<PropertyResetParams<T>>
+);
+
+impl<T: Property> PropertyResetState<T>
+{
+    pub fn new(
+        world: &mut World
+    ) -> Self {
+        Self(SystemState::new(world))
+    }
+}
+
+impl<T: Property> PropertyReset
+for PropertyResetState<T>
+{
+    fn reset(
+        &mut self,
+        world: &mut World,
+        entity: Entity
+    ) {
+        let (mut query, param, mut commands) = self.0.get_mut(world);
+        if let Ok(components) = query.get_mut(entity)
+        {
+            T::reset(components, &PropertyContext::new(&*param), &mut commands);
+        }
+        drop(param);
+
+        self.0.apply(world);
+    }
+
+    fn apply(
+        &mut self,
+        world: &mut World,
+        entity: Entity,
+        values: &PropertyValues
+    ) -> Result<(), BevyCssError> {
+        let cache = T::parse(values)?;
+
+        let (mut query, param, mut commands) = self.0.get_mut(world);
+        if let Ok(components) = query.get_mut(entity)
+        {
+            T::apply(&cache, components, &PropertyContext::new(&*param), &mut commands);
+        }
+        drop(param);
+
+        self.0.apply(world);
+        Ok(())
+    }
+
+    fn property(
+        &self
+    ) -> TypeId {
+        TypeId::of::<T>()
+    }
+}
+
+/// Maps each registered property name to its [`PropertyReset`].
+#[derive(Default, Resource)]
+pub(crate) struct PropertyResetRegistry(
+    pub HashMap<&'static str, Box<dyn PropertyReset + Send + Sync>>,
+);
+
+/// Run condition of [`Property::apply_system`], so only the property registered last with a given name is applied,
+/// like a custom `background-color` replacing the built-in one.
+pub(crate) fn is_registered_property<T: Property>(
+    registry: Res<PropertyResetRegistry>
+) -> bool {
+    registry.0.get(T::name())
+        .is_some_and(|reset| reset.property() == TypeId::of::<T>())
+}
@@ -0,0 +1,16 @@
+use crate::prelude::CssIgnore;
+
+use bevy::prelude::{
+    Entity,
+    Query,
+    With,
+};
+
+pub type QueryEntityIgnored<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = Entity;
+pub type ReadOnlyWorldQuery = With<CssIgnore>;
@@ -1,7 +1,10 @@
+use crate::prelude::Disabled;
+
 use bevy::{
     prelude::{
         Entity,
         Query,
+        Without,
     },
     ui::Interaction,
 };
@@ -13,4 +16,5 @@ pub type QueryEntityInteraction<'w, 's> = Query<
 >;
 
 pub type WorldQuery = (Entity, &'static Interaction);
-pub type ReadOnlyWorldQuery = ();
+/// Disabled entities are skipped, so they aren't matched by interaction pseudo-classes, like `:hover`.
+pub type ReadOnlyWorldQuery = Without<Disabled>;
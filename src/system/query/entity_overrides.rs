@@ -0,0 +1,15 @@
+use crate::prelude::StyleOverride;
+
+use bevy::prelude::{
+    Entity,
+    Query,
+};
+
+pub type QueryEntityOverrides<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static StyleOverride);
+pub type ReadOnlyWorldQuery = ();
@@ -0,0 +1,15 @@
+use crate::prelude::PseudoClasses;
+
+use bevy::prelude::{
+    Entity,
+    Query,
+};
+
+pub type QueryEntityPseudoClasses<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static PseudoClasses);
+pub type ReadOnlyWorldQuery = ();
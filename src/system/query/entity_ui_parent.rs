@@ -0,0 +1,17 @@
+use bevy::prelude::{
+    Entity,
+    Node,
+    Parent,
+    Query,
+    With,
+};
+
+pub type QueryEntityUiParent<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, Option<&'static Parent>);
+/// Only UI nodes are queried, so an entity is a UI root when its parent isn't on this query, like `:root`.
+pub type ReadOnlyWorldQuery = With<Node>;
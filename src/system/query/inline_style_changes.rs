@@ -0,0 +1,16 @@
+use crate::prelude::InlineStyle;
+
+use bevy::prelude::{
+    Changed,
+    Entity,
+    Query,
+};
+
+pub type QueryInlineStyleChanges<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static InlineStyle);
+pub type ReadOnlyWorldQuery = Changed<InlineStyle>;
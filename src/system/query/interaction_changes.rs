@@ -0,0 +1,25 @@
+use crate::prelude::{Checked, Disabled};
+
+use bevy::{
+    prelude::{
+        Added,
+        Changed,
+        Or,
+        Entity,
+        Node,
+        Query,
+        With,
+    },
+    ui::Interaction,
+};
+
+pub type QueryInteractionChanges<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = Entity;
+/// Adding [`Disabled`] or [`Checked`] is also an interaction change, since it changes which pseudo-classes match.
+/// Removing them is tracked by [`RemovedComponents`](bevy::prelude::RemovedComponents) instead.
+pub type ReadOnlyWorldQuery = (Or<(Changed<Interaction>, Added<Disabled>, Added<Checked>)>, With<Node>);
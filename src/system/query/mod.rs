@@ -7,12 +7,52 @@ pub use entity_parent::QueryEntityParent;
 pub mod entity_classes;
 pub use entity_classes::QueryEntityClasses;
 
+pub mod entity_ignored;
+pub use entity_ignored::QueryEntityIgnored;
+
 pub mod entity_names;
 pub use entity_names::QueryEntityNames;
 
+#[cfg(feature = "pseudo_class")]
+pub mod entity_checked;
+#[cfg(feature = "pseudo_class")]
+pub use entity_checked::QueryEntityChecked;
+
+#[cfg(feature = "pseudo_class")]
+pub mod entity_disabled;
+#[cfg(feature = "pseudo_class")]
+pub use entity_disabled::QueryEntityDisabled;
+
+#[cfg(feature = "pseudo_class")]
+pub mod entity_ui_parent;
+#[cfg(feature = "pseudo_class")]
+pub use entity_ui_parent::QueryEntityUiParent;
+
+#[cfg(feature = "pseudo_class")]
+pub mod entity_pseudo_classes;
+#[cfg(feature = "pseudo_class")]
+pub use entity_pseudo_classes::QueryEntityPseudoClasses;
+
+pub mod entity_overrides;
+pub use entity_overrides::QueryEntityOverrides;
+
+pub mod inline_style_changes;
+pub use inline_style_changes::QueryInlineStyleChanges;
+
 pub mod entity_interaction;
 pub use entity_interaction::QueryEntityInteraction;
 
+#[cfg(feature = "pseudo_class")]
+pub mod interaction_changes;
+#[cfg(feature = "pseudo_class")]
+pub use interaction_changes::QueryInteractionChanges;
+
+pub mod override_changes;
+pub use override_changes::QueryOverrideChanges;
+
+pub mod style_sheets;
+pub use style_sheets::QueryStyleSheets;
+
 pub mod ui_changes;
 pub use ui_changes::QueryUiChanges;
 
@@ -0,0 +1,16 @@
+use crate::prelude::StyleOverride;
+
+use bevy::prelude::{
+    Changed,
+    Entity,
+    Query,
+};
+
+pub type QueryOverrideChanges<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = Entity;
+pub type ReadOnlyWorldQuery = Changed<StyleOverride>;
@@ -0,0 +1,16 @@
+use crate::prelude::StyleSheet;
+
+use bevy::prelude::{
+    Entity,
+    Query,
+    Ref,
+};
+
+pub type QueryStyleSheets<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, Ref<'static, StyleSheet>);
+pub type ReadOnlyWorldQuery = ();
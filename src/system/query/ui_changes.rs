@@ -27,11 +27,11 @@ mod monitor_changes
     pub type ReadOnlyWorldQuery = (Or<(Added<StyleSheet>, Changed<StyleSheet>)>, With<Node>);
 }
 
+/// [`Interaction`](bevy::prelude::Interaction) changes aren't monitored here, since they only require
+/// pseudo-class rules to be evaluated again, see [`QueryInteractionChanges`](super::QueryInteractionChanges).
 #[cfg(feature = "monitor_changes")]
 mod monitor_changes
 {
-    pub use pseudo_class::ReadOnlyWorldQuery;
-
     use super::*;
     use crate::prelude::Class;
     use bevy::prelude::{
@@ -39,37 +39,13 @@ mod monitor_changes
         Parent,
     };
 
-    #[cfg(not(feature = "pseudo_class"))]
-    mod pseudo_class
-    {
-        use super::*;
-
-        pub type ReadOnlyWorldQuery = (
-            Or<(
-                Added<StyleSheet>,  Changed<StyleSheet>,
-                Added<Parent>,      Changed<Parent>,
-                Added<Children>,    Changed<Children>,
-                Added<Class>,       Changed<Class>,
-            )>,
-            With<Node>
-        );
-    }
-
-    #[cfg(feature = "pseudo_class")]
-    mod pseudo_class
-    {
-        use super::*;
-        use bevy::prelude::Interaction;
-
-        pub type ReadOnlyWorldQuery = (
-            Or<(
-                Added<StyleSheet>,  Changed<StyleSheet>,
-                Added<Parent>,      Changed<Parent>,
-                Added<Children>,    Changed<Children>,
-                Added<Class>,       Changed<Class>,
-                Added<Interaction>, Changed<Interaction>,
-            )>,
-            With<Node>
-        );
-    }
+    pub type ReadOnlyWorldQuery = (
+        Or<(
+            Added<StyleSheet>,  Changed<StyleSheet>,
+            Added<Parent>,      Changed<Parent>,
+            Added<Children>,    Changed<Children>,
+            Added<Class>,       Changed<Class>,
+        )>,
+        With<Node>
+    );
 }
@@ -0,0 +1,212 @@
+use super::{CssConditions, CssFlags};
+use crate::{
+    property::{PropertyName, INHERITED_PROPERTIES},
+    selector::{Selector, SelectorElement},
+    stylesheet::{StyleRule, StyleSheetAsset},
+};
+
+use bevy::{
+    prelude::{
+        AssetId,
+        Deref, DerefMut,
+        Resource,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Rules which changed between two versions of the same style sheet, see [`RuleDiff::between`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RuleDiff
+{
+    /// Selectors of rules which were added or changed, so entities matched by them must be styled again.
+    pub applied: Vec<Selector>,
+
+    /// Selectors of rules which were changed or removed, with the properties they no longer declare.
+    /// Those properties must be reverted on entities matched by them, before they are styled again.
+    pub reverted: Vec<(Selector, Vec<PropertyName>)>,
+
+    /// If any added, changed or removed rule declares an inherited property, like `color`,
+    /// so descendants of matched entities must be styled again too.
+    pub inherited: bool,
+}
+
+impl RuleDiff
+{
+    /// Compares the rules of two versions of the same style sheet.
+    ///
+    /// Rules are paired by their selector text and the order it appears on the sheet, since rules are given new
+    /// [`Selector`]s every time a sheet is parsed. Returns `None` when the changes can't be isolated to some rules,
+    /// which is when imports or cascade layers changed, or when rules were reordered.
+    pub(crate) fn between(
+        previous: &StyleSheetAsset,
+        current: &StyleSheetAsset
+    ) -> Option<Self> {
+        if previous.layers() != current.layers() || previous.imports() != current.imports()
+        {
+            return None;
+        }
+
+        let mut previous_rules: HashMap<_, _> = keyed_rules(previous)
+            .enumerate()
+            .map(|(position, (key, rule))| (key, (position, rule)))
+            .collect();
+
+        let mut diff = Self::default();
+        let mut last_position = None;
+
+        for (key, rule) in keyed_rules(current)
+        {
+            let (position, previous_rule) = match previous_rules.remove(&key)
+            {
+                Some(found) => found,
+                None => {
+                    diff.push_applied(rule);
+                    continue;
+                }
+            };
+
+            // Rules on the same position of the cascade may now override each other differently
+            if last_position.is_some_and(|last| last > position)
+            {
+                return None;
+            }
+            last_position = Some(position);
+
+            if rule.properties == previous_rule.properties
+                && rule.important == previous_rule.important
+                && rule.layer == previous_rule.layer
+                && rule.conditions == previous_rule.conditions
+                && rule.flags == previous_rule.flags
+                && rule.capabilities == previous_rule.capabilities
+            {
+                continue;
+            }
+
+            diff.push_applied(rule);
+            diff.push_reverted(previous_rule, |name| !rule.properties.contains_key(name));
+        }
+
+        for (_position, previous_rule) in previous_rules.into_values()
+        {
+            diff.push_reverted(previous_rule, |_name| true);
+        }
+
+        Some(diff)
+    }
+
+    /// Rules of the given sheet on `@when` blocks which conditions changed, see [`CssConditions`].
+    ///
+    /// Rules which conditions all hold now are applied, while all properties of the other ones are reverted.
+    pub(crate) fn conditional(
+        sheet: &StyleSheetAsset,
+        changed: &HashSet<String>,
+        conditions: &CssConditions
+    ) -> Self {
+        let mut diff = Self::default();
+
+        for rule in sheet.iter().filter(|rule| rule.conditions.iter().any(|name| changed.contains(name)))
+        {
+            match conditions.allows(rule)
+            {
+                true => diff.push_applied(rule),
+                false => diff.push_reverted(rule, |_name| true),
+            }
+        }
+
+        diff
+    }
+
+    /// Rules of the given sheet on `@when` blocks which flags were enabled or disabled, see [`CssFlags`].
+    ///
+    /// Rules which flags are all enabled now are applied, while all properties of the other ones are reverted.
+    pub(crate) fn flagged(
+        sheet: &StyleSheetAsset,
+        changed: &HashSet<String>,
+        flags: &CssFlags
+    ) -> Self {
+        let mut diff = Self::default();
+
+        for rule in sheet.iter().filter(|rule| rule.flags.iter().any(|name| changed.contains(name)))
+        {
+            match flags.allows(rule)
+            {
+                true => diff.push_applied(rule),
+                false => diff.push_reverted(rule, |_name| true),
+            }
+        }
+
+        diff
+    }
+
+    /// Checks if there is no rule to be applied or reverted.
+    pub(crate) fn is_empty(
+        &self
+    ) -> bool {
+        self.applied.is_empty() && self.reverted.is_empty()
+    }
+
+    /// Merges the given diff into this one, like when a sheet is modified twice before being applied.
+    pub(crate) fn merge(
+        &mut self,
+        other: RuleDiff
+    ) {
+        self.applied.extend(other.applied);
+        self.reverted.extend(other.reverted);
+        self.inherited |= other.inherited;
+    }
+
+    fn push_applied(
+        &mut self,
+        rule: &StyleRule
+    ) {
+        self.inherited |= declares_inherited(rule);
+        self.applied.push(rule.selector.clone());
+    }
+
+    fn push_reverted(
+        &mut self,
+        rule: &StyleRule,
+        filter: impl Fn(&str) -> bool
+    ) {
+        let properties: Vec<PropertyName> = rule.properties.keys()
+            .filter(|name| filter(name))
+            .cloned()
+            .collect();
+
+        if !properties.is_empty()
+        {
+            self.inherited |= declares_inherited(rule);
+            self.reverted.push((rule.selector.clone(), properties));
+        }
+    }
+}
+
+/// Pairs each rule of the given sheet with its selector elements and how many rules with the same selector came before it.
+fn keyed_rules(
+    sheet: &StyleSheetAsset
+) -> impl Iterator<Item = ((&[SelectorElement], usize), &StyleRule)> {
+    let mut occurrences = HashMap::<&[SelectorElement], usize>::new();
+
+    sheet.iter()
+        .map(move |rule|
+        {
+            let occurrence = occurrences.entry(rule.selector.elements()).or_default();
+            *occurrence += 1;
+
+            ((rule.selector.elements(), *occurrence), rule)
+        })
+}
+
+fn declares_inherited(
+    rule: &StyleRule
+) -> bool {
+    INHERITED_PROPERTIES.iter()
+        .any(|property| rule.properties.contains_key(*property))
+}
+
+/// Rules changed by hot reload on each style sheet, waiting to be applied by the next [`prepare`](super::prepare).
+#[derive(Debug, Default)]
+#[derive(Deref, DerefMut, Resource)]
+pub(crate) struct PendingRuleDiffs(
+    HashMap<AssetId<StyleSheetAsset>, RuleDiff>
+);
@@ -0,0 +1,80 @@
+use crate::stylesheet::StyleSheetAsset;
+
+use bevy::{
+    prelude::{
+        AssetId,
+        Entity,
+        Resource,
+    },
+    utils::HashMap,
+};
+use smallvec::SmallVec;
+
+/// Counts how many rules were matched on the last frame which styled any entity, and how many selectors had to be evaluated for that.
+///
+/// Rules with an identical selector on the same sheet, like many `.btn` rules, share the entities matched by the first one,
+/// so `evaluated` is lower than `rules` on sheets which reuse selectors a lot. Rules referencing a class, name or component
+/// which no entity has are `skipped` instead, since they can't match anything.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Resource)]
+pub struct SelectorMatchStats
+{
+    /// Number of rules which had their matched entities selected.
+    pub rules: usize,
+
+    /// Number of selectors evaluated against the UI tree, which is never more than `rules`.
+    pub evaluated: usize,
+
+    /// Number of rules which weren't evaluated, since their selector references a class, name or component no entity has.
+    pub skipped: usize,
+}
+
+/// Style root, updated entity, sheet and selector id of a cached match.
+type MatchKey = (Entity, Entity, AssetId<StyleSheetAsset>, usize);
+
+/// Entities matched by each selector on a single [`prepare`](super::prepare) pass.
+///
+/// Matches depend on the style root and on the updated entity, which limits the evaluated entities, so both are part of the key.
+#[derive(Default)]
+pub(super) struct SelectorMatchCache
+{
+    matches: HashMap<MatchKey, SmallVec<[Entity; 8]>>,
+    stats: SelectorMatchStats,
+}
+
+impl SelectorMatchCache
+{
+    /// Returns entities matched by the selector with the given id, calling `select` only the first time it's requested.
+    pub fn get_or_select(
+        &mut self,
+        root: Entity,
+        updated: Entity,
+        sheet: AssetId<StyleSheetAsset>,
+        selector_id: usize,
+        select: impl FnOnce() -> SmallVec<[Entity; 8]>
+    ) -> SmallVec<[Entity; 8]> {
+        self.stats.rules += 1;
+
+        self.matches.entry((root, updated, sheet, selector_id))
+            .or_insert_with(||
+            {
+                self.stats.evaluated += 1;
+                select()
+            })
+            .clone()
+    }
+
+    /// Counts a rule which was skipped without selecting its matched entities.
+    pub fn skip(
+        &mut self
+    ) {
+        self.stats.skipped += 1;
+    }
+
+    pub fn stats(
+        &self
+    ) -> SelectorMatchStats {
+        self.stats
+    }
+}
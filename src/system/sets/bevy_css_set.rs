@@ -1,17 +1,58 @@
 use bevy::ecs::schedule::SystemSet;
 
-/// System sets  used by `tomt_bevycss` systems
+/// System sets used by `tomt_bevycss` systems.
+///
+/// Sets are configured by [`BevyCssPlugin`](crate::prelude::BevyCssPlugin), which guarantees the following order on every frame:
+/// 1. [`Prepare`](BevyCssSet::Prepare) on [`PreUpdate`](bevy::prelude::PreUpdate).
+/// 2. [`PreApply`](BevyCssSet::PreApply), [`Apply`](BevyCssSet::Apply) and [`PostApply`](BevyCssSet::PostApply),
+///    in this order, on [`Update`](bevy::prelude::Update).
+/// 3. [`Cleanup`](BevyCssSet::Cleanup) on [`PostUpdate`](bevy::prelude::PostUpdate).
+///
+/// Since `bevy_ui` layout is computed on [`PostUpdate`](bevy::prelude::PostUpdate), systems on [`PostApply`](BevyCssSet::PostApply)
+/// always run after styles are applied but before layout. Commands issued by properties are applied once [`Update`](bevy::prelude::Update)
+/// ends, like any other command, so components inserted by properties can be read from [`PostUpdate`](bevy::prelude::PostUpdate) on.
+///
+/// Only [`PreApply`](BevyCssSet::PreApply) and [`PostApply`](BevyCssSet::PostApply) are meant to be used by user systems.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn log_styled_entities(
+///     mut events: EventReader<StyleApplied>
+/// ) {
+///     for event in events.read()
+///     {
+///         info!("Styles applied on {:?}", event.entity);
+///     }
+/// }
+///
+/// # fn some_main() {
+/// App::new()
+///     .add_plugins((DefaultPlugins, BevyCssPlugin::default()))
+///     .add_systems(Update, log_styled_entities.in_set(BevyCssSet::PostApply));
+/// # }
+/// ```
 #[derive(Clone, Copy, Debug)]
 #[derive(PartialEq, Eq, Hash)]
 #[derive(SystemSet)]
 pub enum BevyCssSet {
-    /// Prepares internal state before running apply systems.
-    /// This system runs on [`bevy::prelude::CoreSet::PreUpdate`].
+    /// Selects entities which must be styled and prepares internal state used by [`Property`](crate::prelude::Property) systems.
+    /// This set runs on [`PreUpdate`](bevy::prelude::PreUpdate).
     Prepare,
-    /// All [`crate::prelude::Property`] implementation `systems` are run on this system set.
-    /// Those stages runs on [`bevy::prelude::CoreSet::PreUpdate`] after [`BevyCssSet::Prepare`].
+    /// Runs before any [`Property`](crate::prelude::Property) is applied, but after entities were selected.
+    /// This set is empty, and is meant to be used by user systems.
+    PreApply,
+    /// All [`Property`](crate::prelude::Property) implementation `systems` are run on this set.
+    /// This set runs on [`Update`](bevy::prelude::Update) after [`PreApply`](BevyCssSet::PreApply).
     Apply,
-    /// Clears the internal state used by [`crate::prelude::Property`] implementation `systems` set.
-    /// This system runs on [`bevy::prelude::CoreSet::PostUpdate`].
+    /// Runs after all properties were applied, and [`StyleApplied`](crate::prelude::StyleApplied) and
+    /// [`StyleChanged`](crate::prelude::StyleChanged) events were sent.
+    /// This set is empty, and is meant to be used by user systems.
+    PostApply,
+    /// Clears the internal state used by [`Property`](crate::prelude::Property) implementation `systems`,
+    /// and reverts styles of removed [`StyleSheet`](crate::prelude::StyleSheet)s.
+    /// This set runs on [`PostUpdate`](bevy::prelude::PostUpdate).
     Cleanup,
 }
@@ -0,0 +1,32 @@
+use crate::property::StyleSheetState;
+
+use bevy::prelude::{
+    Entity,
+    Event,
+    EventWriter,
+    Res,
+};
+
+/// Event sent for each entity which had styles applied on the current frame.
+///
+/// It's sent on [`BevyCssSet::Apply`](super::sets::BevyCssSet::Apply), so it can be read on
+/// [`BevyCssSet::PostApply`](super::sets::BevyCssSet::PostApply).
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Event)]
+pub struct StyleApplied
+{
+    /// Entity which had styles applied.
+    pub entity: Entity,
+}
+
+/// Sends a [`StyleApplied`] event for each entity with any computed property, parents before their children.
+pub(crate) fn send_style_applied(
+    state: Res<StyleSheetState>,
+    mut events: EventWriter<StyleApplied>
+) {
+    events.send_batch(state.iter_parents_first()
+        .filter(|(_entity, style)| !style.is_empty())
+        .map(|(entity, _style)| StyleApplied { entity: *entity })
+    );
+}
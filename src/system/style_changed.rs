@@ -0,0 +1,51 @@
+use crate::property::{PropertyName, StyleSheetState};
+
+use bevy::prelude::{
+    Entity,
+    Event,
+    EventWriter,
+    ResMut,
+};
+
+/// Event sent for each entity which properties were applied with another value, or reverted, on the current frame,
+/// like after a [`Class`](crate::prelude::Class) change matches it with other rules.
+///
+/// Unlike [`StyleApplied`](super::StyleApplied), it isn't sent when an entity is styled again with the same values,
+/// so it can be used to react to actual restyles, like laying out custom widgets again.
+/// It's sent right after [`BevyCssSet::Apply`](super::sets::BevyCssSet::Apply), so it can be read on
+/// [`BevyCssSet::PostApply`](super::sets::BevyCssSet::PostApply).
+///
+/// Properties reverted because no rule applies them anymore, like after a pseudo-class state is unset, are reported too,
+/// and so are properties reverted by [`clear_styles`](super::clear_styles), like when a [`StyleSheet`](crate::prelude::StyleSheet) is removed
+/// or [`CssIgnore`](crate::prelude::CssIgnore) is added. Those reverted on [`BevyCssSet::Cleanup`](super::sets::BevyCssSet::Cleanup)
+/// are reported on the next frame.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Event)]
+pub struct StyleChanged
+{
+    /// Entity which styles changed.
+    pub entity: Entity,
+    /// Name of each property which changed, like `width`.
+    pub changed_properties: Vec<PropertyName>,
+}
+
+/// Sends a [`StyleChanged`] event for each entity which properties changed since the last time events were sent.
+///
+/// It runs on its own between [`BevyCssSet::Apply`](super::sets::BevyCssSet::Apply) and [`BevyCssSet::PostApply`](super::sets::BevyCssSet::PostApply),
+/// since it takes the changes out of [`StyleSheetState`], which properties read while being applied.
+pub(crate) fn send_style_changed(
+    mut state: ResMut<StyleSheetState>,
+    mut events: EventWriter<StyleChanged>
+) {
+    // Only taken when needed, so the state isn't flagged as changed every frame
+    if !state.has_pending_changes()
+    {
+        return;
+    }
+
+    events.send_batch(state.take_changed()
+        .into_iter()
+        .map(|(entity, changed_properties)| StyleChanged { entity, changed_properties })
+    );
+}
@@ -0,0 +1,20 @@
+use crate::prelude::BevyCssError;
+
+use bevy::prelude::{
+    Entity,
+    Event,
+};
+
+/// Event sent when styles meant for an entity can't be applied, like an invalid declaration on an [`InlineStyle`](crate::prelude::InlineStyle).
+///
+/// The same errors are also logged, so this is only needed to report them to the user, like on an editor.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Event)]
+pub struct StyleDiagnostic
+{
+    /// Entity which the styles were meant for.
+    pub entity: Entity,
+    /// What went wrong.
+    pub error: BevyCssError,
+}
@@ -0,0 +1,119 @@
+use crate::prelude::{StyleSheet, StyleSheetAsset};
+
+use bevy::{
+    ecs::system::BoxedSystem,
+    prelude::{
+        Assets,
+        Entity,
+        Handle,
+        IntoSystem,
+        Node,
+        Query,
+        Resource,
+        With,
+        World,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Root entities found by a [`StyleRootProvider`], each with the sheets applied to it and its descendants, on the order they are applied.
+pub type StyleRoots = Vec<(Entity, Vec<Handle<StyleSheetAsset>>)>;
+
+/// Sheets of each root entity, by entity.
+pub(crate) type StyleRootMap = HashMap<Entity, Vec<Handle<StyleSheetAsset>>>;
+
+/// Roots found by the last run of a [`StyleRootProvider`].
+#[derive(Default)]
+pub(crate) struct StyleRootUpdate
+{
+    pub roots: StyleRootMap,
+    /// Roots which were found for the first time, which sheets changed or which sheets finished loading,
+    /// so they must be styled again along with their descendants.
+    pub changed: HashSet<Entity>,
+}
+
+/// Finds the root entities which style sheets apply to, which are all UI nodes with a [`StyleSheet`] by default, see [`style_sheet_roots`].
+///
+/// Another provider can be registered with [`register_style_root_provider`](crate::RegisterStyleRootProvider::register_style_root_provider),
+/// which is a system returning [`StyleRoots`], like to find roots by a marker component and keep their sheets on a resource.
+/// It runs before entities are styled on every frame, and roots which are found for the first time, or which sheets change,
+/// are styled again along with their descendants. Sheets of a root are applied after the ones of roots on its ancestors,
+/// like nested [`StyleSheet`] components.
+///
+/// Roots must be UI nodes. Roots which are no longer returned keep the properties they were styled with,
+/// which can be reverted by [`clear_styles`](crate::system::clear_styles).
+#[derive(Resource)]
+pub struct StyleRootProvider
+{
+    system: BoxedSystem<(), StyleRoots>,
+    initialized: bool,
+    /// Sheets of each root found on the last run, along with whether all of them were loaded.
+    previous: HashMap<Entity, (Vec<Handle<StyleSheetAsset>>, bool)>,
+}
+
+impl Default
+for StyleRootProvider
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self::new(style_sheet_roots)
+    }
+}
+
+impl StyleRootProvider
+{
+    /// Creates a provider which finds roots with the given system.
+    pub fn new<M>(
+        system: impl IntoSystem<(), StyleRoots, M>
+    ) -> Self {
+        Self{
+            system: Box::new(IntoSystem::into_system(system)),
+            initialized: false,
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Runs the provider, comparing the roots it found with the ones of the previous run.
+    pub(crate) fn update(
+        &mut self,
+        world: &mut World
+    ) -> StyleRootUpdate {
+        if !self.initialized
+        {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+
+        let roots: StyleRootMap = self.system.run((), world)
+            .into_iter()
+            .collect();
+        self.system.apply_deferred(world);
+
+        let Some(assets) = world.get_resource::<Assets<StyleSheetAsset>>()
+        else {
+            return StyleRootUpdate::default();
+        };
+
+        let current: HashMap<Entity, (Vec<Handle<StyleSheetAsset>>, bool)> = roots.iter()
+            .map(|(root, sheets)| (*root, (sheets.clone(), sheets.iter().all(|sheet| assets.contains(sheet)))))
+            .collect();
+
+        let changed = current.iter()
+            .filter(|(root, sheets)| self.previous.get(*root) != Some(*sheets))
+            .map(|(root, _sheets)| *root)
+            .collect();
+
+        self.previous = current;
+        StyleRootUpdate { roots, changed }
+    }
+}
+
+/// Default root provider, which finds all UI nodes with a [`StyleSheet`], see [`StyleRootProvider`].
+pub fn style_sheet_roots(
+    q_sheets: Query<(Entity, &StyleSheet), With<Node>>
+) -> StyleRoots {
+    q_sheets.iter()
+        .map(|(entity, sheet)| (entity, vec![sheet.handle().clone()]))
+        .collect()
+}
@@ -0,0 +1,110 @@
+use crate::stylesheet::StyleSheetAsset;
+
+use bevy::{
+    log::warn,
+    prelude::{
+        AssetEvent, AssetId, Assets,
+        Deref, DerefMut,
+        EventReader,
+        Res, ResMut, Resource,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Reverse dependencies between style sheets, mapping each sheet imported with `@import` to the sheets importing it.
+///
+/// This is kept up to date by [`track_style_sheet_dependencies`] and used by hot reloading, so modifying an
+/// imported sheet also refreshes every sheet which imports it, directly or through other imports.
+#[derive(Debug, Default)]
+#[derive(Deref, DerefMut, Resource)]
+pub(crate) struct StyleSheetDependencies(
+    HashMap<AssetId<StyleSheetAsset>, HashSet<AssetId<StyleSheetAsset>>>
+);
+
+impl StyleSheetDependencies
+{
+    /// Replaces the sheets imported by the given sheet.
+    pub(crate) fn set_imports(
+        &mut self,
+        importer: AssetId<StyleSheetAsset>,
+        imports: impl Iterator<Item = AssetId<StyleSheetAsset>>
+    ) {
+        self.remove_importer(importer);
+
+        for import in imports
+        {
+            self.entry(import)
+                .or_default()
+                .insert(importer);
+        }
+    }
+
+    /// Forgets all imports of the given sheet.
+    pub(crate) fn remove_importer(
+        &mut self,
+        importer: AssetId<StyleSheetAsset>
+    ) {
+        self.retain(|_import, importers|
+        {
+            importers.remove(&importer);
+            !importers.is_empty()
+        });
+    }
+
+    /// Gets the given sheet and all sheets which import it, directly or through other imports.
+    pub(crate) fn dependents(
+        &self,
+        id: AssetId<StyleSheetAsset>
+    ) -> HashSet<AssetId<StyleSheetAsset>> {
+        let mut result = HashSet::new();
+        let mut pending = vec![id];
+
+        while let Some(current) = pending.pop()
+        {
+            if !result.insert(current)
+            {
+                continue;
+            }
+
+            if let Some(importers) = self.get(&current)
+            {
+                pending.extend(importers.iter().copied());
+            }
+        }
+
+        result
+    }
+}
+
+/// Keeps [`StyleSheetDependencies`] up to date with the imports of loaded style sheets.
+///
+/// When an imported sheet is removed, the sheets importing it keep the rules last applied, and a warning is logged.
+pub(crate) fn track_style_sheet_dependencies(
+    mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    mut dependencies: ResMut<StyleSheetDependencies>,
+) {
+    for evt in assets_events.read()
+    {
+        match evt
+        {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                if let Some(sheet) = assets.get(*id)
+                {
+                    dependencies.set_imports(*id, sheet.imports().iter().map(|handle| handle.id()));
+                }
+            }
+
+            AssetEvent::Removed { id } => {
+                if dependencies.contains_key(id)
+                {
+                    warn!("Imported style sheet {id:?} was removed, sheets importing it keep the rules last applied");
+                }
+
+                dependencies.remove_importer(*id);
+            }
+
+            _ => (),
+        }
+    }
+}
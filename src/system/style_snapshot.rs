@@ -0,0 +1,146 @@
+use super::{
+    reset_properties,
+    PropertyResetRegistry,
+};
+use crate::property::{
+    PropertyName,
+    PropertyValues,
+    StyleSheetState,
+};
+
+use bevy::{
+    log::{debug, error},
+    prelude::{
+        Entity,
+        Mut,
+        World,
+    },
+    utils::HashMap,
+};
+
+/// Properties applied by `tomt_bevycss` on a single entity, with their resolved values, taken by [`snapshot_styles`].
+///
+/// Values are stored as they were applied, with `em` and `rem` already resolved, so a snapshot doesn't depend on the
+/// style sheets it came from and can be restored with [`restore_styles`] even after those change.
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq)]
+pub struct StyleSnapshot
+{
+    properties: HashMap<PropertyName, PropertyValues>,
+}
+
+impl StyleSnapshot
+{
+    /// Resolved values of each property on the snapshot, by property name.
+    pub fn properties(
+        &self
+    ) -> &HashMap<PropertyName, PropertyValues> {
+        &self.properties
+    }
+
+    /// Resolved value of the given property, if it was applied when the snapshot was taken.
+    pub fn get(
+        &self,
+        property: &str
+    ) -> Option<&PropertyValues> {
+        self.properties.get(property)
+    }
+
+    /// Checks if no property was applied when the snapshot was taken.
+    pub fn is_empty(
+        &self
+    ) -> bool {
+        self.properties.is_empty()
+    }
+}
+
+/// Captures every property currently applied by `tomt_bevycss` on the given entity, along with their resolved values.
+///
+/// Only the given entity is captured, its descendants aren't. Use [`restore_styles`] to apply the snapshot again.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::system::{restore_styles, snapshot_styles, StyleSnapshot};
+/// #[derive(Resource)]
+/// struct Undo(Entity, StyleSnapshot);
+///
+/// fn save(
+///     world: &mut World,
+///     entity: Entity
+/// ) {
+///     let snapshot = snapshot_styles(world, entity);
+///     world.insert_resource(Undo(entity, snapshot));
+/// }
+///
+/// fn undo(
+///     world: &mut World
+/// ) {
+///     if let Some(Undo(entity, snapshot)) = world.remove_resource::<Undo>()
+///     {
+///         restore_styles(world, entity, &snapshot);
+///     }
+/// }
+/// ```
+pub fn snapshot_styles(
+    world: &World,
+    entity: Entity
+) -> StyleSnapshot {
+    let properties = world.get_resource::<StyleSheetState>()
+        .and_then(|state| state.applied_values(entity))
+        .cloned()
+        .unwrap_or_default();
+
+    StyleSnapshot { properties }
+}
+
+/// Applies again every property captured by [`snapshot_styles`] on the given entity, and reverts the ones applied since then,
+/// by calling [`Property::reset`](crate::Property::reset).
+///
+/// Restored properties are considered applied, so [`clear_styles`](super::clear_styles) reverts them as usual.
+/// Like any other change made outside of style sheets, they are overridden the next time a sheet styles the entity.
+pub fn restore_styles(
+    world: &mut World,
+    entity: Entity,
+    snapshot: &StyleSnapshot
+) {
+    if world.get_entity(entity).is_none()
+    {
+        return;
+    }
+
+    let applied_since: Vec<PropertyName> = world.get_resource::<StyleSheetState>()
+        .and_then(|state| state.applied_values(entity))
+        .map(|values| values.keys()
+            .filter(|property| !snapshot.properties.contains_key(*property))
+            .cloned()
+            .collect()
+        )
+        .unwrap_or_default();
+
+    debug!("Restoring {} properties on entity {entity:?}, reverting {}", snapshot.properties.len(), applied_since.len());
+    reset_properties(world, entity, applied_since.iter().map(|name| &**name));
+
+    world.resource_scope(|world, mut registry: Mut<PropertyResetRegistry>|
+    {
+        for (property, values) in snapshot.properties.iter()
+        {
+            let result = match registry.0.get_mut(&**property)
+            {
+                Some(handler) => handler.apply(world, entity, values),
+                None => continue,
+            };
+
+            if let Err(err) = result
+            {
+                error!("Failed to restore property {property} on entity {entity:?}. Error: {err}");
+            }
+        }
+    });
+
+    if let Some(mut state) = world.get_resource_mut::<StyleSheetState>()
+    {
+        state.set_applied_values(entity, snapshot.properties.clone());
+    }
+}
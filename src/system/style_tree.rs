@@ -1,10 +1,9 @@
-use super::query;
+use super::{query, StyleRootMap};
 use crate::prelude::StyleSheetAsset;
 
 use bevy::{
     log::{error, debug, trace},
     prelude::{
-        Deref, DerefMut,
         Entity,
         Handle,
     },
@@ -14,70 +13,75 @@ use bevy::{
 #[derive(Clone)]
 pub(super) struct StyleTreeNode
 {
-    pub entity: Entity,
-    pub sheet_handle: Handle<StyleSheetAsset>,
-    pub parent: Option<Handle<StyleSheetAsset>>,
+    /// Closest root on the ancestors of this root.
+    pub parent: Option<Entity>,
 }
 
-#[derive(Default, Deref, DerefMut)]
-pub(super) struct StyleTree(
-    HashMap<
-        Handle<StyleSheetAsset>,
-        StyleTreeNode
-    >
-);
+/// Roots found while styling entities, linked to the closest root on their ancestors.
+pub(super) struct StyleTree<'a>
+{
+    roots: &'a StyleRootMap,
+    nodes: HashMap<Entity, StyleTreeNode>,
+}
 
-impl StyleTree
+impl<'a> StyleTree<'a>
 {
+    pub fn new(
+        roots: &'a StyleRootMap
+    ) -> Self {
+        Self{
+            roots,
+            nodes: HashMap::new(),
+        }
+    }
+
     fn resolve(
         &self,
-        child_node: &Handle<StyleSheetAsset>,
+        root: Entity,
     ) -> Vec<(Entity, Handle<StyleSheetAsset>)> {
-        match self.get(child_node)
+        let (Some(node), Some(sheets)) = (self.nodes.get(&root), self.roots.get(&root))
+        else {
+            return vec![];
+        };
+
+        let iter = sheets.iter().map(|sheet| (root, sheet.clone()));
+        match node.parent
         {
-            Some(style) => {
-                let iter = std::iter::once((style.entity, style.sheet_handle.clone()));
-                match &style.parent
-                {
-                    Some(parent) => self.resolve(parent)
-                        .into_iter()
-                        .chain(iter)
-                        .collect(),
+            Some(parent) => self.resolve(parent)
+                .into_iter()
+                .chain(iter)
+                .collect(),
 
-                    None => iter.collect(),
-                }
-            }
-            None => vec![],
+            None => iter.collect(),
         }
     }
 }
 
-impl<'me, 'w, 's> StyleTree
+impl<'me, 'w, 's> StyleTree<'_>
 {
     fn get_or_find_root(
         &'me mut self,
         entity: Entity,
         query: &'w query::QueryUiNodes<'w, 's>,
-    ) -> Option<StyleTreeNode> {
+    ) -> Option<Entity> {
         let entity_idx = entity.index();
 
-        let (entity, parent, _c, sheet) = match query.get(entity)
+        let (entity, parent) = match query.get(entity)
         {
-            Ok((e, p, c, s)) => (e, p, c, s),
+            Ok((e, p, _c, _s)) => (e, p),
             Err(err) => {
                 error!("Query on entity {entity_idx} failed, {err}");
                 return None;
             }
         };
 
-        match (sheet, parent)
+        match (self.roots.contains_key(&entity), parent)
         {
-            (Some(style), _p) => {
+            (true, _p) => {
                 trace!("Stylesheet found on entity {entity_idx}");
-                let result = if let Some(node) = self.get(style.handle())
+                if self.nodes.contains_key(&entity)
                 {
                     trace!("Entity {entity_idx} is already in the tree, returning early");
-                    node
                 }
                 else
                 {
@@ -89,25 +93,16 @@ impl<'me, 'w, 's> StyleTree
                             debug!("Entity {entity_idx} has no parent UI node, terminating search");
                             None
                         }
-                    }
-                    .map(|p| p.sheet_handle);
+                    };
 
-                    self.insert_unique_unchecked(
-                        style.handle().clone(),
-                        StyleTreeNode
-                        {
-                            entity,
-                            sheet_handle: style.handle().clone(),
-                            parent,
-                        },
-                    ).1
-                };
-                Some(result.clone())
+                    self.nodes.insert(entity, StyleTreeNode { parent });
+                }
+                Some(entity)
             }
 
-            (None, Some(parent)) => self.get_or_find_root(parent.get(), query),
+            (false, Some(parent)) => self.get_or_find_root(parent.get(), query),
 
-            (None, None) => {
+            (false, None) => {
                 debug!("Entity {entity_idx} has no UI parent, or attached stylesheet");
                 None
             }
@@ -119,10 +114,9 @@ impl<'me, 'w, 's> StyleTree
         entity: Entity,
         query: &'w query::QueryUiNodes<'w, 's>,
     ) -> Vec<(Entity, Handle<StyleSheetAsset>)> {
-        let root_node = self.get_or_find_root(entity, query);
-        match root_node
+        match self.get_or_find_root(entity, query)
         {
-            Some(node) => self.resolve(&node.sheet_handle),
+            Some(root) => self.resolve(root),
             None => vec![],
         }
     }
@@ -0,0 +1,2735 @@
+use super::*;
+use crate::prelude::{BareNumberPolicy, BevyCssPlugin, Class, PropertyValues, RootFontSize};
+use crate::test_utils::{TestApp, TestNode};
+
+use bevy::prelude::{
+    App,
+    BackgroundColor,
+    BuildWorldChildren,
+    NodeBundle,
+    Style,
+    Color,
+    Text, TextAlignment, TextBundle, TextSection, TextStyle,
+    UiRect,
+    Val,
+};
+
+/// Creates an app with a `.panel` node with a `.item` child, styled by the given sheet content.
+///
+/// Returns the app, the panel and the item entity.
+fn create_styled_app(
+    content: &str
+) -> (TestApp, Entity, Entity) {
+    create_styled_app_with(BevyCssPlugin::without_hot_reload(), content)
+}
+
+/// Same as [`create_styled_app`], but with the given plugin configuration.
+fn create_styled_app_with(
+    plugin: BevyCssPlugin,
+    content: &str
+) -> (TestApp, Entity, Entity) {
+    let mut app = TestApp::with_plugin(plugin, content);
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+    );
+
+    app.update();
+    (app, tree[0], tree[1])
+}
+
+/// Adds the layout of `bevy_ui` to the given app, along with a primary window, so nodes are laid out like on a real app.
+fn add_layout(
+    app: &mut TestApp
+) {
+    use bevy::{asset::AssetApp, render::render_resource::Shader, sprite::TextureAtlas};
+
+    // Assets used by `UiPlugin`, which are otherwise added by the render plugins
+    app.init_asset::<Shader>();
+    app.init_asset::<TextureAtlas>();
+    app.add_plugins((
+        bevy::transform::TransformPlugin,
+        bevy::hierarchy::HierarchyPlugin,
+        bevy::window::WindowPlugin::default(),
+        bevy::input::InputPlugin,
+        bevy::render::texture::ImagePlugin::default(),
+        bevy::text::TextPlugin,
+        bevy::ui::UiPlugin,
+    ));
+}
+
+fn style(
+    app: &App,
+    entity: Entity
+) -> &Style {
+    app.world.get::<Style>(entity).expect("Should have a style")
+}
+
+#[test]
+fn test_app_styles_spawned_tree(
+    // no args
+) {
+    let mut app = TestApp::with_css("#root { width: 10px; } #root text { height: 20px; } .last { width: 30px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .name("root")
+        .with_child(TestNode::text("first"))
+        .with_child(TestNode::node().class("last"))
+    );
+
+    assert!(app.step_until_styled() >= 2, "Should wait a frame without styles being applied");
+
+    app.expect_style(tree[0], |style| assert_eq!(style.width, Val::Px(10.0)));
+    app.expect_style(tree[1], |style| assert_eq!(style.height, Val::Px(20.0)));
+    app.expect_style(tree[2], |style| assert_eq!(style.width, Val::Px(30.0)));
+    app.expect_component::<Text>(tree[1], |text| assert_eq!(text.sections[0].value, "first"));
+}
+
+#[test]
+fn clear_styles_reverts_applied_properties(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".panel { width: 10px; height: 20px; } .item { width: 5px; }");
+    assert_eq!(style(&app, panel).width, Val::Px(10.0));
+    assert_eq!(style(&app, panel).height, Val::Px(20.0));
+
+    clear_styles(&mut app.world, panel);
+
+    assert_eq!(style(&app, panel).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, panel).height, Style::DEFAULT.height);
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Descendants should be kept untouched");
+}
+
+#[test]
+fn clear_styles_keeps_properties_not_applied_by_sheets(
+    // no args
+) {
+    let (mut app, panel, _item) = create_styled_app(".panel { width: 10px; }");
+    app.world.get_mut::<Style>(panel).unwrap().height = Val::Px(30.0);
+
+    clear_styles(&mut app.world, panel);
+
+    assert_eq!(style(&app, panel).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, panel).height, Val::Px(30.0));
+}
+
+#[test]
+fn removing_style_sheet_reverts_styles(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".panel { width: 10px; } .item { height: 5px; }");
+    assert_eq!(style(&app, item).height, Val::Px(5.0));
+
+    app.world.entity_mut(panel).remove::<StyleSheet>();
+    app.update();
+
+    assert_eq!(style(&app, panel).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height);
+}
+
+#[test]
+fn em_is_relative_to_parent_font_size(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        ".panel { font-size: 10px; } .item { font-size: 2em; padding: 0.5em; } .label { font-size: 1.5em; }"
+    );
+
+    let label = app.world
+        .spawn((TextBundle::from_section("label", TextStyle::default()), Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).padding, UiRect::all(Val::Px(10.0)), "Should be relative to its own font size");
+
+    let text = app.world.get::<Text>(label).unwrap();
+    assert_eq!(text.sections[0].style.font_size, 30.0);
+}
+
+#[test]
+fn em_and_rem_are_resolved_inside_functions(
+    // no args
+) {
+    use crate::property::impls::ClipRect;
+
+    let (app, _panel, item) = create_styled_app(".panel { font-size: 10px; } .item { font-size: 2em; clip: rect(0, 1em, 1rem, 0); }");
+
+    let clip = app.world.get::<ClipRect>(item).expect("Should resolve font relative arguments");
+    assert_eq!(clip.right, Val::Px(20.0), "Should be relative to its own font size, set on the same rule");
+    assert_eq!(clip.bottom, Val::Px(16.0), "Should be relative to the root font size");
+}
+
+#[test]
+fn rem_is_relative_to_root_font_size(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".panel { font-size: 10px; } .item { width: 2rem; }");
+    assert_eq!(style(&app, item).width, Val::Px(32.0));
+
+    app.world.insert_resource(RootFontSize(20.0));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(40.0));
+}
+
+#[test]
+fn changing_root_font_size_restyles_rem_values(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app_with(
+        BevyCssPlugin::without_hot_reload().with_root_font_size(10.0),
+        ".item { width: 2rem; } .label { font-size: 2rem; }"
+    );
+
+    let label = app.world
+        .spawn((TextBundle::from_section("label", TextStyle::default()), Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(20.0));
+    assert_eq!(app.world.get::<Text>(label).unwrap().sections[0].style.font_size, 20.0);
+
+    app.world.resource_mut::<RootFontSize>().0 = 20.0;
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(40.0));
+    assert_eq!(app.world.get::<Text>(label).unwrap().sections[0].style.font_size, 40.0);
+}
+
+#[test]
+fn percentage_font_size_is_relative_to_parent_font_size(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app_with(
+        BevyCssPlugin::without_hot_reload().with_root_font_size(10.0),
+        ".panel { font-size: 200%; } .item { font-size: 150%; width: 1em; } .label { font-size: 50%; }"
+    );
+
+    let label = app.world
+        .spawn((TextBundle::from_section("label", TextStyle::default()), Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    let state = app.world.resource::<StyleSheetState>();
+    assert_eq!(state.font_size(panel).map(|context| context.own), Some(20.0), "Should be relative to the root font size");
+    assert_eq!(style(&app, item).width, Val::Px(30.0));
+    assert_eq!(app.world.get::<Text>(label).unwrap().sections[0].style.font_size, 15.0);
+}
+
+#[test]
+fn relative_font_size_on_root_is_rejected(
+    // no args
+) {
+    let (app, panel, item) = create_styled_app(".panel { font-size: 2em; } .item { width: 1em; }");
+
+    let state = app.world.resource::<StyleSheetState>();
+    assert_eq!(state.font_size(panel).map(|context| context.own), Some(16.0));
+    assert_eq!(style(&app, item).width, Val::Px(16.0));
+
+    // Rejected values aren't applied either
+    let mut app = TestApp::with_css(".title { font-size: 2em; color: red; }");
+    let tree = app.spawn_tree(TestNode::text("title").class("title"));
+    app.step_until_styled();
+
+    app.expect_component::<Text>(tree[0], |text| {
+        assert_eq!(text.sections[0].style.font_size, TextStyle::default().font_size);
+        assert_eq!(text.sections[0].style.color, Color::RED);
+    });
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn interaction_change_reevaluates_only_sensitive_rules(
+    // no args
+) {
+    use bevy::prelude::Interaction;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 5px; } .item { height: 5px; } .item:hover { width: 10px; } .panel .item:hover { min-width: 1px; }"
+    );
+    app.world.entity_mut(item).insert(Interaction::None);
+    app.update();
+
+    // Changed after styling, so it'd be reverted only if the `height` rule is evaluated again
+    app.world.get_mut::<Style>(item).unwrap().height = Val::Px(99.0);
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0));
+    assert_eq!(style(&app, item).min_width, Val::Px(1.0), "Contextual selector should be matched");
+    assert_eq!(style(&app, item).height, Val::Px(99.0), "Rules not affected by interaction shouldn't be applied again");
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::None;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should revert to the non-hovered rule");
+    assert_eq!(style(&app, item).height, Val::Px(99.0));
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn interaction_change_reverts_to_rules_of_other_sheets(
+    // no args
+) {
+    use crate::prelude::StyleSheet;
+    use bevy::prelude::Interaction;
+
+    let (mut app, _panel, item) = create_styled_app(".item { width: 5px; }");
+    let hover = app.world
+        .resource_mut::<Assets<StyleSheetAsset>>()
+        .add(StyleSheetAsset::parse("hover.css", ".item:hover { width: 10px; }"));
+    app.world.entity_mut(item).insert((Interaction::None, StyleSheet::new(hover)));
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0));
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::None;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should revert to the rule of the panel sheet");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn interaction_rules_include_overridden_properties(
+    // no args
+) {
+    let sheet = StyleSheetAsset::parse("", ".a { width: 1px; } .b { height: 1px; } .a:hover { width: 2px; }");
+    let selectors: Vec<String> = sheet.interaction_rules()
+        .map(|(_selector_id, rule)| rule.selector.to_string())
+        .collect();
+
+    assert_eq!(selectors, [".a", ".a:hover"]);
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn root_matches_only_top_level_nodes(
+    // no args
+) {
+    let (app, panel, item) = create_styled_app(":root { width: 10px; } .panel .item:root { height: 10px; }");
+
+    assert_eq!(style(&app, panel).width, Val::Px(10.0));
+    assert_eq!(style(&app, item).width, Style::DEFAULT.width, "Child nodes aren't UI roots");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height);
+}
+
+#[test]
+fn scope_matches_only_the_sheet_entity(
+    // no args
+) {
+    let mut app = TestApp::with_css(":scope { width: 10px; } :scope > .column { height: 5px; } .column :scope { width: 1px; }");
+    let nested = app.add_sheet("nested.css", ":scope { width: 20px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::node().class("column")
+            .with_child(TestNode::node().class("column"))
+        )
+        .with_child(TestNode::node().with(StyleSheet::new(nested)))
+    );
+    app.step_until_styled();
+
+    assert_eq!(style(&app, tree[0]).width, Val::Px(10.0), "Should match without any name or class");
+    assert_eq!(style(&app, tree[1]).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, tree[1]).height, Val::Px(5.0));
+    assert_eq!(style(&app, tree[2]).height, Style::DEFAULT.height, "Should only match direct children");
+    assert_eq!(style(&app, tree[3]).width, Val::Px(20.0), "Should be the entity holding the nested sheet");
+}
+
+#[test]
+fn shared_sheet_is_ordered_by_its_depth_under_each_root(
+    // no args
+) {
+    let mut app = TestApp::with_css(".panel { height: 5px; }");
+    let shared = app.add_sheet("shared.css", ".item { width: 10px; }");
+    let theme = app.add_sheet("theme.css", ".item { width: 20px; }");
+
+    // The shared sheet is closer to the item than the theme on the first tree, and farther on the second one
+    let closer = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::node().with(StyleSheet::new(theme.clone()))
+            .with_child(TestNode::node().with(StyleSheet::new(shared.clone()))
+                .with_child(TestNode::node().class("item"))
+            )
+        )
+    );
+    let farther = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::node().with(StyleSheet::new(shared))
+            .with_child(TestNode::node().with(StyleSheet::new(theme))
+                .with_child(TestNode::node().class("item"))
+            )
+        )
+    );
+    app.step_until_styled();
+
+    assert_eq!(style(&app, closer[3]).width, Val::Px(10.0), "Shared sheet should override the theme above it");
+    assert_eq!(style(&app, farther[3]).width, Val::Px(20.0), "Theme should override the shared sheet above it");
+}
+
+#[test]
+fn style_root_provider_finds_custom_roots(
+    // no args
+) {
+    use crate::RegisterStyleRootProvider;
+    use bevy::prelude::With;
+
+    #[derive(Component)]
+    struct UiRoot;
+
+    #[derive(Resource)]
+    struct UiSheet(Handle<StyleSheetAsset>);
+
+    fn ui_roots(
+        q_roots: Query<Entity, With<UiRoot>>,
+        sheet: Res<UiSheet>,
+    ) -> StyleRoots {
+        q_roots.iter()
+            .map(|root| (root, vec![sheet.0.clone()]))
+            .collect()
+    }
+
+    let mut app = TestApp::with_css(".panel { width: 10px; }");
+    let marked = app.add_sheet("marked.css", ".panel { width: 20px; } .item { height: 5px; }");
+    app.insert_resource(UiSheet(marked))
+        .register_style_root_provider(ui_roots);
+
+    let tree = app.spawn_tree(TestNode::node().class("panel").with(UiRoot)
+        .with_child(TestNode::node().class("item"))
+    );
+    let unmarked = app.spawn_tree(TestNode::node().class("panel"));
+    app.step_until_styled();
+
+    assert_eq!(style(&app, tree[0]).width, Val::Px(20.0), "Should apply the sheets returned by the provider");
+    assert_eq!(style(&app, tree[1]).height, Val::Px(5.0));
+    assert_eq!(style(&app, unmarked[0]).width, Style::DEFAULT.width, "StyleSheet components should no longer be roots");
+
+    // Roots without any changed component are styled once they are found
+    let late = app.world.spawn((NodeBundle::default(), Class::new("panel"))).id();
+    app.update();
+    app.world.entity_mut(late).insert(UiRoot);
+    app.update();
+
+    assert_eq!(style(&app, late).width, Val::Px(20.0));
+}
+
+#[test]
+fn bare_number_is_rejected_by_default(
+    // no args
+) {
+    let (app, panel, item) = create_styled_app(".panel { width: 10; } .item { width: 0; }");
+
+    assert_eq!(style(&app, panel).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, item).width, Val::Px(0.0), "Unitless zero should be accepted");
+}
+
+#[test]
+fn bare_number_as_px_when_lenient(
+    // no args
+) {
+    let plugin = BevyCssPlugin::without_hot_reload().with_bare_number_policy(BareNumberPolicy::Px);
+    let (app, panel, _item) = create_styled_app_with(plugin, ".panel { width: 10; }");
+
+    assert_eq!(style(&app, panel).width, Val::Px(10.0));
+}
+
+#[test]
+fn style_applied_is_readable_on_post_apply(
+    // no args
+) {
+    use crate::prelude::{BevyCssSet, StyleApplied};
+    use bevy::prelude::{EventReader, IntoSystemConfigs, ResMut, Resource, Update};
+
+    #[derive(Default, Resource)]
+    struct Styled(Vec<(Entity, Val)>);
+
+    fn read_events(
+        mut events: EventReader<StyleApplied>,
+        styles: bevy::prelude::Query<&Style>,
+        mut styled: ResMut<Styled>
+    ) {
+        for event in events.read()
+        {
+            styled.0.push((event.entity, styles.get(event.entity).unwrap().width));
+        }
+    }
+
+    let mut app = TestApp::with_css(".panel { width: 10px; }");
+    app.init_resource::<Styled>()
+        .add_systems(Update, read_events.in_set(BevyCssSet::PostApply));
+
+    let panel = app.spawn_tree(TestNode::node().class("panel"))[0];
+    app.update();
+
+    assert_eq!(app.world.resource::<Styled>().0, [(panel, Val::Px(10.0))], "Styles should be applied before PostApply");
+}
+
+#[test]
+fn viewport_units_are_resolved_by_layout(
+    // no args
+) {
+    use bevy::{prelude::{Node, Vec2, With}, window::{PrimaryWindow, Window, WindowResized}};
+
+    let mut app = TestApp::with_css(".panel { width: 50vw; height: 10vh; } .item { width: 10vw; margin: 1vh; }");
+    add_layout(&mut app);
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+    );
+    let (panel, item) = (tree[0], tree[1]);
+    app.step(2);
+
+    assert_eq!(style(&app, panel).width, Val::Vw(50.0));
+    assert_eq!(style(&app, panel).height, Val::Vh(10.0));
+    assert_eq!(style(&app, item).margin, UiRect::all(Val::Vh(1.0)));
+
+    // Like winit does, which `bevy_ui` listens to in order to lay out nodes again
+    let window = app.world.query_filtered::<Entity, With<PrimaryWindow>>().single(&app.world);
+    let resize = |app: &mut TestApp, width, height|
+    {
+        app.world.get_mut::<Window>(window).unwrap().resolution.set(width, height);
+        app.world.send_event(WindowResized { window, width, height });
+    };
+    resize(&mut app, 800.0, 600.0);
+    app.step(2);
+    assert_eq!(app.world.get::<Node>(panel).unwrap().size(), Vec2::new(400.0, 60.0));
+    assert_eq!(app.world.get::<Node>(item).unwrap().size().x, 80.0);
+
+    // `bevy_ui` resolves them against the window on layout, so resizing it doesn't restyle anything
+    let changed = |app: &TestApp| app.world.entity(panel).get_change_ticks::<Style>().unwrap().last_changed_tick();
+    let styled_at = changed(&app);
+    resize(&mut app, 1000.0, 300.0);
+    app.step(2);
+    assert_eq!(changed(&app), styled_at, "Should not restyle on resize");
+    assert_eq!(app.world.get::<Node>(panel).unwrap().size(), Vec2::new(500.0, 30.0));
+}
+
+#[test]
+fn missing_style_is_inserted(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".item { width: 5px; }");
+    app.world.entity_mut(item).remove::<Style>();
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should insert the missing style and apply the property");
+}
+
+#[test]
+fn missing_style_is_inserted_again_after_clear(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".item { width: 5px; }");
+    app.world.entity_mut(item).remove::<Style>();
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    clear_styles(&mut app.world, item);
+    app.update();
+
+    app.world.entity_mut(item).remove::<Style>();
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should handle the missing style again once the property is applied again");
+}
+
+#[test]
+fn class_prefix_and_suffix_match_class_families(
+    // no args
+) {
+    let mut app = TestApp::with_css(".slot-* { width: 48px; } .*-active { height: 5px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::node().class("slot-0"))
+        .with_child(TestNode::node().class("big slot-63 tab-active"))
+        .with_child(TestNode::node().class("slot"))
+        .with_child(TestNode::node().class("my-slot-1 active"))
+    );
+    app.step_until_styled();
+
+    assert_eq!(style(&app, tree[1]).width, Val::Px(48.0));
+    assert_eq!(style(&app, tree[1]).height, Style::DEFAULT.height);
+    assert_eq!(style(&app, tree[2]).width, Val::Px(48.0), "Should match any of the classes");
+    assert_eq!(style(&app, tree[2]).height, Val::Px(5.0));
+    assert_eq!(style(&app, tree[3]).width, Style::DEFAULT.width, "Should not match the prefix itself without `-`");
+    assert_eq!(style(&app, tree[4]).width, Style::DEFAULT.width, "Should only match at the start of the class");
+    assert_eq!(style(&app, tree[4]).height, Style::DEFAULT.height);
+}
+
+#[test]
+fn nth_of_type_only_counts_siblings_with_the_component(
+    // no args
+) {
+    use bevy::prelude::ButtonBundle;
+
+    let mut app = TestApp::with_css("button:nth-of-type(2) { width: 20px; } text:nth-of-type(odd) { height: 5px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::text("first"))
+        .with_child(TestNode::with_bundle(ButtonBundle::default()))
+        .with_child(TestNode::text("second"))
+        .with_child(TestNode::with_bundle(ButtonBundle::default()))
+        .with_child(TestNode::text("third"))
+        .with_child(TestNode::with_bundle(ButtonBundle::default()))
+    );
+    app.step_until_styled();
+
+    let widths: Vec<_> = [2, 4, 6].map(|index| style(&app, tree[index]).width).into();
+    assert_eq!(widths, [Style::DEFAULT.width, Val::Px(20.0), Style::DEFAULT.width], "Should only count buttons");
+
+    let heights: Vec<_> = [1, 3, 5].map(|index| style(&app, tree[index]).height).into();
+    assert_eq!(heights, [Val::Px(5.0), Style::DEFAULT.height, Val::Px(5.0)], "Should only count texts");
+}
+
+#[test]
+fn missing_text_is_not_inserted(
+    // no args
+) {
+    let (app, _panel, item) = create_styled_app(".item { font-size: 20px; }");
+    assert!(app.world.get::<Text>(item).is_none(), "Should only warn about the missing text");
+}
+
+#[test]
+fn text_transform_follows_text_changes(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".label { text-transform: uppercase; }");
+
+    let label = app.world
+        .spawn((TextBundle::from_section("hello", TextStyle::default()), Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    let text = |app: &App| app.world.get::<Text>(label).unwrap().sections[0].value.clone();
+    assert_eq!(text(&app), "HELLO");
+
+    app.world.get_mut::<Text>(label).unwrap().sections[0].value = "bye".to_string();
+    app.update();
+    assert_eq!(text(&app), "BYE", "Should transform the new content");
+
+    clear_styles(&mut app.world, label);
+    assert_eq!(text(&app), "bye", "Should revert to the source text");
+}
+
+#[test]
+fn text_properties_apply_to_all_sections(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        ".label { font-size: 20px; color: red; text-align: center; }"
+    );
+
+    let label = app.world
+        .spawn((
+            TextBundle::from_sections([
+                TextSection::new("first", TextStyle::default()),
+                TextSection::new("second", TextStyle::default()),
+            ]),
+            Class::new("label"),
+        ))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    let text = app.world.get::<Text>(label).unwrap();
+    assert_eq!(text.alignment, TextAlignment::Center, "Should align the whole block");
+    for section in text.sections.iter()
+    {
+        assert_eq!(section.style.font_size, 20.0);
+        assert_eq!(section.style.color, Color::RED);
+    }
+}
+
+#[test]
+fn text_properties_apply_on_text_without_sections(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        r#".label {
+            font-size: 20px;
+            color: red;
+            text-align: center;
+            text-content: "hello";
+            text-transform: uppercase;
+            text-shadow: 1px 1px #000;
+        }"#
+    );
+
+    let label = app.world
+        .spawn((TextBundle { text: Text::default(), ..Default::default() }, Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    app.update();
+
+    let text = app.world.get::<Text>(label).unwrap();
+    assert_eq!(text.sections.len(), 1, "Should create a single section");
+    assert_eq!(text.sections[0].value, "HELLO");
+    assert_eq!(text.sections[0].style.font_size, 20.0);
+    assert_eq!(text.sections[0].style.color, Color::RED);
+    assert_eq!(text.alignment, TextAlignment::Center);
+}
+
+#[test]
+fn white_space_pre_keeps_newlines_without_wrapping(
+    // no args
+) {
+    use bevy::text::BreakLineOn;
+
+    let (mut app, panel, item) = create_styled_app(r#".label { white-space: pre; text-content: "first\A second"; }"#);
+
+    let label = app.world
+        .spawn((TextBundle::from_section("label", TextStyle::default()), Class::new("label")))
+        .id();
+    app.world.entity_mut(item).add_child(label);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    let text = app.world.get::<Text>(label).unwrap();
+    assert_eq!(text.sections[0].value, "first\nsecond", "Should keep the escaped newline");
+    assert_eq!(text.linebreak_behavior, BreakLineOn::NoWrap, "Should only break lines on newlines");
+
+    clear_styles(&mut app.world, label);
+    assert_eq!(app.world.get::<Text>(label).unwrap().linebreak_behavior, BreakLineOn::WordBoundary);
+}
+
+#[test]
+fn logical_properties_left_to_right(
+    // no args
+) {
+    let (app, _panel, item) = create_styled_app(".item { margin-inline-start: 5px; padding-inline-end: 3px; }");
+
+    assert_eq!(style(&app, item).margin.left, Val::Px(5.0));
+    assert_eq!(style(&app, item).padding.right, Val::Px(3.0));
+}
+
+#[test]
+fn logical_properties_right_to_left(
+    // no args
+) {
+    let (app, _panel, item) = create_styled_app(
+        ".item { direction: rtl; margin-inline-start: 5px; padding-inline-end: 3px; }"
+    );
+
+    assert_eq!(style(&app, item).margin.right, Val::Px(5.0));
+    assert_eq!(style(&app, item).margin.left, Val::Px(0.0));
+    assert_eq!(style(&app, item).padding.left, Val::Px(3.0));
+}
+
+#[test]
+fn logical_properties_inherit_direction(
+    // no args
+) {
+    let (app, _panel, item) = create_styled_app(
+        ".panel { direction: rtl; } .item { direction: inherit; margin-inline-end: 5px; }"
+    );
+
+    assert_eq!(style(&app, item).margin.left, Val::Px(5.0), "Should use the direction of the parent");
+}
+
+#[test]
+fn imported_sheet_changes_reapply_importing_sheets(
+    // no args
+) {
+    let mut app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(), "@import \"base.css\"; .item { width: 2px; }");
+    let base = app.add_sheet("base.css", ".item { width: 1px; height: 1px; }");
+    let main = app.sheet().clone();
+    app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&main).unwrap().imports = vec![base.clone()];
+
+    let item = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::node().class("item"))
+    )[1];
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(2.0), "Importing sheet rules should win");
+    assert_eq!(style(&app, item).height, Val::Px(1.0), "Imported sheet rules should be applied");
+
+    *app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&base).unwrap() =
+        StyleSheetAsset::parse("base.css", ".item { width: 1px; height: 3px; }");
+
+    // Asset events are only sent at the end of the frame, then the sheet is refreshed and applied on the next ones
+    for _ in 0..3
+    {
+        app.update();
+    }
+
+    assert_eq!(style(&app, item).height, Val::Px(3.0), "Should reapply the importing sheet");
+    assert_eq!(style(&app, item).width, Val::Px(2.0));
+}
+
+#[test]
+fn reset_display_none_restores_previous_display(
+    // no args
+) {
+    use bevy::prelude::Display;
+
+    let mut app = TestApp::with_css(".panel { display: none; }");
+    let style = Style { display: Display::Grid, ..Default::default() };
+    let panel = app.spawn_tree(TestNode::with_bundle(NodeBundle { style, ..Default::default() }).class("panel"))[0];
+    app.update();
+    assert_eq!(app.world.get::<Style>(panel).unwrap().display, Display::None);
+
+    clear_styles(&mut app.world, panel);
+    assert_eq!(app.world.get::<Style>(panel).unwrap().display, Display::Grid, "Should restore the display before hiding");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn disabled_entities_skip_interaction_pseudo_classes(
+    // no args
+) {
+    use crate::prelude::Disabled;
+    use bevy::prelude::Interaction;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 5px; height: 5px; } .item:hover { width: 10px; } .item:disabled { height: 1px; }"
+    );
+    app.world.entity_mut(item).insert((Interaction::None, Disabled));
+    app.update();
+    assert_eq!(style(&app, item).height, Val::Px(1.0), "Should match :disabled");
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Shouldn't match :hover while disabled");
+
+    app.world.entity_mut(item).remove::<Disabled>();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0), "Should match :hover once enabled");
+    assert_eq!(style(&app, item).height, Val::Px(5.0), "Shouldn't match :disabled once enabled");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn checked_pseudo_class_follows_checked_component(
+    // no args
+) {
+    use crate::prelude::{Checked, PseudoClasses};
+    use bevy::prelude::Interaction;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 5px; height: 5px; } .item:checked { width: 10px; } .item:checked:hover { height: 20px; }"
+    );
+
+    app.world.entity_mut(item).insert((Interaction::None, Checked));
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0), "Should match :checked once checked");
+    assert_eq!(style(&app, item).height, Val::Px(5.0));
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(style(&app, item).height, Val::Px(20.0), "Should combine with :hover");
+
+    app.world.entity_mut(item).remove::<Checked>();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Shouldn't match :checked once unchecked");
+    assert_eq!(style(&app, item).height, Val::Px(5.0));
+
+    app.world.entity_mut(item).insert(PseudoClasses::new(["checked"]));
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0), "Should still match the `checked` custom state");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn flipped_pseudo_class_reevaluates_only_its_entity_and_descendants(
+    // no args
+) {
+    use crate::prelude::PseudoClasses;
+
+    let (mut app, panel, item) = create_styled_app(
+        ".panel { width: 50px; } .item { width: 5px; } .item:selected { width: 10px; } .panel:selected .item { height: 7px; }"
+    );
+    app.world.entity_mut(item).insert(PseudoClasses::default());
+    app.update();
+
+    // Changed after styling, so it'd be reverted only if the panel is styled again
+    app.world.get_mut::<Style>(panel).unwrap().width = Val::Px(99.0);
+
+    app.world.get_mut::<PseudoClasses>(item).unwrap().insert("selected");
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0), "Should match :selected");
+    assert_eq!(style(&app, panel).width, Val::Px(99.0), "Other entities shouldn't be styled again");
+
+    app.world.entity_mut(panel).insert(PseudoClasses::new(["selected"]));
+    app.update();
+    assert_eq!(style(&app, item).height, Val::Px(7.0), "Descendants should match contextual selectors");
+
+    app.world.get_mut::<PseudoClasses>(item).unwrap().toggle("selected");
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should revert once unset");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn pseudo_class_rules_are_indexed_by_name(
+    // no args
+) {
+    let sheet = StyleSheetAsset::parse("", ".a { width: 1px; } .b { height: 1px; } .a:selected { width: 2px; } .a:hover { height: 2px; }");
+    let selectors = |names: &[String]| -> Vec<String> {
+        sheet.pseudo_class_rules(names)
+            .map(|(_selector_id, rule)| rule.selector.to_string())
+            .collect()
+    };
+
+    assert_eq!(selectors(&["selected".to_string()]), [".a", ".a:selected"]);
+    assert_eq!(selectors(&["selected".to_string(), "hover".to_string()]), [".a", ".b", ".a:selected", ".a:hover"]);
+    assert!(selectors(&["invalid".to_string()]).is_empty());
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn unknown_pseudo_classes_are_found_once(
+    // no args
+) {
+    use crate::prelude::PseudoClasses;
+
+    let mut world = World::new();
+    let mut assets = Assets::<StyleSheetAsset>::default();
+    assets.add(StyleSheetAsset::parse("", ".a:hover { width: 1px; } .a:selected { width: 2px; } .a:hovr { width: 3px; } .a:open { width: 4px; }"));
+    world.insert_resource(assets);
+    world.spawn(PseudoClasses::new(["selected"]));
+
+    let flipped = HashMap::from([(Entity::PLACEHOLDER, vec!["open".to_string()])]);
+    let mut checked = HashSet::new();
+    assert_eq!(find_unknown_pseudo_classes(&mut world, &flipped, &mut checked), ["hovr"], "Should skip built-in and set states");
+    assert!(find_unknown_pseudo_classes(&mut world, &HashMap::new(), &mut checked).is_empty(), "Should only be found once");
+}
+
+#[test]
+fn hot_reload_default_follows_build_profile(
+    // no args
+) {
+    use crate::prelude::HotReloadEnabled;
+
+    let expected = HotReloadEnabled(cfg!(all(feature = "debug_hot_reload", debug_assertions)));
+    assert_eq!(HotReloadEnabled::default(), expected, "Should only be enabled on debug builds with the feature");
+
+    let app = TestApp::with_plugin(BevyCssPlugin::default(), "");
+    assert_eq!(*app.world.resource::<HotReloadEnabled>(), expected);
+
+    let app = TestApp::with_plugin(BevyCssPlugin::without_hot_reload(), "");
+    assert_eq!(*app.world.resource::<HotReloadEnabled>(), HotReloadEnabled(false), "Should be disabled regardless of build profile");
+}
+
+#[test]
+fn hot_reload_reapplies_only_changed_rules(
+    // no args
+) {
+    use bevy::prelude::BackgroundColor;
+
+    let (mut app, panel, item) = create_styled_app_with(
+        BevyCssPlugin::with_hot_reload(),
+        ".panel { width: 10px; } .item { background-color: red; } .item { height: 4px; }"
+    );
+    assert_eq!(app.world.get::<BackgroundColor>(item).unwrap().0, Color::RED);
+    assert_eq!(style(&app, item).height, Val::Px(4.0));
+
+    // Would be overwritten if `.panel` was applied again
+    app.world.get_mut::<Style>(panel).unwrap().width = Val::Px(50.0);
+
+    // Lets hot reload see the loaded sheet, since asset events are only sent at the end of the frame
+    app.update();
+
+    let handle = app.world.get::<StyleSheet>(panel).unwrap().handle().clone();
+    *app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&handle).unwrap() =
+        StyleSheetAsset::parse("test.css", ".panel { width: 10px; } .item { background-color: blue; }");
+
+    // Asset events are only sent at the end of the frame, then changed rules are applied on the next ones
+    for _ in 0..3
+    {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<BackgroundColor>(item).unwrap().0, Color::BLUE, "Changed rule should be applied again");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height, "Removed rule should be reverted");
+    assert_eq!(style(&app, panel).width, Val::Px(50.0), "Unchanged rule shouldn't be applied again");
+}
+
+#[test]
+fn hot_reload_reapplies_whole_sheet_when_rules_are_reordered(
+    // no args
+) {
+    let (mut app, panel, _item) = create_styled_app_with(
+        BevyCssPlugin::with_hot_reload(),
+        ".panel { width: 10px; } .item { height: 4px; }"
+    );
+    app.world.get_mut::<Style>(panel).unwrap().width = Val::Px(50.0);
+    app.update();
+
+    let handle = app.world.get::<StyleSheet>(panel).unwrap().handle().clone();
+    *app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&handle).unwrap() =
+        StyleSheetAsset::parse("test.css", ".item { height: 4px; } .panel { width: 10px; }");
+
+    for _ in 0..3
+    {
+        app.update();
+    }
+
+    assert_eq!(style(&app, panel).width, Val::Px(10.0), "Reordered rules should apply the whole sheet again");
+}
+
+#[test]
+fn inline_style_overrides_sheet_rules(
+    // no args
+) {
+    use crate::prelude::InlineStyle;
+
+    let (mut app, _panel, item) = create_styled_app(".item { width: 5px; height: 5px !important; }");
+    app.world.entity_mut(item).insert(InlineStyle::new("width: 20px; height: 1px; left: 3px;"));
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(20.0), "Inline style should win over sheet rules");
+    assert_eq!(style(&app, item).height, Val::Px(5.0), "Important sheet rules should win over inline style");
+    assert_eq!(style(&app, item).left, Val::Px(3.0));
+
+    app.world.get_mut::<InlineStyle>(item).unwrap().set("width: 30px;");
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(30.0));
+    assert_eq!(style(&app, item).left, Style::DEFAULT.left, "Properties no longer declared should be reverted");
+
+    app.world.entity_mut(item).remove::<InlineStyle>();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Sheet rules should be applied again once removed");
+}
+
+#[test]
+fn important_inline_style_overrides_important_sheet_rules(
+    // no args
+) {
+    use crate::prelude::InlineStyle;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { background-color: red !important; width: 5px !important; height: 5px; }"
+    );
+    app.world.entity_mut(item).insert(InlineStyle::new("background-color: blue !important; width: 20px; height: 1px !important;"));
+    app.update();
+
+    let color = app.world.get::<BackgroundColor>(item).unwrap().0;
+    assert_eq!(color, Color::BLUE, "Important inline style should win over important sheet rules");
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Important sheet rules should win over inline style");
+    assert_eq!(style(&app, item).height, Val::Px(1.0));
+}
+
+#[test]
+fn inline_style_reports_invalid_declarations(
+    // no args
+) {
+    use crate::prelude::{InlineStyle, StyleDiagnostic};
+    use bevy::ecs::event::ManualEventReader;
+
+    let (mut app, _panel, item) = create_styled_app("");
+    let style_component = InlineStyle::new("width 10px; height: 3px;");
+    assert_eq!(style_component.errors().len(), 1);
+
+    app.world.entity_mut(item).insert(style_component);
+    app.update();
+    assert_eq!(style(&app, item).height, Val::Px(3.0), "Valid declarations should still be applied");
+
+    let events = app.world.resource::<bevy::prelude::Events<StyleDiagnostic>>();
+    let mut reader = ManualEventReader::<StyleDiagnostic>::default();
+    let diagnostics: Vec<_> = reader.read(events).collect();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].entity, item);
+}
+
+#[test]
+fn cascade_audit_sends_report(
+    // no args
+) {
+    use crate::prelude::{CascadeAuditEnabled, CascadeAuditReport};
+    use bevy::ecs::event::ManualEventReader;
+
+    let plugin = BevyCssPlugin::without_hot_reload().with_cascade_audit(true);
+    let (mut app, _panel, item) = create_styled_app_with(plugin, ".item { width: 1px; }\n.item { width: 2px; }");
+    assert_eq!(style(&app, item).width, Val::Px(2.0));
+
+    let events = app.world.resource::<bevy::prelude::Events<CascadeAuditReport>>();
+    let mut reader = ManualEventReader::<CascadeAuditReport>::default();
+    let reports: Vec<_> = reader.read(events).collect();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].conflicts.len(), 1);
+    assert_eq!(reports[0].conflicts[0].entities, [item]);
+    assert_eq!(reports[0].conflicts[0].winner().map(|winner| winner.location.line), Some(2));
+
+    // Disabling the audit at runtime stops the reports
+    app.world.insert_resource(CascadeAuditEnabled(false));
+    app.world.entity_mut(item).insert(Class::new("item other".to_string()));
+    app.update();
+
+    let events = app.world.resource::<bevy::prelude::Events<CascadeAuditReport>>();
+    assert_eq!(reader.read(events).count(), 0);
+}
+
+#[test]
+fn retained_state_keeps_last_applied_styles(
+    // no args
+) {
+    use crate::prelude::LastAppliedStyles;
+
+    let (app, _panel, _item) = create_styled_app(".item { width: 1px; }");
+    assert!(app.world.get_resource::<LastAppliedStyles>().is_none(), "Should only be kept when enabled");
+
+    let plugin = BevyCssPlugin::without_hot_reload().with_retained_state(true);
+    let (mut app, panel, item) = create_styled_app_with(plugin, ".item { width: 1px; }");
+    assert!(app.world.resource::<StyleSheetState>().computed.is_empty(), "Working state should still be cleared");
+
+    let last_applied = app.world.resource::<LastAppliedStyles>();
+    let source = last_applied.get(&item).and_then(|style| style.get("width")).expect("Should keep the item style");
+    assert_eq!(source.selector.to_string(), ".item");
+    assert!(last_applied.get(&panel).map_or(true, |style| style.is_empty()), "Panel isn't matched by any rule");
+
+    // Frames which don't style any entity keep the last styles
+    app.update();
+    assert!(app.world.resource::<LastAppliedStyles>().contains_key(&item));
+}
+
+#[test]
+fn component_variant_selector_follows_variant(
+    // no args
+) {
+    use crate::RegisterComponentSelector;
+    use bevy::prelude::{Component, Reflect};
+
+    #[derive(Component, Reflect)]
+    enum State
+    {
+        Idle,
+        FastRunning,
+    }
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 5px; } state(fast-running) { width: 10px; } .panel state(idle) { height: 1px; }"
+    );
+    app.register_component_selector_reflect::<State>("state");
+
+    app.world.entity_mut(item).insert(State::Idle);
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+    assert_eq!(style(&app, item).height, Val::Px(1.0));
+
+    *app.world.get_mut::<State>(item).unwrap() = State::FastRunning;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0));
+
+    *app.world.get_mut::<State>(item).unwrap() = State::Idle;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should revert to the rule without variant");
+}
+
+#[test]
+fn component_attribute_selector_follows_interaction(
+    // no args
+) {
+    use bevy::prelude::Interaction;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 5px; } .panel interaction[variant=pressed] { width: 10px; }"
+    );
+    app.world.entity_mut(item).insert(Interaction::None);
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Pressed;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0));
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(5.0), "Should revert once the value no longer matches");
+}
+
+#[test]
+fn component_attribute_selector_follows_fields(
+    // no args
+) {
+    use crate::RegisterComponentSelector;
+    use bevy::prelude::{Component, Reflect};
+
+    #[derive(Component, Reflect)]
+    struct Health
+    {
+        current: u32,
+        poisoned: bool,
+    }
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 1px; height: 1px; } health[current=0] { width: 10px; } health[poisoned=true][current=5] { height: 5px; }"
+    );
+    app.register_component_selector_fields::<Health>("health");
+
+    app.world.entity_mut(item).insert(Health { current: 5, poisoned: true });
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0));
+    assert_eq!(style(&app, item).height, Val::Px(5.0));
+
+    *app.world.get_mut::<Health>(item).unwrap() = Health { current: 0, poisoned: false };
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(10.0));
+    assert_eq!(style(&app, item).height, Val::Px(1.0), "Should revert once the fields no longer match");
+}
+
+#[test]
+fn text_content_follows_bound_component_fields(
+    // no args
+) {
+    use crate::RegisterTextBinding;
+    use bevy::prelude::{Component, DetectChanges, Reflect};
+
+    #[derive(Component, Reflect)]
+    struct Health
+    {
+        current: u32,
+    }
+
+    #[derive(Component, Reflect)]
+    struct Gold(f32);
+
+    let mut app = TestApp::with_css(r#".label { text-content: "HP: {health.current} / {gold} {mana}"; }"#);
+    app.register_text_binding::<Health>("health")
+        .register_text_binding::<Gold>("gold");
+
+    let tree = app.spawn_tree(TestNode::node()
+        .with(Health { current: 10 })
+        .with_child(TestNode::text("").class("label").with(Gold(2.5)))
+    );
+    app.step_until_styled();
+
+    let text = |app: &TestApp| app.world.get::<Text>(tree[1]).unwrap().sections[0].value.clone();
+    assert_eq!(text(&app), "HP: 10 / 2.5 {mana}", "Should use the closest entity with the component, and keep unknown bindings");
+
+    app.world.get_mut::<Health>(tree[0]).unwrap().current = 7;
+    app.update();
+    assert_eq!(text(&app), "HP: 7 / 2.5 {mana}");
+
+    app.update();
+    assert!(!app.world.entity(tree[1]).get_ref::<Text>().unwrap().is_changed(), "Should only be written when the value changes");
+
+    app.world.get_mut::<Gold>(tree[1]).unwrap().0 = 4.0;
+    app.update();
+    assert_eq!(text(&app), "HP: 7 / 4 {mana}");
+
+    // Moved under another ancestor with the component
+    let other = app.world.spawn((NodeBundle::default(), Health { current: 3 })).id();
+    app.world.entity_mut(tree[1]).set_parent(other);
+    app.update();
+    assert_eq!(text(&app), "HP: 3 / 4 {mana}", "Should follow the new ancestor");
+}
+
+#[test]
+fn restoring_snapshot_reapplies_resolved_styles(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        ".panel { font-size: 10px; } .item { width: 2em; height: 5px; } .other { width: 7px; left: 3px; }"
+    );
+    assert_eq!(style(&app, item).width, Val::Px(20.0));
+
+    let snapshot = snapshot_styles(&app.world, item);
+    assert_eq!(snapshot.get("width"), Some(&PropertyValues::parse("20px").unwrap()), "Should keep resolved values");
+
+    app.world.entity_mut(item).insert(Class::new("other"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(7.0));
+    assert_eq!(style(&app, item).left, Val::Px(3.0));
+
+    restore_styles(&mut app.world, item, &snapshot);
+    assert_eq!(style(&app, item).width, Val::Px(20.0));
+    assert_eq!(style(&app, item).height, Val::Px(5.0));
+    assert_eq!(style(&app, item).left, Style::DEFAULT.left, "Properties applied after the snapshot should be reverted");
+    assert_eq!(snapshot_styles(&app.world, item), snapshot);
+}
+
+#[test]
+fn snapshot_skips_reverted_properties(
+    // no args
+) {
+    use crate::prelude::InlineStyle;
+
+    let (mut app, _panel, item) = create_styled_app(".item { width: 5px; }");
+    app.world.entity_mut(item).insert(InlineStyle::new("left: 3px;"));
+    app.update();
+    assert!(snapshot_styles(&app.world, item).get("left").is_some());
+
+    app.world.entity_mut(item).remove::<InlineStyle>();
+    app.update();
+
+    let snapshot = snapshot_styles(&app.world, item);
+    assert_eq!(snapshot.get("left"), None, "Reverted properties should not be restored");
+    assert_eq!(snapshot.get("width"), Some(&PropertyValues::parse("5px").unwrap()));
+}
+
+#[test]
+fn style_override_keeps_properties_owned_by_user_systems(
+    // no args
+) {
+    use crate::prelude::{BevyCssSet, StyleOverride};
+    use bevy::prelude::{IntoSystemConfigs, Query, Update, With};
+
+    fn grow_width(
+        mut q_styles: Query<&mut Style, With<StyleOverride>>
+    ) {
+        for mut style in q_styles.iter_mut()
+        {
+            style.width = match style.width
+            {
+                Val::Px(width) => Val::Px(width + 1.0),
+                _ => Val::Px(0.0),
+            };
+        }
+    }
+
+    let (mut app, panel, item) = create_styled_app(".item { width: 50px; height: 5px; }");
+    app.add_systems(Update, grow_width.before(BevyCssSet::Apply));
+
+    app.world.entity_mut(item).insert(StyleOverride::new(["width"]));
+    app.world.get_mut::<Style>(item).unwrap().width = Val::Px(0.0);
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0));
+
+    // Styling the whole tree again still leaves `width` alone
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(2.0));
+    assert_eq!(style(&app, item).height, Val::Px(5.0), "Properties not overridden should still be applied");
+
+    app.world.entity_mut(item).remove::<StyleOverride>();
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(50.0), "Sheet should own the property again once removed");
+}
+
+#[test]
+fn unmatched_rules_are_reported(
+    // no args
+) {
+    use crate::prelude::{BevyCssError, StyleDiagnostic};
+    use bevy::ecs::event::ManualEventReader;
+
+    let plugin = BevyCssPlugin::without_hot_reload().with_unmatched_rule_warnings(true);
+    let (mut app, panel, _item) = create_styled_app_with(plugin, ".panel .item { width: 5px; } .panel .missing { width: 1px; } #title { height: 2px; }");
+    app.update();
+
+    let events = app.world.resource::<bevy::prelude::Events<StyleDiagnostic>>();
+    let mut reader = ManualEventReader::<StyleDiagnostic>::default();
+    let diagnostics: Vec<_> = reader.read(events).cloned().collect();
+    assert_eq!(diagnostics, [
+        StyleDiagnostic { entity: panel, error: BevyCssError::UnmatchedRule(".panel .missing".to_string()) },
+        StyleDiagnostic { entity: panel, error: BevyCssError::UnmatchedRule("#title".to_string()) },
+    ]);
+}
+
+#[test]
+fn rule_match_tracking_lists_unmatched_rules(
+    // no args
+) {
+    let content = ".panel .item { width: 5px; } .nonexistent { width: 1px; } .panel .missing { height: 2px; } .panel .item { height: 3px; }";
+    let plugin = BevyCssPlugin::without_hot_reload().with_rule_match_tracking(true);
+    let (mut app, panel, item) = create_styled_app_with(plugin, content);
+    app.update();
+
+    assert_eq!(app.world.resource::<StyleSheetState>().unmatched_rules(), [".nonexistent", ".panel .missing"]);
+
+    // Rules which match once while styling are no longer listed
+    app.world.entity_mut(item).insert(Class::new("item missing"));
+    app.update();
+    assert_eq!(app.world.resource::<StyleSheetState>().unmatched_rules(), [".nonexistent"]);
+
+    app.world.resource_mut::<StyleSheetState>().reset_match_counts();
+    assert!(app.world.resource::<StyleSheetState>().unmatched_rules().is_empty());
+
+    // Rules are evaluated on the whole tree after a reset, so matches aren't missed when only some entities are styled
+    app.world.entity_mut(panel).insert(Class::new("panel other"));
+    app.update();
+    assert_eq!(app.world.resource::<StyleSheetState>().unmatched_rules(), [".nonexistent"]);
+
+    // Matches aren't counted unless enabled
+    let (mut app, _panel, _item) = create_styled_app(content);
+    app.update();
+    assert!(app.world.resource::<StyleSheetState>().unmatched_rules().is_empty());
+}
+
+#[test]
+fn border_shorthand_applies_width_and_color(
+    // no args
+) {
+    use bevy::prelude::BorderColor;
+
+    let (app, _panel, item) = create_styled_app(".item { border: 2px solid red; }");
+
+    assert_eq!(style(&app, item).border, UiRect::all(Val::Px(2.0)));
+    assert_eq!(app.world.get::<BorderColor>(item).unwrap().0, Color::RED);
+}
+
+#[test]
+fn auto_lengths_are_applied(
+    // no args
+) {
+    let (app, _panel, item) = create_styled_app(
+        ".item { width: auto; flex-basis: 10px; margin: 5px; margin-left: auto; margin-right: auto; }"
+    );
+
+    assert_eq!(style(&app, item).width, Val::Auto);
+    assert_eq!(style(&app, item).flex_basis, Val::Px(10.0));
+    assert_eq!(style(&app, item).max_height, Style::DEFAULT.max_height, "flex-basis shouldn't touch other fields");
+
+    // Centering, where sides override the shorthand
+    let margin = style(&app, item).margin;
+    assert_eq!(margin, UiRect::new(Val::Auto, Val::Auto, Val::Px(5.0), Val::Px(5.0)));
+}
+
+#[test]
+fn reduced_motion_is_set_by_plugin(
+    // no args
+) {
+    use crate::interpolate::MotionConfig;
+
+    let (app, _panel, _item) = create_styled_app("");
+    assert!(!app.world.resource::<MotionConfig>().reduced);
+
+    let (app, _panel, _item) = create_styled_app_with(BevyCssPlugin::without_hot_reload().with_reduced_motion(true), "");
+    assert!(app.world.resource::<MotionConfig>().reduced);
+}
+
+#[test]
+fn rules_sharing_a_selector_are_matched_once(
+    // no args
+) {
+    let (app, panel, item) = create_styled_app(
+        ".item { width: 1px; } .panel { width: 5px; } .item { height: 2px; } .item { width: 3px; }"
+    );
+
+    // Panel and item are both styled, each evaluating 2 unique selectors for 4 rules
+    let stats = *app.world.resource::<SelectorMatchStats>();
+    assert_eq!(stats, SelectorMatchStats { rules: 8, evaluated: 4, skipped: 0 });
+
+    assert_eq!(style(&app, panel).width, Val::Px(5.0));
+    assert_eq!(style(&app, item).width, Val::Px(3.0));
+    assert_eq!(style(&app, item).height, Val::Px(2.0));
+}
+
+#[test]
+fn rules_referencing_missing_names_are_skipped(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        ".panel { width: 5px; } .panel .missing { width: 1px; } #gone { height: 1px; } .item { height: 2px; }"
+    );
+
+    // Panel and item are both styled, each skipping the 2 rules which can't match
+    let stats = *app.world.resource::<SelectorMatchStats>();
+    assert_eq!(stats, SelectorMatchStats { rules: 4, evaluated: 4, skipped: 4 });
+    assert_eq!(style(&app, panel).width, Val::Px(5.0));
+    assert_eq!(style(&app, item).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, item).height, Val::Px(2.0));
+
+    app.world.entity_mut(item).insert(Class::new("item missing"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, item).width, Val::Px(1.0), "Should match once an entity has the class");
+
+    app.world.entity_mut(panel).insert(bevy::prelude::Name::new("gone"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+
+    assert_eq!(style(&app, panel).height, Val::Px(1.0), "Should match once an entity has the name");
+}
+
+#[test]
+fn box_sizing_includes_padding_and_border_on_content_box(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(
+        ".panel { box-sizing: content-box; width: 100px; height: 50px; padding: 10px; border: 2px; }
+         .item { box-sizing: border-box; width: 100px; padding: 10px; border: 2px; }"
+    );
+
+    assert_eq!(style(&app, panel).width, Val::Px(124.0));
+    assert_eq!(style(&app, panel).height, Val::Px(74.0));
+    assert_eq!(style(&app, item).width, Val::Px(100.0), "Border box sizes already include padding and border");
+
+    // Padding changed by user code must not be added twice
+    app.world.get_mut::<Style>(panel).unwrap().padding = UiRect::all(Val::Px(0.0));
+    app.update();
+    assert_eq!(style(&app, panel).width, Val::Px(104.0));
+
+    app.update();
+    assert_eq!(style(&app, panel).width, Val::Px(104.0));
+
+    // Sizes declared by user code are adjusted as well
+    app.world.get_mut::<Style>(panel).unwrap().width = Val::Px(50.0);
+    app.update();
+    assert_eq!(style(&app, panel).width, Val::Px(54.0));
+}
+
+#[test]
+fn conflicting_size_constraints_are_kept_as_declared(
+    // no args
+) {
+    let sizes = |style: &Style| [style.width, style.min_width, style.max_width, style.height, style.min_height, style.max_height];
+    let expected = [Val::Px(300.0), Val::Px(200.0), Val::Px(100.0), Val::Percent(10.0), Val::Percent(50.0), Val::Percent(20.0)];
+
+    // Same declarations, on different orders and split over several rules
+    for content in [
+        ".panel { width: 300px; min-width: 200px; max-width: 100px; height: 10%; min-height: 50%; max-height: 20%; }",
+        ".panel { max-height: 20%; min-height: 50%; height: 10%; max-width: 100px; min-width: 200px; width: 300px; }",
+        ".panel { max-width: 100px; min-height: 50%; } .panel { height: 10%; width: 300px; } .panel { max-height: 20%; min-width: 200px; }",
+    ]
+    {
+        let (app, panel, _item) = create_styled_app(content);
+        assert_eq!(sizes(style(&app, panel)), expected, "Should be laid out by bevy, where the minimum wins, on {content}");
+    }
+
+    // Both constraints are adjusted the same way, so their order is kept
+    let (app, panel, _item) = create_styled_app(
+        ".panel { box-sizing: content-box; padding: 10px; width: 300px; min-width: 200px; max-width: 100px; }"
+    );
+    assert_eq!(style(&app, panel).width, Val::Px(320.0));
+    assert_eq!(style(&app, panel).min_width, Val::Px(220.0));
+    assert_eq!(style(&app, panel).max_width, Val::Px(120.0));
+}
+
+#[test]
+fn ignored_subtree_is_never_styled(
+    // no args
+) {
+    let mut app = TestApp::with_css(".root { width: 10px; color: red; } .item { width: 5px; } text { height: 20px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .class("root")
+        .with_child(TestNode::node().class("item").with(crate::prelude::CssIgnore)
+            .with_child(TestNode::text("label").class("item"))
+        )
+        .with_child(TestNode::text("styled").class("item"))
+    );
+    app.step_until_styled();
+
+    assert_eq!(style(&app, tree[0]).width, Val::Px(10.0));
+    assert_eq!(style(&app, tree[1]).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, tree[2]).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, tree[2]).height, Style::DEFAULT.height);
+    assert_eq!(app.world.get::<Text>(tree[2]).unwrap().sections[0].style.color, TextStyle::default().color, "Shouldn't inherit properties");
+
+    assert_eq!(style(&app, tree[3]).width, Val::Px(5.0));
+    assert_eq!(app.world.get::<Text>(tree[3]).unwrap().sections[0].style.color, Color::RED);
+}
+
+#[test]
+fn toggling_css_ignore_reverts_and_restyles(
+    // no args
+) {
+    use crate::prelude::CssIgnore;
+
+    let (mut app, panel, item) = create_styled_app(".panel { width: 10px; } .item { width: 5px; }");
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+
+    app.world.entity_mut(panel).insert(CssIgnore);
+    app.update();
+    assert_eq!(style(&app, panel).width, Style::DEFAULT.width);
+    assert_eq!(style(&app, item).width, Style::DEFAULT.width, "Descendants should be reverted too");
+
+    app.world.entity_mut(panel).remove::<CssIgnore>();
+    app.update();
+    app.update();
+    assert_eq!(style(&app, panel).width, Val::Px(10.0));
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+}
+
+#[test]
+fn font_list_keeps_all_fonts_loaded(
+    // no args
+) {
+    use crate::property::text::FontList;
+    use bevy::prelude::{AssetApp, Font};
+
+    let mut app = TestApp::with_css(r#"text { font: "fonts/main.ttf", "fonts/noto-cjk.ttf"; }"#);
+    app.init_asset::<Font>();
+    let tree = app.spawn_tree(TestNode::node().with_child(TestNode::text("label")));
+    app.step_until_styled();
+
+    let fonts = app.world.get::<FontList>(tree[1]).expect("Should list all fonts");
+    assert_eq!(fonts.len(), 2);
+    assert!(fonts.iter().all(|font| font.is_strong()), "Should keep fonts alive");
+
+    let text = app.world.get::<Text>(tree[1]).unwrap();
+    assert_eq!(Some(&text.sections[0].style.font), fonts.primary());
+}
+
+#[test]
+fn clip_rect_clips_node_and_descendants(
+    // no args
+) {
+    use crate::property::impls::ClipRect;
+    use bevy::{math::Rect, ui::CalculatedClip};
+
+    let (mut app, panel, item) = create_styled_app(".panel { clip: rect(0px, 10px, 20px, 0px); }");
+    app.update();
+
+    assert_eq!(app.world.get::<ClipRect>(panel).unwrap().right, Val::Px(10.0));
+    for entity in [panel, item]
+    {
+        let clip = app.world.get::<CalculatedClip>(entity).expect("Should clip the node and its descendants");
+        assert_eq!(clip.clip, Rect::new(0.0, 0.0, 10.0, 20.0));
+    }
+
+    // `bevy_ui` removes clips of nodes it doesn't clip itself on the next frames
+    for _ in 0..3
+    {
+        app.update();
+        assert!(app.world.get::<CalculatedClip>(item).is_some(), "Should keep clipping on later frames");
+    }
+}
+
+#[test]
+fn clip_rect_is_intersected_once_with_overflow_clip(
+    // no args
+) {
+    use bevy::{ecs::change_detection::DetectChanges, math::Rect, ui::CalculatedClip};
+
+    let (mut app, _panel, item) = create_styled_app(".panel { overflow: clip; clip: rect(0px, 10px, 20px, 0px); }");
+    app.update();
+
+    let clip = app.world.get::<CalculatedClip>(item).expect("Should clip the children");
+    assert_eq!(clip.clip, Rect::new(0.0, 0.0, 0.0, 0.0), "Should intersect with the clip of `bevy_ui`");
+
+    app.update();
+    let last_run = app.world.change_tick();
+    app.update();
+
+    let changed = app.world.entity(item).get_ref::<CalculatedClip>().unwrap().last_changed();
+    assert!(!changed.is_newer_than(last_run, app.world.change_tick()), "Should only write the clip when it differs");
+}
+
+#[test]
+fn added_style_sheet_is_applied(
+    // no args
+) {
+    use crate::AddStyleSheet;
+
+    let mut app = TestApp::with_css("");
+
+    let handle = app.add_style_sheet("embedded.css", ".panel { width: 10px; }");
+    let panel = app.world
+        .spawn((NodeBundle::default(), Class::new("panel"), StyleSheet::new(handle)))
+        .id();
+    app.update();
+
+    assert_eq!(app.world.get::<Style>(panel).unwrap().width, Val::Px(10.0));
+}
+
+#[test]
+fn overflow_scroll_translates_children(
+    // no args
+) {
+    use crate::property::impls::style::ScrollPosition;
+    use bevy::{
+        math::{BVec2, Vec2},
+        prelude::Transform,
+        ui::Overflow,
+    };
+
+    let (mut app, panel, item) = create_styled_app(".panel { overflow: hidden scroll; }");
+    assert_eq!(style(&app, panel).overflow, Overflow::clip(), "Scrolled axes should be clipped");
+
+    let scroll = app.world.get::<ScrollPosition>(panel).expect("Should add the scroll position");
+    assert_eq!(scroll.axes(), BVec2::new(false, true));
+
+    let translation = |app: &TestApp| app.world.get::<Transform>(item).unwrap().translation;
+    let initial = translation(&app);
+
+    // Offsets are applied only once, and only on scrolled axes
+    app.world.get_mut::<ScrollPosition>(panel).unwrap().offset = Vec2::new(5.0, 30.0);
+    app.update();
+    app.update();
+    assert_eq!(translation(&app), initial - Vec2::new(0.0, 30.0).extend(0.0));
+
+    // Without a layout there is no content to scroll
+    let mut scroll = app.world.get_mut::<ScrollPosition>(panel).unwrap();
+    assert_eq!(scroll.max_offset(), Vec2::ZERO);
+    scroll.scroll_by(Vec2::new(0.0, 10.0));
+    assert_eq!(scroll.offset, Vec2::ZERO);
+
+    app.update();
+    assert_eq!(translation(&app), initial);
+
+    // Children are moved back once no longer scrolled
+    app.world.get_mut::<ScrollPosition>(panel).unwrap().offset = Vec2::new(0.0, 12.0);
+    app.update();
+    assert_eq!(translation(&app), initial - Vec2::new(0.0, 12.0).extend(0.0));
+
+    app.world.entity_mut(panel).remove::<ScrollPosition>();
+    app.update();
+    assert_eq!(translation(&app), initial);
+}
+
+#[test]
+fn overflow_scroll_is_laid_out_once(
+    // no args
+) {
+    use crate::property::impls::style::ScrollPosition;
+    use bevy::{ecs::change_detection::DetectChanges, math::Vec2, prelude::Transform};
+
+    let mut app = TestApp::with_css(
+        ".panel { width: 100px; height: 50px; overflow: hidden scroll; } .item { width: 10px; height: 40px; } .inner { height: 200px; }"
+    );
+    add_layout(&mut app);
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node()
+            .class("item")
+            .with_child(TestNode::node().class("inner"))
+        )
+    );
+    let (panel, item) = (tree[0], tree[1]);
+    app.step(2);
+
+    let scroll = app.world.get::<ScrollPosition>(panel).unwrap();
+    assert_eq!(scroll.max_offset(), Vec2::new(0.0, 150.0), "Should include descendants overflowing children");
+
+    let translation = |app: &TestApp| app.world.get::<Transform>(item).unwrap().translation.truncate();
+    let initial = translation(&app);
+
+    app.world.get_mut::<ScrollPosition>(panel).unwrap().offset = Vec2::new(0.0, 30.0);
+    app.update();
+    assert_eq!(translation(&app), initial - Vec2::new(0.0, 30.0));
+
+    let last_run = app.world.change_tick();
+    app.step(2);
+    assert_eq!(translation(&app), initial - Vec2::new(0.0, 30.0), "Should keep the offset once laid out again");
+
+    let changed = app.world.entity(item).get_ref::<Transform>().unwrap().last_changed();
+    assert!(!changed.is_newer_than(last_run, app.world.change_tick()), "Should only translate children when the offset changes");
+
+    app.world.entity_mut(panel).remove::<ScrollPosition>();
+    app.update();
+    assert_eq!(translation(&app), initial);
+}
+
+#[test]
+fn declared_scroll_offset_is_applied_only_when_changed(
+    // no args
+) {
+    use crate::property::impls::style::{ScrollPosition, StyledScrollOffset};
+    use bevy::math::Vec2;
+
+    let (mut app, panel, _item) = create_styled_app(
+        ".panel { overflow: hidden scroll; } .panel.top { scroll-top: 0px; } .panel.bottom { scroll-top: 100%; }"
+    );
+    let offset = |app: &TestApp| app.world.get::<ScrollPosition>(panel).unwrap().offset;
+    let user_scroll = |app: &mut TestApp| app.world.get_mut::<ScrollPosition>(panel).unwrap().offset = Vec2::new(0.0, 30.0);
+
+    user_scroll(&mut app);
+    app.update();
+    assert_eq!(offset(&app), Vec2::new(0.0, 30.0));
+
+    app.world.entity_mut(panel).insert(Class::new("panel top"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(app.world.get::<StyledScrollOffset>(panel).unwrap().top, Some(Val::Px(0.0)));
+    assert_eq!(offset(&app), Vec2::ZERO, "Should jump back to the top");
+
+    // Styling the entity again with the same offset doesn't fight user scrolling
+    user_scroll(&mut app);
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(offset(&app), Vec2::new(0.0, 30.0));
+
+    // Without a layout there is no content to scroll, so the bottom is at zero too
+    app.world.entity_mut(panel).insert(Class::new("panel bottom"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(app.world.get::<StyledScrollOffset>(panel).unwrap().top, Some(Val::Percent(100.0)));
+    assert_eq!(offset(&app), Vec2::ZERO, "Should jump once the offset changes");
+
+    // Once no rule declares the offset, adding the class back jumps again
+    app.world.entity_mut(panel).insert(Class::new("panel"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(app.world.get::<StyledScrollOffset>(panel).unwrap().top, None, "Should forget the unmatched offset");
+
+    user_scroll(&mut app);
+    app.world.entity_mut(panel).insert(Class::new("panel bottom"));
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.update();
+    assert_eq!(offset(&app), Vec2::ZERO, "Should jump again once the class is added back");
+}
+
+#[test]
+fn overflow_clip_margin_extends_children_clip(
+    // no args
+) {
+    use bevy::{math::Rect, ui::CalculatedClip};
+
+    let (mut app, panel, item) = create_styled_app(".panel { overflow: clip; overflow-clip-margin: 5px; }");
+    app.update();
+
+    let clip = app.world.get::<CalculatedClip>(item).expect("Should clip the children");
+    assert_eq!(clip.clip, Rect::new(-5.0, -5.0, 5.0, 5.0));
+    assert!(app.world.get::<CalculatedClip>(panel).is_none(), "Should only clip the children");
+
+    for _ in 0..2
+    {
+        app.update();
+        let clip = app.world.get::<CalculatedClip>(item).expect("Should keep clipping on later frames");
+        assert_eq!(clip.clip, Rect::new(-5.0, -5.0, 5.0, 5.0), "Should keep the margin on later frames");
+    }
+}
+
+#[test]
+fn reapplying_identical_values_keeps_components_unchanged(
+    // no args
+) {
+    use crate::prelude::StyleApplied;
+    use bevy::{ecs::{change_detection::DetectChanges, event::ManualEventReader}, prelude::Events};
+
+    let (mut app, panel, item) = create_styled_app_with(
+        BevyCssPlugin::with_hot_reload(),
+        ".panel { width: 10px; overflow: hidden; } .item { height: 4px; margin: 2px; background-color: red; }"
+    );
+    app.update();
+
+    let last_changed = |app: &TestApp, entity| {
+        let entity = app.world.entity(entity);
+        (entity.get_ref::<Style>().unwrap().last_changed(), entity.get_ref::<BackgroundColor>().unwrap().last_changed())
+    };
+    let (panel_ticks, item_ticks) = (last_changed(&app, panel), last_changed(&app, item));
+
+    // Reordered rules apply the whole sheet again, but with the same values
+    let handle = app.world.get::<StyleSheet>(panel).unwrap().handle().clone();
+    *app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&handle).unwrap() =
+        StyleSheetAsset::parse("test.css", ".item { height: 4px; margin: 2px; background-color: red; } .panel { width: 10px; overflow: hidden; }");
+
+    let mut reader = ManualEventReader::<StyleApplied>::default();
+    reader.read(app.world.resource::<Events<StyleApplied>>()).for_each(drop);
+    for _ in 0..3
+    {
+        app.update();
+    }
+
+    assert!(reader.read(app.world.resource::<Events<StyleApplied>>()).any(|event| event.entity == item), "Sheet should be applied again");
+    assert_eq!(last_changed(&app, panel), panel_ticks, "Identical values shouldn't be written again");
+    assert_eq!(last_changed(&app, item), item_ticks, "Identical values shouldn't be written again");
+}
+
+#[test]
+fn when_rules_follow_their_condition(
+    // no args
+) {
+    use crate::RegisterCssCondition;
+    use bevy::prelude::Resource;
+
+    #[derive(Resource)]
+    struct InMenu(bool);
+
+    let (mut app, _panel, item) = create_styled_app(r#".item { width: 1px; } @when state("menu") { .item { width: 2px; height: 3px; } }"#);
+    app.insert_resource(InMenu(false))
+        .register_css_condition("menu", |world| world.resource::<InMenu>().0);
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0));
+
+    app.world.resource_mut::<InMenu>().0 = true;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(2.0), "Should apply rules once the condition holds");
+    assert_eq!(style(&app, item).height, Val::Px(3.0));
+
+    app.world.resource_mut::<InMenu>().0 = false;
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0), "Should apply other rules again once the condition no longer holds");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height, "Should revert properties declared only by conditional rules");
+}
+
+#[test]
+fn when_flag_rules_follow_their_flag(
+    // no args
+) {
+    use crate::prelude::CssFlags;
+
+    let (mut app, _panel, item) = create_styled_app(
+        ".item { width: 1px; } @when experiment-b { .item { width: 2px; height: 3px; } } @when unknown { .item { width: 4px; } }"
+    );
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0), "Unknown flags should be disabled");
+
+    app.world.resource_mut::<CssFlags>().enable("experiment-b");
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(2.0), "Should apply rules once the flag is enabled");
+    assert_eq!(style(&app, item).height, Val::Px(3.0));
+
+    app.world.resource_mut::<CssFlags>().disable("experiment-b");
+    app.update();
+    assert_eq!(style(&app, item).width, Val::Px(1.0), "Should apply other rules again once the flag is disabled");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height, "Should revert properties declared only by flagged rules");
+}
+
+#[test]
+fn supports_rules_require_their_capability(
+    // no args
+) {
+    let (app, panel, item) = create_styled_app(
+        ".panel { width: 1px; } .item { width: 1px; }
+        @supports (grid) { .panel { width: 2px; } }
+        @supports (masonry) { .item { width: 3px; height: 3px; } }
+        @supports (text-transform) { @supports (masonry) { .panel { height: 4px; } } }"
+    );
+
+    assert_eq!(style(&app, panel).width, Val::Px(2.0), "Should apply rules of supported capabilities");
+    assert_eq!(style(&app, item).width, Val::Px(1.0), "Should skip rules of unsupported capabilities");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height);
+    assert_eq!(style(&app, panel).height, Style::DEFAULT.height, "Nested blocks should require all capabilities");
+    assert!(app.world.resource::<CssCapabilities>().supports("text-transform"), "Registered properties should be capabilities");
+    assert!(!app.world.resource::<CssCapabilities>().supports("image-slice"), "Image slicing should only be parsed");
+}
+
+#[test]
+fn background_gradient_images_are_shared_and_swapped(
+    // no args
+) {
+    use crate::property::impls::LinearGradient;
+    use bevy::prelude::{AssetApp, Assets, Image, UiImage};
+
+    let gradient = "linear-gradient(to bottom, #202030, #101018)";
+    let css = format!(".panel {{ background: {gradient}; }} .item {{ background: {gradient}; }}");
+    let mut app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(), &css);
+    app.init_asset::<Image>();
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+    );
+    app.update();
+    let (panel, item) = (tree[0], tree[1]);
+
+    let texture = |app: &TestApp, entity| app.world.get::<UiImage>(entity).expect("Should assign the gradient image").texture.clone();
+    assert_eq!(texture(&app, panel), texture(&app, item), "Identical gradients should share the same image");
+    assert_eq!(app.world.get::<BackgroundColor>(panel).unwrap().0, Color::WHITE, "Image shouldn't be tinted");
+
+    let image = app.world.resource::<Assets<Image>>().get(texture(&app, panel)).unwrap();
+    assert_eq!((image.width(), image.height()), (1, 64), "Vertical gradients should need a single column");
+
+    // Hot reloading to another gradient swaps the image
+    let previous = texture(&app, panel);
+    let handle = app.world.get::<StyleSheet>(panel).unwrap().handle().clone();
+    *app.world.resource_mut::<Assets<StyleSheetAsset>>().get_mut(&handle).unwrap() =
+        StyleSheetAsset::parse("test.css", ".panel { background: linear-gradient(90deg, red, blue); } .item { background: red; }");
+    for _ in 0..3
+    {
+        app.update();
+    }
+
+    assert_ne!(texture(&app, panel), previous, "Should generate an image for the new gradient");
+    assert_eq!(app.world.get::<LinearGradient>(panel).unwrap().angle, 90.0);
+    assert!(app.world.get::<LinearGradient>(item).is_none(), "Flat colors should remove the gradient");
+    assert_eq!(texture(&app, item), Default::default(), "Should restore the default image");
+    assert_eq!(app.world.get::<BackgroundColor>(item).unwrap().0, Color::RED);
+}
+
+#[test]
+fn display_none_hides_and_restores_subtree(
+    // no args
+) {
+    use crate::property::impls::style::HiddenByDisplay;
+    use bevy::{prelude::{Display, Visibility}, ui::FocusPolicy};
+
+    let plugin = BevyCssPlugin::with_hot_reload().with_display_none_hides_subtree(true);
+    let mut app = TestApp::with_plugin(plugin, ".panel { display: none; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item").with((Visibility::Visible, FocusPolicy::Block)))
+    );
+    app.update();
+    let (panel, item) = (tree[0], tree[1]);
+
+    for entity in [panel, item]
+    {
+        assert_eq!(app.world.get::<Visibility>(entity), Some(&Visibility::Hidden));
+        assert_eq!(app.world.get::<FocusPolicy>(entity), Some(&FocusPolicy::Pass));
+    }
+
+    app.set_css(".panel { display: flex; }");
+    app.step(3);
+
+    assert_eq!(app.world.get::<Visibility>(panel), Some(&Visibility::Inherited), "Should restore previous visibility");
+    assert_eq!(app.world.get::<Visibility>(item), Some(&Visibility::Visible));
+    assert_eq!(app.world.get::<FocusPolicy>(item), Some(&FocusPolicy::Block), "Should restore previous focus policy");
+    assert!(app.world.get::<HiddenByDisplay>(item).is_none());
+
+    // Disabled by default, so only the layout changes
+    let (app, panel, _item) = create_styled_app(".panel { display: none; }");
+    assert_eq!(style(&app, panel).display, Display::None);
+    assert_eq!(app.world.get::<Visibility>(panel), Some(&Visibility::Inherited));
+}
+
+#[test]
+fn display_none_hides_added_children_and_already_hidden_nodes(
+    // no args
+) {
+    use bevy::prelude::{BuildWorldChildren, Display, Visibility};
+
+    let plugin = BevyCssPlugin::with_hot_reload().with_display_none_hides_subtree(true);
+    let mut app = TestApp::with_plugin(plugin, ".panel { display: none; }");
+    let panel = app.spawn_tree(TestNode::with_bundle(NodeBundle { style: Style { display: Display::None, ..Default::default() }, ..Default::default() })
+        .class("panel")
+    )[0];
+    app.step_until_styled();
+    assert_eq!(app.world.get::<Visibility>(panel), Some(&Visibility::Hidden), "Should hide nodes without display already");
+
+    let item = app.world.spawn(NodeBundle::default()).id();
+    app.world.entity_mut(panel).add_child(item);
+    app.step(2);
+    assert_eq!(app.world.get::<Visibility>(item), Some(&Visibility::Hidden), "Should hide children added later");
+
+    app.set_css(".panel { width: 1px; }");
+    app.step(3);
+    assert_eq!(style(&app, panel).display, Display::None, "Should restore the previous display");
+    for entity in [panel, item]
+    {
+        assert_eq!(app.world.get::<Visibility>(entity), Some(&Visibility::Inherited), "Should show the subtree again");
+    }
+}
+
+#[test]
+fn reapplying_changed_rule_keeps_identical_components_unchanged(
+    // no args
+) {
+    use crate::property::text::TextTransformSource;
+    use bevy::ecs::change_detection::DetectChanges;
+
+    let mut app = TestApp::with_plugin(
+        BevyCssPlugin::with_hot_reload(),
+        ".panel { width: 10px; padding: 2px; } .panel text { color: red; text-transform: uppercase; }"
+    );
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::text("title"))
+    );
+    app.step_until_styled();
+    app.update();
+
+    let last_changed = |app: &TestApp| {
+        let (panel, text) = (app.world.entity(tree[0]), app.world.entity(tree[1]));
+        (
+            panel.get_ref::<Style>().unwrap().last_changed(),
+            text.get_ref::<Text>().unwrap().last_changed(),
+            text.get_ref::<TextTransformSource>().unwrap().last_changed(),
+        )
+    };
+    let ticks = last_changed(&app);
+
+    // Both rules changed, so they are applied again along with their unchanged declarations
+    app.set_css(".panel { width: 10px; padding: 2px; height: auto; } .panel text { color: red; text-transform: uppercase; height: auto; }");
+    app.step_until_styled();
+    app.update();
+
+    app.expect_component::<Text>(tree[1], |text| assert_eq!(text.sections[0].value, "TITLE"));
+    assert_eq!(last_changed(&app), ticks, "Identical values shouldn't mark components as changed");
+}
+
+#[test]
+fn entities_are_applied_parents_first(
+    // no args
+) {
+    use crate::{prelude::BevyCssError, property::{Property, PropertyContext}, RegisterProperty};
+    use bevy::{
+        ecs::query::QueryItem,
+        prelude::{Commands, Name, Node, Resource, With, World},
+    };
+
+    /// Entities on the order `order-probe` was applied on them.
+    #[derive(Default, Resource)]
+    struct ApplyOrder(Vec<Entity>);
+
+    #[derive(Default)]
+    struct OrderProbeProperty;
+
+    impl Property
+    for OrderProbeProperty
+    {
+        type Cache = ();
+        type Components = Entity;
+        type Filters = With<Node>;
+        type Param = ();
+
+        fn name(
+            // no args
+        ) -> &'static str {
+            "order-probe"
+        }
+
+        fn parse<'a>(
+            _values: &PropertyValues
+        ) -> Result<Self::Cache, BevyCssError> {
+            Ok(())
+        }
+
+        fn apply<'w>(
+            _cache: &Self::Cache,
+            entity: QueryItem<Self::Components>,
+            _context: &PropertyContext<Self>,
+            commands: &mut Commands,
+        ) {
+            commands.add(move |world: &mut World| world.resource_mut::<ApplyOrder>().0.push(entity));
+        }
+    }
+
+    let mut app = TestApp::with_css(
+        ".panel { order-probe: on; direction: rtl; }
+        .item { order-probe: on; direction: inherit; margin-inline-end: 5px; }
+        .leaf { order-probe: on; }"
+    );
+    app.init_resource::<ApplyOrder>();
+    app.register_property::<OrderProbeProperty>();
+
+    // Descendants are spawned first, and the panel on another archetype, so it's stored after them
+    let sheet = StyleSheet::new(app.sheet().clone());
+    let leaf = app.world
+        .spawn((NodeBundle::default(), Class::new("leaf")))
+        .id();
+    let item = app.world
+        .spawn((NodeBundle::default(), Class::new("item")))
+        .add_child(leaf)
+        .id();
+    let panel = app.world
+        .spawn((NodeBundle::default(), Class::new("panel"), Name::new("panel"), sheet))
+        .add_child(item)
+        .id();
+    app.step_until_styled();
+
+    assert_eq!(app.world.resource::<ApplyOrder>().0, [panel, item, leaf], "Parents should be applied before their children");
+    assert_eq!(style(&app, item).margin.left, Val::Px(5.0), "Should use the direction of the parent");
+}
+
+#[test]
+fn focus_policy_is_applied_and_restored(
+    // no args
+) {
+    use crate::property::impls::PreviousFocusPolicy;
+    use bevy::{prelude::ButtonBundle, ui::FocusPolicy};
+
+    let mut app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(), ".overlay { focus-policy: block; } .button { focus-policy: pass; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .class("overlay")
+        .with_child(TestNode::with_bundle(ButtonBundle::default()).class("button"))
+    );
+    app.step_until_styled();
+    let (overlay, button) = (tree[0], tree[1]);
+
+    assert_eq!(app.world.get::<FocusPolicy>(overlay), Some(&FocusPolicy::Block));
+    assert_eq!(app.world.get::<FocusPolicy>(button), Some(&FocusPolicy::Pass));
+
+    app.set_css(".overlay { width: 1px; }");
+    app.step_until_styled();
+
+    assert_eq!(app.world.get::<FocusPolicy>(overlay), Some(&FocusPolicy::Pass), "Should restore the node default");
+    assert_eq!(app.world.get::<FocusPolicy>(button), Some(&FocusPolicy::Block), "Should restore the button default");
+    assert!(app.world.get::<PreviousFocusPolicy>(button).is_none());
+}
+
+#[test]
+fn descendants_match_through_non_ui_entities(
+    // no args
+) {
+    use bevy::prelude::SpatialBundle;
+
+    let css = "#root .item { width: 10px; } #root > .item { height: 5px; }";
+    let spawn = |app: &mut TestApp| app.spawn_tree(TestNode::node()
+        .name("root")
+        .with_child(TestNode::with_bundle(SpatialBundle::default())
+            .with_child(TestNode::node().class("item"))
+        )
+    );
+
+    let mut app = TestApp::with_plugin(BevyCssPlugin::without_hot_reload().with_non_ui_descendants(true), css);
+    let item = spawn(&mut app)[2];
+    app.step_until_styled();
+
+    assert_eq!(style(&app, item).width, Val::Px(10.0), "Should skip the entity without a node");
+    assert_eq!(style(&app, item).height, Style::DEFAULT.height, "Should only match the direct parent");
+
+    // Disabled by default, so descendants stop at the entity without a node
+    let mut app = TestApp::with_css(css);
+    let item = spawn(&mut app)[2];
+    app.step(3);
+
+    assert_eq!(style(&app, item).width, Style::DEFAULT.width);
+}
+
+#[test]
+fn property_param_reads_user_resources(
+    // no args
+) {
+    use crate::{prelude::{BevyCssError, PropertyContext}, property::{Property, PropertyToken}, RegisterProperty};
+    use bevy::{
+        ecs::query::QueryItem,
+        prelude::{Commands, Res, Resource},
+        utils::HashMap,
+    };
+
+    #[derive(Resource)]
+    struct Palette(HashMap<String, Color>);
+
+    /// Color applied by `background-color`, either a color or the name of a color of the palette.
+    #[derive(Default)]
+    enum PaletteColor
+    {
+        #[default]
+        None,
+        Color(Color),
+        Palette(String),
+    }
+
+    #[derive(Default)]
+    struct PaletteBackgroundColorProperty;
+
+    impl Property
+    for PaletteBackgroundColorProperty
+    {
+        type Cache = PaletteColor;
+        type Components = &'static mut BackgroundColor;
+        type Filters = ();
+        type Param = Res<'static, Palette>;
+
+        fn name(
+            // no args
+        ) -> &'static str {
+            "background-color"
+        }
+
+        fn parse<'a>(
+            values: &PropertyValues
+        ) -> Result<Self::Cache, BevyCssError> {
+            match values.as_slice()
+            {
+                [PropertyToken::Function(name, args)] if name == "palette" => args.first()
+                    .and_then(|arg| arg.identifier())
+                    .map(|name| PaletteColor::Palette(name.to_string()))
+                    .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+                _ => values.color()
+                    .map(PaletteColor::Color)
+                    .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut background: QueryItem<Self::Components>,
+            palette: &PropertyContext<Self>,
+            _commands: &mut Commands,
+        ) {
+            let color = match cache
+            {
+                PaletteColor::Color(color) => Some(color),
+                PaletteColor::Palette(name) => palette.0.get(name),
+                PaletteColor::None => None,
+            };
+
+            if let Some(color) = color
+            {
+                background.0 = *color;
+            }
+        }
+    }
+
+    // Replaces the built-in `background-color`, which doesn't know about the palette
+    let mut app = TestApp::with_css(".panel { background-color: palette(primary); } .item { background-color: red; }");
+    app.insert_resource(Palette(HashMap::from([("primary".to_string(), Color::ORANGE)])));
+    app.register_property::<PaletteBackgroundColorProperty>();
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+    );
+    let (panel, item) = (tree[0], tree[1]);
+    app.step_until_styled();
+
+    assert_eq!(app.world.get::<BackgroundColor>(panel).unwrap().0, Color::ORANGE);
+    assert_eq!(app.world.get::<BackgroundColor>(item).unwrap().0, Color::RED);
+}
+
+#[test]
+fn composite_property_writes_several_components(
+    // no args
+) {
+    use crate::{prelude::{BevyCssError, PropertyContext}, property::Property, RegisterProperty};
+    use bevy::{
+        ecs::query::QueryItem,
+        prelude::{BorderColor, Commands},
+    };
+
+    /// Colors applied by `theme` on each component.
+    #[derive(Default)]
+    struct Theme
+    {
+        background: Color,
+        border: Color,
+        text: Color,
+    }
+
+    #[derive(Default)]
+    struct ThemeProperty;
+
+    impl Property
+    for ThemeProperty
+    {
+        type Cache = Theme;
+        type Components = (&'static mut BackgroundColor, Option<&'static mut BorderColor>, Option<&'static mut Text>);
+        type Filters = ();
+        type Param = ();
+
+        fn name(
+            // no args
+        ) -> &'static str {
+            "theme"
+        }
+
+        fn parse<'a>(
+            values: &PropertyValues
+        ) -> Result<Self::Cache, BevyCssError> {
+            match values.keyword(&["dark", "light"])
+            {
+                Some("dark") => Ok(Theme { background: Color::BLACK, border: Color::GRAY, text: Color::WHITE }),
+                Some(_) => Ok(Theme { background: Color::WHITE, border: Color::SILVER, text: Color::BLACK }),
+                None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            (mut background, border, text): QueryItem<Self::Components>,
+            _context: &PropertyContext<Self>,
+            _commands: &mut Commands,
+        ) {
+            background.0 = cache.background;
+            if let Some(mut border) = border
+            {
+                border.0 = cache.border;
+            }
+            if let Some(mut text) = text
+            {
+                text.sections.iter_mut().for_each(|section| section.style.color = cache.text);
+            }
+        }
+
+        fn reset<'w>(
+            components: QueryItem<Self::Components>,
+            context: &PropertyContext<Self>,
+            commands: &mut Commands,
+        ) {
+            let default = Theme { background: BackgroundColor::DEFAULT.0, border: BorderColor::DEFAULT.0, text: TextStyle::default().color };
+            Self::apply(&default, components, context, commands);
+        }
+    }
+
+    let mut app = TestApp::with_css(".card { theme: dark; } .card.light { theme: light; }");
+    app.register_property::<ThemeProperty>();
+    let card = app.spawn_tree(TestNode::text("title").class("card").with(BorderColor(Color::NONE)))[0];
+    app.step_until_styled();
+
+    let colors = |app: &TestApp| (
+        app.world.get::<BackgroundColor>(card).unwrap().0,
+        app.world.get::<BorderColor>(card).unwrap().0,
+        app.world.get::<Text>(card).unwrap().sections[0].style.color,
+    );
+    assert_eq!(colors(&app), (Color::BLACK, Color::GRAY, Color::WHITE));
+
+    *app.world.get_mut::<Class>(card).unwrap() = Class::new("card light");
+    app.step(2);
+    assert_eq!(colors(&app), (Color::WHITE, Color::SILVER, Color::BLACK), "Should apply the winning declaration on all components");
+
+    clear_styles(&mut app.world, card);
+    assert_eq!(colors(&app), (BackgroundColor::DEFAULT.0, BorderColor::DEFAULT.0, TextStyle::default().color), "Should reset all components");
+}
+
+#[test]
+fn background_image_is_loaded_and_reset(
+    // no args
+) {
+    use bevy::prelude::{AssetApp, Handle, Image, UiImage};
+
+    let mut app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(), r#".icon { background-image: "icons/star.png"; } .plain { background-image: none; }"#);
+    app.init_asset::<Image>();
+    let user_image = Handle::weak_from_u128(7);
+    let tree = app.spawn_tree(TestNode::node()
+        .class("icon")
+        .with_child(TestNode::node().class("plain").with(UiImage::new(user_image.clone())))
+    );
+    let (icon, plain) = (tree[0], tree[1]);
+    app.step_until_styled();
+    assert_eq!(app.world.get::<UiImage>(plain).unwrap().texture, user_image, "Should keep images not set by style sheets");
+
+    let texture = |app: &TestApp| app.world.get::<UiImage>(icon).expect("Should insert the missing image").texture.clone();
+    assert_eq!(texture(&app).path().map(|path| path.to_string()).as_deref(), Some("icons/star.png"));
+
+    app.set_css(".icon { width: 1px; }");
+    app.step_until_styled();
+
+    assert_eq!(texture(&app), Handle::default(), "Should restore the default texture");
+}
+
+#[test]
+fn background_shorthand_is_cascaded_with_longhands(
+    // no args
+) {
+    use crate::property::impls::LinearGradient;
+    use bevy::prelude::{AssetApp, Handle, Image, UiImage};
+
+    let mut app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(),
+        ".panel { background-color: red; } .panel.fancy { background: linear-gradient(red, blue); } .item { background: blue; }"
+    );
+    app.init_asset::<Image>();
+    let user_image = Handle::weak_from_u128(7);
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel fancy")
+        .with_child(TestNode::node().class("item").with(UiImage::new(user_image.clone())))
+    );
+    app.step_until_styled();
+    let (panel, item) = (tree[0], tree[1]);
+
+    let background = |app: &TestApp, entity| app.world.get::<BackgroundColor>(entity).unwrap().0;
+    assert_eq!(background(&app, panel), Color::WHITE, "The shorthand of the more specific rule should win");
+    assert!(app.world.get::<LinearGradient>(panel).is_some());
+
+    assert_eq!(background(&app, item), Color::BLUE);
+    assert_eq!(app.world.get::<UiImage>(item).unwrap().texture, user_image, "Should keep images not set by style sheets");
+
+    app.set_css(".panel { width: 1px; } .item { width: 1px; }");
+    app.step_until_styled();
+    app.update();
+
+    assert!(app.world.get::<LinearGradient>(panel).is_none(), "Should remove the gradient once reverted");
+    assert_eq!(app.world.get::<UiImage>(item).unwrap().texture, user_image, "Should keep images not set by style sheets");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn display_none_on_hover_restores_previous_display(
+    // no args
+) {
+    use bevy::prelude::{Display, Interaction};
+
+    // Flex is the Bevy default, so a grid node tells the previous display apart from it
+    let mut app = TestApp::with_css(".item:hover { display: none; }");
+    let item = app.spawn_tree(TestNode::with_bundle(NodeBundle { style: Style { display: Display::Grid, ..Default::default() }, ..Default::default() })
+        .class("item")
+        .with(Interaction::Hovered)
+    )[0];
+    app.step_until_styled();
+    assert_eq!(style(&app, item).display, Display::None);
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::None;
+    app.step(2);
+    assert_eq!(style(&app, item).display, Display::Grid, "Should restore the display before hiding");
+
+    // Hiding again keeps the restored display, not the hidden one
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::Hovered;
+    app.step(2);
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::None;
+    app.step(2);
+    assert_eq!(style(&app, item).display, Display::Grid);
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn text_shadow_follows_text_and_is_despawned_once_unmatched(
+    // no args
+) {
+    use crate::property::text::{StyledTextShadow, TextShadowOf};
+    use bevy::prelude::{Children, Display, Interaction, Transform, Vec2};
+
+    let mut app = TestApp::with_css(".title:hover { text-shadow: 1px 2px #000; } .title { color: red; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::text("hello").class("title").with(Interaction::Hovered))
+    );
+    let (panel, title) = (tree[0], tree[1]);
+    app.step_until_styled();
+
+    let shadow = app.world.get::<StyledTextShadow>(title).expect("Should spawn a shadow").entity();
+    assert_eq!(app.world.get::<TextShadowOf>(shadow), Some(&TextShadowOf(title)));
+    assert_eq!(&**app.world.get::<Children>(panel).unwrap(), &[shadow, title], "Should be drawn behind the text");
+    app.expect_component::<Text>(shadow, |text| {
+        assert_eq!(text.sections[0].value, "hello");
+        assert_eq!(text.sections[0].style.color, Color::BLACK, "Should keep the shadow color");
+    });
+    app.expect_component::<Transform>(shadow, |transform| assert_eq!(transform.translation.truncate(), Vec2::new(1.0, 2.0)));
+
+    app.world.get_mut::<Text>(title).unwrap().sections[0].value = "bye".to_string();
+    app.step(1);
+    app.expect_component::<Text>(shadow, |text| assert_eq!(text.sections[0].value, "bye", "Should follow the text"));
+
+    app.world.get_mut::<Style>(title).unwrap().display = Display::None;
+    app.step(1);
+    app.expect_component::<Style>(shadow, |style| assert_eq!(style.display, Display::None, "Should be hidden along with the text"));
+    app.world.get_mut::<Style>(title).unwrap().display = Display::Flex;
+    app.step(1);
+    app.expect_component::<Style>(shadow, |style| assert_eq!(style.display, Display::Flex));
+    app.expect_component::<Transform>(shadow, |transform| assert_eq!(transform.translation.truncate(), Vec2::new(1.0, 2.0), "Should stay over the text"));
+
+    *app.world.get_mut::<Interaction>(title).unwrap() = Interaction::None;
+    app.step(2);
+    assert!(app.world.get_entity(shadow).is_none(), "Should despawn the shadow");
+    assert!(app.world.get::<StyledTextShadow>(title).is_none());
+    assert_eq!(&**app.world.get::<Children>(panel).unwrap(), &[title]);
+}
+
+#[test]
+fn style_changed_is_sent_when_class_change_restyles(
+    // no args
+) {
+    use crate::prelude::StyleChanged;
+    use bevy::{ecs::event::ManualEventReader, prelude::Events};
+
+    let (mut app, panel, item) = create_styled_app(".item { width: 5px; height: 1px; } .wide { width: 50px; height: 1px; }");
+    app.update();
+
+    let mut reader = ManualEventReader::<StyleChanged>::default();
+    let mut changed = |app: &TestApp| -> Vec<StyleChanged> {
+        reader.read(app.world.resource::<Events<StyleChanged>>()).cloned().collect()
+    };
+    changed(&app);
+
+    *app.world.get_mut::<Class>(item).unwrap() = Class::new("wide");
+    app.step(2);
+    let events = changed(&app);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, item);
+    assert_eq!(events[0].changed_properties, vec!["width".into()], "Only properties with another value should be listed");
+    assert_eq!(style(&app, item).width, Val::Px(50.0));
+
+    // Styling again with the same values changes nothing
+    app.world.get_mut::<StyleSheet>(panel).unwrap().refresh();
+    app.step(2);
+    assert!(changed(&app).is_empty());
+
+    // Reverted properties are reported too
+    clear_styles(&mut app.world, item);
+    app.step(1);
+    let events = changed(&app);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, item);
+    assert_eq!(events[0].changed_properties, vec!["height".into(), "width".into()]);
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn style_changed_is_sent_when_pseudo_class_rules_are_reverted(
+    // no args
+) {
+    use crate::prelude::StyleChanged;
+    use bevy::{ecs::event::ManualEventReader, prelude::{Events, Interaction}};
+
+    let mut app = TestApp::with_css(".item:hover { width: 5px; }");
+    let item = app.spawn_tree(TestNode::node().class("item").with(Interaction::Hovered))[0];
+    app.step_until_styled();
+    assert_eq!(style(&app, item).width, Val::Px(5.0));
+
+    let mut reader = ManualEventReader::<StyleChanged>::default();
+    reader.read(app.world.resource::<Events<StyleChanged>>()).for_each(drop);
+
+    *app.world.get_mut::<Interaction>(item).unwrap() = Interaction::None;
+    app.step(2);
+    let events: Vec<StyleChanged> = reader.read(app.world.resource::<Events<StyleChanged>>()).cloned().collect();
+    assert_eq!(events, [StyleChanged { entity: item, changed_properties: vec!["width".into()] }]);
+    assert_eq!(style(&app, item).width, Style::DEFAULT.width);
+}
+
+#[test]
+fn iter_pending_lists_entities_with_an_unregistered_property(
+    // no args
+) {
+    use crate::prelude::BevyCssSet;
+    use crate::property::StyleSheetState;
+    use bevy::prelude::{IntoSystemConfigs, Res, ResMut, Resource, Update};
+
+    #[derive(Default, Resource)]
+    struct Pending(Vec<(Entity, String, PropertyValues)>);
+
+    fn read_pending(
+        state: Res<StyleSheetState>,
+        mut pending: ResMut<Pending>
+    ) {
+        pending.0.extend(state.iter_pending("outline-glow")
+            .map(|(entity, selector, values)| (entity, selector.to_string(), values.clone()))
+        );
+    }
+
+    let mut app = TestApp::with_css(".panel { font-size: 10px; outline-glow: 2em; } .item { outline-glow: 3px; } .other { width: 1px; }");
+    app.init_resource::<Pending>()
+        .add_systems(Update, read_pending.in_set(BevyCssSet::Apply));
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+        .with_child(TestNode::node().class("other"))
+    );
+    app.step_until_styled();
+
+    let pending = std::mem::take(&mut app.world.resource_mut::<Pending>().0);
+    assert_eq!(pending, [
+        (tree[0], ".panel".to_string(), PropertyValues::parse("20px").unwrap()),
+        (tree[1], ".item".to_string(), PropertyValues::parse("3px").unwrap()),
+    ], "Should list parents first, with resolved values");
+
+    app.step(2);
+    assert!(app.world.resource::<Pending>().0.is_empty(), "Unchanged entities shouldn't be pending again");
+}
+
+#[test]
+fn entities_despawned_after_prepare_are_skipped(
+    // no args
+) {
+    use crate::prelude::BevyCssSet;
+    use crate::property::{StyleSheetState, text::TextTransformSource};
+    use bevy::prelude::{
+        BorderColor, Commands, DespawnRecursiveExt, Display, IntoSystemConfigs, PreUpdate, ResMut, Resource, TextBundle, Update, World,
+    };
+
+    #[derive(Default, Resource)]
+    struct Despawn
+    {
+        now: Vec<Entity>,
+        deferred: Vec<Entity>,
+    }
+
+    // Like gameplay code despawning right after entities were selected
+    fn despawn_now(
+        world: &mut World
+    ) {
+        if world.resource::<StyleSheetState>().computed.is_empty()
+        {
+            return;
+        }
+
+        for entity in std::mem::take(&mut world.resource_mut::<Despawn>().now)
+        {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+
+    // Commands are applied before the ones queued by properties
+    fn despawn_deferred(
+        state: bevy::prelude::Res<StyleSheetState>,
+        mut despawn: ResMut<Despawn>,
+        mut commands: Commands
+    ) {
+        if state.computed.is_empty()
+        {
+            return;
+        }
+
+        for entity in despawn.deferred.drain(..)
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    // Border and display insert components with commands, which must not panic on despawned entities either
+    let mut app = TestApp::with_css(".item { width: 5px; text-transform: uppercase; border: 2px solid red; display: none; }");
+    app.init_resource::<Despawn>()
+        .add_systems(PreUpdate, despawn_now.after(BevyCssSet::Prepare))
+        .add_systems(Update, despawn_deferred.in_set(BevyCssSet::PreApply));
+
+    let mut root = TestNode::node();
+    for _ in 0..6
+    {
+        root = root.with_child(TestNode::text("item").class("item"));
+    }
+    let tree = app.spawn_tree(root);
+    let items = &tree[1..];
+
+    let mut despawn = app.world.resource_mut::<Despawn>();
+    despawn.now = vec![items[0], items[2]];
+    despawn.deferred = vec![items[4]];
+    app.step_until_styled();
+
+    for item in [items[0], items[2], items[4]]
+    {
+        assert!(app.world.get_entity(item).is_none(), "Should be despawned");
+    }
+    for item in [items[1], items[3], items[5]]
+    {
+        assert_eq!(style(&app, item).width, Val::Px(5.0), "Remaining entities should be styled");
+        assert_eq!(style(&app, item).display, Display::None);
+        app.expect_component::<Text>(item, |text| assert_eq!(text.sections[0].value, "ITEM"));
+        app.expect_component::<BorderColor>(item, |border| assert_eq!(border.0, Color::RED));
+    }
+
+    // Entity ids of despawned entities are recycled, and nothing styled them
+    let recycled: Vec<Entity> = (0..3)
+        .map(|_| app.world.spawn(TextBundle::from_section("item", Default::default())).id())
+        .collect();
+    app.step(2);
+
+    for entity in recycled
+    {
+        assert!([items[0], items[2], items[4]].iter().any(|item| item.index() == entity.index()), "Should recycle the id");
+        assert_eq!(style(&app, entity).width, Style::DEFAULT.width);
+        assert!(app.world.get::<TextTransformSource>(entity).is_none(), "Should leak no components");
+        assert!(app.world.get::<BorderColor>(entity).is_none());
+        assert_eq!(style(&app, entity).display, Display::Flex);
+    }
+}
+
+#[test]
+fn flex_factors_are_applied(
+    // no args
+) {
+    let (mut app, panel, item) = create_styled_app(".panel { flex-grow: 0.5; flex-shrink: 2; } .item { flex-grow: -3; flex-shrink: -1; }");
+
+    assert_eq!(style(&app, panel).flex_grow, 0.5);
+    assert_eq!(style(&app, panel).flex_shrink, 2.0);
+    assert_eq!(style(&app, item).flex_grow, 0.0, "Negative factors should be clamped to zero");
+    assert_eq!(style(&app, item).flex_shrink, 0.0);
+
+    clear_styles(&mut app.world, panel);
+    assert_eq!(style(&app, panel).flex_grow, Style::DEFAULT.flex_grow);
+    assert_eq!(style(&app, panel).flex_shrink, Style::DEFAULT.flex_shrink);
+}
+
+#[test]
+fn state_forgets_despawned_entities_without_node(
+    // no args
+) {
+    use crate::property::StyleSheetState;
+    use bevy::prelude::{DespawnRecursiveExt, Name};
+
+    let mut app = TestApp::with_css(".item { width: 5px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .with_child(TestNode::with_bundle(Name::new("not a node")).class("item"))
+    );
+    app.step_until_styled();
+    assert!(app.world.resource::<StyleSheetState>().applied_values(tree[1]).is_some());
+
+    // Spawning another entity on the same frame keeps the entity count unchanged
+    app.world.entity_mut(tree[1]).despawn_recursive();
+    app.world.spawn(Name::new("spawned"));
+    app.update();
+
+    let state = app.world.resource::<StyleSheetState>();
+    assert!(state.applied_values(tree[1]).is_none(), "Should forget despawned entities");
+    #[cfg(feature = "provenance")]
+    assert!(state.computed_property(tree[1], "width").is_none());
+}
+
+#[test]
+fn color_is_only_inherited_by_texts(
+    // no args
+) {
+    use crate::property::StyleSheetState;
+
+    let mut app = TestApp::with_css(".card { color: red; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .class("card")
+        .with_child(TestNode::node()
+            .with_child(TestNode::text("inner"))
+        )
+    );
+    app.step_until_styled();
+
+    app.expect_component::<Text>(tree[2], |text| assert_eq!(text.sections[0].style.color, Color::RED));
+
+    let state = app.world.resource::<StyleSheetState>();
+    assert!(state.applied_values(tree[1]).is_none(), "Containers should only pass the color on to their descendants");
+}
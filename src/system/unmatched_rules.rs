@@ -0,0 +1,181 @@
+use super::{
+    query,
+    select_entities,
+    with_imports,
+    ComponentFilterRegistry,
+    CssQueryParam,
+    StyleDiagnostic,
+};
+use crate::{
+    prelude::BevyCssError,
+    property::{RuleMatchTracking, StyleSheetState},
+    selector::Selector,
+    stylesheet::StyleSheetAsset,
+};
+
+use bevy::{
+    ecs::system::{SystemParam, SystemState},
+    log::warn,
+    prelude::{
+        AssetEvent, Assets,
+        DetectChanges,
+        Entity, EventReader,
+        Local,
+        Mut,
+        Res,
+        Resource,
+        World,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// Whether [`report_unmatched_rules`] warns about rules matching no entity, set by
+/// [`BevyCssPlugin::with_unmatched_rule_warnings`](crate::BevyCssPlugin::with_unmatched_rule_warnings).
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub(crate) struct UnmatchedRuleWarnings(pub bool);
+
+#[derive(SystemParam)]
+pub(crate) struct UnmatchedRuleParam<'w, 's>
+{
+    pub sheets: query::QueryStyleSheets<'w, 's>,
+    pub assets: Res<'w, Assets<StyleSheetAsset>>,
+    pub asset_events: EventReader<'w, 's, AssetEvent<StyleSheetAsset>>,
+    pub warnings: Res<'w, UnmatchedRuleWarnings>,
+    pub tracking: Res<'w, RuleMatchTracking>,
+}
+
+/// Debug system which evaluates every rule on the whole tree of its sheet, to find rules matching no entity.
+///
+/// Rules are checked on every [`StyleSheet`](crate::prelude::StyleSheet) which changed, or which sheet, or any imported sheet, was loaded or modified.
+/// With [`UnmatchedRuleWarnings`], each unmatched rule is logged and sent as a [`StyleDiagnostic`] event for the entity holding the sheet.
+/// With [`RuleMatchTracking`], the matches of every rule are added to [`StyleSheetState::unmatched_rules`], and all sheets are checked
+/// again once tracking is enabled or the counts are reset.
+///
+/// Rules with pseudo-classes, or matching a component variant, are skipped, since those depend on state which changes all the time.
+pub(crate) fn report_unmatched_rules(
+    world: &mut World,
+    params: &mut SystemState<UnmatchedRuleParam<'static, 'static>>,
+    css_query: &mut SystemState<CssQueryParam<'static, 'static>>,
+    mut checked: Local<HashMap<Entity, Vec<u64>>>,
+    mut was_tracking: Local<bool>
+) {
+    let (tracking, roots) = {
+        let reset = world.resource_mut::<StyleSheetState>().take_match_counts_reset();
+        let mut params = params.get_mut(world);
+        let warnings = params.warnings.0;
+        let tracking = params.tracking.0;
+        let recount = tracking && (reset || !*was_tracking);
+        *was_tracking = tracking;
+
+        if !warnings && !tracking
+        {
+            params.asset_events.clear();
+            return;
+        }
+
+        let modified: HashSet<_> = params.asset_events.read()
+            .filter_map(|event| match event
+            {
+                AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+                _ => None,
+            })
+            .collect();
+
+        checked.retain(|entity, _hashes| params.sheets.contains(*entity));
+
+        let mut roots = Vec::new();
+        for (entity, sheet) in params.sheets.iter()
+        {
+            let sheets = with_imports(sheet.handle(), &params.assets);
+            let hashes: Vec<u64> = sheets.iter()
+                .filter_map(|handle| params.assets.get(handle))
+                .map(StyleSheetAsset::hash)
+                .collect();
+
+            // Sheets are usually loaded after the component is added, so skip those already checked with the same content
+            let modified = sheets.iter().any(|handle| modified.contains(&handle.id()))
+                && checked.get(&entity) != Some(&hashes);
+
+            // Sheets checked only to count matches again were already warned about
+            let changed = sheet.is_changed() || modified;
+            if changed || recount
+            {
+                checked.insert(entity, hashes);
+                roots.push((entity, warnings && changed));
+            }
+        }
+
+        (tracking, roots)
+    };
+
+    if roots.is_empty()
+    {
+        return;
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut matches = Vec::new();
+    world.resource_scope(|world, mut registry: Mut<ComponentFilterRegistry>|
+    {
+        let css_query = css_query.get(world);
+
+        for (root, warn) in roots
+        {
+            let handle = match css_query.ui_nodes.get(root)
+            {
+                Ok((_entity, _parent, _children, Some(sheet))) => sheet.handle().clone(),
+                _ => continue,
+            };
+
+            for (id, sheet) in with_imports(&handle, &css_query.assets)
+                .iter()
+                .filter_map(|handle| Some((handle.id(), css_query.assets.get(handle)?)))
+            {
+                for rule in sheet.iter()
+                {
+                    if is_stateful(&rule.selector)
+                    {
+                        continue;
+                    }
+
+                    let entities = select_entities(root, root, &rule.selector, world, &css_query, &mut registry);
+                    if tracking
+                    {
+                        matches.push((id, rule.selector.to_string(), entities.len()));
+                    }
+
+                    if warn && entities.is_empty()
+                    {
+                        warn!("Rule '{}' of sheet '{}' doesn't match any entity under {root:?}", rule.selector, sheet.path());
+                        diagnostics.push(StyleDiagnostic {
+                            entity: root,
+                            error: BevyCssError::UnmatchedRule(rule.selector.to_string()),
+                        });
+                    }
+                }
+            }
+        }
+    });
+
+    let mut state = world.resource_mut::<StyleSheetState>();
+    for (sheet, selector, count) in matches
+    {
+        state.add_rule_matches(sheet, selector, count);
+    }
+
+    world.send_event_batch(diagnostics);
+}
+
+/// Checks if the selector depends on state which changes all the time, like `:hover` or `interaction(pressed)`.
+fn is_stateful(
+    selector: &Selector
+) -> bool {
+    #[cfg(feature = "pseudo_class")]
+    if selector.has_pseudo_class()
+    {
+        return true;
+    }
+
+    selector.has_component_variant()
+}
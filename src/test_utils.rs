@@ -0,0 +1,326 @@
+//! Utilities to test style sheets against an UI, without a window or a renderer.
+//!
+//! Enabled by the `test_utils` feature, so it's usually added as a dev-dependency feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! tomt_bevycss = { version = "*", features = ["test_utils"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! use tomt_bevycss::test_utils::{TestApp, TestNode};
+//!
+//! let mut app = TestApp::with_css(".panel { width: 100px; } .panel .item { height: 10%; }");
+//! let tree = app.spawn_tree(TestNode::node()
+//!     .class("panel")
+//!     .with_child(TestNode::node().class("item"))
+//! );
+//! app.step_until_styled();
+//!
+//! app.expect_style(tree[0], |style| assert_eq!(style.width, Val::Px(100.0)));
+//! app.expect_style(tree[1], |style| assert_eq!(style.height, Val::Percent(10.0)));
+//! ```
+
+use crate::prelude::{
+    BevyCssPlugin,
+    Class,
+    StyleApplied,
+    StyleSheet,
+    StyleSheetAsset,
+};
+
+use bevy::{
+    ecs::event::ManualEventReader,
+    prelude::{
+        App,
+        AssetPlugin,
+        Assets,
+        BuildWorldChildren,
+        Bundle,
+        Component,
+        Deref, DerefMut,
+        Entity,
+        EntityWorldMut,
+        Events,
+        Handle,
+        MinimalPlugins,
+        Name,
+        NodeBundle,
+        Style,
+        TextBundle,
+        TextStyle,
+    },
+};
+
+/// Maximum number of frames [`TestApp::step_until_styled`] waits for styles to be applied.
+const MAX_STEPS: usize = 16;
+
+/// An [`App`] with the minimal set of plugins needed to apply style sheets, and an in-memory style sheet.
+///
+/// It can be used as an [`App`] as well, so anything not covered here can be done directly on it.
+#[derive(Deref, DerefMut)]
+pub struct TestApp
+{
+    #[deref]
+    app: App,
+    sheet: Handle<StyleSheetAsset>,
+    applied: ManualEventReader<StyleApplied>,
+}
+
+impl TestApp
+{
+    /// Creates an app with [`BevyCssPlugin`], without hot reloading, and the given style sheet content.
+    pub fn with_css(
+        css: &str
+    ) -> Self {
+        Self::with_plugin(BevyCssPlugin::without_hot_reload(), css)
+    }
+
+    /// Same as [`with_css`](TestApp::with_css), but with the given plugin configuration.
+    pub fn with_plugin(
+        plugin: BevyCssPlugin,
+        css: &str
+    ) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            plugin,
+        ));
+
+        let sheet = app.world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("test.css", css));
+
+        Self{
+            app,
+            sheet,
+            applied: Default::default(),
+        }
+    }
+
+    /// Handle of the in-memory style sheet.
+    pub fn sheet(
+        &self
+    ) -> &Handle<StyleSheetAsset> {
+        &self.sheet
+    }
+
+    /// Replaces the content of the in-memory style sheet, like a hot reload would.
+    ///
+    /// Changes are only applied on entities when hot reloading is enabled, see [`BevyCssPlugin::with_hot_reload`].
+    pub fn set_css(
+        &mut self,
+        css: &str
+    ) {
+        let mut assets = self.app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        if let Some(sheet) = assets.get_mut(&self.sheet)
+        {
+            *sheet = StyleSheetAsset::parse("test.css", css);
+        }
+    }
+
+    /// Adds another in-memory style sheet, which can be added to nodes with [`TestNode::with`] and [`StyleSheet::new`].
+    pub fn add_sheet(
+        &mut self,
+        path: &str,
+        css: &str
+    ) -> Handle<StyleSheetAsset> {
+        self.app.world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse(path, css))
+    }
+
+    /// Runs the given number of frames, regardless of styles being applied or not.
+    pub fn step(
+        &mut self,
+        frames: usize
+    ) {
+        for _ in 0..frames
+        {
+            self.app.update();
+        }
+
+        // Styles applied on these frames are already seen, so they aren't awaited by `step_until_styled`
+        let events = self.app.world.resource::<Events<StyleApplied>>();
+        self.applied.read(events).for_each(drop);
+    }
+
+    /// Spawns the given tree, with the in-memory style sheet on its root node.
+    ///
+    /// Returns all spawned entities on depth-first order, so the root node is always the first one.
+    pub fn spawn_tree(
+        &mut self,
+        root: TestNode
+    ) -> Vec<Entity> {
+        let mut entities = vec![];
+        let root_entity = root.spawn(&mut self.app, &mut entities);
+
+        let sheet = StyleSheet::new(self.sheet.clone());
+        self.app.world.entity_mut(root_entity).insert(sheet);
+
+        entities
+    }
+
+    /// Runs frames until styles are applied, and no more styles are applied on the next frame.
+    ///
+    /// Returns the number of frames ran.
+    ///
+    /// # Panics
+    ///
+    /// If styles are still being applied after a few frames, or if no styles were applied at all.
+    pub fn step_until_styled(
+        &mut self
+    ) -> usize {
+        let mut styled = false;
+
+        for step in 1..=MAX_STEPS
+        {
+            self.app.update();
+
+            let events = self.app.world.resource::<Events<StyleApplied>>();
+            let applied = self.applied.read(events).count() > 0;
+
+            match (styled, applied)
+            {
+                (true, false) => return step,
+                (_, applied) => styled |= applied,
+            }
+        }
+
+        match styled
+        {
+            true => panic!("Styles are still being applied after {MAX_STEPS} frames"),
+            false => panic!("No styles were applied after {MAX_STEPS} frames"),
+        }
+    }
+
+    /// Calls `check` with the [`Style`] of the given entity, which should assert the expected values.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has no [`Style`].
+    pub fn expect_style(
+        &self,
+        entity: Entity,
+        check: impl FnOnce(&Style)
+    ) {
+        self.expect_component(entity, check);
+    }
+
+    /// Calls `check` with the component `T` of the given entity, which should assert the expected values.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has no component `T`.
+    pub fn expect_component<T>(
+        &self,
+        entity: Entity,
+        check: impl FnOnce(&T)
+    ) where
+        T: Component,
+    {
+        match self.app.world.get::<T>(entity)
+        {
+            Some(component) => check(component),
+            None => panic!("Entity {entity:?} has no {}", std::any::type_name::<T>()),
+        }
+    }
+}
+
+/// Inserts the components of a [`TestNode`] on its spawned entity.
+type InsertFn = Box<dyn FnOnce(&mut EntityWorldMut)>;
+
+/// A node of the tree spawned by [`TestApp::spawn_tree`].
+pub struct TestNode
+{
+    insert: Vec<InsertFn>,
+    children: Vec<TestNode>,
+}
+
+impl TestNode
+{
+    /// A node spawned with a [`NodeBundle`].
+    pub fn node(
+        // no args
+    ) -> Self {
+        Self::with_bundle(NodeBundle::default())
+    }
+
+    /// A text node spawned with a [`TextBundle`] of the given text.
+    pub fn text(
+        text: &str
+    ) -> Self {
+        Self::with_bundle(TextBundle::from_section(text, TextStyle::default()))
+    }
+
+    /// A node spawned with the given bundle, like a `ButtonBundle`.
+    pub fn with_bundle(
+        bundle: impl Bundle
+    ) -> Self {
+        Self{
+            insert: vec![Box::new(move |entity: &mut EntityWorldMut| { entity.insert(bundle); })],
+            children: vec![],
+        }
+    }
+
+    /// Adds the given class names, like `"panel primary"`.
+    pub fn class(
+        self,
+        class: &str
+    ) -> Self {
+        self.with(Class::new(class.to_string()))
+    }
+
+    /// Adds a [`Name`], matched by `#name` selectors.
+    pub fn name(
+        self,
+        name: &str
+    ) -> Self {
+        self.with(Name::new(name.to_string()))
+    }
+
+    /// Adds the given components, like `Interaction::Hovered`.
+    pub fn with(
+        mut self,
+        bundle: impl Bundle
+    ) -> Self {
+        self.insert.push(Box::new(move |entity: &mut EntityWorldMut| { entity.insert(bundle); }));
+        self
+    }
+
+    /// Adds the given node as the last child of this one.
+    pub fn with_child(
+        mut self,
+        child: TestNode
+    ) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn spawn(
+        self,
+        app: &mut App,
+        entities: &mut Vec<Entity>
+    ) -> Entity {
+        let mut entity = app.world.spawn_empty();
+        for insert in self.insert
+        {
+            insert(&mut entity);
+        }
+
+        let id = entity.id();
+        entities.push(id);
+
+        for child in self.children
+        {
+            let child = child.spawn(app, entities);
+            app.world.entity_mut(id).add_child(child);
+        }
+
+        id
+    }
+}
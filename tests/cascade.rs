@@ -0,0 +1,138 @@
+//! End-to-end semantics of how style sheets are applied, on a headless app.
+//!
+//! Requires the `test_utils` feature, like `cargo test --all-features`.
+
+use bevy::prelude::*;
+use tomt_bevycss::{
+    prelude::*,
+    test_utils::{TestApp, TestNode},
+};
+
+/// Spawns a `.panel` node with a single `.item` child, and applies the given style sheet on it.
+fn panel_with_item(
+    mut app: TestApp
+) -> (TestApp, Entity, Entity) {
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+    );
+    app.step_until_styled();
+
+    (app, tree[0], tree[1])
+}
+
+#[test]
+fn more_specific_rule_wins_regardless_of_order(
+    // no args
+) {
+    let (app, _panel, item) = panel_with_item(TestApp::with_css(".panel .item { width: 2px; } .item { width: 1px; height: 1px; }"));
+
+    app.expect_style(item, |style|
+    {
+        assert_eq!(style.width, Val::Px(2.0), "More specific rule should win even when declared first");
+        assert_eq!(style.height, Val::Px(1.0), "Less specific rule should still apply other properties");
+    });
+}
+
+#[test]
+fn later_rule_wins_on_same_specificity(
+    // no args
+) {
+    let (app, _panel, item) = panel_with_item(TestApp::with_css(".item { width: 1px; } .item { width: 2px; }"));
+
+    app.expect_style(item, |style| assert_eq!(style.width, Val::Px(2.0)));
+}
+
+#[test]
+fn important_wins_over_specificity(
+    // no args
+) {
+    let (app, _panel, item) = panel_with_item(TestApp::with_css(".item { width: 1px !important; } .panel .item { width: 2px; }"));
+
+    app.expect_style(item, |style| assert_eq!(style.width, Val::Px(1.0)));
+}
+
+#[cfg(feature = "monitor_changes")]
+#[test]
+fn class_toggle_reverts_values(
+    // no args
+) {
+    let (mut app, _panel, item) = panel_with_item(TestApp::with_css(".item { width: 1px; } .item.wide { width: 5px; }"));
+
+    *app.world.get_mut::<Class>(item).unwrap() = Class::new("item wide");
+    app.step_until_styled();
+    app.expect_style(item, |style| assert_eq!(style.width, Val::Px(5.0)));
+
+    *app.world.get_mut::<Class>(item).unwrap() = Class::new("item");
+    app.step_until_styled();
+    app.expect_style(item, |style| assert_eq!(style.width, Val::Px(1.0), "Should revert to the less specific rule"));
+}
+
+#[test]
+fn hot_reload_reapplies_changed_sheet(
+    // no args
+) {
+    let app = TestApp::with_plugin(BevyCssPlugin::with_hot_reload(), ".item { width: 1px; }");
+    let (mut app, _panel, item) = panel_with_item(app);
+
+    app.set_css(".item { width: 3px; }");
+    app.step_until_styled();
+
+    app.expect_style(item, |style| assert_eq!(style.width, Val::Px(3.0)));
+}
+
+#[test]
+fn nested_sheet_only_applies_to_its_subtree(
+    // no args
+) {
+    let mut app = TestApp::with_css(".item { width: 1px; }");
+    let nested = app.add_sheet("nested.css", ".item { height: 7px; }");
+
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item"))
+        .with_child(TestNode::node()
+            .with(StyleSheet::new(nested))
+            .with_child(TestNode::node().class("item"))
+        )
+    );
+    app.step_until_styled();
+
+    let (outer, inner) = (tree[1], tree[3]);
+    app.expect_style(outer, |style|
+    {
+        assert_eq!(style.width, Val::Px(1.0));
+        assert_eq!(style.height, Style::DEFAULT.height, "Nested sheet shouldn't apply outside its subtree");
+    });
+    app.expect_style(inner, |style|
+    {
+        assert_eq!(style.width, Val::Px(1.0), "Outer sheet should still apply inside the nested one");
+        assert_eq!(style.height, Val::Px(7.0));
+    });
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn pseudo_class_transitions(
+    // no args
+) {
+    let mut app = TestApp::with_css(".item { width: 1px; } .item:hover { width: 2px; } .item:click { width: 3px; }");
+    let tree = app.spawn_tree(TestNode::node()
+        .class("panel")
+        .with_child(TestNode::node().class("item").with(Interaction::None))
+    );
+    app.step_until_styled();
+    let item = tree[1];
+
+    for (interaction, width) in [
+        (Interaction::Hovered, 2.0),
+        (Interaction::Pressed, 3.0),
+        (Interaction::Hovered, 2.0),
+        (Interaction::None, 1.0),
+    ] {
+        *app.world.get_mut::<Interaction>(item).unwrap() = interaction;
+        app.step_until_styled();
+
+        app.expect_style(item, |style| assert_eq!(style.width, Val::Px(width), "Unexpected width when {interaction:?}"));
+    }
+}